@@ -0,0 +1,191 @@
+//! Balance query throughput against a local mock JSON-RPC server
+//!
+//! Groups:
+//! - `handler`: query handler overhead over a direct service call (no I/O)
+//! - `single_query`: one `eth_getBalance` through the handler
+//! - `batch_100`: 100 addresses queried sequentially, concurrently, and in
+//!   one Multicall3 `eth_call`
+//! - `serialization`: JSON encoding of query results
+//!
+//! Run with: cargo bench --bench balance_queries
+
+use alloy::primitives::{Bytes, U256};
+use alloy::sol;
+use alloy::sol_types::{SolCall, SolValue};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use futures::future::join_all;
+use rustwallet::adapter::infrastructure::blockchain::{AlloyBlockchainService, NoopBlockchainService};
+use rustwallet::core::application::{handlers::GetBalancesHandler, GetBalanceHandler};
+use rustwallet::core::domain::{
+    queries::{BalanceQueryResult, GetBalanceQuery, GetBalancesQuery},
+    services::{BlockchainService, QueryHandler},
+    value_objects::{Address, Balance, Network},
+};
+use serde_json::{json, Value};
+use std::hint::black_box;
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+use wiremock::matchers::{body_partial_json, method};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+const BATCH_SIZE: usize = 100;
+const ETH_ADDRESS: &str = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+/// 1 ETH in Wei, as every mocked balance
+const ONE_ETH: u128 = 1_000_000_000_000_000_000;
+
+// Multicall3's return types, to build the canned batch response
+sol! {
+    struct Call3 {
+        address target;
+        bool allowFailure;
+        bytes callData;
+    }
+
+    struct Result3 {
+        bool success;
+        bytes returnData;
+    }
+
+    function aggregate3(Call3[] calls) external payable returns (Result3[] returnData);
+}
+
+/// Respond to a JSON-RPC request with `result`, echoing the request id
+fn json_rpc_result(result: Value) -> impl Fn(&Request) -> ResponseTemplate {
+    move |request: &Request| {
+        let body: Value = serde_json::from_slice(&request.body).unwrap_or_default();
+        ResponseTemplate::new(200).set_body_json(json!({
+            "jsonrpc": "2.0",
+            "id": body["id"],
+            "result": result,
+        }))
+    }
+}
+
+/// Server answering `eth_getBalance` and Multicall3 batches of `BATCH_SIZE` with 1 ETH each
+async fn mock_evm_server() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_getBalance" })))
+        .respond_with(json_rpc_result(json!(format!("{:#x}", ONE_ETH))))
+        .mount(&server)
+        .await;
+
+    let balance = Bytes::from(U256::from(ONE_ETH).abi_encode());
+    let results = vec![
+        Result3 {
+            success: true,
+            returnData: balance,
+        };
+        BATCH_SIZE
+    ];
+    let output = aggregate3Call::abi_encode_returns(&(results,));
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_call" })))
+        .respond_with(json_rpc_result(json!(format!("0x{}", alloy::hex::encode(output)))))
+        .mount(&server)
+        .await;
+    server
+}
+
+fn batch_addresses() -> Vec<Address> {
+    (1..=BATCH_SIZE)
+        .map(|i| Address::new(format!("0x{:040x}", i)).unwrap())
+        .collect()
+}
+
+fn bench_handler_overhead(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let service = Arc::new(NoopBlockchainService::new());
+    let handler = GetBalanceHandler::new(service.clone());
+    let address = Address::new(ETH_ADDRESS.to_string()).unwrap();
+
+    let mut group = c.benchmark_group("handler");
+    group.bench_function("direct_service", |b| {
+        b.to_async(&rt).iter(|| async { black_box(service.get_balance(&address).await.unwrap()) })
+    });
+    group.bench_function("query_handler", |b| {
+        b.to_async(&rt).iter(|| async {
+            let query = GetBalanceQuery::new(address.clone(), Network::Mainnet);
+            black_box(handler.handle(query).await.unwrap())
+        })
+    });
+    group.finish();
+}
+
+fn bench_single_query(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let server = rt.block_on(mock_evm_server());
+    let service = rt.block_on(AlloyBlockchainService::new(Network::Mainnet, &server.uri())).unwrap();
+    let handler = GetBalanceHandler::new(Arc::new(service));
+    let address = Address::new(ETH_ADDRESS.to_string()).unwrap();
+
+    c.bench_function("single_query", |b| {
+        b.to_async(&rt).iter(|| async {
+            let query = GetBalanceQuery::new(address.clone(), Network::Mainnet);
+            black_box(handler.handle(query).await.unwrap())
+        })
+    });
+}
+
+fn bench_batch(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let server = rt.block_on(mock_evm_server());
+    let service: Arc<dyn BlockchainService> =
+        Arc::new(rt.block_on(AlloyBlockchainService::new(Network::Mainnet, &server.uri())).unwrap());
+    let handler = GetBalancesHandler::new(service.clone());
+    let addresses = batch_addresses();
+
+    let mut group = c.benchmark_group("batch_100");
+    group.throughput(Throughput::Elements(BATCH_SIZE as u64));
+    group.sample_size(20);
+    group.bench_function("sequential", |b| {
+        b.to_async(&rt).iter(|| async {
+            for address in &addresses {
+                black_box(service.get_balance(address).await.unwrap());
+            }
+        })
+    });
+    group.bench_function("concurrent", |b| {
+        b.to_async(&rt).iter(|| async {
+            black_box(join_all(addresses.iter().map(|address| service.get_balance(address))).await)
+        })
+    });
+    group.bench_function("multicall", |b| {
+        b.to_async(&rt).iter(|| async {
+            let query = GetBalancesQuery::new(addresses.clone(), Network::Mainnet);
+            let results = handler.handle(query).await.unwrap();
+            assert!(results.iter().all(Result::is_ok));
+            black_box(results)
+        })
+    });
+    group.finish();
+}
+
+fn bench_serialization(c: &mut Criterion) {
+    let results: Vec<BalanceQueryResult> = batch_addresses()
+        .into_iter()
+        .map(|address| BalanceQueryResult::new(address, Network::Mainnet, Balance::from_wei(ONE_ETH)))
+        .collect();
+
+    let mut group = c.benchmark_group("serialization");
+    for count in [1, BATCH_SIZE] {
+        group.throughput(Throughput::Elements(count as u64));
+        group.bench_with_input(BenchmarkId::new("to_json", count), &results[..count], |b, results| {
+            b.iter(|| black_box(serde_json::to_string(results).unwrap()))
+        });
+    }
+    let json = serde_json::to_string(&results).unwrap();
+    group.bench_function("from_json/100", |b| {
+        b.iter(|| black_box(serde_json::from_str::<Vec<BalanceQueryResult>>(&json).unwrap()))
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_handler_overhead,
+    bench_single_query,
+    bench_batch,
+    bench_serialization
+);
+criterion_main!(benches);
@@ -0,0 +1,13 @@
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=proto/wallet.proto");
+
+    // Generate the gRPC server and client from proto/wallet.proto
+    #[cfg(feature = "grpc")]
+    {
+        let descriptors = protox::compile(["wallet.proto"], ["proto"]).expect("proto/wallet.proto is invalid");
+        tonic_build::configure()
+            .compile_fds(descriptors)
+            .expect("failed to generate gRPC code");
+    }
+}
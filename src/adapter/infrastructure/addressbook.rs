@@ -0,0 +1,257 @@
+//! Named addresses persisted in a JSON file
+//!
+//! The book maps a name to an address and the network it was saved for:
+//!
+//! ```json
+//! {
+//!   "alice": { "address": "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045", "network": "Mainnet" }
+//! }
+//! ```
+//!
+//! Addresses are stored as plain strings and validated again on every
+//! lookup, so a hand-edited or corrupted entry is reported instead of used.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use crate::core::domain::{
+    errors::DomainError,
+    value_objects::{Address, Network},
+};
+
+/// Environment variable overriding the address book location
+pub const ADDRESS_BOOK_ENV: &str = "RUSTWALLET_ADDRESS_BOOK";
+
+/// A saved address and the network it belongs to
+#[derive(Debug, Clone)]
+pub struct AddressBookEntry {
+    pub address: Address,
+    pub network: Network,
+}
+
+/// On-disk form of an entry; the address is re-validated when read
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredEntry {
+    address: String,
+    network: Network,
+}
+
+/// Name → (address, network) map backed by a JSON file
+pub struct AddressBook {
+    path: PathBuf,
+    entries: BTreeMap<String, StoredEntry>,
+}
+
+impl AddressBook {
+    /// `$RUSTWALLET_ADDRESS_BOOK`, or `~/.rustwallet/addressbook.json`
+    pub fn default_path() -> Result<PathBuf, DomainError> {
+        if let Some(path) = std::env::var_os(ADDRESS_BOOK_ENV) {
+            return Ok(PathBuf::from(path));
+        }
+        std::env::var_os("HOME")
+            .or_else(|| std::env::var_os("USERPROFILE"))
+            .map(|home| PathBuf::from(home).join(".rustwallet").join("addressbook.json"))
+            .ok_or_else(|| {
+                DomainError::ConfigurationError(format!(
+                    "Cannot locate the address book: set {} or HOME",
+                    ADDRESS_BOOK_ENV
+                ))
+            })
+    }
+
+    /// Open the book at the default location
+    pub fn open_default() -> Result<Self, DomainError> {
+        Self::open(Self::default_path()?)
+    }
+
+    /// Open the book stored at `path`; a missing file is an empty book
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, DomainError> {
+        let path = path.into();
+        let entries = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| {
+                DomainError::ConfigurationError(format!(
+                    "Address book {} is not valid JSON: {}",
+                    path.display(),
+                    e
+                ))
+            })?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => {
+                return Err(DomainError::ConfigurationError(format!(
+                    "Failed to read address book {}: {}",
+                    path.display(),
+                    e
+                )))
+            }
+        };
+        Ok(Self { path, entries })
+    }
+
+    /// File backing this book
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Save `address` under `name`, replacing any previous entry
+    ///
+    /// Names must be non-empty, contain no whitespace, and not themselves be
+    /// valid addresses (otherwise lookups would be ambiguous). The address must
+    /// belong to `network`'s chain.
+    pub fn add(&mut self, name: &str, address: Address, network: Network) -> Result<(), DomainError> {
+        if name.is_empty() || name.chars().any(char::is_whitespace) {
+            return Err(DomainError::InvalidQuery(format!(
+                "Invalid name '{}': must be non-empty without spaces",
+                name
+            )));
+        }
+        if Address::new(name.to_string()).is_ok() {
+            return Err(DomainError::InvalidQuery(format!(
+                "Invalid name '{}': looks like an address",
+                name
+            )));
+        }
+        if address.chain_type() != network.chain_type() {
+            return Err(DomainError::InvalidQuery(format!(
+                "{} is a {} address, but {} is a {} network",
+                address,
+                address.chain_type(),
+                network.name(),
+                network.chain_type()
+            )));
+        }
+
+        self.entries.insert(
+            name.to_string(),
+            StoredEntry {
+                address: address.as_str().to_string(),
+                network,
+            },
+        );
+        Ok(())
+    }
+
+    /// Remove `name`, returning whether it was present
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.entries.remove(name).is_some()
+    }
+
+    /// Look up `name`, re-validating the stored address
+    ///
+    /// Returns `Ok(None)` if the name is not in the book.
+    pub fn resolve(&self, name: &str) -> Result<Option<AddressBookEntry>, DomainError> {
+        let Some(stored) = self.entries.get(name) else {
+            return Ok(None);
+        };
+        let address = Address::new(stored.address.clone()).map_err(|e| {
+            DomainError::ConfigurationError(format!(
+                "Address book entry '{}' ({}) is no longer valid: {}",
+                name, stored.address, e
+            ))
+        })?;
+        Ok(Some(AddressBookEntry {
+            address,
+            network: stored.network.clone(),
+        }))
+    }
+
+    /// All entries sorted by name; invalid stored addresses are reported per entry
+    pub fn entries(&self) -> impl Iterator<Item = (&str, Result<AddressBookEntry, DomainError>)> + '_ {
+        self.entries.keys().map(|name| {
+            let entry = self
+                .resolve(name)
+                .map(|entry| entry.expect("name comes from the book"));
+            (name.as_str(), entry)
+        })
+    }
+
+    /// Whether the book has no entries
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Write the book back to its file, creating the parent directory if needed
+    ///
+    /// Writes a sibling temporary file and renames it over the book, so an
+    /// interrupted save never leaves a truncated file behind.
+    pub fn save(&self) -> Result<(), DomainError> {
+        let io_error = |e: std::io::Error| {
+            DomainError::ConfigurationError(format!(
+                "Failed to write address book {}: {}",
+                self.path.display(),
+                e
+            ))
+        };
+
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).map_err(io_error)?;
+        }
+        let json = serde_json::to_vec_pretty(&self.entries)
+            .map_err(|e| DomainError::ConfigurationError(format!("Failed to encode address book: {}", e)))?;
+        let tmp = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp, json).map_err(io_error)?;
+        std::fs::rename(&tmp, &self.path).map_err(io_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VITALIK: &str = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+
+    fn temp_book_path(test: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("rustwallet-addressbook-{}-{}", test, std::process::id()))
+            .join("addressbook.json")
+    }
+
+    #[test]
+    fn test_address_book_round_trip() {
+        let path = temp_book_path("round-trip");
+        let _ = std::fs::remove_file(&path);
+
+        let mut book = AddressBook::open(&path).unwrap();
+        assert!(book.is_empty());
+        book.add("alice", Address::new(VITALIK.to_string()).unwrap(), Network::Mainnet).unwrap();
+        book.save().unwrap();
+
+        // Survives reopening
+        let mut book = AddressBook::open(&path).unwrap();
+        let alice = book.resolve("alice").unwrap().unwrap();
+        assert_eq!(alice.address.as_str(), VITALIK);
+        assert_eq!(alice.network, Network::Mainnet);
+        assert!(book.resolve("bob").unwrap().is_none());
+
+        assert!(book.remove("alice"));
+        assert!(!book.remove("alice"));
+        book.save().unwrap();
+        assert!(AddressBook::open(&path).unwrap().is_empty());
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_address_book_rejects_bad_entries() {
+        let mut book = AddressBook::open(temp_book_path("bad-entries")).unwrap();
+        let address = Address::new(VITALIK.to_string()).unwrap();
+
+        assert!(book.add("", address.clone(), Network::Mainnet).is_err());
+        assert!(book.add("my wallet", address.clone(), Network::Mainnet).is_err());
+        assert!(book.add(VITALIK, address.clone(), Network::Mainnet).is_err());
+        // EVM address saved for a Bitcoin network
+        assert!(book.add("alice", address, Network::BitcoinMainnet).is_err());
+    }
+
+    #[test]
+    fn test_address_book_revalidates_stored_addresses() {
+        let path = temp_book_path("revalidate");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, r#"{ "mallory": { "address": "0x1234", "network": "Mainnet" } }"#).unwrap();
+
+        let book = AddressBook::open(&path).unwrap();
+        assert!(matches!(book.resolve("mallory"), Err(DomainError::ConfigurationError(_))));
+        assert!(book.entries().all(|(_, entry)| entry.is_err()));
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+}
@@ -0,0 +1,126 @@
+//! Transfer audit trail as an append-only JSON Lines file
+//!
+//! Each transfer attempt handled with this sink becomes one line:
+//!
+//! ```json
+//! {"timestamp":1700000000,"from":"0x...","to":"0x...","amount":1000,"network":"Sepolia","outcome":{"status":"sent","tx_hash":"0x..."}}
+//! ```
+//!
+//! The file is only ever appended to, and each line is flushed to disk
+//! before the sink returns, so an audit trail survives a crash right after
+//! a transfer. The blocking write runs on tokio's blocking pool.
+
+use async_trait::async_trait;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use crate::core::domain::{errors::DomainError, services::AuditSink, value_objects::TransferAuditRecord};
+
+/// Environment variable naming the audit log; the CLI records transfers only when it is set
+pub const AUDIT_LOG_ENV: &str = "RUSTWALLET_AUDIT_LOG";
+
+/// [`AuditSink`] appending one JSON object per line to a file
+pub struct JsonlAuditSink {
+    path: PathBuf,
+    /// Serializes appends so concurrent transfers cannot interleave lines
+    write_lock: Arc<Mutex<()>>,
+}
+
+impl JsonlAuditSink {
+    /// Append to `path`, creating the file (and its directory) on first write
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            write_lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Sink at `$RUSTWALLET_AUDIT_LOG`, or `None` when it is unset
+    pub fn from_env() -> Option<Self> {
+        std::env::var_os(AUDIT_LOG_ENV).map(Self::new)
+    }
+
+    /// File the records are appended to
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+}
+
+/// Append `line` to `path` and flush it to disk, holding `write_lock` throughout
+fn append(path: &Path, write_lock: &Mutex<()>, line: &[u8]) -> std::io::Result<()> {
+    let _guard = write_lock.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(line)?;
+    file.sync_data()
+}
+
+#[async_trait]
+impl AuditSink for JsonlAuditSink {
+    async fn record_transfer(&self, record: &TransferAuditRecord) -> Result<(), DomainError> {
+        let mut line = serde_json::to_vec(record)
+            .map_err(|e| DomainError::ConfigurationError(format!("Failed to encode audit record: {}", e)))?;
+        line.push(b'\n');
+        let (path, write_lock) = (self.path.clone(), self.write_lock.clone());
+        tokio::task::spawn_blocking(move || append(&path, &write_lock, &line))
+            .await
+            .map_err(|e| DomainError::ConfigurationError(format!("Audit log writer failed: {}", e)))?
+            .map_err(|e| {
+                DomainError::ConfigurationError(format!(
+                    "Failed to append to audit log {}: {}",
+                    self.path.display(),
+                    e
+                ))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::domain::value_objects::{Address, Amount, Network, TransactionHash, TransferOutcome};
+
+    #[tokio::test]
+    async fn test_jsonl_audit_sink_appends_one_line_per_record() {
+        let path = std::env::temp_dir()
+            .join(format!("rustwallet-audit-{}", std::process::id()))
+            .join("audit.jsonl");
+        let _ = std::fs::remove_file(&path);
+        let sink = JsonlAuditSink::new(&path);
+
+        let from = Address::new("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEbC".to_string()).unwrap();
+        let to = Address::new("0x8894E0a0c962CB723c1976a4421c95949bE2D4E3".to_string()).unwrap();
+        let sent = TransferAuditRecord::new(
+            1_700_000_000,
+            from.clone(),
+            to.clone(),
+            Some(Amount::from_wei(1_000)),
+            Network::Sepolia,
+            TransferOutcome::Sent {
+                tx_hash: TransactionHash::new(format!("0x{}", "ab".repeat(32))).unwrap(),
+            },
+        );
+        let failed = TransferAuditRecord::new(
+            1_700_000_060,
+            from,
+            to,
+            None,
+            Network::Sepolia,
+            TransferOutcome::Failed { error: "Insufficient balance for the network fee".to_string() },
+        );
+        sink.record_transfer(&sent).await.unwrap();
+        sink.record_transfer(&failed).await.unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let records: Vec<TransferAuditRecord> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(records, vec![sent, failed]);
+        assert!(contents.contains(r#""status":"failed""#));
+    }
+}
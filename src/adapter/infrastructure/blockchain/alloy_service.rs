@@ -1,168 +1,1972 @@
 use async_trait::async_trait;
 use alloy::{
-    network::EthereumWallet,
-    primitives::{Address as AlloyAddress, U256},
-    providers::{Provider, ProviderBuilder, RootProvider},
-    rpc::types::TransactionRequest,
-    signers::local::PrivateKeySigner,
-    transports::http::{Client, Http},
+    consensus::{SignableTransaction, TxEip1559, TxEnvelope, TxLegacy, TypedTransaction},
+    eips::eip2718::Encodable2718,
+    network::TxSignerSync,
+    primitives::{keccak256, Address as AlloyAddress, Bytes, PrimitiveSignature, TxKind, B256, U256},
+    providers::ProviderBuilder,
+    rpc::{client::RpcClient, types::BlockId},
+    transports::{
+        http::{
+            reqwest::Url,
+            Http,
+        },
+        layers::RetryBackoffLayer,
+        BoxTransport,
+    },
+};
+#[cfg(feature = "cli")]
+use alloy::{
+    providers::{Provider, WsConnect},
+    rpc::client::ClientBuilder,
+    transports::{ipc::IpcConnect, TransportError},
+};
+use futures::future;
+use futures::stream::{self, BoxStream, StreamExt, TryStreamExt};
+use std::collections::HashMap;
+use std::future::Future;
+#[cfg(feature = "cli")]
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::{watch, OnceCell};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tower::Layer;
+use super::evm_rpc::{BlockRef, EvmRpc, FeeHistory, RpcFailure, RpcResult, TxRequest};
+use super::failover::FailoverTransport;
+use super::multicall::{self, MULTICALL3_ADDRESS};
+use super::revert;
+use super::rollup::{self, Rollup, GAS_PRICE_ORACLE_ADDRESS, NODE_INTERFACE_ADDRESS};
+use super::http::build_http_client_with_timeout;
+use crate::adapter::infrastructure::{
+    keys::{self, PrivateKeySigner},
+    provider_keys::default_rpc_url,
+    resilience::{retry_with_backoff, RetryPolicy},
 };
 use crate::core::domain::{
     errors::DomainError,
-    services::BlockchainService,
-    value_objects::{Address, Balance, Network, TransactionHash},
+    services::{BlockchainService, Signer},
+    value_objects::{
+        AccountInfo, Address, Amount, Balance, ChainType, ConnectionDiagnostics, FeeDetails, FeeEstimate, Finality, Network,
+        SecretKey, TransactionHash, TransactionReceipt, TxStatus,
+        network::is_local_rpc_url,
+    },
 };
 
+/// Interval between `eth_getTransactionReceipt` polls while waiting for inclusion
+const RECEIPT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// Extra `is_connected` attempts after a rate-limited probe
+const CONNECT_RETRIES: u32 = 2;
+/// Backoff before the first `is_connected` retry (doubled for the second, jittered)
+const CONNECT_RETRY_DELAY: Duration = Duration::from_millis(250);
+/// `eth_getBalance` calls in flight at once while sampling a balance series
+const SERIES_CONCURRENCY: usize = 8;
+/// Most blocks one `balance_series` call samples
+const MAX_SERIES_SAMPLES: u64 = 10_000;
+/// Recent blocks `estimate_confirmation_time` looks back over
+const FEE_HISTORY_BLOCKS: u64 = 20;
+/// Priority fee percentile a gas price must reach to count as included in a block
+const INCLUSION_PERCENTILE: f64 = 10.0;
+/// Node errors meaning the state at a requested block has been pruned
+/// (Geth, Erigon/Reth, Nethermind wording)
+const PRUNED_STATE_ERRORS: [&str; 4] = [
+    "missing trie node",
+    "historical state",
+    "state is not available",
+    "state not available",
+];
+
 /// Alloy-based Ethereum blockchain service implementation
 pub struct AlloyBlockchainService {
-    provider: RootProvider<Http<Client>>,
+    /// Every node call goes through here: Alloy's provider over the shared
+    /// client (endpoints, headers, timeout and retry layer), or a fake in tests
+    rpc: Arc<dyn EvmRpc>,
     network: Network,
+    /// Configuration this service was built from, reused by `with_endpoint`
+    config: AlloyBlockchainServiceBuilder,
+    /// Set once `eth_chainId` matched the network; `None` when the check is off
+    chain_id_check: Option<OnceCell<()>>,
+    /// Flipped by `shutdown`, ending every `new_block_ticks` stream (and
+    /// with it any `newHeads` subscription and its socket)
+    closed: watch::Sender<bool>,
+}
+
+/// Builder for [`AlloyBlockchainService`]
+///
+/// ```no_run
+/// # use rustwallet::adapter::infrastructure::blockchain::AlloyBlockchainService;
+/// # use rustwallet::adapter::infrastructure::resilience::RetryPolicy;
+/// # use rustwallet::core::domain::value_objects::Network;
+/// # use std::time::Duration;
+/// # async fn example() -> Result<(), rustwallet::core::domain::errors::DomainError> {
+/// let service = AlloyBlockchainService::builder()
+///     .network(Network::Mainnet)
+///     .rpc_urls(["https://eth.llamarpc.com", "https://ethereum-rpc.publicnode.com"])
+///     .timeout(Duration::from_secs(10))
+///     .retry_policy(RetryPolicy::default())
+///     .build()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct AlloyBlockchainServiceBuilder {
+    network: Option<Network>,
+    rpc_urls: Vec<String>,
+    timeout: Option<Duration>,
+    retry_policy: Option<RetryPolicy>,
+    headers: HashMap<String, String>,
+    ws_url: Option<String>,
+    verify_chain_id: Option<bool>,
+    #[cfg(feature = "safe")]
+    safe_service_url: Option<String>,
+}
+
+impl AlloyBlockchainServiceBuilder {
+    /// Network to connect to (required)
+    pub fn network(mut self, network: Network) -> Self {
+        self.network = Some(network);
+        self
+    }
+
+    /// RPC endpoints in priority order
+    ///
+    /// Requests go to the first URL and fail over to the next one on
+    /// transport errors. Defaults to the network's public RPC URL.
+    pub fn rpc_urls<I, S>(mut self, urls: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.rpc_urls = urls.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Per-request timeout (no timeout by default)
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Retry rate-limited and other transient failures (no retries by default)
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    /// Custom headers (e.g. `Authorization`, `x-api-key`) sent with every
+    /// JSON-RPC request, keeping API keys out of the URL
+    pub fn headers(mut self, headers: HashMap<String, String>) -> Self {
+        self.headers = headers;
+        self
+    }
+
+    /// WebSocket endpoint (`wss://...`) used by `watch_balance` to check once
+    /// per new block instead of polling
+    ///
+    /// Subscriptions need Alloy's WebSocket transport (the `cli` feature);
+    /// without it, or if the subscription fails, `watch_balance` polls.
+    pub fn ws_url(mut self, url: impl Into<String>) -> Self {
+        self.ws_url = Some(url.into());
+        self
+    }
+
+    /// Check on first use that the endpoint's `eth_chainId` matches the network
+    ///
+    /// On by default for remote endpoints and off for local ones
+    /// (`localhost`, loopback IPs), where dev nodes often run under another
+    /// chain ID. Pass `false` to opt out for a custom node.
+    pub fn verify_chain_id(mut self, verify: bool) -> Self {
+        self.verify_chain_id = Some(verify);
+        self
+    }
+
+    /// Safe Transaction Service used by `propose_safe_transaction` (`safe`
+    /// feature); defaults to Safe's hosted service for the network
+    #[cfg(feature = "safe")]
+    pub fn safe_service_url(mut self, url: impl Into<String>) -> Self {
+        self.safe_service_url = Some(url.into());
+        self
+    }
+
+    /// Build the service
+    pub async fn build(self) -> Result<AlloyBlockchainService, DomainError> {
+        let network = self.network.clone().ok_or_else(|| {
+            DomainError::ConfigurationError("AlloyBlockchainService requires a network".to_string())
+        })?;
+        let rpc_urls = if self.rpc_urls.is_empty() {
+            vec![default_rpc_url(&network)]
+        } else {
+            self.rpc_urls.clone()
+        };
+
+        let http_client = build_http_client_with_timeout(&self.headers, self.timeout)?;
+        let urls = rpc_urls
+            .iter()
+            .map(|rpc_url| parse_rpc_url(rpc_url))
+            .collect::<Result<Vec<_>, DomainError>>()?;
+        // A node on this machine lets Alloy poll more often; unlike Alloy's
+        // own guess this also recognizes loopback IPv6 (`[::1]`)
+        let is_local = is_local_rpc_url(urls[0].as_str());
+        let endpoints: Vec<_> = urls
+            .into_iter()
+            .map(|url| Http::with_client(http_client.clone(), url))
+            .collect();
+
+        let transport = match endpoints.len() {
+            1 => BoxTransport::new(endpoints.into_iter().next().expect("one endpoint")),
+            _ => BoxTransport::new(FailoverTransport::new(endpoints)),
+        };
+        let transport = match self.retry_policy {
+            Some(policy) => BoxTransport::new(
+                RetryBackoffLayer::new(
+                    policy.max_retries,
+                    policy.initial_backoff.as_millis() as u64,
+                    // No compute-unit budgeting; only back off on errors
+                    u64::MAX,
+                )
+                .layer(transport),
+            ),
+            None => transport,
+        };
+
+        let client = RpcClient::new(transport, is_local);
+        let chain_id_check = self.verify_chain_id.unwrap_or(!is_local).then(OnceCell::new);
+
+        Ok(AlloyBlockchainService {
+            rpc: Arc::new(ProviderBuilder::new().on_client(client)),
+            network,
+            config: self,
+            chain_id_check,
+            closed: watch::Sender::new(false),
+        })
+    }
 }
 
 impl AlloyBlockchainService {
+    /// Start configuring a service
+    pub fn builder() -> AlloyBlockchainServiceBuilder {
+        AlloyBlockchainServiceBuilder::default()
+    }
+
     /// Create new Alloy blockchain service
     pub async fn new(network: Network, rpc_url: &str) -> Result<Self, DomainError> {
-        let provider = ProviderBuilder::new()
-            .on_http(rpc_url.parse().map_err(|e| {
-                DomainError::ConfigurationError(format!("Invalid RPC URL: {}", e))
-            })?);
+        Self::builder().network(network).rpc_urls([rpc_url]).build().await
+    }
+
+    /// Create service that sends custom headers (e.g. `Authorization`, `x-api-key`)
+    /// with every JSON-RPC request, keeping API keys out of the URL
+    pub async fn new_with_headers(
+        network: Network,
+        rpc_url: &str,
+        headers: HashMap<String, String>,
+    ) -> Result<Self, DomainError> {
+        Self::builder()
+            .network(network)
+            .rpc_urls([rpc_url])
+            .headers(headers)
+            .build()
+            .await
+    }
+
+    /// Connect to a local node over its IPC socket (e.g. `~/.ethereum/geth.ipc`)
+    ///
+    /// For nodes that don't expose HTTP. `with_endpoint` on the result builds
+    /// an HTTP service as usual.
+    #[cfg(feature = "cli")]
+    pub async fn new_ipc(network: Network, path: impl Into<PathBuf>) -> Result<Self, DomainError> {
+        let path = path.into();
+        let client = ClientBuilder::default()
+            .ipc(IpcConnect::new(path.clone()))
+            .await
+            .map_err(|e| {
+                DomainError::NetworkError(format!("Failed to connect to IPC socket {}: {}", path.display(), e))
+            })?
+            .boxed();
+
+        Ok(Self {
+            rpc: Arc::new(ProviderBuilder::new().on_client(client)),
+            config: Self::builder().network(network.clone()),
+            network,
+            chain_id_check: None,
+            closed: watch::Sender::new(false),
+        })
+    }
 
-        Ok(Self { provider, network })
+    /// Set `address`'s balance to `wei` with `anvil_setBalance`
+    ///
+    /// For seeding accounts deterministically in tests against a local Anvil
+    /// (or Hardhat) node; other nodes reject the method.
+    #[cfg(feature = "dev")]
+    pub async fn set_balance(&self, address: &Address, wei: u128) -> Result<(), DomainError> {
+        self.rpc
+            .set_balance(address, wei)
+            .await
+            .map_err(|e| rpc_error("Failed to set balance", &e))
     }
 
     /// Create service with default RPC URL for network
     pub async fn new_with_default_rpc(network: Network) -> Result<Self, DomainError> {
-        let rpc_url = network.default_rpc_url().to_string();
-        Self::new(network, &rpc_url).await
+        Self::builder().network(network).build().await
+    }
+
+    /// Route balance reads, calls, broadcasts and block lookups through `rpc`
+    #[cfg(test)]
+    pub(crate) fn with_rpc(mut self, rpc: Arc<dyn EvmRpc>) -> Self {
+        self.rpc = rpc;
+        self
     }
 
     /// Get the network this service is connected to
     pub fn network(&self) -> &Network {
         &self.network
     }
-}
 
-#[async_trait]
-impl BlockchainService for AlloyBlockchainService {
-    async fn get_balance(&self, address: &Address) -> Result<Balance, DomainError> {
-        // Parse the address string into Alloy's Address type
-        let alloy_address: alloy::primitives::Address = address
-            .as_str()
-            .parse()
-            .map_err(|e| DomainError::BlockchainError(format!("Invalid address: {}", e)))?;
+    /// Transaction Service set with the builder's `safe_service_url`, if any
+    #[cfg(feature = "safe")]
+    pub(crate) fn configured_safe_service_url(&self) -> Option<&str> {
+        self.config.safe_service_url.as_deref()
+    }
+
+    /// Check that the endpoint serves this service's network (`eth_chainId`)
+    ///
+    /// Runs automatically before the first balance query, call or broadcast
+    /// unless disabled with the builder's `verify_chain_id(false)`; a match is
+    /// remembered, so the node is asked once. Querying or sending against a
+    /// node of another chain fails with a `ConfigurationError`.
+    pub async fn verify_chain_id(&self) -> Result<(), DomainError> {
+        let actual = self
+            .rpc
+            .chain_id()
+            .await
+            .map_err(|e| rpc_error("Failed to get chain ID", &e))?;
+        let expected = self.network.chain_id();
+        if actual != expected {
+            return Err(DomainError::ConfigurationError(format!(
+                "RPC endpoint serves chain ID {}, but {} is chain ID {}; check the RPC URL",
+                actual,
+                self.network.name(),
+                expected
+            )));
+        }
+        Ok(())
+    }
+
+    /// `verify_chain_id` once, if enabled
+    async fn ensure_chain_id(&self) -> Result<(), DomainError> {
+        match &self.chain_id_check {
+            Some(check) => check.get_or_try_init(|| self.verify_chain_id()).await.copied(),
+            None => Ok(()),
+        }
+    }
+
+    /// Query `eth_getBalance` at the given block (`latest`, `pending` or a number)
+    async fn query_balance(&self, address: &Address, block: BlockRef) -> Result<Balance, DomainError> {
+        ensure_evm_address(address, "address")?;
+        self.ensure_chain_id().await?;
 
-        // Get balance from the blockchain
         let balance_wei = self
-            .provider
-            .get_balance(alloy_address)
+            .rpc
+            .get_balance(address, block)
             .await
-            .map_err(|e| DomainError::NetworkError(format!("Failed to get balance: {}", e)))?;
+            .map_err(|e| match block {
+                BlockRef::Number(number) => {
+                    pruned_state_error(number, &e).unwrap_or_else(|| rpc_error("Failed to get balance", &e))
+                }
+                _ => rpc_error("Failed to get balance", &e),
+            })?;
 
-        // Convert U256 to u128 (will panic if balance > u128::MAX, which is extremely unlikely)
-        let balance_u128 = balance_wei.to::<u128>();
+        Ok(Balance::from_wei(balance_wei))
+    }
 
-        Ok(Balance::from_wei(balance_u128))
+    /// Execute a read-only `eth_call` of `to` with raw calldata at `block`
+    ///
+    /// Returns the raw (ABI-encoded) return bytes. Lets callers query any
+    /// view function without a typed wrapper; a revert surfaces as a
+    /// `BlockchainError` carrying the decoded revert reason.
+    pub async fn call(&self, to: &Address, data: Vec<u8>, block: BlockId) -> Result<Vec<u8>, DomainError> {
+        ensure_evm_address(to, "address")?;
+        self.call_at(to.clone(), data, block.into()).await
     }
 
-    async fn is_connected(&self) -> bool {
-        self.provider.get_block_number().await.is_ok()
+    /// Read the raw 32-byte word at `slot` of `contract`'s storage at `block` (`eth_getStorageAt`)
+    ///
+    /// Reaches state no view function exposes: EIP-1967 proxy implementation
+    /// slots, mapping entries (`keccak256(key ‖ slot)`), unverified contracts.
+    /// Decoding the word is left to the caller.
+    pub async fn get_storage_at(&self, contract: &Address, slot: U256, block: BlockId) -> Result<B256, DomainError> {
+        ensure_evm_address(contract, "address")?;
+        let value = self
+            .rpc
+            .storage_at(contract, slot.to_be_bytes(), block.into())
+            .await
+            .map_err(|e| rpc_error("Failed to read storage", &e))?;
+        Ok(B256::from(value))
     }
 
-    async fn get_block_number(&self) -> Result<u64, DomainError> {
-        self.provider
-            .get_block_number()
+    /// `call` of a contract the service knows by its Alloy address (Multicall3, rollup fee oracles)
+    async fn call_raw(&self, to: AlloyAddress, data: Vec<u8>, block: BlockRef) -> Result<Vec<u8>, DomainError> {
+        self.call_at(evm_address(to), data, block).await
+    }
+
+    /// `eth_call` of `to` with raw calldata, mapping a revert to a `BlockchainError`
+    async fn call_at(&self, to: Address, data: Vec<u8>, block: BlockRef) -> Result<Vec<u8>, DomainError> {
+        self.ensure_chain_id().await?;
+        let tx = TxRequest { data, ..TxRequest::new(to, 0) };
+
+        let output = self.rpc.call(&tx, block).await.map_err(|e| {
+            if let Some(reason) = revert::revert_reason(&e) {
+                return DomainError::BlockchainError(format!("eth_call failed: execution reverted: {}", reason));
+            }
+            match rpc_error("eth_call failed", &e) {
+                // The node answered: the call itself failed (revert, bad opcode, ...)
+                DomainError::NetworkError(message) if matches!(e, RpcFailure::Response(_)) => {
+                    DomainError::BlockchainError(message)
+                }
+                other => other,
+            }
+        })?;
+        Ok(output)
+    }
+
+    /// `call_raw` at the latest block that hands back the revert data of a reverted call
+    ///
+    /// `Ok(Err(data))` means the call reverted with `data` (the ABI-encoded
+    /// error, possibly empty), which callers such as CCIP-read need to inspect.
+    pub(crate) async fn try_call_raw(
+        &self,
+        to: AlloyAddress,
+        data: Vec<u8>,
+    ) -> Result<Result<Vec<u8>, Vec<u8>>, DomainError> {
+        self.ensure_chain_id().await?;
+        let tx = TxRequest { data, ..TxRequest::new(evm_address(to), 0) };
+
+        match self.rpc.call(&tx, BlockRef::Latest).await {
+            Ok(output) => Ok(Ok(output)),
+            Err(RpcFailure::Response(response)) if response.revert_data.is_some() => {
+                Ok(Err(response.revert_data.unwrap_or_default()))
+            }
+            Err(e) => Err(match rpc_error("eth_call failed", &e) {
+                DomainError::NetworkError(message) if matches!(e, RpcFailure::Response(_)) => {
+                    DomainError::BlockchainError(message)
+                }
+                other => other,
+            }),
+        }
+    }
+
+    /// `address`'s balance at every `step`-th block from `from_block` through `to_block`
+    ///
+    /// `to_block` is always included, even when the range is not a multiple
+    /// of `step`. Up to `SERIES_CONCURRENCY` balances are fetched at once and
+    /// returned in block order. Blocks whose state the node has pruned fail
+    /// the whole series with a `ConfigurationError` asking for an archive node.
+    pub async fn balance_series(
+        &self,
+        address: &Address,
+        from_block: u64,
+        to_block: u64,
+        step: u64,
+    ) -> Result<Vec<(u64, Balance)>, DomainError> {
+        let blocks = series_blocks(from_block, to_block, step)?;
+        stream::iter(blocks)
+            .map(|block| async move {
+                let balance = self.query_balance(address, BlockRef::Number(block)).await?;
+                Ok((block, balance))
+            })
+            .buffered(SERIES_CONCURRENCY)
+            .try_collect()
             .await
-            .map_err(|e| DomainError::NetworkError(format!("Failed to get block number: {}", e)))
     }
 
-    /// Transfer funds between addresses
+    /// Find the last block mined at or before `unix_ts`
+    ///
+    /// Binary-searches block timestamps via `eth_getBlockByNumber` (~log2(height) requests).
+    /// Timestamps before genesis or in the future are rejected with `InvalidQuery`.
+    pub async fn block_at_timestamp(&self, unix_ts: u64) -> Result<u64, DomainError> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(u64::MAX);
+        if unix_ts > now {
+            return Err(DomainError::InvalidQuery(format!(
+                "Timestamp {} is in the future (now is {})",
+                unix_ts, now
+            )));
+        }
+
+        let latest = self.get_block_number().await?;
+        search_block_by_timestamp(latest, unix_ts, |n| self.block_timestamp(n)).await
+    }
+
+    /// Timestamp of a block by number
+    async fn block_timestamp(&self, block_number: u64) -> Result<u64, DomainError> {
+        self.rpc
+            .get_block(BlockRef::Number(block_number))
+            .await
+            .map_err(|e| DomainError::NetworkError(format!("Failed to get block: {}", e)))?
+            .map(|block| block.timestamp)
+            .ok_or_else(|| DomainError::BlockchainError(format!("Block {} not found", block_number)))
+    }
+
+    /// Estimate gas limit × gas price for a transaction request
+    async fn estimate_tx_fee(&self, tx: &TxRequest) -> Result<FeeEstimate, DomainError> {
+        let gas_limit = self
+            .rpc
+            .estimate_gas(tx)
+            .await
+            .map_err(|e| estimate_gas_error(&e))?;
+        let gas_price = match tx.gas_price {
+            Some(gas_price) => gas_price,
+            None => self
+                .rpc
+                .gas_price()
+                .await
+                .map_err(|e| rpc_error("Failed to get gas price", &e))?,
+        };
+
+        let fee = FeeEstimate::from_details(FeeDetails::Evm { gas_limit, gas_price })
+            .ok_or_else(|| DomainError::BlockchainError("Gas cost overflows u128".to_string()))?;
+
+        match Rollup::for_chain_id(self.network.chain_id()) {
+            Some(rollup) => self.add_l1_data_fee(rollup, tx, fee).await,
+            None => Ok(fee),
+        }
+    }
+
+    /// Ask the rollup's fee oracle what posting `tx` to L1 costs
+    ///
+    /// OP Stack chains charge it on top of L2 gas, so it is added to the
+    /// total. Arbitrum charges it as extra L2 gas that `eth_estimateGas`
+    /// already includes, so it is only broken out.
+    async fn add_l1_data_fee(
+        &self,
+        rollup: Rollup,
+        tx: &TxRequest,
+        fee: FeeEstimate,
+    ) -> Result<FeeEstimate, DomainError> {
+        let FeeDetails::Evm { gas_limit, gas_price } = &fee.details else {
+            return Ok(fee);
+        };
+        let to = parse_evm_address(&tx.to, "to address")?;
+        let input = Bytes::from(tx.data.clone());
+        let overflow = || DomainError::BlockchainError("L1 data fee overflows u128".to_string());
+
+        match rollup {
+            Rollup::OpStack => {
+                let data = rollup::encode_l1_fee_call(
+                    self.network.chain_id(),
+                    to,
+                    U256::from(tx.value),
+                    input,
+                    *gas_limit,
+                );
+                let output = self.call_raw(GAS_PRICE_ORACLE_ADDRESS, data, BlockRef::Latest).await?;
+                let l1_data_fee = u128::try_from(rollup::decode_l1_fee(&output)?).map_err(|_| overflow())?;
+                fee.with_l1_data_fee(l1_data_fee).ok_or_else(overflow)
+            }
+            Rollup::Arbitrum => {
+                let data = rollup::encode_l1_gas_call(to, input);
+                let output = self.call_raw(NODE_INTERFACE_ADDRESS, data, BlockRef::Latest).await?;
+                let l1_gas = rollup::decode_l1_gas(&output)?;
+                let l1_data_fee = (l1_gas as u128).checked_mul(*gas_price).ok_or_else(overflow)?;
+                Ok(FeeEstimate {
+                    l1_data_fee: Some(Amount::from_wei(l1_data_fee)),
+                    ..fee
+                })
+            }
+        }
+    }
+
+    /// Transfer funds, optionally with an explicit nonce
     ///
     /// Implements complete transaction workflow:
-    /// 1. Parse private key and create wallet
+    /// 1. Check the signer controls `from`
     /// 2. Build transaction with proper parameters
-    /// 3. Sign transaction
-    /// 4. Broadcast to network
-    /// 5. Return transaction hash
+    /// 3. Sign transaction with `signer` (a local key, a Ledger, ...)
+    /// 4. Skip broadcasting if a transaction with the same hash is already known
+    /// 5. Check the balance covers amount plus gas
+    /// 6. Broadcast to network and return the transaction hash
+    ///
+    /// Step 4 makes retries idempotent: if a broadcast timed out but the node
+    /// accepted it, re-sending with the same `nonce` (and unchanged fees)
+    /// produces the same signed transaction, so the existing hash is returned
+    /// instead of broadcasting again. It comes before step 5 because the
+    /// accepted transaction may already have spent the balance. With
+    /// `nonce: None` the next pending nonce is used, which on retry would
+    /// create a second transaction; nothing can match it, so the balance is
+    /// checked first and nothing is signed when it falls short.
     ///
     /// # Security Notes
     /// - Private keys are handled in memory only
     /// - Keys are not logged or persisted
     /// - Use environment variables or secure key storage in production
-    async fn transfer(
+    pub async fn transfer_with_nonce(
         &self,
         from: &Address,
         to: &Address,
         amount: u128,
-        private_key: &str,
+        signer: &dyn Signer,
+        nonce: Option<u64>,
     ) -> Result<TransactionHash, DomainError> {
-        // Step 1: Parse private key and create signer
-        let signer: PrivateKeySigner = private_key
-            .parse()
-            .map_err(|_| DomainError::InvalidPrivateKey)?;
+        self.send_transfer(from, to, amount, signer, nonce, None).await
+    }
 
-        // Verify that the signer's address matches the from address
-        let signer_address = signer.address();
-        let from_alloy: AlloyAddress = from
-            .as_str()
-            .parse()
-            .map_err(|e| DomainError::BlockchainError(format!("Invalid from address: {}", e)))?;
+    /// [`transfer_with_nonce`](Self::transfer_with_nonce), as a legacy
+    /// transaction paying `gas_price` if one is given
+    async fn send_transfer(
+        &self,
+        from: &Address,
+        to: &Address,
+        amount: u128,
+        signer: &dyn Signer,
+        nonce: Option<u64>,
+        gas_price: Option<u128>,
+    ) -> Result<TransactionHash, DomainError> {
+        // Step 1: Check the signer controls `from`
+        Self::check_signer(from, signer)?;
+
+        // Step 2: Build transaction request
+        ensure_evm_address(to, "to address")?;
+        let tx = TxRequest { from: Some(from.clone()), gas_price, ..TxRequest::new(to.clone(), amount) };
+
+        let Some(nonce) = nonce else {
+            self.ensure_can_afford(from, amount, &tx).await?;
+            return self.sign_and_send(signer, tx).await;
+        };
+
+        // Steps 3-6: a retry of an accepted transaction reproduces its hash
+        let (envelope, tx_hash) = self.sign_filled(signer, TxRequest { nonce: Some(nonce), ..tx.clone() }).await?;
+        if self.is_broadcast(&tx_hash).await? {
+            return Ok(tx_hash);
+        }
+        self.ensure_can_afford(from, amount, &tx).await?;
+        self.broadcast(&envelope, tx_hash).await
+    }
 
-        if signer_address != from_alloy {
-            return Err(DomainError::TransferFailed(
-                "Private key does not match from address".to_string(),
+    /// Check the balance of `from` covers `amount` plus the estimated fee of `tx`
+    async fn ensure_can_afford(&self, from: &Address, amount: u128, tx: &TxRequest) -> Result<(), DomainError> {
+        let fee = self.estimate_tx_fee(tx).await?;
+        let balance = self.get_balance(from).await?;
+        ensure_sufficient_balance(balance.to_wei(), amount, fee.total_native.to_wei())
+    }
+
+    /// Send the whole balance of `from` to `to`, minus the network fee,
+    /// returning the transaction hash and the amount sent
+    ///
+    /// Uses a legacy gas price so the fee is exactly `gas_limit × gas_price`
+    /// (plus the L1 data fee on rollups) and nothing but refunded gas is left
+    /// behind. Fails with `InsufficientBalance` if the balance does not cover
+    /// the fee.
+    pub async fn sweep(
+        &self,
+        from: &Address,
+        to: &Address,
+        signer: &dyn Signer,
+    ) -> Result<(TransactionHash, Amount), DomainError> {
+        Self::check_signer(from, signer)?;
+        ensure_evm_address(to, "to address")?;
+
+        // Estimate with a zero value: the full balance would leave nothing for gas
+        let tx = TxRequest { from: Some(from.clone()), ..TxRequest::new(to.clone(), 0) };
+        let fee = self.estimate_tx_fee(&tx).await?;
+        let FeeDetails::Evm { gas_limit, gas_price } = fee.details else {
+            return Err(DomainError::BlockchainError(
+                "Unexpected fee details for an EVM chain".to_string(),
             ));
+        };
+
+        let balance = self.get_balance(from).await?.to_wei();
+        let cost = fee.total_native.to_wei();
+        let amount = balance.checked_sub(cost).filter(|amount| *amount > 0).ok_or(
+            DomainError::InsufficientBalance {
+                required: cost.saturating_add(1),
+                available: balance,
+            },
+        )?;
+        tracing::info!("Sweeping {} Wei ({} Wei balance - {} Wei fee)", amount, balance, cost);
+
+        let tx = TxRequest {
+            value: amount,
+            gas_limit: Some(gas_limit),
+            gas_price: Some(gas_price),
+            ..tx
+        };
+        let tx_hash = self.sign_and_send(signer, tx).await?;
+        Ok((tx_hash, Amount::from_wei(amount)))
+    }
+
+    /// Replace the stuck transfer `original`: same nonce, recipient and amount, higher fees
+    ///
+    /// Nodes only accept a replacement that raises both the max fee and the
+    /// priority fee by at least 10% over the replaced transaction's own, so
+    /// `original` is looked up for its nonce and fees. `new_max_fee` must be at
+    /// least 10% above its max fee (or gas price); otherwise `InvalidQuery` is
+    /// returned before anything is signed. The priority fee is 10% above the
+    /// original's, or the current estimate if higher, capped at `new_max_fee`;
+    /// a cap below the required bump is also `InvalidQuery`.
+    pub async fn speed_up(
+        &self,
+        original: &TransactionHash,
+        from: &Address,
+        to: &Address,
+        amount: u128,
+        new_max_fee: u128,
+        signer: &dyn Signer,
+    ) -> Result<TransactionHash, DomainError> {
+        Self::check_signer(from, signer)?;
+        ensure_evm_address(to, "to address")?;
+
+        let stuck = self
+            .rpc
+            .transaction(original)
+            .await
+            .map_err(|e| rpc_error("Failed to look up transaction", &e))?
+            .ok_or_else(|| DomainError::InvalidQuery(format!("Transaction {} is not known to the node", original)))?;
+        if stuck.from != *from {
+            return Err(DomainError::InvalidQuery(format!(
+                "Transaction {} was sent by {}, not {}",
+                original, stuck.from, from
+            )));
         }
 
-        // Step 2: Parse destination address
-        let to_alloy: AlloyAddress = to
+        let min_max_fee = min_replacement_fee(stuck.fees.max_fee_per_gas);
+        if new_max_fee < min_max_fee {
+            return Err(DomainError::InvalidQuery(format!(
+                "New max fee {} Wei is too low to replace {}: need at least {} Wei (10% above its {} Wei)",
+                new_max_fee, original, min_max_fee, stuck.fees.max_fee_per_gas
+            )));
+        }
+        let min_priority_fee = min_replacement_fee(stuck.fees.max_priority_fee_per_gas);
+        if new_max_fee < min_priority_fee {
+            return Err(DomainError::InvalidQuery(format!(
+                "New max fee {} Wei is too low to replace {}: its priority fee needs at least {} Wei \
+                 (10% above its {} Wei)",
+                new_max_fee, original, min_priority_fee, stuck.fees.max_priority_fee_per_gas
+            )));
+        }
+        let estimate = self
+            .rpc
+            .eip1559_fees()
+            .await
+            .map_err(|e| rpc_error("Failed to estimate fees", &e))?;
+        let current_priority_fee = estimate.map_or(0, |estimate| estimate.max_priority_fee_per_gas);
+        let priority_fee = min_priority_fee.max(current_priority_fee).min(new_max_fee);
+        let original_nonce = stuck.nonce;
+
+        let mut tx = TxRequest {
+            from: Some(from.clone()),
+            nonce: Some(original_nonce),
+            max_fee_per_gas: Some(new_max_fee),
+            max_priority_fee_per_gas: Some(priority_fee),
+            ..TxRequest::new(to.clone(), amount)
+        };
+        let gas_limit = self
+            .rpc
+            .estimate_gas(&tx)
+            .await
+            .map_err(|e| estimate_gas_error(&e))?;
+        tx.gas_limit = Some(gas_limit);
+
+        let balance = self.get_balance(from).await?;
+        let gas_cost = (gas_limit as u128).checked_mul(new_max_fee).ok_or_else(|| {
+            DomainError::BlockchainError("Gas cost overflows u128".to_string())
+        })?;
+        ensure_sufficient_balance(balance.to_wei(), amount, gas_cost)?;
+
+        tracing::info!(
+            "Replacing nonce {} with max fee {} Wei (priority {} Wei)",
+            original_nonce,
+            new_max_fee,
+            priority_fee
+        );
+        self.sign_and_send(signer, tx).await
+    }
+
+    /// Check that `signer` controls `from`
+    fn check_signer(from: &Address, signer: &dyn Signer) -> Result<(), DomainError> {
+        if signer.address() != *from {
+            return Err(DomainError::SignerMismatch {
+                signer: signer.address().to_string(),
+                from: from.to_string(),
+            });
+        }
+        ensure_evm_address(from, "from address")
+    }
+
+    /// Fill the remaining fields of `tx`, sign it and broadcast it
+    ///
+    /// If a transaction with the same hash is already known (a retry with an
+    /// explicit nonce), its hash is returned without broadcasting again.
+    async fn sign_and_send(&self, signer: &dyn Signer, tx: TxRequest) -> Result<TransactionHash, DomainError> {
+        let (envelope, tx_hash) = self.sign_filled(signer, tx).await?;
+        if self.is_broadcast(&tx_hash).await? {
+            return Ok(tx_hash);
+        }
+        self.broadcast(&envelope, tx_hash).await
+    }
+
+    /// Fill the remaining fields of `tx` from the node and sign it
+    async fn sign_filled(
+        &self,
+        signer: &dyn Signer,
+        tx: TxRequest,
+    ) -> Result<(TxEnvelope, TransactionHash), DomainError> {
+        self.ensure_chain_id().await?;
+        let unsigned = self.fill(tx).await.map_err(|e| match revert::revert_reason(&e) {
+            Some(reason) => DomainError::WouldRevert(reason),
+            None => DomainError::TransferFailed(format!("Failed to prepare transaction: {}", e)),
+        })?;
+
+        let envelope = sign_typed_transaction(signer, unsigned).await?;
+        let tx_hash = TransactionHash::new(format!("{:?}", envelope.tx_hash()))?;
+        Ok((envelope, tx_hash))
+    }
+
+    /// Build the transaction `tx` describes, filling the nonce (next
+    /// pending), gas limit, fees and chain id it leaves open from the node
+    ///
+    /// Fees follow Alloy's gas filler: a legacy gas price when `tx` sets
+    /// one, EIP-1559 fees otherwise, and a legacy gas price on chains
+    /// without a base fee.
+    async fn fill(&self, tx: TxRequest) -> RpcResult<TypedTransaction> {
+        let nonce = async {
+            match (tx.nonce, &tx.from) {
+                (Some(nonce), _) => Ok(nonce),
+                (None, Some(from)) => self.rpc.transaction_count(from, BlockRef::Pending).await,
+                (None, None) => Err(RpcFailure::InvalidRequest("no sender to pick a nonce for".to_string())),
+            }
+        };
+        let gas_limit = async {
+            match tx.gas_limit {
+                Some(gas_limit) => Ok(gas_limit),
+                None => self.rpc.estimate_gas(&tx).await,
+            }
+        };
+        let fees = async {
+            match (tx.gas_price, tx.max_fee_per_gas, tx.max_priority_fee_per_gas) {
+                (Some(gas_price), _, _) => Ok(Err(gas_price)),
+                (None, Some(max_fee), Some(priority_fee)) => Ok(Ok((max_fee, priority_fee))),
+                _ => match self.rpc.eip1559_fees().await? {
+                    Some(fees) => Ok(Ok((fees.max_fee_per_gas, fees.max_priority_fee_per_gas))),
+                    None => self.rpc.gas_price().await.map(Err),
+                },
+            }
+        };
+        let (chain_id, nonce, gas_limit, fees) =
+            future::try_join4(self.rpc.chain_id(), nonce, gas_limit, fees).await?;
+
+        let to = TxKind::Call(
+            tx.to
+                .as_str()
+                .parse()
+                .map_err(|e| RpcFailure::InvalidRequest(format!("invalid address {}: {}", tx.to, e)))?,
+        );
+        let value = U256::from(tx.value);
+        let input = Bytes::from(tx.data);
+        Ok(match fees {
+            Ok((max_fee_per_gas, max_priority_fee_per_gas)) => TypedTransaction::Eip1559(TxEip1559 {
+                chain_id,
+                nonce,
+                gas_limit,
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+                to,
+                value,
+                input,
+                ..Default::default()
+            }),
+            Err(gas_price) => TypedTransaction::Legacy(TxLegacy {
+                chain_id: Some(chain_id),
+                nonce,
+                gas_price,
+                gas_limit,
+                to,
+                value,
+                input,
+            }),
+        })
+    }
+
+    /// Whether the node already knows this exact signed transaction
+    async fn is_broadcast(&self, tx_hash: &TransactionHash) -> Result<bool, DomainError> {
+        let known = self
+            .rpc
+            .has_transaction(tx_hash)
+            .await
+            .map_err(|e| rpc_error("Failed to look up transaction", &e))?;
+        if known {
+            tracing::info!("Transaction {} already broadcast, not sending again", tx_hash);
+        }
+        Ok(known)
+    }
+
+    /// Broadcast a signed transaction, treating "already known" as success
+    async fn broadcast(&self, envelope: &TxEnvelope, tx_hash: TransactionHash) -> Result<TransactionHash, DomainError> {
+        let sent = self.rpc.send_raw(&envelope.encoded_2718()).await;
+        match sent {
+            Ok(sent_hash) => Ok(sent_hash),
+            // Raced with an earlier broadcast of the same transaction
+            Err(e) if matches!(send_error(&e), DomainError::AlreadyKnown(_)) => Ok(tx_hash),
+            Err(e) => Err(send_error(&e)),
+        }
+    }
+
+    /// Sign an EIP-1559 transfer without touching the network
+    ///
+    /// Every field that would normally be filled from the node (nonce, gas
+    /// limit, fees) comes from `tx`; only the chain id is taken from this
+    /// service's network. Returns the signed transaction as 0x-prefixed
+    /// EIP-2718 hex, ready for [`broadcast_raw`](Self::broadcast_raw), and
+    /// its hash (`keccak256` of the raw bytes).
+    pub fn sign_transaction_offline(
+        &self,
+        tx: &OfflineTransaction,
+        private_key: &SecretKey,
+    ) -> Result<(String, TransactionHash), DomainError> {
+        let signer = keys::parse_evm_signer(private_key.expose_secret())?;
+        let to: AlloyAddress = tx
+            .to
             .as_str()
             .parse()
             .map_err(|e| DomainError::BlockchainError(format!("Invalid to address: {}", e)))?;
 
-        // Step 3: Check sender balance
-        let balance = self.get_balance(from).await?;
-        if balance.to_wei() < amount {
-            return Err(DomainError::InsufficientBalance);
+        let mut unsigned = TxEip1559 {
+            chain_id: self.network.chain_id(),
+            nonce: tx.nonce,
+            gas_limit: tx.gas_limit,
+            max_fee_per_gas: tx.max_fee_per_gas,
+            max_priority_fee_per_gas: tx.max_priority_fee_per_gas,
+            to: TxKind::Call(to),
+            value: U256::from(tx.value),
+            input: Bytes::from(tx.data.clone()),
+            ..Default::default()
+        };
+        let signature = signer
+            .sign_transaction_sync(&mut unsigned)
+            .map_err(|e| DomainError::TransferFailed(format!("Failed to sign transaction: {}", e)))?;
+        let raw = TxEnvelope::from(unsigned.into_signed(signature)).encoded_2718();
+
+        let tx_hash = TransactionHash::new(format!("{:?}", keccak256(&raw)))?;
+        Ok((format!("0x{}", alloy::hex::encode(raw)), tx_hash))
+    }
+
+    /// Submit a transaction signed elsewhere (e.g. by `sign_transaction_offline`)
+    ///
+    /// `raw_tx_hex` is the EIP-2718 encoding, with or without `0x`.
+    pub async fn broadcast_raw(&self, raw_tx_hex: &str) -> Result<TransactionHash, DomainError> {
+        let raw = alloy::hex::decode(raw_tx_hex.trim())
+            .map_err(|e| DomainError::TransferFailed(format!("Invalid raw transaction hex: {}", e)))?;
+        self.ensure_chain_id().await?;
+        self.rpc.send_raw(&raw).await.map_err(|e| send_error(&e))
+    }
+
+    /// Derive the EOA address controlled by a private key
+    pub fn address_from_private_key(private_key: &str) -> Result<Address, DomainError> {
+        keys::evm_address_from_private_key(private_key)
+    }
+}
+
+#[async_trait]
+impl BlockchainService for AlloyBlockchainService {
+    fn chain_type(&self) -> ChainType {
+        ChainType::Ethereum
+    }
+
+    async fn get_balance(&self, address: &Address) -> Result<Balance, DomainError> {
+        self.query_balance(address, BlockRef::Latest).await
+    }
+
+    async fn get_pending_balance(&self, address: &Address) -> Result<Balance, DomainError> {
+        self.query_balance(address, BlockRef::Pending).await
+    }
+
+    /// Old blocks require an archive node; a pruned node's "missing trie node"
+    /// (or similar) refusal becomes a `ConfigurationError` saying so.
+    async fn get_balance_at_block(&self, address: &Address, block_number: u64) -> Result<Balance, DomainError> {
+        self.query_balance(address, BlockRef::Number(block_number)).await
+    }
+
+    /// `eth_getBalance`, `eth_getTransactionCount` and `eth_getCode`, sent concurrently
+    async fn get_account_info(&self, address: &Address) -> Result<AccountInfo, DomainError> {
+        ensure_evm_address(address, "address")?;
+        let nonce = async {
+            self.rpc
+                .transaction_count(address, BlockRef::Latest)
+                .await
+                .map_err(|e| rpc_error("Failed to get transaction count", &e))
+        };
+        let code = async {
+            self.rpc
+                .code(address)
+                .await
+                .map_err(|e| rpc_error("Failed to get code", &e))
+        };
+        let (balance, nonce, code) = future::try_join3(self.get_balance(address), nonce, code).await?;
+        Ok(AccountInfo::new(address.clone(), balance)
+            .with_transaction_count(nonce)
+            .with_code_size(code.len() as u64))
+    }
+
+    /// One `eth_call` to Multicall3's `getEthBalance` for every address
+    ///
+    /// Falls back to one `eth_getBalance` per address if the batch fails
+    /// (e.g. on a dev chain without Multicall3 deployed).
+    async fn get_balances(&self, addresses: &[Address]) -> Vec<Result<Balance, DomainError>> {
+        if addresses.is_empty() {
+            return Vec::new();
         }
 
-        // Step 4: Create wallet from signer
-        let wallet = EthereumWallet::from(signer);
+        let batch = async {
+            let targets = addresses
+                .iter()
+                .map(|address| {
+                    address
+                        .as_str()
+                        .parse::<AlloyAddress>()
+                        .map_err(|e| DomainError::BlockchainError(format!("Invalid address: {}", e)))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let output = self
+                .call_raw(MULTICALL3_ADDRESS, multicall::encode_balances(&targets), BlockRef::Latest)
+                .await?;
+            multicall::decode_balances(&output, targets.len())
+        };
+
+        match batch.await {
+            Ok(balances) => balances.into_iter().map(multicall_balance).collect(),
+            Err(e) => {
+                tracing::warn!("Multicall balance batch failed ({}), querying addresses one by one", e);
+                future::join_all(addresses.iter().map(|address| self.get_balance(address))).await
+            }
+        }
+    }
 
-        // Step 5: Create provider with wallet
-        let rpc_url = self.network.default_rpc_url();
-        let provider_with_wallet = ProviderBuilder::new()
-            .with_recommended_fillers()
-            .wallet(wallet)
-            .on_http(rpc_url.parse().map_err(|e| {
-                DomainError::ConfigurationError(format!("Invalid RPC URL: {}", e))
-            })?);
+    async fn estimate_fee(
+        &self,
+        from: &Address,
+        to: &Address,
+        amount: u128,
+    ) -> Result<FeeEstimate, DomainError> {
+        ensure_evm_address(from, "from address")?;
+        ensure_evm_address(to, "to address")?;
 
-        // Step 6: Build transaction request
-        let tx = TransactionRequest::default()
-            .to(to_alloy)
-            .value(U256::from(amount))
-            .from(from_alloy);
+        let tx = TxRequest { from: Some(from.clone()), ..TxRequest::new(to.clone(), amount) };
+        self.estimate_tx_fee(&tx).await
+    }
 
-        // Step 7: Send transaction and get pending transaction
-        let pending_tx = provider_with_wallet
-            .send_transaction(tx)
+    /// Average recent block time divided by the share of the last
+    /// [`FEE_HISTORY_BLOCKS`] blocks whose base fee plus
+    /// [`INCLUSION_PERCENTILE`]th-percentile priority fee the gas price covers
+    async fn estimate_confirmation_time(&self, fee: &FeeEstimate) -> Result<Duration, DomainError> {
+        let FeeDetails::Evm { gas_price, .. } = fee.details else {
+            return Err(DomainError::ConfigurationError(
+                "Not an EVM fee estimate".to_string(),
+            ));
+        };
+        let history = self
+            .rpc
+            .fee_history(FEE_HISTORY_BLOCKS, &[INCLUSION_PERCENTILE])
             .await
-            .map_err(|e| {
-                DomainError::TransferFailed(format!("Failed to send transaction: {}", e))
-            })?;
+            .map_err(|e| DomainError::NetworkError(format!("Failed to get fee history: {}", e)))?;
+        let (included, blocks) = blocks_including(&history, gas_price);
+        if blocks < 2 {
+            return Err(DomainError::BlockchainError(
+                "Not enough fee history to estimate confirmation time".to_string(),
+            ));
+        }
+        if included == 0 {
+            return Err(DomainError::BlockchainError(format!(
+                "A gas price of {} wei is below what the last {} blocks included",
+                gas_price, blocks
+            )));
+        }
+
+        let newest = history.oldest_block + blocks - 1;
+        let (oldest_time, newest_time) = future::try_join(
+            self.block_timestamp(history.oldest_block),
+            self.block_timestamp(newest),
+        )
+        .await?;
+        let block_time = newest_time.saturating_sub(oldest_time) as f64 / (blocks - 1) as f64;
+        Ok(Duration::from_secs_f64(block_time * blocks as f64 / included as f64))
+    }
+
+    async fn wait_for_receipt(
+        &self,
+        tx_hash: &TransactionHash,
+        timeout: Duration,
+    ) -> Result<TransactionReceipt, DomainError> {
+        tx_hash.validate().map_err(|_| DomainError::InvalidTransactionHash)?;
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            let receipt = self
+                .rpc
+                .receipt(tx_hash)
+                .await
+                .map_err(|e| DomainError::NetworkError(format!("Failed to get receipt: {}", e)))?;
+
+            if let Some(receipt) = receipt {
+                return Ok(receipt);
+            }
+            if Instant::now() + RECEIPT_POLL_INTERVAL > deadline {
+                return Err(DomainError::BlockchainError(format!(
+                    "Timed out after {:?} waiting for receipt of {}",
+                    timeout, tx_hash
+                )));
+            }
+            tokio::time::sleep(RECEIPT_POLL_INTERVAL).await;
+        }
+    }
 
-        // Step 8: Get transaction hash
-        let tx_hash = *pending_tx.tx_hash();
+    /// A receipt means the transaction was mined; without one, the mempool decides
+    /// between `Pending` and `NotFound`
+    async fn get_transaction_status(&self, tx_hash: &TransactionHash) -> Result<TxStatus, DomainError> {
+        tx_hash.validate().map_err(|_| DomainError::InvalidTransactionHash)?;
 
-        // Convert to our domain TransactionHash
-        let tx_hash_str = format!("{:?}", tx_hash);
-        TransactionHash::new(tx_hash_str)
+        let receipt = self
+            .rpc
+            .receipt(tx_hash)
+            .await
+            .map_err(|e| rpc_error("Failed to get receipt", &e))?;
+        if let Some(receipt) = receipt {
+            let block = receipt.block_number;
+            return Ok(if receipt.success {
+                TxStatus::Confirmed { block }
+            } else {
+                TxStatus::Reverted { block }
+            });
+        }
+
+        let known = self
+            .rpc
+            .has_transaction(tx_hash)
+            .await
+            .map_err(|e| rpc_error("Failed to get transaction", &e))?;
+        Ok(if known { TxStatus::Pending } else { TxStatus::NotFound })
+    }
+
+    /// Subscribes to `newHeads` when a WebSocket URL is configured
+    ///
+    /// The stream ends on [`shutdown`](BlockchainService::shutdown), which
+    /// drops the subscription and closes its WebSocket.
+    fn new_block_ticks(&self, fallback: BoxStream<'static, ()>) -> BoxStream<'static, ()> {
+        let ticks = match self.config.ws_url.clone() {
+            Some(ws_url) => subscribe_new_blocks(ws_url, fallback),
+            None => fallback,
+        };
+        let mut closed = self.closed.subscribe();
+        let shut_down = async move {
+            // Outliving the service is not a shutdown
+            if closed.wait_for(|closed| *closed).await.is_err() {
+                future::pending::<()>().await;
+            }
+        };
+        ticks.take_until(shut_down).boxed()
+    }
+
+    async fn shutdown(&self) {
+        self.closed.send_replace(true);
+    }
+
+    async fn with_endpoint(&self, url: &str) -> Result<Arc<dyn BlockchainService>, DomainError> {
+        let sibling = self.config.clone().rpc_urls([url]).build().await?;
+        Ok(Arc::new(sibling))
+    }
+
+    /// Retries briefly on rate limits; an endpoint that keeps throttling is
+    /// still reported as connected, since it is up, just busy
+    async fn is_connected(&self) -> bool {
+        let policy = RetryPolicy::new(CONNECT_RETRIES, CONNECT_RETRY_DELAY);
+        let rate_limited = |e: &DomainError| matches!(e, DomainError::RateLimited(_));
+        match retry_with_backoff(&policy, rate_limited, || self.get_block_number()).await {
+            Ok(_) => true,
+            Err(DomainError::RateLimited(e)) => {
+                tracing::warn!("Endpoint is rate limiting requests: {}", e);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    async fn get_block_number(&self) -> Result<u64, DomainError> {
+        self.rpc
+            .block_number()
+            .await
+            .map_err(|e| rpc_error("Failed to get block number", &e))
+    }
+
+    /// Maps to the `latest`, `safe` and `finalized` block tags
+    ///
+    /// Chains without beacon-chain finality (or old nodes) may not know the
+    /// `safe`/`finalized` tags and return an RPC error.
+    async fn get_height(&self, finality: Finality) -> Result<u64, DomainError> {
+        let tag = match finality {
+            Finality::Latest => return self.get_block_number().await,
+            Finality::Safe => BlockRef::Safe,
+            Finality::Finalized => BlockRef::Finalized,
+        };
+        self.rpc
+            .get_block(tag)
+            .await
+            .map_err(|e| DomainError::NetworkError(format!("Failed to get {} block: {}", finality, e)))?
+            .map(|block| block.number)
+            .ok_or_else(|| DomainError::BlockchainError(format!("No {} block reported by node", finality)))
+    }
+
+    /// Adds the node's `web3_clientVersion` (e.g. "Geth/v1.14.0-stable/linux-amd64/go1.22")
+    async fn diagnose(&self) -> ConnectionDiagnostics {
+        let diagnostics = ConnectionDiagnostics::measure(self.get_block_number()).await;
+        if !diagnostics.reachable {
+            return diagnostics;
+        }
+        let version = self.rpc.client_version().await.ok();
+        diagnostics.with_rpc_version(version)
+    }
+
+    /// Transfer funds between addresses
+    ///
+    /// Picks the next pending nonce; see [`AlloyBlockchainService::transfer_with_nonce`]
+    /// for retry-safe sends.
+    async fn transfer(
+        &self,
+        from: &Address,
+        to: &Address,
+        amount: u128,
+        signer: &dyn Signer,
+    ) -> Result<TransactionHash, DomainError> {
+        self.transfer_with_nonce(from, to, amount, signer, None).await
+    }
+
+    /// Sent as a legacy transaction, so `gas_price` is exactly what each unit of gas costs
+    async fn transfer_with_gas_price(
+        &self,
+        from: &Address,
+        to: &Address,
+        amount: u128,
+        signer: &dyn Signer,
+        gas_price: u128,
+    ) -> Result<TransactionHash, DomainError> {
+        self.send_transfer(from, to, amount, signer, None, Some(gas_price)).await
+    }
+
+    fn signer_for_key(&self, private_key: &SecretKey) -> Result<Box<dyn Signer>, DomainError> {
+        Ok(Box::new(PrivateKeySigner::new(private_key, &self.network)?))
+    }
+}
+
+/// Sign `tx` with `signer`, which receives the EIP-2718 signing encoding
+async fn sign_typed_transaction(signer: &dyn Signer, tx: TypedTransaction) -> Result<TxEnvelope, DomainError> {
+    async fn signature<T>(signer: &dyn Signer, tx: &T) -> Result<PrimitiveSignature, DomainError>
+    where
+        T: SignableTransaction<PrimitiveSignature>,
+    {
+        let signature = signer.sign_transaction(&tx.encoded_for_signing()).await?;
+        PrimitiveSignature::try_from(signature.as_slice())
+            .map_err(|e| DomainError::TransferFailed(format!("Signer returned an invalid signature: {}", e)))
+    }
+
+    match tx {
+        TypedTransaction::Legacy(tx) => {
+            let signature = signature(signer, &tx).await?;
+            Ok(tx.into_signed(signature).into())
+        }
+        TypedTransaction::Eip2930(tx) => {
+            let signature = signature(signer, &tx).await?;
+            Ok(tx.into_signed(signature).into())
+        }
+        TypedTransaction::Eip1559(tx) => {
+            let signature = signature(signer, &tx).await?;
+            Ok(tx.into_signed(signature).into())
+        }
+        TypedTransaction::Eip4844(_) | TypedTransaction::Eip7702(_) => Err(DomainError::TransferFailed(
+            "Blob and EIP-7702 transactions are not supported".to_string(),
+        )),
+    }
+}
+
+/// Parse an HTTP(S) RPC endpoint; IPv6 hosts must be bracketed (`http://[::1]:8545`)
+fn parse_rpc_url(rpc_url: &str) -> Result<Url, DomainError> {
+    let invalid = |reason: String| {
+        DomainError::ConfigurationError(format!("Invalid RPC URL '{}': {}", rpc_url, reason))
+    };
+    let url = Url::parse(rpc_url).map_err(|e| {
+        // `http://::1:8545` fails as an invalid port; point at the usual mistake
+        if rpc_url.matches(':').count() > 2 && !rpc_url.contains('[') {
+            invalid(format!("{} (write IPv6 addresses in brackets, e.g. http://[::1]:8545)", e))
+        } else {
+            invalid(e.to_string())
+        }
+    })?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(invalid(format!(
+            "unsupported scheme '{}' (use http(s)://, or new_ipc for a socket path)",
+            url.scheme()
+        )));
+    }
+    if url.host().is_none() {
+        return Err(invalid("missing host".to_string()));
+    }
+    Ok(url)
+}
+
+/// Map an RPC failure to a domain error, flagging rate limits (HTTP 429 or a
+/// provider's rate-limit error response) as `RateLimited`
+fn rpc_error(context: &str, error: &RpcFailure) -> DomainError {
+    let message = format!("{}: {}", context, error);
+    if let Some(classified) = classify_tx_error(error, &message) {
+        return classified;
+    }
+    match error {
+        RpcFailure::InvalidRequest(_) => DomainError::BlockchainError(message),
+        _ if error.is_rate_limited() => DomainError::RateLimited(message),
+        _ => DomainError::NetworkError(message),
     }
 }
 
+/// Of the blocks in `history`, how many `gas_price` would have made it into
+/// (covering the base fee plus the lowest requested priority fee percentile),
+/// and how many there were
+fn blocks_including(history: &FeeHistory, gas_price: u128) -> (u64, u64) {
+    let rewards = history.reward.as_deref().unwrap_or_default();
+    let included = history
+        .base_fee_per_gas
+        .iter()
+        .zip(rewards)
+        .filter(|(base_fee, reward)| {
+            let tip = reward.first().copied().unwrap_or_default();
+            gas_price >= base_fee.saturating_add(tip)
+        })
+        .count();
+    (included as u64, history.gas_used_ratio.len() as u64)
+}
+
+/// Map a failed `eth_estimateGas`, reporting a revert as a transfer that
+/// would fail, with its decoded reason
+fn estimate_gas_error(error: &RpcFailure) -> DomainError {
+    match revert::revert_reason(error) {
+        Some(reason) => DomainError::WouldRevert(reason),
+        None => DomainError::NetworkError(format!("Failed to estimate gas: {}", error)),
+    }
+}
+
+/// One balance from a Multicall3 batch; `None` is a failed read
+///
+/// The word is a `uint256`, so a node could return more than fits in a
+/// `u128`; that is rejected rather than truncated.
+fn multicall_balance(balance: Option<U256>) -> Result<Balance, DomainError> {
+    let wei = balance.ok_or_else(|| DomainError::BlockchainError("Multicall balance read failed".to_string()))?;
+    u128::try_from(wei)
+        .map(Balance::from_wei)
+        .map_err(|_| DomainError::InvalidResponse(format!("Multicall balance {} does not fit in 128 bits", wei)))
+}
+
+/// Explain a pruned node's refusal to read state at `block`
+fn pruned_state_error(block: u64, error: &RpcFailure) -> Option<DomainError> {
+    let RpcFailure::Response(response) = error else {
+        return None;
+    };
+    let reason = response.message.to_lowercase();
+    PRUNED_STATE_ERRORS.iter().any(|pruned| reason.contains(pruned)).then(|| {
+        DomainError::ConfigurationError(format!(
+            "The node no longer has the state of block {} ({}); querying old blocks needs an archive node",
+            block, response.message
+        ))
+    })
+}
+
+/// Blocks sampled by `balance_series`: every `step`-th from `from_block`, plus `to_block`
+fn series_blocks(from_block: u64, to_block: u64, step: u64) -> Result<Vec<u64>, DomainError> {
+    if step == 0 {
+        return Err(DomainError::InvalidQuery("step must be >= 1".to_string()));
+    }
+    if from_block > to_block {
+        return Err(DomainError::InvalidQuery(format!(
+            "from_block ({}) must not exceed to_block ({})",
+            from_block, to_block
+        )));
+    }
+    let samples = ((to_block - from_block) / step).saturating_add(1);
+    if samples > MAX_SERIES_SAMPLES {
+        return Err(DomainError::InvalidQuery(format!(
+            "{} samples requested, at most {} allowed; increase step",
+            samples, MAX_SERIES_SAMPLES
+        )));
+    }
+    let mut blocks: Vec<u64> = (0..samples).map(|i| from_block + i * step).collect();
+    if blocks.last() != Some(&to_block) {
+        blocks.push(to_block);
+    }
+    Ok(blocks)
+}
+
+/// Map a failed `eth_sendRawTransaction` to a domain error; anything not
+/// recognized by `classify_tx_error` is a plain `TransferFailed`
+fn send_error(error: &RpcFailure) -> DomainError {
+    let message = format!("Failed to send transaction: {}", error);
+    classify_tx_error(error, &message).unwrap_or(DomainError::TransferFailed(message))
+}
+
+/// Recognize the node's transaction-pool rejections
+///
+/// Geth and most forks report all of these as `-32000` (some use `-32010`
+/// or `-32003`), so the code alone is not enough: the message decides.
+/// Returns `None` for anything else, including internal errors (`-32603`).
+fn classify_tx_error(error: &RpcFailure, message: &str) -> Option<DomainError> {
+    let RpcFailure::Response(response) = error else {
+        return None;
+    };
+    if !matches!(response.code, -32000 | -32003 | -32010) {
+        return None;
+    }
+    let reason = response.message.to_lowercase();
+    let message = message.to_string();
+    if reason.contains("nonce too low") {
+        Some(DomainError::NonceTooLow(message))
+    } else if reason.contains("already known") || reason.contains("known transaction") {
+        Some(DomainError::AlreadyKnown(message))
+    } else if reason.contains("replacement transaction underpriced") {
+        Some(DomainError::ReplacementUnderpriced(message))
+    } else if reason.contains("insufficient funds") {
+        // "insufficient funds for gas * price + value: address 0x… have 1 want 2"
+        let amount_after = |label: &str| {
+            let start = reason.find(label)? + label.len();
+            reason[start..].split_whitespace().next()?.parse::<u128>().ok()
+        };
+        Some(match (amount_after("have "), amount_after("want ")) {
+            (Some(available), Some(required)) => DomainError::InsufficientBalance { required, available },
+            _ => DomainError::TransferFailed(message),
+        })
+    } else {
+        None
+    }
+}
+
+/// Binary search for the highest block in `0..=latest` whose timestamp is `<= unix_ts`
+async fn search_block_by_timestamp<F, Fut>(
+    latest: u64,
+    unix_ts: u64,
+    block_timestamp: F,
+) -> Result<u64, DomainError>
+where
+    F: Fn(u64) -> Fut,
+    Fut: Future<Output = Result<u64, DomainError>>,
+{
+    let genesis_ts = block_timestamp(0).await?;
+    if unix_ts < genesis_ts {
+        return Err(DomainError::InvalidQuery(format!(
+            "Timestamp {} is before the genesis block ({})",
+            unix_ts, genesis_ts
+        )));
+    }
+    if block_timestamp(latest).await? <= unix_ts {
+        return Ok(latest);
+    }
+
+    // Invariant: ts(low) <= unix_ts < ts(high)
+    let (mut low, mut high) = (0, latest);
+    while high - low > 1 {
+        let mid = low + (high - low) / 2;
+        if block_timestamp(mid).await? <= unix_ts {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    Ok(low)
+}
+
+/// Ticks immediately, then on every `newHeads` notification from `ws_url`
+///
+/// Falls back to `fallback` if the subscription cannot be set up, and after
+/// the socket closes.
+#[cfg(feature = "cli")]
+fn subscribe_new_blocks(ws_url: String, fallback: BoxStream<'static, ()>) -> BoxStream<'static, ()> {
+    let heads = stream::once(async move {
+        let subscribed = async {
+            let provider = ProviderBuilder::new().on_ws(WsConnect::new(ws_url)).await?;
+            let subscription = provider.subscribe_blocks().await?;
+            Ok::<_, TransportError>((provider, subscription))
+        }
+        .await;
+
+        match subscribed {
+            Ok((provider, subscription)) => subscription
+                .into_stream()
+                // The provider owns the socket; keep it alive as long as the stream
+                .map(move |_| {
+                    let _ = &provider;
+                })
+                .chain(fallback)
+                .boxed(),
+            Err(e) => {
+                tracing::warn!("Block subscription failed ({}), polling instead", e);
+                fallback
+            }
+        }
+    })
+    .flatten();
+
+    stream::once(async {}).chain(heads).boxed()
+}
+
+/// Without Alloy's WebSocket transport, always poll
+#[cfg(not(feature = "cli"))]
+fn subscribe_new_blocks(_ws_url: String, fallback: BoxStream<'static, ()>) -> BoxStream<'static, ()> {
+    tracing::warn!("Block subscriptions need the `cli` feature, polling instead");
+    fallback
+}
+
+/// Fully specified EIP-1559 transfer for [`AlloyBlockchainService::sign_transaction_offline`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OfflineTransaction {
+    /// Recipient
+    pub to: Address,
+    /// Amount in Wei
+    pub value: u128,
+    /// Sender's nonce (look it up while online, or track it yourself)
+    pub nonce: u64,
+    /// Gas limit (21,000 for a plain transfer)
+    pub gas_limit: u64,
+    /// Maximum total fee per gas, in Wei
+    pub max_fee_per_gas: u128,
+    /// Maximum priority fee (tip) per gas, in Wei
+    pub max_priority_fee_per_gas: u128,
+    /// Calldata (empty for a plain transfer)
+    pub data: Vec<u8>,
+}
+
+impl OfflineTransaction {
+    /// Plain transfer of `value` Wei with a 21,000 gas limit
+    pub fn new(to: Address, value: u128, nonce: u64, max_fee_per_gas: u128, max_priority_fee_per_gas: u128) -> Self {
+        Self {
+            to,
+            value,
+            nonce,
+            gas_limit: 21_000,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            data: Vec::new(),
+        }
+    }
+
+    /// Use an explicit gas limit
+    pub fn with_gas_limit(mut self, gas_limit: u64) -> Self {
+        self.gas_limit = gas_limit;
+        self
+    }
+
+    /// Attach calldata (e.g. a contract call)
+    pub fn with_data(mut self, data: Vec<u8>) -> Self {
+        self.data = data;
+        self
+    }
+}
+
+/// Check that `address` is a 20-byte hex address before handing it to the node
+fn ensure_evm_address(address: &Address, what: &str) -> Result<(), DomainError> {
+    parse_evm_address(address, what).map(|_| ())
+}
+
+fn parse_evm_address(address: &Address, what: &str) -> Result<AlloyAddress, DomainError> {
+    address
+        .as_str()
+        .parse()
+        .map_err(|e| DomainError::BlockchainError(format!("Invalid {}: {}", what, e)))
+}
+
+/// Domain address of a contract the service knows by its Alloy address
+fn evm_address(address: AlloyAddress) -> Address {
+    Address::new_unchecked(address.to_checksum(None))
+}
+
+/// Smallest fee a node accepts when replacing a transaction that paid `fee`
+/// (10% more, rounded up)
+fn min_replacement_fee(fee: u128) -> u128 {
+    fee.saturating_add(fee.div_ceil(10))
+}
+
+/// Verify `balance >= amount + gas_cost` without wrapping arithmetic
+fn ensure_sufficient_balance(balance: u128, amount: u128, gas_cost: u128) -> Result<(), DomainError> {
+    let required = amount.checked_add(gas_cost).ok_or_else(|| {
+        DomainError::TransferFailed("Transfer amount plus gas cost overflows u128".to_string())
+    })?;
+
+    if balance < required {
+        return Err(DomainError::InsufficientBalance {
+            required,
+            available: balance,
+        });
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use super::super::evm_rpc::{BlockSummary, Eip1559Fees, ErrorResponse, SentTransaction};
+
+    fn error_response(code: i64, message: &'static str) -> RpcFailure {
+        RpcFailure::Response(ErrorResponse::new(code, message))
+    }
+
+    /// Failure of a node call a test fake does not answer
+    fn not_faked<T>() -> RpcResult<T> {
+        Err(RpcFailure::Transport { message: "not faked".to_string(), rate_limited: false })
+    }
+
+    #[test]
+    fn test_parse_rpc_url_accepts_bracketed_ipv6() {
+        let url = parse_rpc_url("http://[::1]:8545").unwrap();
+        assert_eq!(url.host_str(), Some("[::1]"));
+        assert_eq!(url.port(), Some(8545));
+        assert!(is_local_rpc_url(url.as_str()));
+
+        let url = parse_rpc_url("https://[2001:db8::7]/rpc").unwrap();
+        assert_eq!(url.path(), "/rpc");
+        assert!(!is_local_rpc_url(url.as_str()));
+
+        assert!(is_local_rpc_url(parse_rpc_url("http://127.0.0.1:8545").unwrap().as_str()));
+        assert!(is_local_rpc_url(parse_rpc_url("http://localhost:8545").unwrap().as_str()));
+        assert!(!is_local_rpc_url(parse_rpc_url("https://eth.llamarpc.com").unwrap().as_str()));
+    }
+
+    #[test]
+    fn test_parse_rpc_url_rejects_unbracketed_ipv6_and_other_schemes() {
+        let Err(DomainError::ConfigurationError(message)) = parse_rpc_url("http://::1:8545") else {
+            panic!("unbracketed IPv6 should be rejected");
+        };
+        assert!(message.contains("brackets"));
+        assert!(matches!(parse_rpc_url("/tmp/geth.ipc"), Err(DomainError::ConfigurationError(_))));
+        assert!(matches!(parse_rpc_url("ws://[::1]:8546"), Err(DomainError::ConfigurationError(_))));
+    }
+
+    #[test]
+    fn test_min_replacement_fee_is_ten_percent_rounded_up() {
+        assert_eq!(min_replacement_fee(1_000_000_000), 1_100_000_000);
+        assert_eq!(min_replacement_fee(15), 17);
+        assert_eq!(min_replacement_fee(0), 0);
+        assert_eq!(min_replacement_fee(u128::MAX), u128::MAX);
+    }
+
+    #[test]
+    fn test_send_error_classifies_pool_rejections() {
+        assert!(matches!(
+            send_error(&error_response(-32000, "nonce too low: next nonce 5, tx nonce 3")),
+            DomainError::NonceTooLow(_)
+        ));
+        assert!(matches!(send_error(&error_response(-32000, "already known")), DomainError::AlreadyKnown(_)));
+        assert!(matches!(
+            send_error(&error_response(-32010, "Known transaction")),
+            DomainError::AlreadyKnown(_)
+        ));
+        assert!(matches!(
+            send_error(&error_response(-32000, "replacement transaction underpriced")),
+            DomainError::ReplacementUnderpriced(_)
+        ));
+        assert!(matches!(
+            send_error(&error_response(
+                -32000,
+                "insufficient funds for gas * price + value: address 0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf have 100 want 250"
+            )),
+            DomainError::InsufficientBalance { required: 250, available: 100 }
+        ));
+    }
+
+    #[test]
+    fn test_send_error_leaves_other_errors_unclassified() {
+        assert!(matches!(
+            send_error(&error_response(-32603, "internal error")),
+            DomainError::TransferFailed(_)
+        ));
+        // Same text under an unrelated code is not a pool rejection
+        assert!(matches!(
+            send_error(&error_response(3, "nonce too low")),
+            DomainError::TransferFailed(_)
+        ));
+        assert!(matches!(
+            rpc_error("eth_call failed", &error_response(-32000, "execution reverted")),
+            DomainError::NetworkError(_)
+        ));
+        assert!(matches!(
+            rpc_error("Failed to get balance", &error_response(-32000, "nonce too low")),
+            DomainError::NonceTooLow(_)
+        ));
+    }
+
+    #[test]
+    fn test_series_blocks_include_both_ends() {
+        assert_eq!(series_blocks(100, 130, 10).unwrap(), vec![100, 110, 120, 130]);
+        assert_eq!(series_blocks(100, 125, 10).unwrap(), vec![100, 110, 120, 125]);
+        assert_eq!(series_blocks(7, 7, 5).unwrap(), vec![7]);
+        assert!(matches!(series_blocks(1, 10, 0), Err(DomainError::InvalidQuery(_))));
+        assert!(matches!(series_blocks(10, 1, 1), Err(DomainError::InvalidQuery(_))));
+        assert!(matches!(series_blocks(0, u64::MAX, 1), Err(DomainError::InvalidQuery(_))));
+    }
+
+    #[test]
+    fn test_pruned_state_error_asks_for_archive_node() {
+        let pruned = error_response(-32000, "missing trie node 1c2a5f… (path ) state 0x… is not available");
+        match pruned_state_error(42, &pruned) {
+            Some(DomainError::ConfigurationError(message)) => {
+                assert!(message.contains("block 42") && message.contains("archive node"))
+            }
+            other => panic!("expected ConfigurationError, got {:?}", other),
+        }
+        assert!(pruned_state_error(42, &error_response(-32000, "header not found")).is_none());
+    }
+
+    /// In-memory node: the balance at block `n` is `n` wei, blocks below 100
+    /// are pruned, blocks come every 12s, and every broadcast is already known
+    struct FakeRpc;
+
+    #[async_trait]
+    impl EvmRpc for FakeRpc {
+        async fn chain_id(&self) -> RpcResult<u64> {
+            Ok(1)
+        }
+
+        async fn block_number(&self) -> RpcResult<u64> {
+            Ok(120)
+        }
+
+        async fn get_block(&self, block: BlockRef) -> RpcResult<Option<BlockSummary>> {
+            Ok(match block {
+                BlockRef::Finalized => Some(BlockSummary { number: 90, timestamp: 2_080 }),
+                BlockRef::Number(number) => Some(BlockSummary { number, timestamp: 1_000 + number * 12 }),
+                _ => None,
+            })
+        }
+
+        async fn get_balance(&self, _address: &Address, block: BlockRef) -> RpcResult<u128> {
+            match block {
+                BlockRef::Number(n) if n < 100 => Err(error_response(-32000, "missing trie node abc (path )")),
+                BlockRef::Number(n) => Ok(n as u128),
+                _ => Ok(7),
+            }
+        }
+
+        async fn transaction_count(&self, _address: &Address, _block: BlockRef) -> RpcResult<u64> {
+            Ok(3)
+        }
+
+        async fn code(&self, _address: &Address) -> RpcResult<Vec<u8>> {
+            Ok(Vec::new())
+        }
+
+        async fn storage_at(&self, _address: &Address, _slot: [u8; 32], _block: BlockRef) -> RpcResult<[u8; 32]> {
+            not_faked()
+        }
+
+        async fn call(&self, _tx: &TxRequest, _block: BlockRef) -> RpcResult<Vec<u8>> {
+            Err(error_response(3, "execution reverted"))
+        }
+
+        async fn estimate_gas(&self, _tx: &TxRequest) -> RpcResult<u64> {
+            Ok(21_000)
+        }
+
+        async fn gas_price(&self) -> RpcResult<u128> {
+            Ok(11)
+        }
+
+        async fn eip1559_fees(&self) -> RpcResult<Option<Eip1559Fees>> {
+            Ok(None)
+        }
+
+        async fn fee_history(&self, _block_count: u64, _reward_percentiles: &[f64]) -> RpcResult<FeeHistory> {
+            Ok(fee_history(&[10, 10, 30, 30, 30], 1))
+        }
+
+        async fn send_raw(&self, _raw: &[u8]) -> RpcResult<TransactionHash> {
+            Err(error_response(-32000, "already known"))
+        }
+
+        async fn has_transaction(&self, _tx_hash: &TransactionHash) -> RpcResult<bool> {
+            Ok(true)
+        }
+
+        async fn transaction(&self, _tx_hash: &TransactionHash) -> RpcResult<Option<SentTransaction>> {
+            not_faked()
+        }
+
+        async fn receipt(&self, _tx_hash: &TransactionHash) -> RpcResult<Option<TransactionReceipt>> {
+            Ok(None)
+        }
+
+        async fn client_version(&self) -> RpcResult<String> {
+            not_faked()
+        }
+
+        #[cfg(feature = "dev")]
+        async fn set_balance(&self, _address: &Address, _wei: u128) -> RpcResult<()> {
+            not_faked()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_service_logic_runs_against_fake_rpc() {
+        // Nothing listens on the URL: every call below must go through the fake
+        let service = AlloyBlockchainService::new(Network::Mainnet, "http://127.0.0.1:1")
+            .await
+            .unwrap()
+            .with_rpc(Arc::new(FakeRpc));
+        let address = Address::new("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEbC".to_string()).unwrap();
+
+        assert_eq!(service.get_balance(&address).await.unwrap().to_wei(), 7);
+        let series = service.balance_series(&address, 100, 120, 10).await.unwrap();
+        let blocks: Vec<(u64, u128)> = series.iter().map(|(block, balance)| (*block, balance.to_wei())).collect();
+        assert_eq!(blocks, vec![(100, 100), (110, 110), (120, 120)]);
+        assert!(matches!(
+            service.get_balance_at_block(&address, 42).await,
+            Err(DomainError::ConfigurationError(m)) if m.contains("archive node")
+        ));
+
+        assert_eq!(service.get_height(Finality::Finalized).await.unwrap(), 90);
+        assert!(matches!(service.get_height(Finality::Safe).await, Err(DomainError::BlockchainError(_))));
+        assert!(matches!(
+            service.call(&address, Vec::new(), BlockId::latest()).await,
+            Err(DomainError::BlockchainError(m)) if m.contains("execution reverted")
+        ));
+        assert!(matches!(service.broadcast_raw("0x02").await, Err(DomainError::AlreadyKnown(_))));
+        let account = service.get_account_info(&address).await.unwrap();
+        assert_eq!(account.transaction_count, Some(3));
+        let tx_hash = TransactionHash::new(format!("0x{}", "ab".repeat(32))).unwrap();
+        assert_eq!(service.get_transaction_status(&tx_hash).await.unwrap(), TxStatus::Pending);
+
+        // 11 wei/gas clears two of the four blocks: twice the 12s block time
+        let fee = FeeEstimate::from_details(FeeDetails::Evm { gas_limit: 21_000, gas_price: 11 }).unwrap();
+        assert_eq!(service.estimate_confirmation_time(&fee).await.unwrap(), Duration::from_secs(24));
+        let fee = FeeEstimate::from_details(FeeDetails::Evm { gas_limit: 21_000, gas_price: 10 }).unwrap();
+        assert!(matches!(service.estimate_confirmation_time(&fee).await, Err(DomainError::BlockchainError(_))));
+    }
+
+    #[cfg(feature = "cli")]
+    #[tokio::test]
+    async fn test_shutdown_closes_block_subscription() {
+        use tokio::io::AsyncReadExt;
+
+        // Accepts the WebSocket connection but never completes the handshake
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let ws_url = format!("ws://{}", listener.local_addr().unwrap());
+        let service = AlloyBlockchainService::builder()
+            .network(Network::Mainnet)
+            .rpc_urls(["http://127.0.0.1:1"])
+            .ws_url(ws_url)
+            .build()
+            .await
+            .unwrap();
+        let polled = service.new_block_ticks(stream::pending().boxed());
+
+        let mut ticks = service.new_block_ticks(stream::pending().boxed());
+        assert_eq!(ticks.next().await, Some(()));
+        let watcher = tokio::spawn(async move { ticks.next().await });
+        let (mut socket, _) = listener.accept().await.unwrap();
+
+        service.shutdown().await;
+        assert_eq!(watcher.await.unwrap(), None);
+        // The half-open socket was dropped with the subscription
+        let mut buf = [0u8; 1024];
+        let closed = tokio::time::timeout(Duration::from_secs(5), async {
+            while socket.read(&mut buf).await.unwrap() > 0 {}
+        });
+        assert!(closed.await.is_ok());
+        // Streams taken before shutdown end too, and later ones never tick
+        assert_eq!(polled.skip(1).next().await, None);
+        assert_eq!(service.new_block_ticks(stream::repeat(()).boxed()).next().await, None);
+    }
+
+    #[test]
+    fn test_multicall_balance_rejects_oversized_word() {
+        use alloy::sol_types::SolValue;
+
+        // aggregate3 output: (bool success, bytes returnData)[] with getEthBalance words
+        let words = [U256::from(42u64), U256::from(u128::MAX) + U256::from(1u8), U256::MAX];
+        let results: Vec<(bool, Bytes)> = words.iter().map(|word| (true, word.abi_encode().into())).collect();
+        let balances = multicall::decode_balances(&results.abi_encode(), 3).unwrap();
+
+        let balances: Vec<_> = balances.into_iter().map(multicall_balance).collect();
+        assert_eq!(balances[0].as_ref().unwrap().to_wei(), 42);
+        assert!(matches!(&balances[1], Err(DomainError::InvalidResponse(m)) if m.contains("128 bits")));
+        assert!(matches!(balances[2], Err(DomainError::InvalidResponse(_))));
+        assert!(matches!(multicall_balance(None), Err(DomainError::BlockchainError(_))));
+    }
+
+    /// Fee history from block 100 with the given base fees (one more than
+    /// blocks) and the same lowest-percentile priority fee in every block
+    fn fee_history(base_fees: &[u128], tip: u128) -> FeeHistory {
+        let blocks = base_fees.len() - 1;
+        FeeHistory {
+            base_fee_per_gas: base_fees.to_vec(),
+            gas_used_ratio: vec![0.5; blocks],
+            oldest_block: 100,
+            reward: Some(vec![vec![tip]; blocks]),
+        }
+    }
+
+    #[test]
+    fn test_blocks_including() {
+        let history = fee_history(&[10, 20, 30, 40], 2);
+        assert_eq!(blocks_including(&history, 11), (0, 3));
+        assert_eq!(blocks_including(&history, 22), (2, 3));
+        assert_eq!(blocks_including(&history, 100), (3, 3));
+        assert_eq!(blocks_including(&FeeHistory::default(), 100), (0, 0));
+    }
+
+    /// Blocks every 12s starting at t=1000
+    async fn fake_timestamp(block: u64) -> Result<u64, DomainError> {
+        Ok(1_000 + block * 12)
+    }
+
+    #[tokio::test]
+    async fn test_block_at_timestamp_search() {
+        // Exact hit, between blocks, and after the latest block
+        assert_eq!(search_block_by_timestamp(100, 1_120, fake_timestamp).await.unwrap(), 10);
+        assert_eq!(search_block_by_timestamp(100, 1_125, fake_timestamp).await.unwrap(), 10);
+        assert_eq!(search_block_by_timestamp(100, 1_000, fake_timestamp).await.unwrap(), 0);
+        assert_eq!(search_block_by_timestamp(100, 9_999, fake_timestamp).await.unwrap(), 100);
+    }
+
+    #[tokio::test]
+    async fn test_block_at_timestamp_before_genesis() {
+        let result = search_block_by_timestamp(100, 999, fake_timestamp).await;
+        assert!(matches!(result, Err(DomainError::InvalidQuery(msg)) if msg.contains("genesis")));
+    }
+
+    #[test]
+    fn test_balance_check_includes_gas() {
+        // Amount alone fits, but amount + gas does not
+        let result = ensure_sufficient_balance(1_000, 900, 200);
+        match result {
+            Err(DomainError::InsufficientBalance { required, available }) => {
+                assert_eq!(required, 1_100);
+                assert_eq!(available, 1_000);
+            }
+            other => panic!("Expected InsufficientBalance, got {:?}", other),
+        }
+
+        assert!(ensure_sufficient_balance(1_100, 900, 200).is_ok());
+    }
+
+    #[test]
+    fn test_private_key_prefix_normalization() {
+        let key = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+        let bare = keys::parse_evm_signer(key).unwrap();
+        let prefixed = keys::parse_evm_signer(&format!("  0x{}\n", key)).unwrap();
+        assert_eq!(bare.address(), prefixed.address());
+
+        assert!(matches!(
+            keys::parse_evm_signer("invalid_private_key"),
+            Err(DomainError::InvalidPrivateKey(_))
+        ));
+    }
+
+    #[test]
+    fn test_balance_check_overflow() {
+        let result = ensure_sufficient_balance(u128::MAX, u128::MAX, 1);
+        assert!(matches!(result, Err(DomainError::TransferFailed(_))));
+    }
 
     #[tokio::test]
     #[ignore] // Ignore by default as it requires network connection
@@ -179,4 +1983,19 @@ mod tests {
         assert!(balance.is_ok());
         println!("Balance: {:?}", balance);
     }
+
+    #[tokio::test]
+    #[ignore] // Requires network connection
+    async fn test_get_pending_balance_real_network() {
+        let address = Address::new("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".to_string())
+            .expect("Valid address");
+
+        let service = AlloyBlockchainService::new_with_default_rpc(Network::Mainnet)
+            .await
+            .expect("Failed to create service");
+
+        let pending = service.get_pending_balance(&address).await;
+        assert!(pending.is_ok());
+        println!("Pending balance: {:?}", pending);
+    }
 }
@@ -0,0 +1,752 @@
+//! Minimal BIP-174 PSBTs for native SegWit (P2WPKH) transfers
+//!
+//! Just enough of the format to build a transfer from the sender's UTXOs,
+//! let any BIP-174 signer (this crate, a hardware wallet, a multisig
+//! coordinator) add signatures, then finalize and extract the transaction
+//! for broadcast, without pulling in rust-bitcoin. Fields this module does
+//! not interpret (BIP-32 derivations, redeem scripts, proprietary keys) are
+//! carried through unchanged.
+
+use base64::Engine;
+use bech32::{hrp, segwit};
+use k256::ecdsa::{signature::hazmat::PrehashSigner, Signature, SigningKey};
+use sha2::{Digest, Sha256};
+use crate::adapter::infrastructure::keys::hash160;
+use crate::core::domain::{
+    errors::DomainError,
+    value_objects::{Address, ChainType, Network, Utxo},
+};
+
+const PSBT_MAGIC: &[u8] = b"psbt\xff";
+const PSBT_GLOBAL_UNSIGNED_TX: u8 = 0x00;
+const PSBT_IN_WITNESS_UTXO: u8 = 0x01;
+const PSBT_IN_PARTIAL_SIG: u8 = 0x02;
+const PSBT_IN_SIGHASH_TYPE: u8 = 0x03;
+const PSBT_IN_FINAL_SCRIPTSIG: u8 = 0x07;
+const PSBT_IN_FINAL_SCRIPTWITNESS: u8 = 0x08;
+
+const SIGHASH_ALL: u32 = 1;
+/// nSequence opting in to replace-by-fee (BIP-125), so a stuck transfer can be bumped
+const SEQUENCE_RBF: u32 = 0xffff_fffd;
+/// Smallest P2WPKH output relayed under default node policy
+pub(crate) const DUST_LIMIT_SATS: u64 = 294;
+
+/// Virtual size of version, locktime, input/output counts and the SegWit marker
+pub(crate) const TX_OVERHEAD_VSIZE: u64 = 11;
+/// Virtual size of a P2WPKH input including its signature and public key
+const P2WPKH_INPUT_VSIZE: u64 = 68;
+
+/// Key-value pair of a PSBT map, key type byte included
+type KeyValue = (Vec<u8>, Vec<u8>);
+
+/// Transaction output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TxOut {
+    pub value: u64,
+    pub script_pubkey: Vec<u8>,
+}
+
+impl TxOut {
+    fn read(reader: &mut Reader<'_>) -> Result<Self, DomainError> {
+        Ok(Self {
+            value: reader.u64()?,
+            script_pubkey: reader.var_bytes()?.to_vec(),
+        })
+    }
+
+    fn write(&self, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(&self.value.to_le_bytes());
+        write_var_bytes(buffer, &self.script_pubkey);
+    }
+}
+
+/// Transaction input, without its unlocking script
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TxIn {
+    /// Txid of the spent output in internal (reversed) byte order
+    pub txid: [u8; 32],
+    pub vout: u32,
+    pub sequence: u32,
+}
+
+/// Transaction as stored in a PSBT: every scriptSig and witness empty
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct UnsignedTx {
+    pub version: u32,
+    pub inputs: Vec<TxIn>,
+    pub outputs: Vec<TxOut>,
+    pub lock_time: u32,
+}
+
+impl UnsignedTx {
+    fn read(reader: &mut Reader<'_>) -> Result<Self, DomainError> {
+        let version = reader.u32()?;
+        // Zero inputs would be read as the SegWit marker, which a PSBT never contains
+        let input_count = reader.compact_size()?;
+        if input_count == 0 {
+            return Err(invalid("unsigned transaction has no inputs"));
+        }
+        let mut inputs = Vec::new();
+        for _ in 0..input_count {
+            let txid = reader.take(32)?.try_into().expect("length checked");
+            let vout = reader.u32()?;
+            if !reader.var_bytes()?.is_empty() {
+                return Err(invalid("unsigned transaction has a scriptSig"));
+            }
+            inputs.push(TxIn { txid, vout, sequence: reader.u32()? });
+        }
+        let mut outputs = Vec::new();
+        for _ in 0..reader.compact_size()? {
+            outputs.push(TxOut::read(reader)?);
+        }
+        Ok(Self { version, inputs, outputs, lock_time: reader.u32()? })
+    }
+
+    /// Serialize with the given per-input scriptSigs and witnesses (missing ones are empty)
+    ///
+    /// Uses the SegWit format only when some witness is non-empty.
+    fn encode(&self, script_sigs: &[Vec<u8>], witnesses: &[Vec<Vec<u8>>]) -> Vec<u8> {
+        let segwit = witnesses.iter().any(|witness| !witness.is_empty());
+        let mut buffer = Vec::new();
+        buffer.extend_from_slice(&self.version.to_le_bytes());
+        if segwit {
+            buffer.extend_from_slice(&[0x00, 0x01]);
+        }
+        write_compact_size(&mut buffer, self.inputs.len() as u64);
+        for (index, input) in self.inputs.iter().enumerate() {
+            buffer.extend_from_slice(&input.txid);
+            buffer.extend_from_slice(&input.vout.to_le_bytes());
+            write_var_bytes(&mut buffer, script_sigs.get(index).map(Vec::as_slice).unwrap_or_default());
+            buffer.extend_from_slice(&input.sequence.to_le_bytes());
+        }
+        write_compact_size(&mut buffer, self.outputs.len() as u64);
+        for output in &self.outputs {
+            output.write(&mut buffer);
+        }
+        if segwit {
+            for index in 0..self.inputs.len() {
+                let witness = witnesses.get(index).map(Vec::as_slice).unwrap_or_default();
+                write_compact_size(&mut buffer, witness.len() as u64);
+                for item in witness {
+                    write_var_bytes(&mut buffer, item);
+                }
+            }
+        }
+        buffer.extend_from_slice(&self.lock_time.to_le_bytes());
+        buffer
+    }
+
+    /// BIP-143 signature hash (SIGHASH_ALL) of input `index` spending `value` sats
+    fn segwit_v0_sighash(&self, index: usize, script_code: &[u8], value: u64) -> [u8; 32] {
+        let mut prevouts = Vec::new();
+        let mut sequences = Vec::new();
+        for input in &self.inputs {
+            prevouts.extend_from_slice(&input.txid);
+            prevouts.extend_from_slice(&input.vout.to_le_bytes());
+            sequences.extend_from_slice(&input.sequence.to_le_bytes());
+        }
+        let mut outputs = Vec::new();
+        for output in &self.outputs {
+            output.write(&mut outputs);
+        }
+
+        let input = &self.inputs[index];
+        let mut preimage = Vec::new();
+        preimage.extend_from_slice(&self.version.to_le_bytes());
+        preimage.extend_from_slice(&sha256d(&prevouts));
+        preimage.extend_from_slice(&sha256d(&sequences));
+        preimage.extend_from_slice(&input.txid);
+        preimage.extend_from_slice(&input.vout.to_le_bytes());
+        write_var_bytes(&mut preimage, script_code);
+        preimage.extend_from_slice(&value.to_le_bytes());
+        preimage.extend_from_slice(&input.sequence.to_le_bytes());
+        preimage.extend_from_slice(&sha256d(&outputs));
+        preimage.extend_from_slice(&self.lock_time.to_le_bytes());
+        preimage.extend_from_slice(&SIGHASH_ALL.to_le_bytes());
+        sha256d(&preimage)
+    }
+}
+
+/// Per-input PSBT fields
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct PsbtInput {
+    /// Output being spent (value and scriptPubKey), needed to sign SegWit inputs
+    pub witness_utxo: Option<TxOut>,
+    /// `(compressed public key, DER signature + sighash byte)` pairs
+    pub partial_sigs: Vec<(Vec<u8>, Vec<u8>)>,
+    pub sighash_type: Option<u32>,
+    pub final_script_sig: Option<Vec<u8>>,
+    pub final_script_witness: Option<Vec<Vec<u8>>>,
+    /// Fields this module does not interpret
+    pub unknown: Vec<KeyValue>,
+}
+
+impl PsbtInput {
+    fn from_map(entries: Vec<KeyValue>) -> Result<Self, DomainError> {
+        let mut input = Self::default();
+        for (key, value) in entries {
+            match (key[0], key.len()) {
+                (PSBT_IN_WITNESS_UTXO, 1) => {
+                    let mut reader = Reader::new(&value);
+                    input.witness_utxo = Some(TxOut::read(&mut reader)?);
+                    reader.finish("witness UTXO")?;
+                }
+                (PSBT_IN_PARTIAL_SIG, 34) => input.partial_sigs.push((key[1..].to_vec(), value)),
+                (PSBT_IN_SIGHASH_TYPE, 1) => {
+                    let bytes = value.try_into().map_err(|_| invalid("malformed sighash type"))?;
+                    input.sighash_type = Some(u32::from_le_bytes(bytes));
+                }
+                (PSBT_IN_FINAL_SCRIPTSIG, 1) => input.final_script_sig = Some(value),
+                (PSBT_IN_FINAL_SCRIPTWITNESS, 1) => {
+                    let mut reader = Reader::new(&value);
+                    let mut witness = Vec::new();
+                    for _ in 0..reader.compact_size()? {
+                        witness.push(reader.var_bytes()?.to_vec());
+                    }
+                    reader.finish("final witness")?;
+                    input.final_script_witness = Some(witness);
+                }
+                _ => input.unknown.push((key, value)),
+            }
+        }
+        Ok(input)
+    }
+
+    fn to_map(&self) -> Vec<KeyValue> {
+        let mut entries = Vec::new();
+        if let Some(utxo) = &self.witness_utxo {
+            let mut value = Vec::new();
+            utxo.write(&mut value);
+            entries.push((vec![PSBT_IN_WITNESS_UTXO], value));
+        }
+        for (public_key, signature) in &self.partial_sigs {
+            let mut key = vec![PSBT_IN_PARTIAL_SIG];
+            key.extend_from_slice(public_key);
+            entries.push((key, signature.clone()));
+        }
+        if let Some(sighash_type) = self.sighash_type {
+            entries.push((vec![PSBT_IN_SIGHASH_TYPE], sighash_type.to_le_bytes().to_vec()));
+        }
+        if let Some(script_sig) = &self.final_script_sig {
+            entries.push((vec![PSBT_IN_FINAL_SCRIPTSIG], script_sig.clone()));
+        }
+        if let Some(witness) = &self.final_script_witness {
+            let mut value = Vec::new();
+            write_compact_size(&mut value, witness.len() as u64);
+            for item in witness {
+                write_var_bytes(&mut value, item);
+            }
+            entries.push((vec![PSBT_IN_FINAL_SCRIPTWITNESS], value));
+        }
+        entries.extend(self.unknown.iter().cloned());
+        entries
+    }
+}
+
+/// Partially signed Bitcoin transaction (BIP-174, version 0)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Psbt {
+    pub tx: UnsignedTx,
+    pub global_unknown: Vec<KeyValue>,
+    pub inputs: Vec<PsbtInput>,
+    pub outputs: Vec<Vec<KeyValue>>,
+}
+
+impl Psbt {
+    /// Unsigned PSBT paying `amount` to `recipient_script`, with any change
+    /// going back to `from_script` (the P2WPKH script every selected UTXO pays)
+    pub fn transfer(
+        selection: &CoinSelection,
+        from_script: &[u8],
+        recipient_script: Vec<u8>,
+        amount: u64,
+    ) -> Result<Self, DomainError> {
+        let spent = TxOut { value: 0, script_pubkey: from_script.to_vec() };
+        let mut inputs = Vec::new();
+        let mut psbt_inputs = Vec::new();
+        for utxo in &selection.utxos {
+            inputs.push(TxIn { txid: internal_txid(&utxo.txid)?, vout: utxo.vout, sequence: SEQUENCE_RBF });
+            psbt_inputs.push(PsbtInput {
+                witness_utxo: Some(TxOut { value: utxo.value_sats, ..spent.clone() }),
+                ..Default::default()
+            });
+        }
+
+        let mut outputs = vec![TxOut { value: amount, script_pubkey: recipient_script }];
+        if selection.change > 0 {
+            outputs.push(TxOut { value: selection.change, ..spent });
+        }
+
+        Ok(Self {
+            outputs: vec![Vec::new(); outputs.len()],
+            tx: UnsignedTx { version: 2, inputs, outputs, lock_time: 0 },
+            global_unknown: Vec::new(),
+            inputs: psbt_inputs,
+        })
+    }
+
+    pub fn from_base64(psbt: &str) -> Result<Self, DomainError> {
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(psbt.trim())
+            .map_err(|_| invalid("not valid base64"))?;
+        Self::parse(&bytes)
+    }
+
+    pub fn to_base64(&self) -> String {
+        base64::engine::general_purpose::STANDARD.encode(self.serialize())
+    }
+
+    fn parse(bytes: &[u8]) -> Result<Self, DomainError> {
+        let mut reader = Reader::new(bytes.strip_prefix(PSBT_MAGIC).ok_or_else(|| invalid("missing magic bytes"))?);
+
+        let mut tx = None;
+        let mut global_unknown = Vec::new();
+        for (key, value) in read_map(&mut reader)? {
+            if key == [PSBT_GLOBAL_UNSIGNED_TX] {
+                let mut tx_reader = Reader::new(&value);
+                tx = Some(UnsignedTx::read(&mut tx_reader)?);
+                tx_reader.finish("unsigned transaction")?;
+            } else {
+                global_unknown.push((key, value));
+            }
+        }
+        let tx = tx.ok_or_else(|| invalid("missing unsigned transaction"))?;
+
+        let inputs = tx
+            .inputs
+            .iter()
+            .map(|_| read_map(&mut reader).and_then(PsbtInput::from_map))
+            .collect::<Result<Vec<_>, _>>()?;
+        let outputs = tx.outputs.iter().map(|_| read_map(&mut reader)).collect::<Result<Vec<_>, _>>()?;
+        reader.finish("PSBT")?;
+
+        Ok(Self { tx, global_unknown, inputs, outputs })
+    }
+
+    fn serialize(&self) -> Vec<u8> {
+        let mut buffer = PSBT_MAGIC.to_vec();
+        let mut global = vec![(vec![PSBT_GLOBAL_UNSIGNED_TX], self.tx.encode(&[], &[]))];
+        global.extend(self.global_unknown.iter().cloned());
+        write_map(&mut buffer, &global);
+        for input in &self.inputs {
+            write_map(&mut buffer, &input.to_map());
+        }
+        for output in &self.outputs {
+            write_map(&mut buffer, output);
+        }
+        buffer
+    }
+
+    /// Sign every input spending the P2WPKH output of `key`; returns how many were signed
+    pub fn sign(&mut self, key: &SigningKey) -> Result<usize, DomainError> {
+        let public_key = key.verifying_key().to_encoded_point(true).as_bytes().to_vec();
+        let pubkey_hash = hash160(&public_key);
+        let script = p2wpkh_script(&pubkey_hash);
+
+        let mut signed = 0;
+        for index in 0..self.inputs.len() {
+            let input = &self.inputs[index];
+            let Some(utxo) = input.witness_utxo.as_ref().filter(|utxo| utxo.script_pubkey == script) else {
+                continue;
+            };
+            if input.final_script_witness.is_some() {
+                continue;
+            }
+            if input.sighash_type.is_some_and(|sighash_type| sighash_type != SIGHASH_ALL) {
+                return Err(DomainError::TransferFailed(format!(
+                    "PSBT input {} requests a sighash type other than SIGHASH_ALL",
+                    index
+                )));
+            }
+
+            let sighash = self.tx.segwit_v0_sighash(index, &p2pkh_script(&pubkey_hash), utxo.value);
+            let signature: Signature = key
+                .sign_prehash(&sighash)
+                .map_err(|e| DomainError::TransferFailed(format!("Failed to sign PSBT input {}: {}", index, e)))?;
+            // Nodes only relay low-S signatures (BIP-146)
+            let signature = signature.normalize_s().unwrap_or(signature);
+            let mut signature = signature.to_der().as_bytes().to_vec();
+            signature.push(SIGHASH_ALL as u8);
+
+            let input = &mut self.inputs[index];
+            input.partial_sigs.retain(|(key, _)| *key != public_key);
+            input.partial_sigs.push((public_key.clone(), signature));
+            signed += 1;
+        }
+        Ok(signed)
+    }
+
+    /// Build each input's final witness from its signature and extract the
+    /// signed transaction; returns the raw transaction and its txid
+    pub fn finalize(&mut self) -> Result<(Vec<u8>, String), DomainError> {
+        for (index, input) in self.inputs.iter_mut().enumerate() {
+            if input.final_script_witness.is_some() || input.final_script_sig.is_some() {
+                continue;
+            }
+            let utxo = input
+                .witness_utxo
+                .as_ref()
+                .ok_or_else(|| DomainError::TransferFailed(format!("PSBT input {} has no witness UTXO", index)))?;
+            let (public_key, signature) = input
+                .partial_sigs
+                .iter()
+                .find(|(public_key, _)| utxo.script_pubkey == p2wpkh_script(&hash160(public_key)))
+                .ok_or_else(|| DomainError::TransferFailed(format!("PSBT input {} is not signed", index)))?;
+            input.final_script_witness = Some(vec![signature.clone(), public_key.clone()]);
+            input.partial_sigs.clear();
+            input.sighash_type = None;
+        }
+
+        let script_sigs: Vec<_> = self.inputs.iter().map(|input| input.final_script_sig.clone().unwrap_or_default()).collect();
+        let witnesses: Vec<_> = self
+            .inputs
+            .iter()
+            .map(|input| input.final_script_witness.clone().unwrap_or_default())
+            .collect();
+
+        // The txid commits to everything but the witnesses
+        let mut txid = sha256d(&self.tx.encode(&script_sigs, &[]));
+        txid.reverse();
+        Ok((self.tx.encode(&script_sigs, &witnesses), alloy::hex::encode(txid)))
+    }
+}
+
+/// UTXOs chosen for a transfer, the fee they pay and the change left over
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CoinSelection {
+    pub utxos: Vec<Utxo>,
+    pub fee: u64,
+    pub change: u64,
+}
+
+/// Pick P2WPKH UTXOs, largest first, covering `amount` plus the fee at `fee_rate` sat/vB
+///
+/// Change below the dust limit is left to the miner rather than creating an
+/// output nobody can afford to spend.
+pub(crate) fn select_coins(
+    mut utxos: Vec<Utxo>,
+    amount: u64,
+    fee_rate: u64,
+    recipient_script_len: usize,
+    change_script_len: usize,
+) -> Result<CoinSelection, DomainError> {
+    utxos.sort_by_key(|utxo| std::cmp::Reverse(utxo.value_sats));
+    let fee_for = |inputs: usize, outputs_vsize: u64| {
+        fee_rate.saturating_mul(TX_OVERHEAD_VSIZE + P2WPKH_INPUT_VSIZE * inputs as u64 + outputs_vsize)
+    };
+    let recipient_vsize = output_vsize(recipient_script_len);
+    let change_vsize = output_vsize(change_script_len);
+
+    let mut selected = Vec::new();
+    let mut total: u64 = 0;
+    for utxo in utxos {
+        total = total.saturating_add(utxo.value_sats);
+        selected.push(utxo);
+
+        let fee = fee_for(selected.len(), recipient_vsize);
+        let Some(left) = total.checked_sub(amount.saturating_add(fee)) else {
+            continue;
+        };
+        let fee_with_change = fee_for(selected.len(), recipient_vsize + change_vsize);
+        return Ok(match left.checked_sub(fee_with_change - fee) {
+            Some(change) if change >= DUST_LIMIT_SATS => CoinSelection { utxos: selected, fee: fee_with_change, change },
+            _ => CoinSelection { utxos: selected, fee: fee + left, change: 0 },
+        });
+    }
+
+    Err(DomainError::InsufficientBalance {
+        required: amount as u128 + fee_for(selected.len().max(1), recipient_vsize) as u128,
+        available: total as u128,
+    })
+}
+
+/// scriptPubKey paying `address`, which must be an address of `network`
+pub(crate) fn script_pubkey(address: &Address, network: &Network) -> Result<Vec<u8>, DomainError> {
+    let testnet = match network {
+        Network::BitcoinMainnet => false,
+        Network::BitcoinTestnet => true,
+        _ => {
+            return Err(DomainError::ConfigurationError(
+                "Network must be a Bitcoin network".to_string(),
+            ))
+        }
+    };
+    let malformed = |reason: &str| DomainError::InvalidAddress {
+        chain: ChainType::Bitcoin,
+        reason: reason.to_string(),
+    };
+    let wrong_network = || DomainError::ConfigurationError(format!("{} is not a {} address", address, network.name()));
+
+    let lower = address.as_str().to_ascii_lowercase();
+    if lower.starts_with("bc1") || lower.starts_with("tb1") {
+        let (address_hrp, version, program) =
+            segwit::decode(address.as_str()).map_err(|_| malformed("invalid Bech32 checksum or witness program"))?;
+        if address_hrp != if testnet { hrp::TB } else { hrp::BC } {
+            return Err(wrong_network());
+        }
+        // OP_0 for version 0, OP_1..OP_16 after that
+        let version = version.to_u8();
+        let mut script = vec![if version == 0 { 0x00 } else { 0x50 + version }, program.len() as u8];
+        script.extend_from_slice(&program);
+        return Ok(script);
+    }
+
+    let payload = bs58::decode(address.as_str())
+        .with_check(None)
+        .into_vec()
+        .map_err(|_| malformed("invalid Base58Check encoding or checksum"))?;
+    let [version, hash @ ..] = payload.as_slice() else {
+        return Err(malformed("expected a version byte and a 20-byte hash"));
+    };
+    let hash: &[u8; 20] = hash
+        .try_into()
+        .map_err(|_| malformed("expected a version byte and a 20-byte hash"))?;
+    match (version, testnet) {
+        (0x00, false) | (0x6f, true) => Ok(p2pkh_script(hash)),
+        // OP_HASH160 <20 bytes> OP_EQUAL
+        (0x05, false) | (0xc4, true) => Ok([&[0xa9, 0x14][..], hash, &[0x87]].concat()),
+        (0x00 | 0x05, true) | (0x6f | 0xc4, false) => Err(wrong_network()),
+        _ => Err(malformed("unknown Base58 version byte")),
+    }
+}
+
+/// Whether `script` is a P2WPKH scriptPubKey (OP_0 <20 bytes>)
+pub(crate) fn is_p2wpkh(script: &[u8]) -> bool {
+    script.len() == 22 && script[..2] == [0x00, 0x14]
+}
+
+fn p2wpkh_script(pubkey_hash: &[u8; 20]) -> Vec<u8> {
+    [&[0x00, 0x14][..], pubkey_hash].concat()
+}
+
+/// OP_DUP OP_HASH160 <20 bytes> OP_EQUALVERIFY OP_CHECKSIG (also the BIP-143 script code of P2WPKH)
+fn p2pkh_script(pubkey_hash: &[u8; 20]) -> Vec<u8> {
+    [&[0x76, 0xa9, 0x14][..], pubkey_hash, &[0x88, 0xac]].concat()
+}
+
+pub(crate) fn output_vsize(script_len: usize) -> u64 {
+    // value, script length prefix, script
+    8 + 1 + script_len as u64
+}
+
+/// Txid as shown by explorers (hex, big-endian) to the byte order used inside transactions
+fn internal_txid(txid: &str) -> Result<[u8; 32], DomainError> {
+    let mut bytes: [u8; 32] = alloy::hex::decode(txid)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| DomainError::BlockchainError(format!("Invalid UTXO txid '{}'", txid)))?;
+    bytes.reverse();
+    Ok(bytes)
+}
+
+fn sha256d(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(Sha256::digest(data)).into()
+}
+
+fn invalid(reason: &str) -> DomainError {
+    DomainError::TransferFailed(format!("Invalid PSBT: {}", reason))
+}
+
+fn read_map(reader: &mut Reader<'_>) -> Result<Vec<KeyValue>, DomainError> {
+    let mut entries: Vec<KeyValue> = Vec::new();
+    loop {
+        let key = reader.var_bytes()?;
+        if key.is_empty() {
+            return Ok(entries);
+        }
+        let value = reader.var_bytes()?;
+        if entries.iter().any(|(existing, _)| existing == key) {
+            return Err(invalid("duplicate key"));
+        }
+        entries.push((key.to_vec(), value.to_vec()));
+    }
+}
+
+fn write_map(buffer: &mut Vec<u8>, entries: &[KeyValue]) {
+    for (key, value) in entries {
+        write_var_bytes(buffer, key);
+        write_var_bytes(buffer, value);
+    }
+    buffer.push(0x00);
+}
+
+fn write_compact_size(buffer: &mut Vec<u8>, n: u64) {
+    match n {
+        0..=0xfc => buffer.push(n as u8),
+        0xfd..=0xffff => {
+            buffer.push(0xfd);
+            buffer.extend_from_slice(&(n as u16).to_le_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            buffer.push(0xfe);
+            buffer.extend_from_slice(&(n as u32).to_le_bytes());
+        }
+        _ => {
+            buffer.push(0xff);
+            buffer.extend_from_slice(&n.to_le_bytes());
+        }
+    }
+}
+
+fn write_var_bytes(buffer: &mut Vec<u8>, bytes: &[u8]) {
+    write_compact_size(buffer, bytes.len() as u64);
+    buffer.extend_from_slice(bytes);
+}
+
+/// Cursor over serialized PSBT / transaction data
+struct Reader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], DomainError> {
+        let end = self
+            .position
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| invalid("unexpected end of data"))?;
+        let bytes = &self.data[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+
+    fn u32(&mut self) -> Result<u32, DomainError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().expect("length checked")))
+    }
+
+    fn u64(&mut self) -> Result<u64, DomainError> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().expect("length checked")))
+    }
+
+    fn compact_size(&mut self) -> Result<u64, DomainError> {
+        Ok(match self.take(1)?[0] {
+            0xfd => u16::from_le_bytes(self.take(2)?.try_into().expect("length checked")) as u64,
+            0xfe => self.u32()? as u64,
+            0xff => self.u64()?,
+            n => n as u64,
+        })
+    }
+
+    fn var_bytes(&mut self) -> Result<&'a [u8], DomainError> {
+        let len = self.compact_size()?;
+        self.take(usize::try_from(len).map_err(|_| invalid("length out of range"))?)
+    }
+
+    /// Fail if anything is left after `what`
+    fn finish(&self, what: &str) -> Result<(), DomainError> {
+        if self.position == self.data.len() {
+            Ok(())
+        } else {
+            Err(invalid(&format!("trailing bytes after {}", what)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::ecdsa::{signature::hazmat::PrehashVerifier, VerifyingKey};
+
+    // Native P2WPKH example from BIP-143: input 1 spends 6 BTC from a P2WPKH output
+    const BIP143_TX: &str = "0100000002fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f0000000000eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac11000000";
+    const BIP143_KEY: &str = "619c335025c7f4012e556c2a58b2506e30b8511b53ade95ea316fd8c3286feb9";
+    const BIP143_SCRIPT: &str = "00141d0f172a0ecb48aee1be1f2687d2963ae33f71a1";
+    const BIP143_SIGHASH: &str = "c37af31116d1b27caf68aae9e3ac82f1477929014d5b917657d0eb49478cb670";
+
+    fn utxo(txid_byte: u8, value_sats: u64) -> Utxo {
+        Utxo { txid: alloy::hex::encode([txid_byte; 32]), vout: 0, value_sats, confirmations: 6 }
+    }
+
+    fn bip143_psbt() -> Psbt {
+        let raw = alloy::hex::decode(BIP143_TX).unwrap();
+        let tx = UnsignedTx::read(&mut Reader::new(&raw)).unwrap();
+        let mut inputs = vec![PsbtInput::default(), PsbtInput::default()];
+        inputs[1].witness_utxo = Some(TxOut {
+            value: 600_000_000,
+            script_pubkey: alloy::hex::decode(BIP143_SCRIPT).unwrap(),
+        });
+        Psbt { outputs: vec![Vec::new(); tx.outputs.len()], tx, global_unknown: Vec::new(), inputs }
+    }
+
+    #[test]
+    fn test_segwit_v0_sighash_matches_bip143() {
+        let psbt = bip143_psbt();
+        let pubkey_hash: [u8; 20] = alloy::hex::decode(&BIP143_SCRIPT[4..]).unwrap().try_into().unwrap();
+        let sighash = psbt.tx.segwit_v0_sighash(1, &p2pkh_script(&pubkey_hash), 600_000_000);
+        assert_eq!(alloy::hex::encode(sighash), BIP143_SIGHASH);
+        assert_eq!(alloy::hex::encode(psbt.tx.encode(&[], &[])), BIP143_TX);
+    }
+
+    #[test]
+    fn test_sign_round_trip_and_finalize() {
+        let key = SigningKey::from_slice(&alloy::hex::decode(BIP143_KEY).unwrap()).unwrap();
+        let mut psbt = Psbt::from_base64(&bip143_psbt().to_base64()).unwrap();
+        psbt.inputs[0].unknown.push((vec![0xfc, 0x01], vec![0xaa]));
+        assert_eq!(psbt.sign(&key).unwrap(), 1);
+
+        // Signatures and unknown fields survive serialization
+        let mut psbt = Psbt::from_base64(&psbt.to_base64()).unwrap();
+        assert_eq!(psbt.inputs[0].unknown, vec![(vec![0xfc, 0x01], vec![0xaa])]);
+        let (public_key, signature) = &psbt.inputs[1].partial_sigs[0];
+        let (der, sighash_type) = signature.split_at(signature.len() - 1);
+        assert_eq!(sighash_type, [0x01]);
+        let sighash: [u8; 32] = alloy::hex::decode(BIP143_SIGHASH).unwrap().try_into().unwrap();
+        VerifyingKey::from_sec1_bytes(public_key)
+            .unwrap()
+            .verify_prehash(&sighash, &Signature::from_der(der).unwrap())
+            .unwrap();
+
+        // Input 0 (P2PK) has no signature, so the PSBT cannot be finalized yet
+        assert!(matches!(psbt.finalize(), Err(DomainError::TransferFailed(_))));
+        psbt.inputs[0].final_script_sig = Some(vec![0x00]);
+        let (raw, txid) = psbt.finalize().unwrap();
+        assert_eq!(&raw[4..6], [0x00, 0x01]);
+        assert_eq!(txid.len(), 64);
+    }
+
+    #[test]
+    fn test_select_coins() {
+        let utxos = vec![utxo(1, 10_000), utxo(2, 50_000), utxo(3, 20_000)];
+
+        // The largest UTXO covers amount and fee, leaving change
+        let selection = select_coins(utxos.clone(), 30_000, 2, 22, 22).unwrap();
+        assert_eq!(selection.utxos, vec![utxo(2, 50_000)]);
+        assert_eq!(selection.fee, 2 * (11 + 68 + 31 + 31));
+        assert_eq!(selection.change, 50_000 - 30_000 - selection.fee);
+
+        // Change that would be dust goes to the fee instead
+        let selection = select_coins(utxos.clone(), 49_700, 1, 22, 22).unwrap();
+        assert_eq!((selection.fee, selection.change), (300, 0));
+
+        let selection = select_coins(utxos.clone(), 71_000, 1, 22, 22).unwrap();
+        assert_eq!(selection.utxos.len(), 3);
+
+        assert!(matches!(
+            select_coins(utxos, 80_000, 1, 22, 22),
+            Err(DomainError::InsufficientBalance { available: 80_000, .. })
+        ));
+    }
+
+    #[test]
+    fn test_script_pubkey() {
+        let script = |address: &str, network: &Network| script_pubkey(&Address::new_unchecked(address.to_string()), network);
+        assert_eq!(
+            alloy::hex::encode(script("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4", &Network::BitcoinMainnet).unwrap()),
+            "0014751e76e8199196d454941c45d1b3a323f1433bd6"
+        );
+        assert_eq!(
+            alloy::hex::encode(script("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa", &Network::BitcoinMainnet).unwrap()),
+            "76a91462e907b15cbf27d5425399ebf6f0fb50ebb88f1888ac"
+        );
+        assert!(matches!(
+            script("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa", &Network::BitcoinTestnet),
+            Err(DomainError::ConfigurationError(_))
+        ));
+        assert!(is_p2wpkh(&script("tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx", &Network::BitcoinTestnet).unwrap()));
+    }
+}
@@ -1,17 +1,76 @@
 use async_trait::async_trait;
+use futures::future;
 use reqwest::Client;
 use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use super::bitcoin_psbt::{self, Psbt, DUST_LIMIT_SATS, TX_OVERHEAD_VSIZE};
+use super::http::{
+    build_http_client_with_timeout, error_chain, http_status_error, read_body_capped, DEFAULT_MAX_RESPONSE_SIZE,
+};
+use crate::adapter::infrastructure::{
+    keys,
+    resilience::{retry_with_backoff, RetryPolicy},
+};
 use crate::core::domain::{
-    errors::DomainError,
-    services::BlockchainService,
-    value_objects::{Address, Balance, Network, TransactionHash},
+    errors::{redact_payloads, DomainError},
+    services::{BlockchainService, Signer},
+    value_objects::{
+        AccountInfo, Address, Balance, BitcoinAddressType, BitcoinBalance, ChainType, FeeDetails, FeeEstimate, Finality,
+        MaxSendable, Network, SecretKey, TransactionHash, Utxo,
+    },
 };
 
+/// Confirmations after which a Bitcoin block is treated as `Finality::Safe`
+const SAFE_CONFIRMATIONS: u64 = 3;
+/// Unspent outputs requested per `/unspent` page (the API maximum)
+const UNSPENT_PAGE_SIZE: usize = 1000;
+
+/// Recommended fee rates (sat/vB) from a mempool.space-compatible fee API
+///
+/// Only the half-hour rate is required; the other buckets refine
+/// confirmation time estimates when the API provides them.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RecommendedFees {
+    fastest_fee: Option<u64>,
+    half_hour_fee: u64,
+    hour_fee: Option<u64>,
+    economy_fee: Option<u64>,
+}
+
+impl RecommendedFees {
+    /// Expected wait for a transaction paying `sat_per_vbyte`: the target of
+    /// the fastest bucket the rate reaches, or `None` below every bucket
+    fn confirmation_time(&self, sat_per_vbyte: u64) -> Option<Duration> {
+        const MINUTE: u64 = 60;
+        [
+            (self.fastest_fee, 10 * MINUTE),
+            (Some(self.half_hour_fee), 30 * MINUTE),
+            (self.hour_fee, 60 * MINUTE),
+            (self.economy_fee, 24 * 60 * MINUTE),
+        ]
+        .into_iter()
+        .find_map(|(rate, secs)| rate.filter(|rate| sat_per_vbyte >= *rate).map(|_| Duration::from_secs(secs)))
+    }
+}
+
 /// Bitcoin blockchain service using blockchain.info API
+///
+/// blockchain.info's `final_balance` already includes unconfirmed (mempool)
+/// transactions, so `get_pending_balance` uses the default `get_balance` fallback.
 pub struct BitcoinBlockchainService {
     client: Client,
     network: Network,
     api_base_url: String,
+    fee_api_url: String,
+    /// Confirmations before an output counts as confirmed in `get_balance_detailed`
+    min_confirmations: u64,
+    /// Responses with larger bodies are rejected unread
+    max_response_size: usize,
+    /// Retries of failed API reads (broadcasts are sent once)
+    retry_policy: RetryPolicy,
 }
 
 impl BitcoinBlockchainService {
@@ -23,81 +82,499 @@ impl BitcoinBlockchainService {
             ));
         }
 
-        let api_base_url = match network {
-            Network::BitcoinMainnet => "https://blockchain.info",
-            Network::BitcoinTestnet => "https://testnet.blockchain.info",
+        let (api_base_url, fee_api_url) = match &network {
+            Network::BitcoinMainnet => (
+                "https://blockchain.info",
+                "https://mempool.space/api/v1/fees/recommended",
+            ),
+            Network::BitcoinTestnet => (
+                "https://testnet.blockchain.info",
+                "https://mempool.space/testnet/api/v1/fees/recommended",
+            ),
             _ => unreachable!(),
         };
 
+        Self::new_with_api_urls(network, api_base_url, fee_api_url).await
+    }
+
+    /// Create service against explicit blockchain.info-compatible and fee API URLs
+    pub async fn new_with_api_urls(
+        network: Network,
+        api_base_url: &str,
+        fee_api_url: &str,
+    ) -> Result<Self, DomainError> {
+        if !network.is_bitcoin() {
+            return Err(DomainError::ConfigurationError(
+                "Network must be a Bitcoin network".to_string(),
+            ));
+        }
+
         Ok(Self {
             client: Client::new(),
             network,
             api_base_url: api_base_url.to_string(),
+            fee_api_url: fee_api_url.to_string(),
+            min_confirmations: 1,
+            max_response_size: DEFAULT_MAX_RESPONSE_SIZE,
+            retry_policy: RetryPolicy::none(),
         })
     }
 
+    /// Query a different blockchain.info-compatible API (e.g. a self-hosted mirror)
+    pub fn with_api_base_url(mut self, api_base_url: &str) -> Self {
+        self.api_base_url = api_base_url.to_string();
+        self
+    }
+
+    /// Fail API requests that take longer than `timeout` with a `NetworkError`
+    pub fn with_timeout(mut self, timeout: Duration) -> Result<Self, DomainError> {
+        self.client = build_http_client_with_timeout(&HashMap::new(), Some(timeout))?;
+        Ok(self)
+    }
+
+    /// Retry API reads that fail with a network error or rate limit (default: no retries)
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Confirmations an output needs to count as confirmed (default 1)
+    pub fn with_min_confirmations(mut self, min_confirmations: u64) -> Self {
+        self.min_confirmations = min_confirmations;
+        self
+    }
+
+    /// Reject response bodies larger than `max_response_size` bytes (default 4 MiB)
+    pub fn with_max_response_size(mut self, max_response_size: usize) -> Self {
+        self.max_response_size = max_response_size;
+        self
+    }
+
     /// Get the network this service is connected to
     pub fn network(&self) -> &Network {
         &self.network
     }
-}
 
-#[async_trait]
-impl BlockchainService for BitcoinBlockchainService {
-    async fn get_balance(&self, address: &Address) -> Result<Balance, DomainError> {
-        // Call blockchain.info API: /balance?active=address
-        let url = format!("{}/balance?active={}", self.api_base_url, address.as_str());
+    /// Fetch the recommended fee rate (sat/vB) targeting ~30 minute confirmation
+    async fn get_fee_rate(&self) -> Result<u64, DomainError> {
+        Ok(self.get_recommended_fees().await?.half_hour_fee)
+    }
+
+    /// Fetch the fee API's rate buckets
+    async fn get_recommended_fees(&self) -> Result<RecommendedFees, DomainError> {
+        self.with_retries(|| async {
+            let response = self
+                .client
+                .get(&self.fee_api_url)
+                .send()
+                .await
+                .map_err(|e| DomainError::NetworkError(format!("Failed to query fee rate: {}", e)))?;
+            serde_json::from_slice(&self.read_body(response).await?)
+                .map_err(|e| DomainError::NetworkError(format!("Failed to parse fee rate response: {}", e)))
+        })
+        .await
+    }
+
+    /// Confirmed vs unconfirmed balance, summed from the address's unspent outputs
+    pub async fn get_balance_detailed(&self, address: &Address) -> Result<BitcoinBalance, DomainError> {
+        let utxos = self.list_utxos(address).await?;
+        Ok(BitcoinBalance::from_utxos(
+            utxos.iter().map(|utxo| (utxo.value_sats, utxo.confirmations)),
+            self.min_confirmations,
+        ))
+    }
+
+    /// Unspent outputs of `address`, including unconfirmed ones
+    ///
+    /// Pages through `/unspent` so addresses with more than 1000 UTXOs are
+    /// listed in full.
+    pub async fn list_utxos(&self, address: &Address) -> Result<Vec<Utxo>, DomainError> {
+        #[derive(Deserialize)]
+        struct UnspentOutput {
+            tx_hash_big_endian: String,
+            tx_output_n: u32,
+            value: u64,
+            confirmations: u64,
+        }
+
+        #[derive(Deserialize)]
+        struct UnspentResponse {
+            unspent_outputs: Vec<UnspentOutput>,
+        }
+
+        let mut utxos = Vec::new();
+        loop {
+            let url = format!(
+                "{}/unspent?active={}&limit={}&offset={}",
+                self.api_base_url,
+                address.as_str(),
+                UNSPENT_PAGE_SIZE,
+                utxos.len()
+            );
+            let page = self
+                .with_retries(|| async {
+                    let response = self.client.get(&url).send().await.map_err(|e| {
+                        DomainError::NetworkError(format!("Failed to query unspent outputs: {}", error_chain(&e)))
+                    })?;
+
+                    let status = response.status();
+                    let body = self.read_body(response).await?;
+                    // blockchain.info answers an address without UTXOs with a 500 and this message
+                    if String::from_utf8_lossy(&body).contains("No free outputs to spend") {
+                        return Ok(None);
+                    }
+                    if !status.is_success() {
+                        return Err(http_status_error("Failed to query unspent outputs", status));
+                    }
+                    serde_json::from_slice::<UnspentResponse>(&body).map(Some).map_err(|e| {
+                        DomainError::BlockchainError(format!("Failed to parse unspent outputs: {}", e))
+                    })
+                })
+                .await?;
+            let Some(page) = page else {
+                break;
+            };
+            let page_len = page.unspent_outputs.len();
+            utxos.extend(page.unspent_outputs.into_iter().map(|utxo| Utxo {
+                txid: utxo.tx_hash_big_endian,
+                vout: utxo.tx_output_n,
+                value_sats: utxo.value,
+                confirmations: utxo.confirmations,
+            }));
+            if page_len < UNSPENT_PAGE_SIZE {
+                break;
+            }
+        }
+
+        Ok(utxos)
+    }
+
+    /// Build an unsigned PSBT (BIP-174, base64) sending `amount` sats from `from` to `to`
+    ///
+    /// Spends confirmed outputs of `from` (see `with_min_confirmations`),
+    /// largest first, paying `fee_rate` sat/vB and returning change to
+    /// `from`, which must be a native SegWit (P2WPKH) address. Inputs signal
+    /// replace-by-fee. Sign the result with [`sign_psbt`](Self::sign_psbt)
+    /// or any BIP-174 signer such as a hardware wallet, then send it with
+    /// [`finalize_and_broadcast`](Self::finalize_and_broadcast).
+    pub async fn create_psbt(
+        &self,
+        from: &Address,
+        to: &Address,
+        amount: u64,
+        fee_rate: u64,
+    ) -> Result<String, DomainError> {
+        if amount == 0 {
+            return Err(DomainError::InvalidAmount);
+        }
+        if amount < DUST_LIMIT_SATS {
+            return Err(DomainError::TransferFailed(format!(
+                "Amount of {} sats is below the dust limit of {} sats",
+                amount, DUST_LIMIT_SATS
+            )));
+        }
+        let from_script = bitcoin_psbt::script_pubkey(from, &self.network)?;
+        if !bitcoin_psbt::is_p2wpkh(&from_script) {
+            return Err(DomainError::ConfigurationError(
+                "PSBTs can only spend from native SegWit (P2WPKH) addresses".to_string(),
+            ));
+        }
+        let to_script = bitcoin_psbt::script_pubkey(to, &self.network)?;
+
+        let spendable = self
+            .list_utxos(from)
+            .await?
+            .into_iter()
+            .filter(|utxo| utxo.confirmations >= self.min_confirmations)
+            .collect();
+        let selection = bitcoin_psbt::select_coins(spendable, amount, fee_rate, to_script.len(), from_script.len())?;
+        tracing::debug!(
+            "PSBT spends {} UTXOs, fee {} sats, change {} sats",
+            selection.utxos.len(),
+            selection.fee,
+            selection.change
+        );
+
+        Ok(Psbt::transfer(&selection, &from_script, to_script, amount)?.to_base64())
+    }
+
+    /// Add signatures for every PSBT input `private_key` (WIF or hex) can spend
+    ///
+    /// Only P2WPKH inputs are signed; inputs owned by other keys are left for
+    /// their signers. Fails if the key owns none of the inputs.
+    pub fn sign_psbt(&self, psbt: &str, private_key: &SecretKey) -> Result<String, DomainError> {
+        let (signing_key, compressed) = keys::parse_bitcoin_private_key(
+            private_key.expose_secret(),
+            matches!(self.network, Network::BitcoinTestnet),
+        )?;
+        if !compressed {
+            return Err(DomainError::InvalidPrivateKey(
+                "SegWit inputs require a compressed public key".to_string(),
+            ));
+        }
+
+        let mut psbt = Psbt::from_base64(psbt)?;
+        if psbt.sign(&signing_key)? == 0 {
+            return Err(DomainError::TransferFailed(
+                "None of the PSBT inputs can be spent by this key".to_string(),
+            ));
+        }
+        Ok(psbt.to_base64())
+    }
+
+    /// Finalize a fully signed PSBT and broadcast the extracted transaction
+    ///
+    /// Returns the txid (hex, as shown by block explorers).
+    pub async fn finalize_and_broadcast(&self, psbt: &str) -> Result<TransactionHash, DomainError> {
+        let (raw, txid) = Psbt::from_base64(psbt)?.finalize()?;
 
         let response = self
             .client
-            .get(&url)
+            .post(format!("{}/pushtx", self.api_base_url))
+            .form(&[("tx", alloy::hex::encode(raw))])
             .send()
             .await
-            .map_err(|e| DomainError::NetworkError(format!("Failed to query Bitcoin balance: {}", e)))?;
+            .map_err(|e| DomainError::NetworkError(format!("Failed to broadcast transaction: {}", error_chain(&e))))?;
 
-        if !response.status().is_success() {
-            return Err(DomainError::NetworkError(format!(
-                "Bitcoin API returned error: {}",
-                response.status()
+        let status = response.status();
+        let body = self.read_body(response).await?;
+        if !status.is_success() {
+            return Err(DomainError::TransferFailed(format!(
+                "Broadcast rejected ({}): {}",
+                status,
+                redact_payloads(String::from_utf8_lossy(&body).trim())
             )));
         }
 
-        // Parse response JSON
-        let response_text = response
-            .text()
-            .await
-            .map_err(|e| DomainError::NetworkError(format!("Failed to read response: {}", e)))?;
+        Ok(TransactionHash::new_unchecked(txid))
+    }
 
-        // The response is like: {"address":{"final_balance":123456}}
-        let parsed: serde_json::Value = serde_json::from_str(&response_text)
-            .map_err(|e| DomainError::BlockchainError(format!("Failed to parse response: {}", e)))?;
+    /// GET `url` and parse the body as JSON
+    async fn get_json(&self, url: &str, context: &str) -> Result<serde_json::Value, DomainError> {
+        self.with_retries(|| async {
+            let response = self
+                .client
+                .get(url)
+                .send()
+                .await
+                .map_err(|e| DomainError::NetworkError(format!("{}: {}", context, error_chain(&e))))?;
 
-        // Extract balance from nested structure
-        let balance_satoshis = parsed
-            .get(address.as_str())
-            .and_then(|addr_info| addr_info.get("final_balance"))
-            .and_then(|bal| bal.as_u64())
-            .ok_or_else(|| {
-                DomainError::BlockchainError(format!("Failed to extract balance from response: {}", response_text))
-            })?;
+            if !response.status().is_success() {
+                return Err(http_status_error(context, response.status()));
+            }
+
+            serde_json::from_slice(&self.read_body(response).await?)
+                .map_err(|e| DomainError::BlockchainError(format!("{}: invalid JSON response: {}", context, e)))
+        })
+        .await
+    }
+
+    /// Run one API read under the retry policy, retrying network errors and rate limits
+    async fn with_retries<T, F, Fut>(&self, op: F) -> Result<T, DomainError>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<T, DomainError>>,
+    {
+        retry_with_backoff(&self.retry_policy, DomainError::is_retryable, op).await
+    }
+
+    /// Final balance (satoshis) and transaction count of `address`
+    async fn address_summary(&self, address: &Address) -> Result<(u64, Option<u64>), DomainError> {
+        // The response is like: {"<address>":{"final_balance":123456,"n_tx":2,...}}
+        let url = format!("{}/balance?active={}", self.api_base_url, address.as_str());
+        let parsed = self.get_json(&url, "Failed to query Bitcoin balance").await?;
+        if let Some(info) = parsed.get(address.as_str()) {
+            if let Some(satoshis) = final_balance(info) {
+                return Ok((satoshis, info.get("n_tx").and_then(|n| n.as_u64())));
+            }
+        }
+
+        // Some address formats come back keyed differently (or not at
+        // all); /rawaddr reports the balance at the top level instead
+        tracing::warn!(
+            "Unexpected /balance response for {}, falling back to /rawaddr",
+            address
+        );
+        let url = format!("{}/rawaddr/{}?limit=0", self.api_base_url, address.as_str());
+        let raw = self.get_json(&url, "Failed to query Bitcoin address").await?;
+        let satoshis = final_balance(&raw).ok_or_else(|| {
+            DomainError::BlockchainError(format!(
+                "Failed to extract balance for {}: neither /balance nor /rawaddr reported final_balance",
+                address
+            ))
+        })?;
+        Ok((satoshis, raw.get("n_tx").and_then(|n| n.as_u64())))
+    }
+
+    /// Read a response body, enforcing `max_response_size`
+    async fn read_body(&self, response: reqwest::Response) -> Result<Vec<u8>, DomainError> {
+        read_body_capped(response, self.max_response_size).await
+    }
+}
+
+/// `final_balance` of a blockchain.info address summary
+///
+/// Addresses that have never been used may omit the balance fields entirely;
+/// a summary reporting no transactions (`n_tx: 0`) counts as a zero balance.
+fn final_balance(info: &serde_json::Value) -> Option<u64> {
+    match info.get("final_balance") {
+        Some(balance) => balance.as_u64(),
+        None => (info.get("n_tx").and_then(|n| n.as_u64()) == Some(0)).then_some(0),
+    }
+}
+
+/// Approximate virtual size of a 1-input, 2-output transaction spending from `address_type`
+fn estimate_vsize(address_type: BitcoinAddressType) -> u64 {
+    match address_type {
+        BitcoinAddressType::P2pkh => 226,
+        BitcoinAddressType::P2sh => 166, // P2SH-wrapped P2WPKH
+        BitcoinAddressType::SegwitV0 => 141,
+        BitcoinAddressType::Taproot => 154,
+    }
+}
+
+/// Approximate virtual size of one input spending from `address_type`, signature included
+fn input_vsize(address_type: BitcoinAddressType) -> u64 {
+    match address_type {
+        BitcoinAddressType::P2pkh => 148,
+        BitcoinAddressType::P2sh => 91, // P2SH-wrapped P2WPKH
+        BitcoinAddressType::SegwitV0 => 68,
+        BitcoinAddressType::Taproot => 58,
+    }
+}
+
+#[async_trait]
+impl BlockchainService for BitcoinBlockchainService {
+    fn chain_type(&self) -> ChainType {
+        ChainType::Bitcoin
+    }
+
+    async fn get_balance(&self, address: &Address) -> Result<Balance, DomainError> {
+        let (balance_satoshis, _) = self.address_summary(address).await?;
 
         // Convert satoshis to Wei for consistency (1 BTC = 10^8 satoshis, 1 ETH = 10^18 Wei)
         // We'll use the same Wei format but interpret it as satoshis for Bitcoin
         Ok(Balance::from_wei(balance_satoshis as u128))
     }
 
+    /// Balance and transaction count (`n_tx`) from the same address summary
+    async fn get_account_info(&self, address: &Address) -> Result<AccountInfo, DomainError> {
+        let (balance_satoshis, n_tx) = self.address_summary(address).await?;
+        let info = AccountInfo::new(address.clone(), Balance::from_wei(balance_satoshis as u128));
+        Ok(match n_tx {
+            Some(n_tx) => info.with_transaction_count(n_tx),
+            None => info,
+        })
+    }
+
+    /// One `/balance?active=a|b|c` request for all addresses
+    ///
+    /// Addresses missing from the response are looked up individually via
+    /// `get_balance` (which falls back to `/rawaddr`).
+    async fn get_balances(&self, addresses: &[Address]) -> Vec<Result<Balance, DomainError>> {
+        if addresses.is_empty() {
+            return Vec::new();
+        }
+
+        let active = addresses.iter().map(Address::as_str).collect::<Vec<_>>().join("|");
+        let url = format!("{}/balance?active={}", self.api_base_url, active);
+        let parsed = match self.get_json(&url, "Failed to query Bitcoin balances").await {
+            Ok(parsed) => parsed,
+            Err(e) => return addresses.iter().map(|_| Err(e.clone())).collect(),
+        };
+
+        future::join_all(addresses.iter().map(|address| {
+            let parsed = &parsed;
+            async move {
+                match parsed.get(address.as_str()).and_then(final_balance) {
+                    Some(satoshis) => Ok(Balance::from_wei(satoshis as u128)),
+                    None => self.get_balance(address).await,
+                }
+            }
+        }))
+        .await
+    }
+
     async fn transfer(
         &self,
         _from: &Address,
         _to: &Address,
         _amount: u128,
-        _private_key: &str,
+        _signer: &dyn Signer,
     ) -> Result<TransactionHash, DomainError> {
         Err(DomainError::TransferFailed(
             "Bitcoin transfers not yet implemented".to_string(),
         ))
     }
 
+    async fn estimate_fee(
+        &self,
+        from: &Address,
+        _to: &Address,
+        _amount: u128,
+    ) -> Result<FeeEstimate, DomainError> {
+        let vsize = estimate_vsize(from.bitcoin_address_type()?);
+        let sat_per_vbyte = self.get_fee_rate().await?;
+
+        FeeEstimate::from_details(FeeDetails::Bitcoin { vsize, sat_per_vbyte })
+            .ok_or_else(|| DomainError::BlockchainError("Fee estimate overflows u128".to_string()))
+    }
+
+    /// Spends every confirmed UTXO (see `with_min_confirmations`) into a
+    /// single output with no change, so the fee grows with the number of inputs
+    ///
+    /// An amount below the dust limit counts as nothing left to send.
+    async fn max_sendable(&self, from: &Address, to: &Address) -> Result<MaxSendable, DomainError> {
+        let input_vsize = input_vsize(from.bitcoin_address_type()?);
+        let to_script = bitcoin_psbt::script_pubkey(to, &self.network)?;
+        let (utxos, sat_per_vbyte) = future::try_join(self.list_utxos(from), self.get_fee_rate()).await?;
+
+        let spendable: Vec<Utxo> = utxos
+            .into_iter()
+            .filter(|utxo| utxo.confirmations >= self.min_confirmations)
+            .collect();
+        let total: u64 = spendable.iter().map(|utxo| utxo.value_sats).sum();
+        let inputs = spendable.len().max(1) as u64;
+        let vsize = TX_OVERHEAD_VSIZE + input_vsize * inputs + bitcoin_psbt::output_vsize(to_script.len());
+        let fee = FeeEstimate::from_details(FeeDetails::Bitcoin { vsize, sat_per_vbyte })
+            .ok_or_else(|| DomainError::BlockchainError("Fee estimate overflows u128".to_string()))?;
+
+        let dust_floor = fee.total_native.to_wei().saturating_add(DUST_LIMIT_SATS as u128);
+        if (total as u128) < dust_floor {
+            return Err(DomainError::InsufficientBalance {
+                required: dust_floor,
+                available: total as u128,
+            });
+        }
+        MaxSendable::new(Balance::from_wei(total as u128), fee)
+    }
+
+    /// Target of the fee API's fastest bucket the fee rate reaches
+    /// (next block ≈ 10 min, half hour, hour, economy ≈ 1 day)
+    async fn estimate_confirmation_time(&self, fee: &FeeEstimate) -> Result<Duration, DomainError> {
+        let FeeDetails::Bitcoin { sat_per_vbyte, .. } = fee.details else {
+            return Err(DomainError::ConfigurationError(
+                "Not a Bitcoin fee estimate".to_string(),
+            ));
+        };
+        let fees = self.get_recommended_fees().await?;
+        fees.confirmation_time(sat_per_vbyte).ok_or_else(|| {
+            DomainError::BlockchainError(format!(
+                "{} sat/vB is below every recommended fee rate; the transaction may not confirm until the mempool clears",
+                sat_per_vbyte
+            ))
+        })
+    }
+
+    /// `url` replaces the blockchain.info-compatible API; the fee API is kept
+    async fn with_endpoint(&self, url: &str) -> Result<Arc<dyn BlockchainService>, DomainError> {
+        let sibling = Self::new_with_api_urls(self.network.clone(), url, &self.fee_api_url)
+            .await?
+            .with_min_confirmations(self.min_confirmations)
+            .with_retry_policy(self.retry_policy);
+        Ok(Arc::new(sibling))
+    }
+
     async fn is_connected(&self) -> bool {
         // Try to fetch chain info
         let url = format!("{}/latestblock", self.api_base_url);
@@ -105,33 +582,87 @@ impl BlockchainService for BitcoinBlockchainService {
     }
 
     async fn get_block_number(&self) -> Result<u64, DomainError> {
-        let url = format!("{}/latestblock", self.api_base_url);
-
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| DomainError::NetworkError(format!("Failed to get latest block: {}", e)))?;
-
         #[derive(Deserialize)]
         struct LatestBlock {
             height: u64,
         }
 
-        let block: LatestBlock = response
-            .json()
-            .await
-            .map_err(|e| DomainError::NetworkError(format!("Failed to parse block response: {}", e)))?;
+        let url = format!("{}/latestblock", self.api_base_url);
+        let block: LatestBlock = self
+            .with_retries(|| async {
+                let response = self.client.get(&url).send().await.map_err(|e| {
+                    DomainError::NetworkError(format!("Failed to get latest block: {}", error_chain(&e)))
+                })?;
+
+                if !response.status().is_success() {
+                    return Err(http_status_error("Failed to get latest block", response.status()));
+                }
+
+                serde_json::from_slice(&self.read_body(response).await?)
+                    .map_err(|e| DomainError::NetworkError(format!("Failed to parse block response: {}", e)))
+            })
+            .await?;
 
         Ok(block.height)
     }
+
+    /// Tip height, or the newest block with 3 (`Safe`) / 6 (`Finalized`) confirmations
+    async fn get_height(&self, finality: Finality) -> Result<u64, DomainError> {
+        let tip = self.get_block_number().await?;
+        let confirmations = match finality {
+            Finality::Latest => 1,
+            Finality::Safe => SAFE_CONFIRMATIONS,
+            Finality::Finalized => self.network.recommended_confirmations(),
+        };
+        Ok(height_with_confirmations(tip, confirmations))
+    }
+}
+
+/// Height of the newest block that has `confirmations` confirmations (the tip has one)
+fn height_with_confirmations(tip: u64, confirmations: u64) -> u64 {
+    tip.saturating_sub(confirmations.saturating_sub(1))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_confirmation_time_buckets() {
+        let fees = RecommendedFees { fastest_fee: Some(20), half_hour_fee: 12, hour_fee: Some(8), economy_fee: Some(4) };
+        assert_eq!(fees.confirmation_time(25), Some(Duration::from_secs(600)));
+        assert_eq!(fees.confirmation_time(12), Some(Duration::from_secs(1_800)));
+        assert_eq!(fees.confirmation_time(9), Some(Duration::from_secs(3_600)));
+        assert_eq!(fees.confirmation_time(4), Some(Duration::from_secs(86_400)));
+        assert_eq!(fees.confirmation_time(3), None);
+
+        // Only the half-hour bucket is known
+        let fees = RecommendedFees { fastest_fee: None, half_hour_fee: 12, hour_fee: None, economy_fee: None };
+        assert_eq!(fees.confirmation_time(50), Some(Duration::from_secs(1_800)));
+        assert_eq!(fees.confirmation_time(11), None);
+    }
+
+    #[test]
+    fn test_height_with_confirmations() {
+        assert_eq!(height_with_confirmations(800_000, 1), 800_000);
+        assert_eq!(height_with_confirmations(800_000, 6), 799_995);
+        assert_eq!(height_with_confirmations(2, 6), 0);
+    }
+
+    #[test]
+    fn test_final_balance_of_unused_address_is_zero() {
+        assert_eq!(final_balance(&serde_json::json!({ "final_balance": 42, "n_tx": 1 })), Some(42));
+        assert_eq!(final_balance(&serde_json::json!({ "n_tx": 0 })), Some(0));
+        assert_eq!(final_balance(&serde_json::json!({ "n_tx": 3 })), None);
+        assert_eq!(final_balance(&serde_json::json!({})), None);
+    }
+
+    #[test]
+    fn test_estimate_vsize_by_address_type() {
+        assert!(estimate_vsize(BitcoinAddressType::SegwitV0) < estimate_vsize(BitcoinAddressType::P2sh));
+        assert!(estimate_vsize(BitcoinAddressType::P2sh) < estimate_vsize(BitcoinAddressType::P2pkh));
+    }
+
     #[tokio::test]
     #[ignore] // Requires network connection
     async fn test_bitcoin_service_creation() {
@@ -0,0 +1,489 @@
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use crate::core::domain::{
+    errors::DomainError,
+    services::{BlockchainService, Signer},
+    value_objects::{
+        AccountInfo, Address, Balance, ChainType, ConnectionDiagnostics, FeeEstimate, Finality, MaxSendable, Network,
+        SecretKey, TransactionHash, TransactionReceipt, TxStatus,
+    },
+};
+
+/// How long a `latest` balance is served from cache by default (about one Ethereum block)
+pub const DEFAULT_BALANCE_TTL: Duration = Duration::from_secs(12);
+/// Historical balances kept by default before the least recently used is evicted
+pub const DEFAULT_HISTORICAL_CAPACITY: usize = 1024;
+/// Final transaction statuses and receipts kept by default (each)
+pub const DEFAULT_TRANSACTION_CAPACITY: usize = 1024;
+
+/// Caching decorator for balance and transaction reads
+///
+/// Keeps four caches:
+/// - `latest` balances (`get_balance`, `get_balances`) keyed by address,
+///   served for `ttl` and then fetched again
+/// - historical balances (`get_balance_at_block`) keyed by
+///   `(address, network, block)`, which never change and so never expire;
+///   the least recently used entry is evicted once `capacity` is reached
+/// - terminal transaction statuses (`Confirmed`/`Reverted`) and receipts,
+///   keyed by transaction hash and likewise kept until evicted; `Pending`
+///   and `NotFound` are always fetched again, since they are what pollers
+///   wait to see change
+///
+/// Errors are never cached, and everything else (transfers, heights,
+/// pending balances) goes straight to the inner service. A chain
+/// reorganization can in principle undo an inclusion; callers that care
+/// should wait for finality before trusting a cached status.
+///
+/// ```text
+/// caller ──> CachedBlockchainService ──(miss)──> inner service
+/// ```
+pub struct CachedBlockchainService {
+    inner: Arc<dyn BlockchainService>,
+    network: Network,
+    ttl: Duration,
+    latest: Arc<Mutex<HashMap<String, (Balance, Instant)>>>,
+    historical: Arc<Mutex<LruCache<HistoricalKey, Balance>>>,
+    statuses: Arc<Mutex<LruCache<String, TxStatus>>>,
+    receipts: Arc<Mutex<LruCache<String, TransactionReceipt>>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct HistoricalKey {
+    address: String,
+    network: String,
+    block_number: u64,
+}
+
+impl CachedBlockchainService {
+    /// Cache balances read from `inner`, which serves `network`
+    pub fn new(inner: Arc<dyn BlockchainService>, network: Network) -> Self {
+        Self {
+            inner,
+            network,
+            ttl: DEFAULT_BALANCE_TTL,
+            latest: Arc::new(Mutex::new(HashMap::new())),
+            historical: Arc::new(Mutex::new(LruCache::new(DEFAULT_HISTORICAL_CAPACITY))),
+            statuses: Arc::new(Mutex::new(LruCache::new(DEFAULT_TRANSACTION_CAPACITY))),
+            receipts: Arc::new(Mutex::new(LruCache::new(DEFAULT_TRANSACTION_CAPACITY))),
+        }
+    }
+
+    /// Serve `latest` balances from cache for `ttl` (zero disables the cache)
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Keep at most `capacity` historical balances (zero disables the cache)
+    pub fn with_historical_capacity(mut self, capacity: usize) -> Self {
+        self.historical = Arc::new(Mutex::new(LruCache::new(capacity)));
+        self
+    }
+
+    /// Keep at most `capacity` final statuses and as many receipts (zero disables the cache)
+    pub fn with_transaction_capacity(mut self, capacity: usize) -> Self {
+        self.statuses = Arc::new(Mutex::new(LruCache::new(capacity)));
+        self.receipts = Arc::new(Mutex::new(LruCache::new(capacity)));
+        self
+    }
+
+    fn cached_latest(&self, address: &Address) -> Option<Balance> {
+        let latest = self.latest.lock().unwrap();
+        latest
+            .get(address.as_str())
+            .filter(|(_, fetched_at)| fetched_at.elapsed() < self.ttl)
+            .map(|(balance, _)| *balance)
+    }
+
+    fn store_latest(&self, address: &Address, balance: &Balance) {
+        if self.ttl.is_zero() {
+            return;
+        }
+        let mut latest = self.latest.lock().unwrap();
+        // Drop expired entries so the map only grows with live addresses
+        latest.retain(|_, (_, fetched_at)| fetched_at.elapsed() < self.ttl);
+        latest.insert(address.as_str().to_string(), (*balance, Instant::now()));
+    }
+
+    fn historical_key(&self, address: &Address, block_number: u64) -> HistoricalKey {
+        HistoricalKey {
+            address: address.as_str().to_string(),
+            network: self.network.name().to_string(),
+            block_number,
+        }
+    }
+}
+
+#[async_trait]
+impl BlockchainService for CachedBlockchainService {
+    fn chain_type(&self) -> ChainType {
+        self.inner.chain_type()
+    }
+
+    async fn get_balance(&self, address: &Address) -> Result<Balance, DomainError> {
+        if let Some(balance) = self.cached_latest(address) {
+            return Ok(balance);
+        }
+        let balance = self.inner.get_balance(address).await?;
+        self.store_latest(address, &balance);
+        Ok(balance)
+    }
+
+    /// Fetches only the uncached addresses, in one batch through the inner service
+    async fn get_balances(&self, addresses: &[Address]) -> Vec<Result<Balance, DomainError>> {
+        let mut results: Vec<_> = addresses.iter().map(|address| self.cached_latest(address).map(Ok)).collect();
+        let misses: Vec<Address> = addresses
+            .iter()
+            .zip(&results)
+            .filter(|(_, cached)| cached.is_none())
+            .map(|(address, _)| address.clone())
+            .collect();
+        if misses.is_empty() {
+            return results.into_iter().flatten().collect();
+        }
+
+        let mut fetched = self.inner.get_balances(&misses).await.into_iter();
+        for (address, slot) in addresses.iter().zip(results.iter_mut()) {
+            if slot.is_none() {
+                let result = fetched.next().unwrap_or_else(|| {
+                    Err(DomainError::InvalidResponse("Missing balance in batch response".to_string()))
+                });
+                if let Ok(balance) = &result {
+                    self.store_latest(address, balance);
+                }
+                *slot = Some(result);
+            }
+        }
+        results.into_iter().flatten().collect()
+    }
+
+    async fn get_pending_balance(&self, address: &Address) -> Result<Balance, DomainError> {
+        self.inner.get_pending_balance(address).await
+    }
+
+    async fn get_balance_at_block(&self, address: &Address, block_number: u64) -> Result<Balance, DomainError> {
+        let key = self.historical_key(address, block_number);
+        if let Some(balance) = self.historical.lock().unwrap().get(&key) {
+            return Ok(*balance);
+        }
+        let balance = self.inner.get_balance_at_block(address, block_number).await?;
+        self.historical.lock().unwrap().insert(key, balance);
+        Ok(balance)
+    }
+
+    /// Never cached: nonce and code change independently of the cached balance
+    async fn get_account_info(&self, address: &Address) -> Result<AccountInfo, DomainError> {
+        self.inner.get_account_info(address).await
+    }
+
+    async fn transfer(
+        &self,
+        from: &Address,
+        to: &Address,
+        amount: u128,
+        signer: &dyn Signer,
+    ) -> Result<TransactionHash, DomainError> {
+        self.inner.transfer(from, to, amount, signer).await
+    }
+
+    async fn transfer_with_gas_price(
+        &self,
+        from: &Address,
+        to: &Address,
+        amount: u128,
+        signer: &dyn Signer,
+        gas_price: u128,
+    ) -> Result<TransactionHash, DomainError> {
+        self.inner.transfer_with_gas_price(from, to, amount, signer, gas_price).await
+    }
+
+    fn signer_for_key(&self, private_key: &SecretKey) -> Result<Box<dyn Signer>, DomainError> {
+        self.inner.signer_for_key(private_key)
+    }
+
+    async fn estimate_fee(
+        &self,
+        from: &Address,
+        to: &Address,
+        amount: u128,
+    ) -> Result<FeeEstimate, DomainError> {
+        self.inner.estimate_fee(from, to, amount).await
+    }
+
+    /// Never cached: the fee moves with the network, and the balance must be current
+    async fn max_sendable(&self, from: &Address, to: &Address) -> Result<MaxSendable, DomainError> {
+        self.inner.max_sendable(from, to).await
+    }
+
+    /// Never cached: recent block prices move every block
+    async fn estimate_confirmation_time(&self, fee: &FeeEstimate) -> Result<Duration, DomainError> {
+        self.inner.estimate_confirmation_time(fee).await
+    }
+
+    /// A receipt only exists once the transaction is mined, so it is cached as soon as it is returned
+    async fn wait_for_receipt(
+        &self,
+        tx_hash: &TransactionHash,
+        timeout: Duration,
+    ) -> Result<TransactionReceipt, DomainError> {
+        if let Some(receipt) = self.receipts.lock().unwrap().get(&tx_hash.as_str().to_string()) {
+            return Ok(receipt.clone());
+        }
+        let receipt = self.inner.wait_for_receipt(tx_hash, timeout).await?;
+        self.receipts
+            .lock()
+            .unwrap()
+            .insert(tx_hash.as_str().to_string(), receipt.clone());
+        Ok(receipt)
+    }
+
+    /// Only terminal statuses are cached; `Pending` and `NotFound` are fetched every time
+    async fn get_transaction_status(&self, tx_hash: &TransactionHash) -> Result<TxStatus, DomainError> {
+        let key = tx_hash.as_str().to_string();
+        if let Some(status) = self.statuses.lock().unwrap().get(&key) {
+            return Ok(*status);
+        }
+        let status = self.inner.get_transaction_status(tx_hash).await?;
+        if status.is_terminal() {
+            self.statuses.lock().unwrap().insert(key, status);
+        }
+        Ok(status)
+    }
+
+    async fn is_connected(&self) -> bool {
+        self.inner.is_connected().await
+    }
+
+    async fn get_block_number(&self) -> Result<u64, DomainError> {
+        self.inner.get_block_number().await
+    }
+
+    async fn get_height(&self, finality: Finality) -> Result<u64, DomainError> {
+        self.inner.get_height(finality).await
+    }
+
+    async fn diagnose(&self) -> ConnectionDiagnostics {
+        self.inner.diagnose().await
+    }
+
+    /// Wraps the inner sibling, sharing this service's caches
+    ///
+    /// Both endpoints serve the same network, so cached balances stay valid.
+    async fn with_endpoint(&self, url: &str) -> Result<Arc<dyn BlockchainService>, DomainError> {
+        Ok(Arc::new(Self {
+            inner: self.inner.with_endpoint(url).await?,
+            network: self.network.clone(),
+            ttl: self.ttl,
+            latest: self.latest.clone(),
+            historical: self.historical.clone(),
+            statuses: self.statuses.clone(),
+            receipts: self.receipts.clone(),
+        }))
+    }
+
+    fn new_block_ticks(&self, fallback: BoxStream<'static, ()>) -> BoxStream<'static, ()> {
+        self.inner.new_block_ticks(fallback)
+    }
+
+    async fn shutdown(&self) {
+        self.inner.shutdown().await
+    }
+}
+
+/// Fixed-size map that evicts the least recently used entry
+///
+/// Recency is a counter bumped on every hit and insert; `order` maps it back
+/// to the key so the oldest entry is found in O(log n).
+struct LruCache<K, V> {
+    capacity: usize,
+    tick: u64,
+    entries: HashMap<K, (V, u64)>,
+    order: BTreeMap<u64, K>,
+}
+
+impl<K: Clone + Eq + Hash, V> LruCache<K, V> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            tick: 0,
+            entries: HashMap::new(),
+            order: BTreeMap::new(),
+        }
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn get(&mut self, key: &K) -> Option<&V> {
+        let tick = self.next_tick();
+        let (value, used) = self.entries.get_mut(key)?;
+        if let Some(key) = self.order.remove(used) {
+            self.order.insert(tick, key);
+        }
+        *used = tick;
+        Some(value)
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+        if let Some((_, used)) = self.entries.remove(&key) {
+            self.order.remove(&used);
+        } else if self.entries.len() >= self.capacity {
+            if let Some((_, oldest)) = self.order.pop_first() {
+                self.entries.remove(&oldest);
+            }
+        }
+        let tick = self.next_tick();
+        self.order.insert(tick, key.clone());
+        self.entries.insert(key, (value, tick));
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Counts reads; the balance at block `n` is `n` wei, and a transaction is
+    /// pending on the first status query and confirmed in block 7 after that
+    struct CountingService {
+        calls: AtomicU32,
+    }
+
+    #[async_trait]
+    impl BlockchainService for CountingService {
+        fn chain_type(&self) -> ChainType {
+            ChainType::Ethereum
+        }
+
+        async fn get_balance(&self, _address: &Address) -> Result<Balance, DomainError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(Balance::from_wei(1))
+        }
+
+        async fn get_balance_at_block(&self, _address: &Address, block_number: u64) -> Result<Balance, DomainError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(Balance::from_wei(block_number as u128))
+        }
+
+        async fn get_transaction_status(&self, _tx_hash: &TransactionHash) -> Result<TxStatus, DomainError> {
+            Ok(match self.calls.fetch_add(1, Ordering::SeqCst) {
+                0 => TxStatus::Pending,
+                _ => TxStatus::Confirmed { block: Some(7) },
+            })
+        }
+
+        async fn wait_for_receipt(
+            &self,
+            tx_hash: &TransactionHash,
+            _timeout: Duration,
+        ) -> Result<TransactionReceipt, DomainError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(TransactionReceipt {
+                tx_hash: tx_hash.clone(),
+                block_number: Some(7),
+                success: true,
+                gas_used: 21_000,
+                effective_gas_price: 1,
+                logs: Vec::new(),
+            })
+        }
+
+        async fn transfer(
+            &self,
+            _from: &Address,
+            _to: &Address,
+            _amount: u128,
+            _signer: &dyn Signer,
+        ) -> Result<TransactionHash, DomainError> {
+            Err(DomainError::TransferFailed("not supported".to_string()))
+        }
+
+        async fn is_connected(&self) -> bool {
+            true
+        }
+
+        async fn get_block_number(&self) -> Result<u64, DomainError> {
+            Ok(1)
+        }
+    }
+
+    fn address() -> Address {
+        Address::new("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEbC".to_string()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_historical_balance_is_cached_and_latest_expires() {
+        let inner = Arc::new(CountingService { calls: AtomicU32::new(0) });
+        let service = CachedBlockchainService::new(inner.clone(), Network::Mainnet)
+            .with_ttl(Duration::from_millis(50));
+        let address = address();
+
+        assert_eq!(service.get_balance_at_block(&address, 100).await.unwrap().to_wei(), 100);
+        assert_eq!(service.get_balance_at_block(&address, 100).await.unwrap().to_wei(), 100);
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+
+        service.get_balance(&address).await.unwrap();
+        service.get_balance(&address).await.unwrap();
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        service.get_balance(&address).await.unwrap();
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 3);
+
+        // Historical entries outlive the TTL
+        service.get_balance_at_block(&address, 100).await.unwrap();
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_historical_cache_evicts_least_recently_used() {
+        let inner = Arc::new(CountingService { calls: AtomicU32::new(0) });
+        let service = CachedBlockchainService::new(inner.clone(), Network::Mainnet).with_historical_capacity(2);
+        let address = address();
+
+        service.get_balance_at_block(&address, 1).await.unwrap();
+        service.get_balance_at_block(&address, 2).await.unwrap();
+        service.get_balance_at_block(&address, 1).await.unwrap(); // block 2 is now the oldest
+        service.get_balance_at_block(&address, 3).await.unwrap(); // evicts block 2
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 3);
+        assert_eq!(service.historical.lock().unwrap().len(), 2);
+
+        service.get_balance_at_block(&address, 1).await.unwrap();
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 3);
+        service.get_balance_at_block(&address, 2).await.unwrap();
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_terminal_status_and_receipt_are_served_from_cache() {
+        let inner = Arc::new(CountingService { calls: AtomicU32::new(0) });
+        let service = CachedBlockchainService::new(inner.clone(), Network::Mainnet);
+        let tx_hash = TransactionHash::new(format!("0x{}", "ab".repeat(32))).unwrap();
+
+        // Pending is not cached, so the next query reaches the node again
+        assert_eq!(service.get_transaction_status(&tx_hash).await.unwrap(), TxStatus::Pending);
+        let confirmed = TxStatus::Confirmed { block: Some(7) };
+        assert_eq!(service.get_transaction_status(&tx_hash).await.unwrap(), confirmed);
+        assert_eq!(service.get_transaction_status(&tx_hash).await.unwrap(), confirmed);
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+
+        let receipt = service.wait_for_receipt(&tx_hash, Duration::from_secs(1)).await.unwrap();
+        let cached = service.wait_for_receipt(&tx_hash, Duration::from_secs(1)).await.unwrap();
+        assert_eq!(cached, receipt);
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 3);
+    }
+}
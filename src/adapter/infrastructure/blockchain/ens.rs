@@ -0,0 +1,279 @@
+//! ENS name resolution with wildcard (ENSIP-10) and CCIP-read (EIP-3668) support
+//!
+//! Names are looked up on Ethereum mainnet: the registry gives the name's
+//! resolver (or, for wildcard names, the nearest parent's), which is asked
+//! for the `addr` record. Resolvers that keep their records offchain (L2s,
+//! databases) revert with `OffchainLookup`; the wallet then fetches the
+//! answer from the gateway URLs in the revert and hands it back to the
+//! resolver's callback, following at most `max_hops` such redirects.
+
+use alloy::primitives::{address, keccak256, Address as AlloyAddress, Bytes, FixedBytes, B256};
+use alloy::sol;
+use alloy::sol_types::{SolCall, SolError, SolValue};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::Duration;
+use super::http::{build_http_client_with_timeout, error_chain, read_body_capped, DEFAULT_MAX_RESPONSE_SIZE};
+use super::AlloyBlockchainService;
+use crate::core::domain::{errors::DomainError, services::NameResolver, value_objects::Address};
+
+/// ENS registry, deployed at the same address on mainnet and the testnets
+const ENS_REGISTRY_ADDRESS: AlloyAddress = address!("00000000000C2E074eC69A0dFb2997BA6C7d2e1e");
+/// ENSIP-10 `IExtendedResolver` interface id (`resolve(bytes,bytes)`)
+const EXTENDED_RESOLVER_INTERFACE: [u8; 4] = [0x90, 0x61, 0xb9, 0x23];
+/// Default bound on CCIP-read redirects per lookup (EIP-3668 suggests a small limit)
+pub const DEFAULT_MAX_CCIP_HOPS: usize = 4;
+/// Gateway requests give up after this long
+const GATEWAY_TIMEOUT: Duration = Duration::from_secs(10);
+
+sol! {
+    function resolver(bytes32 node) external view returns (address);
+    function supportsInterface(bytes4 interfaceID) external view returns (bool);
+    function addr(bytes32 node) external view returns (address);
+    function resolve(bytes name, bytes data) external view returns (bytes);
+
+    error OffchainLookup(address sender, string[] urls, bytes callData, bytes4 callbackFunction, bytes extraData);
+}
+
+/// Gateway reply to a CCIP-read request
+#[derive(Deserialize)]
+struct GatewayResponse {
+    data: Bytes,
+}
+
+/// [`NameResolver`] for ENS names, querying an Ethereum mainnet endpoint
+pub struct EnsResolver {
+    service: AlloyBlockchainService,
+    http: Client,
+    max_hops: usize,
+}
+
+impl EnsResolver {
+    /// Resolve through `service`, which must be connected to Ethereum mainnet
+    pub fn new(service: AlloyBlockchainService) -> Result<Self, DomainError> {
+        Ok(Self {
+            service,
+            http: build_http_client_with_timeout(&HashMap::new(), Some(GATEWAY_TIMEOUT))?,
+            max_hops: DEFAULT_MAX_CCIP_HOPS,
+        })
+    }
+
+    /// Follow at most `max_hops` CCIP-read redirects per lookup (0 disables offchain names)
+    pub fn with_max_hops(mut self, max_hops: usize) -> Self {
+        self.max_hops = max_hops;
+        self
+    }
+
+    /// Resolver contract for `name`, and whether it was set on `name` itself
+    /// rather than on a parent (a wildcard resolver, per ENSIP-10)
+    async fn find_resolver(&self, name: &str) -> Result<Option<(AlloyAddress, bool)>, DomainError> {
+        let mut suffix = name;
+        loop {
+            let output = self
+                .service
+                .try_call_raw(ENS_REGISTRY_ADDRESS, resolverCall { node: namehash(suffix) }.abi_encode())
+                .await?
+                .map_err(|_| DomainError::BlockchainError("ENS registry call reverted".to_string()))?;
+            let resolver = resolverCall::abi_decode_returns(&output, true)
+                .map_err(|e| DomainError::BlockchainError(format!("Invalid ENS registry response: {}", e)))?
+                ._0;
+            if !resolver.is_zero() {
+                return Ok(Some((resolver, suffix == name)));
+            }
+            match suffix.split_once('.') {
+                Some((_, parent)) => suffix = parent,
+                None => return Ok(None),
+            }
+        }
+    }
+
+    /// `eth_call` that follows `OffchainLookup` reverts through the gateways they name
+    async fn ccip_call(&self, mut to: AlloyAddress, mut data: Vec<u8>) -> Result<Vec<u8>, DomainError> {
+        let mut hops = 0;
+        loop {
+            let revert = match self.service.try_call_raw(to, data).await? {
+                Ok(output) => return Ok(output),
+                Err(revert) => revert,
+            };
+            let lookup = match OffchainLookup::abi_decode(&revert, true) {
+                Ok(lookup) => lookup,
+                Err(_) => return Err(DomainError::BlockchainError("ENS resolver call reverted".to_string())),
+            };
+            // EIP-3668: a lookup may only call back into the contract that raised it
+            if lookup.sender != to {
+                return Err(DomainError::BlockchainError(format!(
+                    "OffchainLookup sender {} does not match resolver {}",
+                    lookup.sender, to
+                )));
+            }
+
+            if hops == self.max_hops {
+                return Err(DomainError::BlockchainError(format!(
+                    "ENS lookup exceeded {} CCIP-read redirects",
+                    self.max_hops
+                )));
+            }
+            hops += 1;
+
+            let response = self.query_gateways(&lookup).await?;
+            to = lookup.sender;
+            data = [
+                lookup.callbackFunction.as_slice(),
+                &(response, lookup.extraData).abi_encode_params(),
+            ]
+            .concat();
+        }
+    }
+
+    /// Ask each gateway URL in turn; 5xx and network errors move on to the next one
+    async fn query_gateways(&self, lookup: &OffchainLookup) -> Result<Bytes, DomainError> {
+        let sender = format!("{:#x}", lookup.sender);
+        let call_data = format!("0x{}", alloy::hex::encode(&lookup.callData));
+        let mut last_error = DomainError::BlockchainError("OffchainLookup lists no gateway URLs".to_string());
+
+        for url in &lookup.urls {
+            let target = url.replace("{sender}", &sender).replace("{data}", &call_data);
+            // URLs with {data} are fetched with GET, the rest get the request as a JSON body
+            let request = if url.contains("{data}") {
+                self.http.get(&target)
+            } else {
+                self.http
+                    .post(&target)
+                    .json(&serde_json::json!({ "data": call_data, "sender": sender }))
+            };
+
+            let response = match request.send().await {
+                Ok(response) => response,
+                Err(e) => {
+                    last_error = DomainError::NetworkError(format!("CCIP-read gateway failed: {}", error_chain(&e)));
+                    continue;
+                }
+            };
+            let status = response.status();
+            if status.is_server_error() {
+                last_error = DomainError::NetworkError(format!("CCIP-read gateway returned HTTP {}", status));
+                continue;
+            }
+            if !status.is_success() {
+                return Err(DomainError::BlockchainError(format!("CCIP-read gateway returned HTTP {}", status)));
+            }
+
+            let body = read_body_capped(response, DEFAULT_MAX_RESPONSE_SIZE).await?;
+            let reply: GatewayResponse = serde_json::from_slice(&body)
+                .map_err(|e| DomainError::InvalidResponse(format!("Invalid CCIP-read gateway response: {}", e)))?;
+            return Ok(reply.data);
+        }
+        Err(last_error)
+    }
+}
+
+#[async_trait]
+impl NameResolver for EnsResolver {
+    async fn resolve_name(&self, name: &str) -> Result<Address, DomainError> {
+        let name = normalize_name(name)?;
+        let node = namehash(&name);
+        let not_found = || DomainError::InvalidQuery(format!("ENS name {} does not resolve to an address", name));
+
+        let Some((resolver, exact)) = self.find_resolver(&name).await? else {
+            return Err(not_found());
+        };
+
+        let addr_call = addrCall { node }.abi_encode();
+        let supports_extended = self
+            .service
+            .try_call_raw(
+                resolver,
+                supportsInterfaceCall { interfaceID: FixedBytes(EXTENDED_RESOLVER_INTERFACE) }.abi_encode(),
+            )
+            .await?
+            .ok()
+            .and_then(|output| supportsInterfaceCall::abi_decode_returns(&output, true).ok())
+            .is_some_and(|supported| supported._0);
+
+        let output = if supports_extended {
+            let call = resolveCall { name: dns_encode(&name)?.into(), data: addr_call.into() };
+            let output = self.ccip_call(resolver, call.abi_encode()).await?;
+            resolveCall::abi_decode_returns(&output, true)
+                .map_err(|e| DomainError::BlockchainError(format!("Invalid ENS resolver response: {}", e)))?
+                ._0
+                .to_vec()
+        } else if exact {
+            self.ccip_call(resolver, addr_call).await?
+        } else {
+            // A parent's resolver only answers for subnames if it supports wildcards
+            return Err(not_found());
+        };
+
+        let address = addrCall::abi_decode_returns(&output, true)
+            .map_err(|e| DomainError::BlockchainError(format!("Invalid ENS resolver response: {}", e)))?
+            ._0;
+        if address.is_zero() {
+            return Err(not_found());
+        }
+        Address::new(address.to_checksum(None))
+    }
+}
+
+/// Lowercase `name` and check it is a dotted name of non-empty labels
+///
+/// Only ASCII case folding is applied; full ENSIP-15 normalization of
+/// Unicode names is left to the caller.
+fn normalize_name(name: &str) -> Result<String, DomainError> {
+    let name = name.trim().trim_end_matches('.').to_lowercase();
+    if !name.contains('.') || name.split('.').any(str::is_empty) {
+        return Err(DomainError::InvalidQuery(format!("Invalid ENS name '{}'", name)));
+    }
+    Ok(name)
+}
+
+/// EIP-137 namehash of `name`
+fn namehash(name: &str) -> B256 {
+    name.rsplit('.')
+        .filter(|label| !label.is_empty())
+        .fold(B256::ZERO, |node, label| {
+            keccak256([node.as_slice(), keccak256(label.as_bytes()).as_slice()].concat())
+        })
+}
+
+/// DNS wire-format encoding of `name`, as `resolve(bytes,bytes)` expects (ENSIP-10)
+fn dns_encode(name: &str) -> Result<Vec<u8>, DomainError> {
+    let mut encoded = Vec::with_capacity(name.len() + 2);
+    for label in name.split('.') {
+        let len = u8::try_from(label.len())
+            .ok()
+            .filter(|len| (1..=63).contains(len))
+            .ok_or_else(|| DomainError::InvalidQuery(format!("Invalid ENS label '{}'", label)))?;
+        encoded.push(len);
+        encoded.extend_from_slice(label.as_bytes());
+    }
+    encoded.push(0);
+    Ok(encoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::b256;
+
+    #[test]
+    fn test_namehash_and_dns_encode() {
+        assert_eq!(namehash(""), B256::ZERO);
+        assert_eq!(
+            namehash("eth"),
+            b256!("93cdeb708b7545dc668eb9280176169d1c33cfd8ed6f04690a0bcc88a93fc4ae")
+        );
+        assert_eq!(
+            namehash("foo.eth"),
+            b256!("de9b09fd7c5f901e23a3f19fecc54828e9c848539801e86591bd9801b019f84f")
+        );
+
+        assert_eq!(dns_encode("foo.eth").unwrap(), b"\x03foo\x03eth\x00");
+        assert!(dns_encode(&format!("{}.eth", "a".repeat(64))).is_err());
+
+        assert_eq!(normalize_name(" Vitalik.ETH. ").unwrap(), "vitalik.eth");
+        assert!(normalize_name("eth").is_err());
+        assert!(normalize_name("foo..eth").is_err());
+    }
+}
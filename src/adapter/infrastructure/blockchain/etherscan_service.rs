@@ -0,0 +1,233 @@
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use crate::core::domain::{
+    errors::DomainError,
+    queries::{GetTransactionHistoryQuery, Page, TransactionSummary},
+    services::TransactionHistoryService,
+    value_objects::{Address, Amount, Network, TransactionHash},
+};
+
+/// Transaction history service using the Etherscan-family `txlist` API
+/// (Etherscan for Ethereum networks, BscScan for BSC, PolygonScan for Polygon)
+pub struct EtherscanHistoryService {
+    client: Client,
+    network: Network,
+    api_base_url: String,
+    api_key: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct EtherscanResponse {
+    status: String,
+    message: String,
+    result: serde_json::Value,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EtherscanTransaction {
+    block_number: String,
+    time_stamp: String,
+    hash: String,
+    from: String,
+    to: String,
+    value: String,
+    is_error: String,
+}
+
+impl EtherscanHistoryService {
+    /// Create new Etherscan history service for an EVM network
+    pub fn new(network: Network, api_key: Option<String>) -> Result<Self, DomainError> {
+        let api_base_url = match network {
+            Network::Mainnet => "https://api.etherscan.io/api",
+            Network::Sepolia => "https://api-sepolia.etherscan.io/api",
+            Network::Holesky => "https://api-holesky.etherscan.io/api",
+            Network::Goerli => "https://api-goerli.etherscan.io/api",
+            Network::BscMainnet => "https://api.bscscan.com/api",
+            Network::BscTestnet => "https://api-testnet.bscscan.com/api",
+            Network::Polygon => "https://api.polygonscan.com/api",
+            _ => {
+                return Err(DomainError::ConfigurationError(format!(
+                    "Transaction history is not available for {}",
+                    network.name()
+                )))
+            }
+        };
+
+        Ok(Self::new_with_base_url(network, api_base_url, api_key))
+    }
+
+    /// Create service against an explicit Etherscan-compatible API URL
+    pub fn new_with_base_url(network: Network, api_base_url: &str, api_key: Option<String>) -> Self {
+        Self {
+            client: Client::new(),
+            network,
+            api_base_url: api_base_url.to_string(),
+            api_key,
+        }
+    }
+
+    /// Get the network this service is connected to
+    pub fn network(&self) -> &Network {
+        &self.network
+    }
+}
+
+#[async_trait]
+impl TransactionHistoryService for EtherscanHistoryService {
+    async fn get_transaction_history(
+        &self,
+        query: &GetTransactionHistoryQuery,
+    ) -> Result<Page<TransactionSummary>, DomainError> {
+        // Etherscan maps 1:1 onto our pagination: page (1-based) + offset (page size)
+        let mut params = vec![
+            ("module", "account".to_string()),
+            ("action", "txlist".to_string()),
+            ("address", query.address.as_str().to_string()),
+            ("page", query.page.to_string()),
+            ("offset", query.page_size.to_string()),
+            ("sort", "asc".to_string()),
+        ];
+        if let Some(from_block) = query.from_block {
+            params.push(("startblock", from_block.to_string()));
+        }
+        if let Some(to_block) = query.to_block {
+            params.push(("endblock", to_block.to_string()));
+        }
+        if let Some(api_key) = &self.api_key {
+            params.push(("apikey", api_key.clone()));
+        }
+
+        let response = self
+            .client
+            .get(&self.api_base_url)
+            .query(&params)
+            .send()
+            .await
+            .map_err(|e| DomainError::NetworkError(format!("Failed to query transaction history: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(DomainError::NetworkError(format!(
+                "Etherscan API returned error: {}",
+                response.status()
+            )));
+        }
+
+        let body = response
+            .text()
+            .await
+            .map_err(|e| DomainError::NetworkError(format!("Failed to read response: {}", e)))?;
+
+        parse_txlist_response(&body, query.page, query.page_size)
+    }
+}
+
+/// Parse an Etherscan `txlist` response body into a page of summaries
+fn parse_txlist_response(
+    body: &str,
+    page: u32,
+    page_size: u32,
+) -> Result<Page<TransactionSummary>, DomainError> {
+    let response: EtherscanResponse = serde_json::from_str(body)
+        .map_err(|e| DomainError::BlockchainError(format!("Failed to parse response: {}", e)))?;
+
+    // status "0" is used both for errors and for an empty result set
+    if response.status != "1" {
+        if response.message.starts_with("No transactions found") {
+            return Ok(Page::new(Vec::new(), page, page_size));
+        }
+        return Err(DomainError::BlockchainError(format!(
+            "Etherscan API error: {} ({})",
+            response.message, response.result
+        )));
+    }
+
+    let transactions: Vec<EtherscanTransaction> = serde_json::from_value(response.result)
+        .map_err(|e| DomainError::BlockchainError(format!("Failed to parse transactions: {}", e)))?;
+
+    let items = transactions
+        .into_iter()
+        .map(|tx| {
+            let parse_u64 = |field: &str, value: &str| {
+                value.parse::<u64>().map_err(|e| {
+                    DomainError::BlockchainError(format!("Invalid {} '{}': {}", field, value, e))
+                })
+            };
+
+            Ok(TransactionSummary {
+                hash: TransactionHash::new(tx.hash)?,
+                block_number: parse_u64("blockNumber", &tx.block_number)?,
+                timestamp: parse_u64("timeStamp", &tx.time_stamp)?,
+                from: Address::new(tx.from)?,
+                // Contract creations have an empty `to`
+                to: if tx.to.is_empty() { None } else { Some(Address::new(tx.to)?) },
+                value: Amount::from_wei(tx.value.parse::<u128>().map_err(|e| {
+                    DomainError::BlockchainError(format!("Invalid value '{}': {}", tx.value, e))
+                })?),
+                success: tx.is_error == "0",
+            })
+        })
+        .collect::<Result<Vec<_>, DomainError>>()?;
+
+    Ok(Page::new(items, page, page_size))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_txlist_response() {
+        let body = r#"{
+            "status": "1",
+            "message": "OK",
+            "result": [
+                {
+                    "blockNumber": "14923678",
+                    "timeStamp": "1654646411",
+                    "hash": "0xc52783ad354aecc04c670047754f062e3d6d04e8f5b24774472651f9c3882c60",
+                    "from": "0x9aa99c23f67c81701c772b106b4f83f6e858dd2e",
+                    "to": "",
+                    "value": "1000000000000000000",
+                    "isError": "0"
+                },
+                {
+                    "blockNumber": "14923700",
+                    "timeStamp": "1654646700",
+                    "hash": "0xd52783ad354aecc04c670047754f062e3d6d04e8f5b24774472651f9c3882c60",
+                    "from": "0x9aa99c23f67c81701c772b106b4f83f6e858dd2e",
+                    "to": "0x8894e0a0c962cb723c1976a4421c95949be2d4e3",
+                    "value": "0",
+                    "isError": "1"
+                }
+            ]
+        }"#;
+
+        let page = parse_txlist_response(body, 1, 2).unwrap();
+        assert_eq!(page.items.len(), 2);
+        assert!(page.has_more);
+        assert_eq!(page.items[0].block_number, 14923678);
+        assert!(page.items[0].to.is_none());
+        assert_eq!(page.items[0].value.to_wei(), 1_000_000_000_000_000_000);
+        assert!(page.items[0].success);
+        assert!(!page.items[1].success);
+    }
+
+    #[test]
+    fn test_parse_txlist_empty_and_error() {
+        let empty = r#"{"status":"0","message":"No transactions found","result":[]}"#;
+        let page = parse_txlist_response(empty, 1, 100).unwrap();
+        assert!(page.items.is_empty());
+        assert!(!page.has_more);
+
+        let error = r#"{"status":"0","message":"NOTOK","result":"Invalid API Key"}"#;
+        assert!(parse_txlist_response(error, 1, 100).is_err());
+    }
+
+    #[test]
+    fn test_non_evm_network_rejected() {
+        assert!(EtherscanHistoryService::new(Network::BitcoinMainnet, None).is_err());
+        assert!(EtherscanHistoryService::new(Network::Mainnet, None).is_ok());
+    }
+}
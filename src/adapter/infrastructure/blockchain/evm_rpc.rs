@@ -0,0 +1,535 @@
+//! The EVM JSON-RPC calls `AlloyBlockchainService` builds its logic on
+//!
+//! The service talks to the node only through [`EvmRpc`], in the wallet's
+//! own types (domain addresses, hashes and receipts, plain integers and
+//! bytes, and [`RpcFailure`] for errors), so that logic (error mapping, fee
+//! and nonce filling, block searches, series sampling) can run against an
+//! in-memory fake, and another client library could stand in for Alloy's
+//! provider, which implements the trait here.
+
+use alloy::{
+    primitives::{Address as AlloyAddress, Bytes, TxHash, U128, U256, U64},
+    providers::{Provider, RootProvider},
+    rpc::types::{
+        BlockId, BlockNumberOrTag, BlockTransactionsKind, TransactionReceipt as AlloyReceipt, TransactionRequest,
+    },
+    transports::{BoxTransport, RpcError, TransportErrorKind},
+};
+use async_trait::async_trait;
+use std::fmt;
+use super::http::error_chain;
+use crate::core::domain::value_objects::{Address, Log, TransactionHash, TransactionReceipt};
+
+/// Result of one JSON-RPC call
+pub(crate) type RpcResult<T> = Result<T, RpcFailure>;
+
+/// Why a JSON-RPC call failed, with enough detail to tell reverts, rate
+/// limits, pool rejections and pruned state apart
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum RpcFailure {
+    /// The node answered with a JSON-RPC error object
+    Response(ErrorResponse),
+    /// No usable answer: connection refused, HTTP error status, malformed body, ...
+    Transport { message: String, rate_limited: bool },
+    /// The request could not be encoded (e.g. a non-EVM address)
+    InvalidRequest(String),
+}
+
+/// JSON-RPC error object returned by the node
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ErrorResponse {
+    pub code: i64,
+    pub message: String,
+    /// ABI-encoded revert data attached to an "execution reverted" error
+    pub revert_data: Option<Vec<u8>>,
+    /// Whether the provider refused the request for exceeding its rate limit
+    pub rate_limited: bool,
+}
+
+#[cfg(test)]
+impl ErrorResponse {
+    /// Error object with `code` and `message`, no revert data, not rate limited
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            revert_data: None,
+            rate_limited: false,
+        }
+    }
+}
+
+impl RpcFailure {
+    /// Whether the endpoint refused the request for exceeding its rate limit
+    pub fn is_rate_limited(&self) -> bool {
+        match self {
+            RpcFailure::Response(response) => response.rate_limited,
+            RpcFailure::Transport { rate_limited, .. } => *rate_limited,
+            RpcFailure::InvalidRequest(_) => false,
+        }
+    }
+}
+
+impl fmt::Display for RpcFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RpcFailure::Response(response) => write!(
+                f,
+                "server returned an error response: error code {}: {}",
+                response.code, response.message
+            ),
+            RpcFailure::Transport { message, .. } => f.write_str(message),
+            RpcFailure::InvalidRequest(message) => write!(f, "invalid request: {}", message),
+        }
+    }
+}
+
+impl From<RpcError<TransportErrorKind>> for RpcFailure {
+    fn from(error: RpcError<TransportErrorKind>) -> Self {
+        match &error {
+            RpcError::ErrorResp(payload) => RpcFailure::Response(ErrorResponse {
+                code: payload.code,
+                message: payload.message.to_string(),
+                revert_data: payload.as_revert_data().map(|data| data.to_vec()),
+                // Alloy's retry check also covers transient node errors ("header not found");
+                // only count the rate-limit ones
+                rate_limited: payload.is_retry_err()
+                    && (payload.code == 429 || payload.code == -32005 || payload.message.contains("limit")),
+            }),
+            RpcError::Transport(kind) => RpcFailure::Transport {
+                message: error_chain(&error),
+                rate_limited: matches!(kind, TransportErrorKind::HttpError(http) if http.is_rate_limit_err()),
+            },
+            _ => RpcFailure::Transport { message: error_chain(&error), rate_limited: false },
+        }
+    }
+}
+
+/// Block a state read or call runs against
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BlockRef {
+    Latest,
+    Pending,
+    Safe,
+    Finalized,
+    Earliest,
+    Number(u64),
+    Hash([u8; 32]),
+}
+
+impl From<BlockId> for BlockRef {
+    fn from(block: BlockId) -> Self {
+        match block {
+            BlockId::Hash(hash) => BlockRef::Hash(hash.block_hash.0),
+            BlockId::Number(BlockNumberOrTag::Latest) => BlockRef::Latest,
+            BlockId::Number(BlockNumberOrTag::Pending) => BlockRef::Pending,
+            BlockId::Number(BlockNumberOrTag::Safe) => BlockRef::Safe,
+            BlockId::Number(BlockNumberOrTag::Finalized) => BlockRef::Finalized,
+            BlockId::Number(BlockNumberOrTag::Earliest) => BlockRef::Earliest,
+            BlockId::Number(BlockNumberOrTag::Number(number)) => BlockRef::Number(number),
+        }
+    }
+}
+
+impl From<BlockRef> for BlockId {
+    fn from(block: BlockRef) -> Self {
+        match block {
+            BlockRef::Latest => BlockId::latest(),
+            BlockRef::Pending => BlockId::pending(),
+            BlockRef::Safe => BlockId::safe(),
+            BlockRef::Finalized => BlockId::finalized(),
+            BlockRef::Earliest => BlockId::earliest(),
+            BlockRef::Number(number) => BlockId::number(number),
+            BlockRef::Hash(hash) => BlockId::hash(hash.into()),
+        }
+    }
+}
+
+/// An EVM transaction as the service builds it; fields left `None` are
+/// filled from the node before signing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TxRequest {
+    pub from: Option<Address>,
+    pub to: Address,
+    /// Amount in Wei
+    pub value: u128,
+    pub data: Vec<u8>,
+    pub nonce: Option<u64>,
+    pub gas_limit: Option<u64>,
+    /// Legacy gas price; when set, the transaction is sent as a legacy one
+    pub gas_price: Option<u128>,
+    pub max_fee_per_gas: Option<u128>,
+    pub max_priority_fee_per_gas: Option<u128>,
+}
+
+impl TxRequest {
+    /// Plain transfer (or call) of `value` Wei to `to`
+    pub fn new(to: Address, value: u128) -> Self {
+        Self {
+            from: None,
+            to,
+            value,
+            data: Vec::new(),
+            nonce: None,
+            gas_limit: None,
+            gas_price: None,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+        }
+    }
+}
+
+/// EIP-1559 fee suggestion for the next block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Eip1559Fees {
+    pub max_fee_per_gas: u128,
+    pub max_priority_fee_per_gas: u128,
+}
+
+/// Sender, nonce and fee caps of a transaction the node knows; a legacy
+/// transaction's gas price counts as both caps, as it does for replacement
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SentTransaction {
+    pub from: Address,
+    pub nonce: u64,
+    pub fees: Eip1559Fees,
+}
+
+/// Number and timestamp of a block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BlockSummary {
+    pub number: u64,
+    pub timestamp: u64,
+}
+
+/// `eth_feeHistory` result: per-block base fees (one more than blocks, for
+/// the next block), gas used ratios and priority fees at the requested percentiles
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct FeeHistory {
+    pub oldest_block: u64,
+    pub base_fee_per_gas: Vec<u128>,
+    pub gas_used_ratio: Vec<f64>,
+    pub reward: Option<Vec<Vec<u128>>>,
+}
+
+/// EVM node calls used by `AlloyBlockchainService`
+#[async_trait]
+pub(crate) trait EvmRpc: Send + Sync {
+    /// `eth_chainId`
+    async fn chain_id(&self) -> RpcResult<u64>;
+
+    /// `eth_blockNumber`
+    async fn block_number(&self) -> RpcResult<u64>;
+
+    /// `eth_getBlockByNumber` / `eth_getBlockByHash`; `None` if the node has no such block
+    async fn get_block(&self, block: BlockRef) -> RpcResult<Option<BlockSummary>>;
+
+    /// `eth_getBalance` at `block`, in Wei
+    async fn get_balance(&self, address: &Address, block: BlockRef) -> RpcResult<u128>;
+
+    /// `eth_getTransactionCount` at `block`
+    async fn transaction_count(&self, address: &Address, block: BlockRef) -> RpcResult<u64>;
+
+    /// `eth_getCode` at the latest block
+    async fn code(&self, address: &Address) -> RpcResult<Vec<u8>>;
+
+    /// `eth_getStorageAt`: the 32-byte word at `slot` of `address` at `block`
+    async fn storage_at(&self, address: &Address, slot: [u8; 32], block: BlockRef) -> RpcResult<[u8; 32]>;
+
+    /// `eth_call` of `tx` at `block`, returning the raw output
+    async fn call(&self, tx: &TxRequest, block: BlockRef) -> RpcResult<Vec<u8>>;
+
+    /// `eth_estimateGas` of `tx`
+    async fn estimate_gas(&self, tx: &TxRequest) -> RpcResult<u64>;
+
+    /// `eth_gasPrice`
+    async fn gas_price(&self) -> RpcResult<u128>;
+
+    /// EIP-1559 fee suggestion from recent fee history; `None` on chains without a base fee
+    async fn eip1559_fees(&self) -> RpcResult<Option<Eip1559Fees>>;
+
+    /// `eth_feeHistory` of the `block_count` blocks up to the latest, with
+    /// priority fees at `reward_percentiles`
+    async fn fee_history(&self, block_count: u64, reward_percentiles: &[f64]) -> RpcResult<FeeHistory>;
+
+    /// `eth_sendRawTransaction` of an EIP-2718 encoded transaction
+    async fn send_raw(&self, raw: &[u8]) -> RpcResult<TransactionHash>;
+
+    /// `eth_getTransactionByHash`: whether the node knows the transaction (mined or pending)
+    async fn has_transaction(&self, tx_hash: &TransactionHash) -> RpcResult<bool>;
+
+    /// `eth_getTransactionByHash`: sender, nonce and fees; `None` if the node does not know it
+    async fn transaction(&self, tx_hash: &TransactionHash) -> RpcResult<Option<SentTransaction>>;
+
+    /// `eth_getTransactionReceipt`; `None` until the transaction is mined
+    async fn receipt(&self, tx_hash: &TransactionHash) -> RpcResult<Option<TransactionReceipt>>;
+
+    /// `web3_clientVersion`
+    async fn client_version(&self) -> RpcResult<String>;
+
+    /// `anvil_setBalance` (Anvil and Hardhat only)
+    #[cfg(feature = "dev")]
+    async fn set_balance(&self, address: &Address, wei: u128) -> RpcResult<()>;
+}
+
+#[async_trait]
+impl EvmRpc for RootProvider<BoxTransport> {
+    async fn chain_id(&self) -> RpcResult<u64> {
+        Ok(self.get_chain_id().await?)
+    }
+
+    async fn block_number(&self) -> RpcResult<u64> {
+        Ok(self.get_block_number().await?)
+    }
+
+    async fn get_block(&self, block: BlockRef) -> RpcResult<Option<BlockSummary>> {
+        let block = match BlockId::from(block) {
+            BlockId::Number(number) => self.get_block_by_number(number, BlockTransactionsKind::Hashes).await?,
+            BlockId::Hash(hash) => self.get_block_by_hash(hash.block_hash, BlockTransactionsKind::Hashes).await?,
+        };
+        Ok(block.map(|block| BlockSummary {
+            number: block.header.number,
+            timestamp: block.header.timestamp,
+        }))
+    }
+
+    async fn get_balance(&self, address: &Address, block: BlockRef) -> RpcResult<u128> {
+        let balance = Provider::get_balance(self, alloy_address(address)?).block_id(block.into()).await?;
+        Ok(balance.saturating_to())
+    }
+
+    async fn transaction_count(&self, address: &Address, block: BlockRef) -> RpcResult<u64> {
+        Ok(self.get_transaction_count(alloy_address(address)?).block_id(block.into()).await?)
+    }
+
+    async fn code(&self, address: &Address) -> RpcResult<Vec<u8>> {
+        Ok(self.get_code_at(alloy_address(address)?).await?.to_vec())
+    }
+
+    async fn storage_at(&self, address: &Address, slot: [u8; 32], block: BlockRef) -> RpcResult<[u8; 32]> {
+        let value = self
+            .get_storage_at(alloy_address(address)?, U256::from_be_bytes(slot))
+            .block_id(block.into())
+            .await?;
+        Ok(value.to_be_bytes())
+    }
+
+    async fn call(&self, tx: &TxRequest, block: BlockRef) -> RpcResult<Vec<u8>> {
+        Ok(Provider::call(self, &transaction_request(tx)?).block(block.into()).await?.to_vec())
+    }
+
+    async fn estimate_gas(&self, tx: &TxRequest) -> RpcResult<u64> {
+        Ok(Provider::estimate_gas(self, &transaction_request(tx)?).await?)
+    }
+
+    async fn gas_price(&self) -> RpcResult<u128> {
+        Ok(self.get_gas_price().await?)
+    }
+
+    async fn eip1559_fees(&self) -> RpcResult<Option<Eip1559Fees>> {
+        match self.estimate_eip1559_fees(None).await {
+            Ok(estimate) => Ok(Some(Eip1559Fees {
+                max_fee_per_gas: estimate.max_fee_per_gas,
+                max_priority_fee_per_gas: estimate.max_priority_fee_per_gas,
+            })),
+            // The latest block has no base fee
+            Err(RpcError::UnsupportedFeature(_)) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn fee_history(&self, block_count: u64, reward_percentiles: &[f64]) -> RpcResult<FeeHistory> {
+        let history = self.get_fee_history(block_count, BlockNumberOrTag::Latest, reward_percentiles).await?;
+        Ok(FeeHistory {
+            oldest_block: history.oldest_block,
+            base_fee_per_gas: history.base_fee_per_gas,
+            gas_used_ratio: history.gas_used_ratio,
+            reward: history.reward,
+        })
+    }
+
+    async fn send_raw(&self, raw: &[u8]) -> RpcResult<TransactionHash> {
+        let pending = self.send_raw_transaction(raw).await?;
+        Ok(transaction_hash(pending.tx_hash()))
+    }
+
+    async fn has_transaction(&self, tx_hash: &TransactionHash) -> RpcResult<bool> {
+        // Untyped, so transactions of types Alloy cannot decode still count
+        let transaction: Option<serde_json::Value> =
+            self.raw_request("eth_getTransactionByHash".into(), (alloy_hash(tx_hash)?,)).await?;
+        Ok(transaction.is_some())
+    }
+
+    async fn transaction(&self, tx_hash: &TransactionHash) -> RpcResult<Option<SentTransaction>> {
+        /// The fields needed, untyped like `has_transaction` so any transaction type parses
+        #[derive(Debug, serde::Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Fields {
+            from: AlloyAddress,
+            nonce: U64,
+            gas_price: Option<U128>,
+            max_fee_per_gas: Option<U128>,
+            max_priority_fee_per_gas: Option<U128>,
+        }
+
+        let fields: Option<Fields> =
+            self.raw_request("eth_getTransactionByHash".into(), (alloy_hash(tx_hash)?,)).await?;
+        let Some(fields) = fields else {
+            return Ok(None);
+        };
+        let gas_price = fields.gas_price.map(|price| price.to::<u128>());
+        let (max_fee_per_gas, max_priority_fee_per_gas) =
+            match (fields.max_fee_per_gas, fields.max_priority_fee_per_gas, gas_price) {
+                (Some(max_fee), Some(priority_fee), _) => (max_fee.to::<u128>(), priority_fee.to::<u128>()),
+                (_, _, Some(gas_price)) => (gas_price, gas_price),
+                _ => {
+                    return Err(RpcFailure::Transport {
+                        message: format!("transaction {} has no fee fields", tx_hash.as_str()),
+                        rate_limited: false,
+                    })
+                }
+            };
+        Ok(Some(SentTransaction {
+            from: Address::new_unchecked(fields.from.to_checksum(None)),
+            nonce: fields.nonce.to::<u64>(),
+            fees: Eip1559Fees {
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+            },
+        }))
+    }
+
+    async fn receipt(&self, tx_hash: &TransactionHash) -> RpcResult<Option<TransactionReceipt>> {
+        let receipt = self.get_transaction_receipt(alloy_hash(tx_hash)?).await?;
+        Ok(receipt.map(|receipt| convert_receipt(tx_hash, &receipt)))
+    }
+
+    async fn client_version(&self) -> RpcResult<String> {
+        Ok(self.get_client_version().await?)
+    }
+
+    #[cfg(feature = "dev")]
+    async fn set_balance(&self, address: &Address, wei: u128) -> RpcResult<()> {
+        let params = (alloy_address(address)?, U256::from(wei));
+        self.raw_request::<_, serde_json::Value>("anvil_setBalance".into(), params).await?;
+        Ok(())
+    }
+}
+
+/// Alloy's request for `tx`
+fn transaction_request(tx: &TxRequest) -> RpcResult<TransactionRequest> {
+    let mut request = TransactionRequest::default()
+        .to(alloy_address(&tx.to)?)
+        .value(U256::from(tx.value))
+        .input(Bytes::from(tx.data.clone()).into());
+    if let Some(from) = &tx.from {
+        request = request.from(alloy_address(from)?);
+    }
+    request.nonce = tx.nonce;
+    request.gas = tx.gas_limit;
+    request.gas_price = tx.gas_price;
+    request.max_fee_per_gas = tx.max_fee_per_gas;
+    request.max_priority_fee_per_gas = tx.max_priority_fee_per_gas;
+    Ok(request)
+}
+
+fn alloy_address(address: &Address) -> RpcResult<AlloyAddress> {
+    address
+        .as_str()
+        .parse()
+        .map_err(|e| RpcFailure::InvalidRequest(format!("invalid address {}: {}", address, e)))
+}
+
+fn alloy_hash(tx_hash: &TransactionHash) -> RpcResult<TxHash> {
+    tx_hash
+        .as_str()
+        .parse()
+        .map_err(|e| RpcFailure::InvalidRequest(format!("invalid transaction hash {}: {}", tx_hash.as_str(), e)))
+}
+
+fn transaction_hash(hash: &TxHash) -> TransactionHash {
+    TransactionHash::new_unchecked(format!("{:?}", hash))
+}
+
+/// Convert an Alloy receipt into the domain receipt, keeping topics and data of every log
+fn convert_receipt(tx_hash: &TransactionHash, receipt: &AlloyReceipt) -> TransactionReceipt {
+    let logs = receipt
+        .inner
+        .logs()
+        .iter()
+        .map(|log| Log {
+            address: Address::new_unchecked(log.address().to_checksum(None)),
+            topics: log.topics().iter().map(|t| format!("{:?}", t)).collect(),
+            data: log.data().data.to_vec(),
+        })
+        .collect();
+
+    TransactionReceipt {
+        tx_hash: tx_hash.clone(),
+        block_number: receipt.block_number,
+        success: receipt.status(),
+        gas_used: receipt.gas_used,
+        effective_gas_price: receipt.effective_gas_price,
+        logs,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::rpc::json_rpc::ErrorPayload;
+
+    fn error_payload(code: i64, message: &'static str, data: Option<&str>) -> RpcError<TransportErrorKind> {
+        RpcError::ErrorResp(ErrorPayload {
+            code,
+            message: message.into(),
+            data: data.map(|data| serde_json::value::to_raw_value(data).unwrap()),
+        })
+    }
+
+    #[test]
+    fn test_rpc_failure_keeps_what_error_mapping_needs() {
+        let reverted = RpcFailure::from(error_payload(3, "execution reverted", Some("0x08c379a0")));
+        assert_eq!(
+            reverted,
+            RpcFailure::Response(ErrorResponse {
+                revert_data: Some(vec![0x08, 0xc3, 0x79, 0xa0]),
+                ..ErrorResponse::new(3, "execution reverted")
+            })
+        );
+        assert_eq!(reverted.to_string(), "server returned an error response: error code 3: execution reverted");
+
+        assert!(RpcFailure::from(error_payload(-32005, "daily request count exceeded, request rate limited", None))
+            .is_rate_limited());
+        // Retryable, but not a rate limit
+        assert!(!RpcFailure::from(error_payload(-32000, "header not found", None)).is_rate_limited());
+        assert!(matches!(
+            RpcFailure::from(TransportErrorKind::backend_gone()),
+            RpcFailure::Transport { rate_limited: false, .. }
+        ));
+    }
+
+    #[test]
+    fn test_block_ref_round_trips_through_block_id() {
+        for block in [
+            BlockRef::Latest,
+            BlockRef::Pending,
+            BlockRef::Safe,
+            BlockRef::Finalized,
+            BlockRef::Earliest,
+            BlockRef::Number(16),
+            BlockRef::Hash([7; 32]),
+        ] {
+            assert_eq!(BlockRef::from(BlockId::from(block)), block);
+        }
+    }
+
+    #[test]
+    fn test_transaction_request_rejects_non_evm_addresses() {
+        let solana = Address::new_unchecked("11111111111111111111111111111111".to_string());
+        assert!(matches!(
+            transaction_request(&TxRequest::new(solana, 1)),
+            Err(RpcFailure::InvalidRequest(_))
+        ));
+    }
+}
@@ -0,0 +1,58 @@
+//! JSON-RPC transport that fails over across several HTTP endpoints
+
+use alloy::rpc::json_rpc::{RequestPacket, ResponsePacket};
+use alloy::transports::{
+    http::{Client, Http},
+    TransportError, TransportFut,
+};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower::Service;
+
+/// Sends each request to the first endpoint, moving on to the next one only
+/// on transport failures (connection errors, timeouts, HTTP errors)
+///
+/// JSON-RPC error responses (e.g. "execution reverted") are returned as-is:
+/// another provider would give the same answer.
+#[derive(Debug, Clone)]
+pub(crate) struct FailoverTransport {
+    endpoints: Arc<[Http<Client>]>,
+}
+
+impl FailoverTransport {
+    /// Endpoints in priority order; must not be empty
+    pub(crate) fn new(endpoints: Vec<Http<Client>>) -> Self {
+        assert!(!endpoints.is_empty(), "FailoverTransport needs at least one endpoint");
+        Self {
+            endpoints: endpoints.into(),
+        }
+    }
+}
+
+impl Service<RequestPacket> for FailoverTransport {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Each call clones a fresh HTTP transport, which is always ready
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let endpoints = self.endpoints.clone();
+        Box::pin(async move {
+            let mut last_error = None;
+            for (index, endpoint) in endpoints.iter().enumerate() {
+                match endpoint.clone().call(request.clone()).await {
+                    Ok(response) => return Ok(response),
+                    Err(e) => {
+                        tracing::warn!("RPC endpoint #{} failed: {}", index, e);
+                        last_error = Some(e);
+                    }
+                }
+            }
+            Err(last_error.expect("at least one endpoint was tried"))
+        })
+    }
+}
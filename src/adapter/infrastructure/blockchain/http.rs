@@ -0,0 +1,241 @@
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Client, Response, StatusCode};
+use std::collections::HashMap;
+use std::time::Duration;
+use crate::core::domain::errors::{redact_payloads, DomainError};
+
+/// Build an HTTP client that sends `headers` with every request
+///
+/// Header values are marked sensitive so API keys are redacted from debug output.
+pub fn build_http_client(headers: &HashMap<String, String>) -> Result<Client, DomainError> {
+    build_http_client_with_timeout(headers, None)
+}
+
+/// Like [`build_http_client`], additionally bounding each request by `timeout`
+///
+/// The timeout is ignored on wasm32, where the browser's fetch API applies its own.
+pub fn build_http_client_with_timeout(
+    headers: &HashMap<String, String>,
+    timeout: Option<Duration>,
+) -> Result<Client, DomainError> {
+    let builder = Client::builder().default_headers(header_map(headers)?);
+    #[cfg(not(target_arch = "wasm32"))]
+    let builder = match timeout {
+        Some(timeout) => builder.timeout(timeout),
+        None => builder,
+    };
+    #[cfg(target_arch = "wasm32")]
+    let _ = timeout;
+
+    build(builder)
+}
+
+/// Like [`build_http_client`], tuned for bursts of small requests to one host
+///
+/// Keeps up to `pool_size` idle connections open (with TCP keep-alive) so a
+/// burst reuses them instead of reconnecting, and negotiates HTTP/2 over TLS
+/// when the server offers it, multiplexing requests on a single connection.
+/// `pool_size == 0` closes every connection after use. Pool settings and
+/// `timeout` are ignored on wasm32, where the browser manages connections.
+pub fn build_pooled_http_client(
+    headers: &HashMap<String, String>,
+    pool_size: usize,
+    timeout: Option<Duration>,
+) -> Result<Client, DomainError> {
+    let builder = Client::builder().default_headers(header_map(headers)?);
+    #[cfg(not(target_arch = "wasm32"))]
+    let builder = builder
+        .pool_max_idle_per_host(pool_size)
+        .pool_idle_timeout(POOL_IDLE_TIMEOUT)
+        .tcp_keepalive(TCP_KEEPALIVE)
+        .http2_adaptive_window(true);
+    #[cfg(not(target_arch = "wasm32"))]
+    let builder = match timeout {
+        Some(timeout) => builder.timeout(timeout),
+        None => builder,
+    };
+    #[cfg(target_arch = "wasm32")]
+    let _ = (pool_size, timeout);
+
+    build(builder)
+}
+
+/// Idle pooled connections are closed after this long (public RPCs drop them at ~60-120 s)
+#[cfg(not(target_arch = "wasm32"))]
+const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+/// TCP keep-alive probe interval for pooled connections
+#[cfg(not(target_arch = "wasm32"))]
+const TCP_KEEPALIVE: Duration = Duration::from_secs(30);
+
+/// Default headers from `headers`, marked sensitive
+fn header_map(headers: &HashMap<String, String>) -> Result<HeaderMap, DomainError> {
+    let mut header_map = HeaderMap::with_capacity(headers.len());
+    for (name, value) in headers {
+        let name = HeaderName::from_bytes(name.as_bytes()).map_err(|e| {
+            DomainError::ConfigurationError(format!("Invalid header name '{}': {}", name, e))
+        })?;
+        let mut value = HeaderValue::from_str(value).map_err(|e| {
+            DomainError::ConfigurationError(format!("Invalid value for header '{}': {}", name, e))
+        })?;
+        value.set_sensitive(true);
+        header_map.insert(name, value);
+    }
+    Ok(header_map)
+}
+
+fn build(builder: reqwest::ClientBuilder) -> Result<Client, DomainError> {
+    builder
+        .build()
+        .map_err(|e| DomainError::ConfigurationError(format!("Failed to build HTTP client: {}", e)))
+}
+
+/// Largest response body the HTTP services accept by default (4 MiB)
+///
+/// Far above any legitimate balance, UTXO page or JSON-RPC response, but
+/// small enough that a misbehaving endpoint cannot exhaust memory.
+pub const DEFAULT_MAX_RESPONSE_SIZE: usize = 4 * 1024 * 1024;
+
+/// Read `response`'s body, failing with `InvalidResponse` once it exceeds `max_size` bytes
+///
+/// A `Content-Length` above the limit is rejected before reading anything;
+/// otherwise the body is read chunk by chunk and abandoned as soon as it
+/// grows past the limit, so chunked responses are capped too. On wasm32 the
+/// browser buffers the body itself and only the size check applies.
+pub async fn read_body_capped(response: Response, max_size: usize) -> Result<Vec<u8>, DomainError> {
+    let too_large = || {
+        DomainError::InvalidResponse(format!("Response body exceeds {} bytes", max_size))
+    };
+    let read_error = |e: reqwest::Error| {
+        DomainError::NetworkError(format!("Failed to read response: {}", error_chain(&e)))
+    };
+
+    if response.content_length().is_some_and(|len| len > max_size as u64) {
+        return Err(too_large());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let mut response = response;
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await.map_err(read_error)? {
+            if body.len() + chunk.len() > max_size {
+                return Err(too_large());
+            }
+            body.extend_from_slice(&chunk);
+        }
+        Ok(body)
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        let body = response.bytes().await.map_err(read_error)?;
+        if body.len() > max_size {
+            return Err(too_large());
+        }
+        Ok(body.to_vec())
+    }
+}
+
+/// Render an error with its whole `source()` chain
+///
+/// HTTP client errors usually display as "error sending request for url (...)";
+/// the actual cause (DNS lookup failure, TLS certificate error, connection
+/// refused) only appears further down the chain. Long payloads quoted by
+/// the errors (e.g. an unparseable response body) are redacted.
+pub fn error_chain(error: &dyn std::error::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        let cause_message = cause.to_string();
+        // Wrappers often repeat their cause verbatim
+        if !message.contains(&cause_message) {
+            message.push_str(": ");
+            message.push_str(&cause_message);
+        }
+        source = cause.source();
+    }
+    redact_payloads(&message)
+}
+
+/// Explain a non-success HTTP status from an API endpoint
+pub fn describe_http_status(status: StatusCode) -> String {
+    match status {
+        StatusCode::TOO_MANY_REQUESTS => "rate limited by the endpoint (HTTP 429)".to_string(),
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+            format!("request rejected (HTTP {}) - check the API key", status.as_u16())
+        }
+        StatusCode::NOT_FOUND | StatusCode::METHOD_NOT_ALLOWED => {
+            format!("endpoint not found (HTTP {}) - check the URL", status.as_u16())
+        }
+        _ => format!("endpoint returned HTTP {}", status),
+    }
+}
+
+/// Error for a non-success HTTP status, flagging rate limits as `RateLimited`
+pub fn http_status_error(context: &str, status: StatusCode) -> DomainError {
+    let message = format!("{}: {}", context, describe_http_status(status));
+    if status == StatusCode::TOO_MANY_REQUESTS {
+        DomainError::RateLimited(message)
+    } else {
+        DomainError::NetworkError(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_http_client_with_headers() {
+        let headers = HashMap::from([
+            ("x-api-key".to_string(), "secret".to_string()),
+            ("Authorization".to_string(), "Bearer token".to_string()),
+        ]);
+        assert!(build_http_client(&headers).is_ok());
+    }
+
+    #[test]
+    fn test_build_http_client_rejects_invalid_header() {
+        let bad_name = HashMap::from([("bad header".to_string(), "v".to_string())]);
+        assert!(matches!(
+            build_http_client(&bad_name),
+            Err(DomainError::ConfigurationError(_))
+        ));
+
+        let bad_value = HashMap::from([("x-api-key".to_string(), "line\nbreak".to_string())]);
+        assert!(build_http_client(&bad_value).is_err());
+    }
+
+    #[test]
+    fn test_error_chain_includes_causes() {
+        #[derive(Debug)]
+        struct Wrapper(std::io::Error);
+        impl std::fmt::Display for Wrapper {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "error sending request")
+            }
+        }
+        impl std::error::Error for Wrapper {
+            fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+                Some(&self.0)
+            }
+        }
+
+        let error = Wrapper(std::io::Error::other("dns error: failed to lookup address"));
+        assert_eq!(error_chain(&error), "error sending request: dns error: failed to lookup address");
+    }
+
+    #[test]
+    fn test_describe_http_status() {
+        assert!(describe_http_status(StatusCode::TOO_MANY_REQUESTS).contains("rate limited"));
+        assert!(describe_http_status(StatusCode::NOT_FOUND).contains("check the URL"));
+        assert!(describe_http_status(StatusCode::UNAUTHORIZED).contains("API key"));
+        assert!(matches!(
+            http_status_error("Failed", StatusCode::TOO_MANY_REQUESTS),
+            DomainError::RateLimited(_)
+        ));
+        assert!(matches!(
+            http_status_error("Failed", StatusCode::BAD_GATEWAY),
+            DomainError::NetworkError(_)
+        ));
+    }
+}
@@ -1,9 +1,33 @@
 pub mod alloy_service;
+mod bitcoin_psbt;
 pub mod bitcoin_service;
+pub mod cached_service;
+pub mod ens;
+pub mod etherscan_service;
+mod evm_rpc;
+mod failover;
+pub mod http;
+mod multicall;
+mod revert;
+mod rollup;
+pub mod noop_service;
+pub mod rate_limited_service;
+#[cfg(feature = "safe")]
+pub mod safe;
 pub mod solana_service;
+mod solana_tx;
+mod ticks;
 pub mod multi_chain_service;
 
-pub use alloy_service::AlloyBlockchainService;
+pub use alloy_service::{AlloyBlockchainService, AlloyBlockchainServiceBuilder, OfflineTransaction};
 pub use bitcoin_service::BitcoinBlockchainService;
-pub use solana_service::SolanaBlockchainService;
-pub use multi_chain_service::MultiChainBlockchainService;
+pub use cached_service::CachedBlockchainService;
+pub use ens::EnsResolver;
+pub use etherscan_service::EtherscanHistoryService;
+pub use noop_service::NoopBlockchainService;
+pub use rate_limited_service::RateLimitedBlockchainService;
+#[cfg(feature = "safe")]
+pub use safe::SafeTxHash;
+pub use solana_service::{SolanaBlockchainService, SolanaTxVersion};
+pub use ticks::interval_ticks;
+pub use multi_chain_service::{MultiChainBlockchainService, MultiChainConfig, NetworkServiceConfig};
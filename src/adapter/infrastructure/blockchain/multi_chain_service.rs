@@ -1,12 +1,121 @@
 use async_trait::async_trait;
+use futures::future::join_all;
+use futures::stream::BoxStream;
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
+use crate::adapter::infrastructure::{provider_keys::default_rpc_url, resilience::RetryPolicy};
 use crate::core::domain::{
     errors::DomainError,
-    services::BlockchainService,
-    value_objects::{Address, Balance, ChainType, Network, TransactionHash},
+    services::{BlockchainService, Signer},
+    value_objects::{
+        AccountInfo, Address, Balance, ChainType, ConnectionDiagnostics, FeeEstimate, Finality, MaxSendable,
+        Network, SecretKey, TransactionHash, TransactionReceipt, TxStatus,
+    },
 };
 use super::{AlloyBlockchainService, BitcoinBlockchainService, SolanaBlockchainService};
 
+/// Chain types `MultiChainBlockchainService` has a backing service for
+const SUPPORTED_CHAIN_TYPES: &[ChainType] = &[ChainType::Ethereum, ChainType::Bitcoin, ChainType::Solana];
+
+/// Endpoints and resilience settings for one network's service
+///
+/// EVM networks use all of it: requests fail over through `rpc_urls` in
+/// order, with retries and a per-request timeout. Solana and Bitcoin
+/// services talk to the first URL (a JSON-RPC endpoint, or a
+/// blockchain.info-compatible API) with the retries, the timeout and, for
+/// Solana, the headers; Bitcoin broadcasts are not retried.
+#[derive(Debug, Clone, Default)]
+pub struct NetworkServiceConfig {
+    rpc_urls: Vec<String>,
+    retry_policy: Option<RetryPolicy>,
+    timeout: Option<Duration>,
+    headers: HashMap<String, String>,
+}
+
+impl NetworkServiceConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Endpoints in priority order (default: the network's public endpoint)
+    pub fn with_rpc_urls<I, S>(mut self, urls: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.rpc_urls = urls.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Retry transient failures (overrides `MultiChainConfig::with_retry_policy`)
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    /// Per-request timeout (overrides `MultiChainConfig::with_timeout`)
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Headers (e.g. an API key) sent with every request
+    pub fn with_headers(mut self, headers: HashMap<String, String>) -> Self {
+        self.headers = headers;
+        self
+    }
+}
+
+/// Per-network service settings for [`MultiChainBlockchainService::from_config`]
+///
+/// Networks without an entry use their public endpoint, with the default
+/// retry policy and timeout if set.
+#[derive(Debug, Clone, Default)]
+pub struct MultiChainConfig {
+    networks: Vec<(Network, NetworkServiceConfig)>,
+    retry_policy: Option<RetryPolicy>,
+    timeout: Option<Duration>,
+}
+
+impl MultiChainConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use `config` for `network`, replacing any earlier entry
+    pub fn with_network(mut self, network: Network, config: NetworkServiceConfig) -> Self {
+        self.networks.retain(|(known, _)| *known != network);
+        self.networks.push((network, config));
+        self
+    }
+
+    /// Retry policy for networks whose entry does not set one
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    /// Request timeout for networks whose entry does not set one
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Settings for `network`, with the defaults filled in
+    pub fn for_network(&self, network: &Network) -> NetworkServiceConfig {
+        let mut config = self
+            .networks
+            .iter()
+            .find(|(known, _)| known == network)
+            .map(|(_, config)| config.clone())
+            .unwrap_or_default();
+        config.retry_policy = config.retry_policy.or(self.retry_policy);
+        config.timeout = config.timeout.or(self.timeout);
+        config
+    }
+}
+
 /// Multi-chain blockchain service that routes requests to the appropriate chain-specific service
 ///
 /// This service acts as a facade that automatically routes blockchain operations to the correct
@@ -39,17 +148,29 @@ pub struct MultiChainBlockchainService {
     solana_service: Option<Arc<SolanaBlockchainService>>,
     /// Current network context (if set)
     current_network: Option<Network>,
+    /// Endpoints and resilience settings the services are built with
+    config: MultiChainConfig,
 }
 
 impl MultiChainBlockchainService {
     /// Create a new multi-chain service with all services initialized
     pub async fn new() -> Result<Self, DomainError> {
-        Ok(Self {
+        Ok(Self::from_config(MultiChainConfig::default()))
+    }
+
+    /// Create a multi-chain service whose services use `config`'s endpoints,
+    /// retry policies and timeouts
+    ///
+    /// Services are still created lazily, by `initialize_for_network` or
+    /// when a network is first queried.
+    pub fn from_config(config: MultiChainConfig) -> Self {
+        Self {
             evm_service: None,
             bitcoin_service: None,
             solana_service: None,
             current_network: None,
-        })
+            config,
+        }
     }
 
     /// Create a multi-chain service for a specific network
@@ -63,57 +184,133 @@ impl MultiChainBlockchainService {
         Ok(service)
     }
 
+    /// Chain types this facade can route to
+    pub fn supported_chain_types(&self) -> &'static [ChainType] {
+        SUPPORTED_CHAIN_TYPES
+    }
+
+    /// Resolve the chain type serving `network`, rejecting networks this facade cannot serve
+    ///
+    /// `Network::Custom` is assumed to be EVM; a chain id of 0 (invalid under EIP-155)
+    /// marks a custom network that is not EVM and therefore has no backing service.
+    fn chain_type_for(network: &Network) -> Result<ChainType, DomainError> {
+        let supported = || {
+            SUPPORTED_CHAIN_TYPES
+                .iter()
+                .map(|c| c.name())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        if let Network::Custom { name, chain_id: 0, .. } = network {
+            return Err(DomainError::ConfigurationError(format!(
+                "Custom network '{}' has no EVM chain id and is not supported. Supported chains: {}",
+                name,
+                supported()
+            )));
+        }
+
+        let chain_type = network.chain_type();
+        if !SUPPORTED_CHAIN_TYPES.contains(&chain_type) {
+            return Err(DomainError::ConfigurationError(format!(
+                "Chain type {} (network {}) is not supported. Supported chains: {}",
+                chain_type,
+                network,
+                supported()
+            )));
+        }
+        Ok(chain_type)
+    }
+
     /// Initialize services for all supported chains
     pub async fn initialize_all(&mut self) -> Result<(), DomainError> {
         // Initialize Ethereum service (default to Mainnet)
-        self.evm_service = Some(Arc::new(
-            AlloyBlockchainService::new_with_default_rpc(Network::Mainnet).await?
-        ));
+        self.evm_service = Some(Arc::new(self.build_evm_service(&Network::Mainnet).await?));
 
         // Initialize Bitcoin service
-        self.bitcoin_service = Some(Arc::new(
-            BitcoinBlockchainService::new(Network::BitcoinMainnet).await?
-        ));
+        self.bitcoin_service = Some(Arc::new(self.build_bitcoin_service(&Network::BitcoinMainnet).await?));
 
         // Initialize Solana service
-        self.solana_service = Some(Arc::new(
-            SolanaBlockchainService::new(Network::SolanaMainnet).await?
-        ));
+        self.solana_service = Some(Arc::new(self.build_solana_service(&Network::SolanaMainnet).await?));
 
         Ok(())
     }
 
     /// Initialize service for a specific network
     pub async fn initialize_for_network(&mut self, network: &Network) -> Result<(), DomainError> {
-        match network.chain_type() {
+        match Self::chain_type_for(network)? {
             ChainType::Ethereum => {
                 if self.evm_service.is_none() {
-                    self.evm_service = Some(Arc::new(
-                        AlloyBlockchainService::new_with_default_rpc(network.clone()).await?
-                    ));
+                    self.evm_service = Some(Arc::new(self.build_evm_service(network).await?));
                 }
             }
             ChainType::Bitcoin => {
                 if self.bitcoin_service.is_none() {
-                    self.bitcoin_service = Some(Arc::new(
-                        BitcoinBlockchainService::new(network.clone()).await?
-                    ));
+                    self.bitcoin_service = Some(Arc::new(self.build_bitcoin_service(network).await?));
                 }
             }
             ChainType::Solana => {
                 if self.solana_service.is_none() {
-                    self.solana_service = Some(Arc::new(
-                        SolanaBlockchainService::new(network.clone()).await?
-                    ));
+                    self.solana_service = Some(Arc::new(self.build_solana_service(network).await?));
                 }
             }
         }
         Ok(())
     }
 
+    /// EVM service for `network` with its configured endpoints, retries and timeout
+    async fn build_evm_service(&self, network: &Network) -> Result<AlloyBlockchainService, DomainError> {
+        let config = self.config.for_network(network);
+        let mut builder = AlloyBlockchainService::builder()
+            .network(network.clone())
+            .rpc_urls(config.rpc_urls)
+            .headers(config.headers);
+        if let Some(policy) = config.retry_policy {
+            builder = builder.retry_policy(policy);
+        }
+        if let Some(timeout) = config.timeout {
+            builder = builder.timeout(timeout);
+        }
+        builder.build().await
+    }
+
+    /// Bitcoin service for `network`, against its configured API and with its retries and timeout if any
+    async fn build_bitcoin_service(&self, network: &Network) -> Result<BitcoinBlockchainService, DomainError> {
+        let config = self.config.for_network(network);
+        let mut service = BitcoinBlockchainService::new(network.clone()).await?;
+        if let Some(policy) = config.retry_policy {
+            service = service.with_retry_policy(policy);
+        }
+        if let Some(url) = config.rpc_urls.first() {
+            service = service.with_api_base_url(url);
+        }
+        match config.timeout {
+            Some(timeout) => service.with_timeout(timeout),
+            None => Ok(service),
+        }
+    }
+
+    /// Solana service for `network` with its configured endpoint, headers, retries and timeout
+    async fn build_solana_service(&self, network: &Network) -> Result<SolanaBlockchainService, DomainError> {
+        let config = self.config.for_network(network);
+        let rpc_url = config
+            .rpc_urls
+            .first()
+            .map_or_else(|| default_rpc_url(network), Clone::clone);
+        let mut service = SolanaBlockchainService::new_with_rpc_url(network.clone(), &rpc_url, config.headers).await?;
+        if let Some(policy) = config.retry_policy {
+            service = service.with_retry_policy(policy);
+        }
+        match config.timeout {
+            Some(timeout) => service.with_timeout(timeout),
+            None => Ok(service),
+        }
+    }
+
+
     /// Get the appropriate service for a given network
     fn get_service_for_network(&self, network: &Network) -> Result<Arc<dyn BlockchainService>, DomainError> {
-        match network.chain_type() {
+        match Self::chain_type_for(network)? {
             ChainType::Ethereum => {
                 self.evm_service
                     .as_ref()
@@ -141,6 +338,68 @@ impl MultiChainBlockchainService {
         }
     }
 
+    /// Return the initialized service if it already targets `network`, otherwise
+    /// create a standalone one from the network's configuration
+    async fn service_for_exact_network(
+        &self,
+        network: &Network,
+    ) -> Result<Arc<dyn BlockchainService>, DomainError> {
+        match Self::chain_type_for(network)? {
+            ChainType::Ethereum => match &self.evm_service {
+                Some(s) if s.network() == network => Ok(s.clone()),
+                _ => Ok(Arc::new(self.build_evm_service(network).await?)),
+            },
+            ChainType::Bitcoin => match &self.bitcoin_service {
+                Some(s) if s.network() == network => Ok(s.clone()),
+                _ => Ok(Arc::new(self.build_bitcoin_service(network).await?)),
+            },
+            ChainType::Solana => match &self.solana_service {
+                Some(s) if s.network() == network => Ok(s.clone()),
+                _ => Ok(Arc::new(self.build_solana_service(network).await?)),
+            },
+        }
+    }
+
+    /// Query the same address on several networks concurrently
+    ///
+    /// Each distinct network gets one service (reusing an already-initialized one
+    /// when it targets that network), so duplicate entries do not create extra
+    /// clients. Results are returned in input order and errors stay per-network:
+    /// a failing RPC on one chain does not affect the others.
+    pub async fn get_balances_across_networks(
+        &self,
+        address: &Address,
+        networks: &[Network],
+    ) -> Vec<(Network, Result<Balance, DomainError>)> {
+        let mut distinct: Vec<&Network> = Vec::new();
+        for network in networks {
+            if !distinct.contains(&network) {
+                distinct.push(network);
+            }
+        }
+
+        let services = join_all(distinct.iter().map(|n| self.service_for_exact_network(n))).await;
+        let balances = join_all(services.into_iter().map(|service| async move {
+            service?.get_balance(address).await
+        }))
+        .await;
+
+        let by_network: Vec<(&Network, Result<Balance, DomainError>)> =
+            distinct.into_iter().zip(balances).collect();
+
+        networks
+            .iter()
+            .map(|network| {
+                let result = by_network
+                    .iter()
+                    .find(|(n, _)| *n == network)
+                    .map(|(_, r)| r.clone())
+                    .expect("every network has a result");
+                (network.clone(), result)
+            })
+            .collect()
+    }
+
     /// Get balance for an address on a specific network
     ///
     /// This method automatically routes to the correct blockchain service based on the network.
@@ -153,6 +412,16 @@ impl MultiChainBlockchainService {
         service.get_balance(address).await
     }
 
+    /// Get pending (mempool-inclusive) balance for an address on a specific network
+    pub async fn get_pending_balance_for_network(
+        &self,
+        address: &Address,
+        network: &Network,
+    ) -> Result<Balance, DomainError> {
+        let service = self.get_service_for_network(network)?;
+        service.get_pending_balance(address).await
+    }
+
     /// Transfer funds on a specific network
     pub async fn transfer_on_network(
         &self,
@@ -160,10 +429,46 @@ impl MultiChainBlockchainService {
         from: &Address,
         to: &Address,
         amount: u128,
-        private_key: &str,
+        signer: &dyn Signer,
     ) -> Result<TransactionHash, DomainError> {
         let service = self.get_service_for_network(network)?;
-        service.transfer(from, to, amount, private_key).await
+        service.transfer(from, to, amount, signer).await
+    }
+
+    /// Estimate the transfer fee on a specific network
+    ///
+    /// Gives a uniform "this will cost X" across EVM (gas × price), Bitcoin
+    /// (vsize × sat/vB) and Solana (signatures × lamports per signature).
+    pub async fn estimate_fee_on_network(
+        &self,
+        network: &Network,
+        from: &Address,
+        to: &Address,
+        amount: u128,
+    ) -> Result<FeeEstimate, DomainError> {
+        let service = self.get_service_for_network(network)?;
+        service.estimate_fee(from, to, amount).await
+    }
+
+    /// Wait for a transaction's receipt on a specific network
+    pub async fn wait_for_receipt_on_network(
+        &self,
+        network: &Network,
+        tx_hash: &TransactionHash,
+        timeout: Duration,
+    ) -> Result<TransactionReceipt, DomainError> {
+        let service = self.get_service_for_network(network)?;
+        service.wait_for_receipt(tx_hash, timeout).await
+    }
+
+    /// Look up a transaction's status on a specific network
+    pub async fn get_transaction_status_on_network(
+        &self,
+        network: &Network,
+        tx_hash: &TransactionHash,
+    ) -> Result<TxStatus, DomainError> {
+        let service = self.get_service_for_network(network)?;
+        service.get_transaction_status(tx_hash).await
     }
 
     /// Check if a specific network is connected
@@ -179,11 +484,38 @@ impl MultiChainBlockchainService {
         let service = self.get_service_for_network(network)?;
         service.get_block_number().await
     }
+
+    /// Latest height of a specific network with what it counts
+    ///
+    /// `get_block_number` returns an EVM block number, a Bitcoin height or a
+    /// Solana slot depending on the network; the label ("block", "height" or
+    /// "slot", see [`ChainType::height_unit`]) says which, for display.
+    pub async fn get_height_labeled(&self, network: &Network) -> Result<(u64, &'static str), DomainError> {
+        let height = self.get_block_number_for_network(network).await?;
+        Ok((height, network.chain_type().height_unit()))
+    }
+
+    /// Get the chain height at the requested finality for a specific network
+    pub async fn get_height_for_network(
+        &self,
+        network: &Network,
+        finality: Finality,
+    ) -> Result<u64, DomainError> {
+        let service = self.get_service_for_network(network)?;
+        service.get_height(finality).await
+    }
 }
 
 // Implement BlockchainService for the current network context
 #[async_trait]
 impl BlockchainService for MultiChainBlockchainService {
+    /// Chain of the current network (Ethereum, like the default network, until one is selected)
+    fn chain_type(&self) -> ChainType {
+        self.current_network
+            .as_ref()
+            .map_or(ChainType::Ethereum, Network::chain_type)
+    }
+
     async fn get_balance(&self, address: &Address) -> Result<Balance, DomainError> {
         let network = self.current_network.as_ref().ok_or_else(|| {
             DomainError::ConfigurationError(
@@ -193,19 +525,147 @@ impl BlockchainService for MultiChainBlockchainService {
         self.get_balance_for_network(address, network).await
     }
 
+    /// Uses the current network's batched implementation
+    async fn get_balances(&self, addresses: &[Address]) -> Vec<Result<Balance, DomainError>> {
+        let service = self
+            .current_network
+            .as_ref()
+            .ok_or_else(|| {
+                DomainError::ConfigurationError(
+                    "No network context set. Create with new_for_network()".to_string(),
+                )
+            })
+            .and_then(|network| self.get_service_for_network(network));
+        match service {
+            Ok(service) => service.get_balances(addresses).await,
+            Err(e) => addresses.iter().map(|_| Err(e.clone())).collect(),
+        }
+    }
+
+    async fn get_pending_balance(&self, address: &Address) -> Result<Balance, DomainError> {
+        let network = self.current_network.as_ref().ok_or_else(|| {
+            DomainError::ConfigurationError(
+                "No network context set. Use get_pending_balance_for_network() or create with new_for_network()".to_string()
+            )
+        })?;
+        self.get_pending_balance_for_network(address, network).await
+    }
+
+    async fn get_balance_at_block(&self, address: &Address, block_number: u64) -> Result<Balance, DomainError> {
+        let network = self.current_network.as_ref().ok_or_else(|| {
+            DomainError::ConfigurationError(
+                "No network context set. Create with new_for_network() to query historical balances".to_string()
+            )
+        })?;
+        let service = self.get_service_for_network(network)?;
+        service.get_balance_at_block(address, block_number).await
+    }
+
+    async fn get_account_info(&self, address: &Address) -> Result<AccountInfo, DomainError> {
+        let network = self.current_network.as_ref().ok_or_else(|| {
+            DomainError::ConfigurationError(
+                "No network context set. Create with new_for_network() to query account info".to_string()
+            )
+        })?;
+        let service = self.get_service_for_network(network)?;
+        service.get_account_info(address).await
+    }
+
     async fn transfer(
         &self,
         from: &Address,
         to: &Address,
         amount: u128,
-        private_key: &str,
+        signer: &dyn Signer,
     ) -> Result<TransactionHash, DomainError> {
         let network = self.current_network.as_ref().ok_or_else(|| {
             DomainError::ConfigurationError(
                 "No network context set. Use transfer_on_network() or create with new_for_network()".to_string()
             )
         })?;
-        self.transfer_on_network(network, from, to, amount, private_key).await
+        self.transfer_on_network(network, from, to, amount, signer).await
+    }
+
+    async fn transfer_with_gas_price(
+        &self,
+        from: &Address,
+        to: &Address,
+        amount: u128,
+        signer: &dyn Signer,
+        gas_price: u128,
+    ) -> Result<TransactionHash, DomainError> {
+        let network = self.current_network.as_ref().ok_or_else(|| {
+            DomainError::ConfigurationError(
+                "No network context set. Create with new_for_network() to transfer".to_string()
+            )
+        })?;
+        let service = self.get_service_for_network(network)?;
+        service.transfer_with_gas_price(from, to, amount, signer, gas_price).await
+    }
+
+    fn signer_for_key(&self, private_key: &SecretKey) -> Result<Box<dyn Signer>, DomainError> {
+        let network = self.current_network.as_ref().ok_or_else(|| {
+            DomainError::ConfigurationError(
+                "No network context set. Create with new_for_network() to sign with a private key".to_string()
+            )
+        })?;
+        self.get_service_for_network(network)?.signer_for_key(private_key)
+    }
+
+    async fn estimate_fee(
+        &self,
+        from: &Address,
+        to: &Address,
+        amount: u128,
+    ) -> Result<FeeEstimate, DomainError> {
+        let network = self.current_network.as_ref().ok_or_else(|| {
+            DomainError::ConfigurationError(
+                "No network context set. Use estimate_fee_on_network() or create with new_for_network()".to_string()
+            )
+        })?;
+        self.estimate_fee_on_network(network, from, to, amount).await
+    }
+
+    async fn max_sendable(&self, from: &Address, to: &Address) -> Result<MaxSendable, DomainError> {
+        let network = self.current_network.as_ref().ok_or_else(|| {
+            DomainError::ConfigurationError(
+                "No network context set. Create with new_for_network() to compute the max sendable amount".to_string()
+            )
+        })?;
+        let service = self.get_service_for_network(network)?;
+        service.max_sendable(from, to).await
+    }
+
+    async fn estimate_confirmation_time(&self, fee: &FeeEstimate) -> Result<Duration, DomainError> {
+        let network = self.current_network.as_ref().ok_or_else(|| {
+            DomainError::ConfigurationError(
+                "No network context set. Create with new_for_network() to estimate confirmation time".to_string()
+            )
+        })?;
+        let service = self.get_service_for_network(network)?;
+        service.estimate_confirmation_time(fee).await
+    }
+
+    async fn wait_for_receipt(
+        &self,
+        tx_hash: &TransactionHash,
+        timeout: Duration,
+    ) -> Result<TransactionReceipt, DomainError> {
+        let network = self.current_network.as_ref().ok_or_else(|| {
+            DomainError::ConfigurationError(
+                "No network context set. Use wait_for_receipt_on_network() or create with new_for_network()".to_string()
+            )
+        })?;
+        self.wait_for_receipt_on_network(network, tx_hash, timeout).await
+    }
+
+    async fn get_transaction_status(&self, tx_hash: &TransactionHash) -> Result<TxStatus, DomainError> {
+        let network = self.current_network.as_ref().ok_or_else(|| {
+            DomainError::ConfigurationError(
+                "No network context set. Use get_transaction_status_on_network() or create with new_for_network()".to_string()
+            )
+        })?;
+        self.get_transaction_status_on_network(network, tx_hash).await
     }
 
     async fn is_connected(&self) -> bool {
@@ -223,11 +683,101 @@ impl BlockchainService for MultiChainBlockchainService {
         })?;
         self.get_block_number_for_network(network).await
     }
+
+    async fn get_height(&self, finality: Finality) -> Result<u64, DomainError> {
+        let network = self.current_network.as_ref().ok_or_else(|| {
+            DomainError::ConfigurationError(
+                "No network context set. Use get_height_for_network() or create with new_for_network()".to_string()
+            )
+        })?;
+        self.get_height_for_network(network, finality).await
+    }
+
+    async fn diagnose(&self) -> ConnectionDiagnostics {
+        let service = self.current_network.as_ref().ok_or_else(|| {
+            DomainError::ConfigurationError(
+                "No network context set. Create with new_for_network() to diagnose".to_string()
+            )
+        });
+        match service.and_then(|network| self.get_service_for_network(network)) {
+            Ok(service) => service.diagnose().await,
+            Err(e) => ConnectionDiagnostics::measure(async { Err(e) }).await,
+        }
+    }
+
+    /// Sibling of the current network's chain-specific service
+    async fn with_endpoint(&self, url: &str) -> Result<Arc<dyn BlockchainService>, DomainError> {
+        let network = self.current_network.as_ref().ok_or_else(|| {
+            DomainError::ConfigurationError(
+                "No network context set. Create with new_for_network() to switch endpoints".to_string()
+            )
+        })?;
+        self.get_service_for_network(network)?.with_endpoint(url).await
+    }
+
+    /// Ticks of the current network's service, or `fallback` without a network context
+    fn new_block_ticks(&self, fallback: BoxStream<'static, ()>) -> BoxStream<'static, ()> {
+        match self.current_network.as_ref().map(|network| self.get_service_for_network(network)) {
+            Some(Ok(service)) => service.new_block_ticks(fallback),
+            _ => fallback,
+        }
+    }
+
+    async fn shutdown(&self) {
+        if let Some(service) = &self.evm_service {
+            service.shutdown().await;
+        }
+        if let Some(service) = &self.bitcoin_service {
+            service.shutdown().await;
+        }
+        if let Some(service) = &self.solana_service {
+            service.shutdown().await;
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::domain::value_objects::NetworkKind;
+
+    #[test]
+    fn test_multi_chain_config_fills_in_defaults() {
+        let config = MultiChainConfig::new()
+            .with_retry_policy(RetryPolicy::default())
+            .with_timeout(Duration::from_secs(10))
+            .with_network(
+                Network::Mainnet,
+                NetworkServiceConfig::new()
+                    .with_rpc_urls(["https://a.example", "https://b.example"])
+                    .with_timeout(Duration::from_secs(2)),
+            );
+
+        let mainnet = config.for_network(&Network::Mainnet);
+        assert_eq!(mainnet.rpc_urls, vec!["https://a.example", "https://b.example"]);
+        assert_eq!(mainnet.timeout, Some(Duration::from_secs(2)));
+        assert_eq!(mainnet.retry_policy, Some(RetryPolicy::default()));
+
+        let polygon = config.for_network(&Network::Polygon);
+        assert!(polygon.rpc_urls.is_empty());
+        assert_eq!(polygon.timeout, Some(Duration::from_secs(10)));
+    }
+
+    #[tokio::test]
+    async fn test_non_evm_services_take_retry_policy_and_timeout() {
+        let config = MultiChainConfig::new()
+            .with_retry_policy(RetryPolicy::default())
+            .with_timeout(Duration::from_secs(5))
+            .with_network(
+                Network::BitcoinMainnet,
+                NetworkServiceConfig::new().with_retry_policy(RetryPolicy::new(1, Duration::ZERO)),
+            );
+        let mut service = MultiChainBlockchainService::from_config(config);
+
+        service.initialize_for_network(&Network::SolanaMainnet).await.unwrap();
+        service.initialize_for_network(&Network::BitcoinMainnet).await.unwrap();
+        assert!(service.get_service_for_network(&Network::BitcoinMainnet).is_ok());
+    }
 
     #[tokio::test]
     async fn test_multi_chain_service_creation() {
@@ -287,4 +837,111 @@ mod tests {
             _ => panic!("Expected ConfigurationError"),
         }
     }
+
+    #[tokio::test]
+    async fn test_unsupported_custom_network_rejected() {
+        let mut service = MultiChainBlockchainService::new().await.unwrap();
+        assert_eq!(
+            service.supported_chain_types(),
+            &[ChainType::Ethereum, ChainType::Bitcoin, ChainType::Solana]
+        );
+
+        let cosmos = Network::Custom {
+            name: "cosmoshub".to_string(),
+            chain_id: 0,
+            rpc_url: "https://rpc.cosmos.network".to_string(),
+            kind: NetworkKind::Mainnet,
+            symbol: None,
+            explorer_url: None,
+        };
+
+        match service.initialize_for_network(&cosmos).await {
+            Err(DomainError::ConfigurationError(msg)) => {
+                assert!(msg.contains("cosmoshub"));
+                assert!(msg.contains("Ethereum, Bitcoin, Solana"));
+            }
+            other => panic!("Expected ConfigurationError, got {:?}", other.map(|_| ())),
+        }
+        assert!(matches!(
+            service.get_service_for_network(&cosmos),
+            Err(DomainError::ConfigurationError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_estimate_fee_routes_to_solana() {
+        let service = MultiChainBlockchainService::new_for_network(Network::SolanaDevnet).await.unwrap();
+        let from = Address::new("DRpbCBMxVnDK7maPM5tGv6MvB3v1sRMC86PZ8okm21hy".to_string()).unwrap();
+        let to = Address::new("Vote111111111111111111111111111111111111111".to_string()).unwrap();
+
+        let fee = service.estimate_fee(&from, &to, 1_000).await.unwrap();
+        assert_eq!(fee.total_native.to_wei(), 5_000);
+        assert_eq!(fee.unit, "Lamport");
+    }
+
+    #[tokio::test]
+    async fn test_balances_across_networks_isolate_errors() {
+        let service = MultiChainBlockchainService::new().await.unwrap();
+        let address = Address::new("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".to_string()).unwrap();
+        let bad_url = Network::Custom {
+            name: "bad-url".to_string(),
+            chain_id: 1337,
+            rpc_url: "not a url".to_string(),
+            kind: NetworkKind::Local,
+            symbol: None,
+            explorer_url: None,
+        };
+        let unreachable = Network::Custom {
+            name: "unreachable".to_string(),
+            chain_id: 31337,
+            rpc_url: "http://127.0.0.1:1".to_string(),
+            kind: NetworkKind::Local,
+            symbol: None,
+            explorer_url: None,
+        };
+        let networks = [bad_url.clone(), unreachable.clone(), bad_url.clone()];
+
+        let results = service.get_balances_across_networks(&address, &networks).await;
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].0, bad_url);
+        assert!(matches!(results[0].1, Err(DomainError::ConfigurationError(_))));
+        assert_eq!(results[1].0, unreachable);
+        assert!(matches!(results[1].1, Err(DomainError::NetworkError(_))));
+        assert!(matches!(results[2].1, Err(DomainError::ConfigurationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_releases_all_services() {
+        use futures::stream::{self, StreamExt};
+
+        let mut service = MultiChainBlockchainService::new().await.unwrap();
+        service.initialize_for_network(&Network::Sepolia).await.unwrap();
+        service.initialize_for_network(&Network::SolanaDevnet).await.unwrap();
+
+        let mut ticks = service.evm_service.as_ref().unwrap().new_block_ticks(stream::pending().boxed());
+
+        // Shutdown must be safe with a mix of initialized and missing services
+        service.shutdown().await;
+        // ...and must reach the EVM service, ending its block watchers
+        assert_eq!(ticks.next().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_block_ticks_come_from_the_current_network() {
+        use futures::stream::{self, StreamExt};
+
+        let service = MultiChainBlockchainService::new_for_network(Network::Sepolia).await.unwrap();
+        let mut ticks = service.new_block_ticks(stream::pending().boxed());
+
+        // Only the EVM service's ticks end on shutdown; the fallback never would
+        service.shutdown().await;
+        let next = tokio::time::timeout(Duration::from_secs(5), ticks.next()).await;
+        assert_eq!(next, Ok(None));
+
+        // Without a network context the fallback is returned as is
+        let service = MultiChainBlockchainService::new().await.unwrap();
+        let mut ticks = service.new_block_ticks(stream::iter([(), ()]).boxed());
+        assert_eq!(ticks.next().await, Some(()));
+    }
 }
@@ -0,0 +1,89 @@
+//! Batched native-balance reads through the Multicall3 contract
+
+use alloy::primitives::{address, Address as AlloyAddress, U256};
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use crate::core::domain::errors::DomainError;
+
+/// Multicall3, deployed at the same address on Ethereum, BSC, Polygon and their testnets
+pub(crate) const MULTICALL3_ADDRESS: AlloyAddress = address!("cA11bde05977b3631167028862bE2a173976CA11");
+
+sol! {
+    struct Call3 {
+        address target;
+        bool allowFailure;
+        bytes callData;
+    }
+
+    struct Result3 {
+        bool success;
+        bytes returnData;
+    }
+
+    function aggregate3(Call3[] calls) external payable returns (Result3[] returnData);
+    function getEthBalance(address addr) external view returns (uint256 balance);
+}
+
+/// Calldata for one `aggregate3` call reading the native balance of every address
+pub(crate) fn encode_balances(addresses: &[AlloyAddress]) -> Vec<u8> {
+    let calls = addresses
+        .iter()
+        .map(|addr| Call3 {
+            target: MULTICALL3_ADDRESS,
+            allowFailure: true,
+            callData: getEthBalanceCall { addr: *addr }.abi_encode().into(),
+        })
+        .collect();
+    aggregate3Call { calls }.abi_encode()
+}
+
+/// Decode the `aggregate3` return data into one balance per address, in order
+pub(crate) fn decode_balances(output: &[u8], expected: usize) -> Result<Vec<Option<U256>>, DomainError> {
+    let results = aggregate3Call::abi_decode_returns(output, true)
+        .map_err(|e| DomainError::BlockchainError(format!("Invalid multicall response: {}", e)))?
+        .returnData;
+    if results.len() != expected {
+        return Err(DomainError::BlockchainError(format!(
+            "Multicall returned {} results for {} calls",
+            results.len(),
+            expected
+        )));
+    }
+
+    Ok(results
+        .into_iter()
+        .map(|result| {
+            result
+                .success
+                .then(|| getEthBalanceCall::abi_decode_returns(&result.returnData, true).ok())
+                .flatten()
+                .map(|decoded| decoded.balance)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::sol_types::SolValue;
+
+    #[test]
+    fn test_multicall_balances_round_trip() {
+        let addresses = [AlloyAddress::repeat_byte(0x11), AlloyAddress::repeat_byte(0x22)];
+        let calldata = encode_balances(&addresses);
+        assert_eq!(&calldata[..4], &aggregate3Call::SELECTOR);
+
+        let decoded = aggregate3Call::abi_decode(&calldata, true).unwrap();
+        assert_eq!(decoded.calls.len(), 2);
+        assert_eq!(decoded.calls[1].callData[16..], addresses[1].abi_encode()[12..]);
+
+        let output = vec![
+            Result3 { success: true, returnData: U256::from(42u64).abi_encode().into() },
+            Result3 { success: false, returnData: Default::default() },
+        ]
+        .abi_encode();
+        let balances = decode_balances(&output, 2).unwrap();
+        assert_eq!(balances, vec![Some(U256::from(42u64)), None]);
+        assert!(decode_balances(&output, 3).is_err());
+    }
+}
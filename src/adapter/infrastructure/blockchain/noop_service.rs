@@ -0,0 +1,143 @@
+use async_trait::async_trait;
+use crate::adapter::infrastructure::keys::PrivateKeySigner;
+use crate::core::domain::{
+    errors::DomainError,
+    services::{BlockchainService, Signer},
+    value_objects::{Address, Balance, ChainType, ConnectionDiagnostics, Network, SecretKey, TransactionHash},
+};
+
+/// Balance reported by [`NoopBlockchainService::new`]: 1.5 native units
+const DEFAULT_BALANCE_WEI: u128 = 1_500_000_000_000_000_000;
+/// Block number reported by [`NoopBlockchainService::new`]
+const DEFAULT_BLOCK_NUMBER: u64 = 1_000_000;
+
+/// Zero-config stub that never touches the network
+///
+/// Every address has the same fixed balance and the chain is frozen at a
+/// fixed block, so command parsing, routing and formatting can be exercised
+/// deterministically (smoke tests, demos, `balance --offline`). Transfers are
+/// rejected rather than faked.
+#[derive(Debug, Clone)]
+pub struct NoopBlockchainService {
+    balance: Balance,
+    block_number: u64,
+    chain_type: ChainType,
+}
+
+impl NoopBlockchainService {
+    /// Stub for an Ethereum chain reporting 1.5 native units at block #1,000,000
+    pub fn new() -> Self {
+        Self {
+            balance: Balance::from_wei(DEFAULT_BALANCE_WEI),
+            block_number: DEFAULT_BLOCK_NUMBER,
+            chain_type: ChainType::Ethereum,
+        }
+    }
+
+    /// Report `balance` for every address
+    pub fn with_balance(mut self, balance: Balance) -> Self {
+        self.balance = balance;
+        self
+    }
+
+    /// Report `block_number` as the chain height
+    pub fn with_block_number(mut self, block_number: u64) -> Self {
+        self.block_number = block_number;
+        self
+    }
+
+    /// Pretend to serve `chain_type` (e.g. for `balance --offline` on Bitcoin)
+    pub fn with_chain_type(mut self, chain_type: ChainType) -> Self {
+        self.chain_type = chain_type;
+        self
+    }
+}
+
+impl Default for NoopBlockchainService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl BlockchainService for NoopBlockchainService {
+    fn chain_type(&self) -> ChainType {
+        self.chain_type
+    }
+
+    async fn get_balance(&self, _address: &Address) -> Result<Balance, DomainError> {
+        Ok(self.balance)
+    }
+
+    async fn transfer(
+        &self,
+        _from: &Address,
+        _to: &Address,
+        _amount: u128,
+        _signer: &dyn Signer,
+    ) -> Result<TransactionHash, DomainError> {
+        Err(DomainError::TransferFailed(
+            "Transfers are not sent in offline mode".to_string(),
+        ))
+    }
+
+    /// Parses the key as on the chain type's mainnet, so bad keys are still rejected
+    fn signer_for_key(&self, private_key: &SecretKey) -> Result<Box<dyn Signer>, DomainError> {
+        let network = match self.chain_type {
+            ChainType::Ethereum => Network::Mainnet,
+            ChainType::Bitcoin => Network::BitcoinMainnet,
+            ChainType::Solana => Network::SolanaMainnet,
+        };
+        Ok(Box::new(PrivateKeySigner::new(private_key, &network)?))
+    }
+
+    async fn is_connected(&self) -> bool {
+        true
+    }
+
+    async fn get_block_number(&self) -> Result<u64, DomainError> {
+        Ok(self.block_number)
+    }
+
+    /// Always reachable with zero latency
+    async fn diagnose(&self) -> ConnectionDiagnostics {
+        ConnectionDiagnostics {
+            reachable: true,
+            throttled: false,
+            latency_ms: 0,
+            block_height: Some(self.block_number),
+            rpc_version: Some("offline".to_string()),
+            error: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_noop_service_is_deterministic() {
+        let address = Address::new("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".to_string()).unwrap();
+        let service = NoopBlockchainService::new();
+
+        assert_eq!(service.get_balance(&address).await.unwrap().to_wei(), DEFAULT_BALANCE_WEI);
+        assert_eq!(service.get_block_number().await.unwrap(), DEFAULT_BLOCK_NUMBER);
+        assert!(service.diagnose().await.reachable);
+        assert_eq!(service.chain_type(), ChainType::Ethereum);
+
+        let signer = service.signer_for_key(&SecretKey::new("1".repeat(64))).unwrap();
+        assert!(matches!(
+            service.transfer(&address, &address, 1, signer.as_ref()).await,
+            Err(DomainError::TransferFailed(_))
+        ));
+
+        let custom = service
+            .with_balance(Balance::zero())
+            .with_block_number(7)
+            .with_chain_type(ChainType::Bitcoin);
+        assert_eq!(custom.get_balance(&address).await.unwrap(), Balance::zero());
+        assert_eq!(custom.get_block_number().await.unwrap(), 7);
+        assert_eq!(custom.chain_type(), ChainType::Bitcoin);
+    }
+}
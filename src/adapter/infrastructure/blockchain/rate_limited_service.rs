@@ -0,0 +1,334 @@
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use crate::core::domain::{
+    errors::DomainError,
+    services::{BlockchainService, Signer},
+    value_objects::{
+        AccountInfo, Address, Balance, ChainType, ConnectionDiagnostics, FeeEstimate, Finality, MaxSendable,
+        SecretKey, TransactionHash, TransactionReceipt, TxStatus,
+    },
+};
+
+/// Rate-limiting decorator that throttles outgoing RPC requests
+///
+/// Uses a token bucket: each call consumes one token, tokens refill at
+/// `requests_per_second`, and callers wait (rather than error) when the
+/// bucket is empty. Wraps any `Arc<dyn BlockchainService>`, so it composes
+/// with other decorators.
+///
+/// ```text
+/// caller ──> RateLimitedBlockchainService ──(token)──> inner service
+/// ```
+pub struct RateLimitedBlockchainService {
+    inner: Arc<dyn BlockchainService>,
+    bucket: Mutex<TokenBucket>,
+}
+
+struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// Take a token if available, otherwise return how long until one is
+    fn try_acquire(&mut self) -> Result<(), Duration> {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - self.tokens) / self.refill_per_sec))
+        }
+    }
+}
+
+impl RateLimitedBlockchainService {
+    /// Wrap a service, allowing bursts of up to one second's worth of requests
+    pub fn new(inner: Arc<dyn BlockchainService>, requests_per_second: f64) -> Result<Self, DomainError> {
+        Self::with_burst(inner, requests_per_second, requests_per_second.max(1.0) as u32)
+    }
+
+    /// Wrap a service with an explicit burst size (bucket capacity)
+    pub fn with_burst(
+        inner: Arc<dyn BlockchainService>,
+        requests_per_second: f64,
+        burst: u32,
+    ) -> Result<Self, DomainError> {
+        if !(requests_per_second.is_finite() && requests_per_second > 0.0) {
+            return Err(DomainError::ConfigurationError(
+                "requests_per_second must be a positive number".to_string(),
+            ));
+        }
+        if burst == 0 {
+            return Err(DomainError::ConfigurationError("burst must be at least 1".to_string()));
+        }
+
+        Ok(Self {
+            inner,
+            bucket: Mutex::new(TokenBucket {
+                capacity: burst as f64,
+                tokens: burst as f64,
+                refill_per_sec: requests_per_second,
+                last_refill: Instant::now(),
+            }),
+        })
+    }
+
+    /// Wait until a request token is available
+    async fn acquire(&self) {
+        loop {
+            let wait = match self.bucket.lock().await.try_acquire() {
+                Ok(()) => return,
+                Err(wait) => wait,
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+#[async_trait]
+impl BlockchainService for RateLimitedBlockchainService {
+    /// Not rate limited: answered locally by the inner service
+    fn chain_type(&self) -> ChainType {
+        self.inner.chain_type()
+    }
+
+    async fn get_balance(&self, address: &Address) -> Result<Balance, DomainError> {
+        self.acquire().await;
+        self.inner.get_balance(address).await
+    }
+
+    /// Takes one slot per batch, keeping the inner service's batched request
+    async fn get_balances(&self, addresses: &[Address]) -> Vec<Result<Balance, DomainError>> {
+        self.acquire().await;
+        self.inner.get_balances(addresses).await
+    }
+
+    async fn get_pending_balance(&self, address: &Address) -> Result<Balance, DomainError> {
+        self.acquire().await;
+        self.inner.get_pending_balance(address).await
+    }
+
+    async fn get_balance_at_block(&self, address: &Address, block_number: u64) -> Result<Balance, DomainError> {
+        self.acquire().await;
+        self.inner.get_balance_at_block(address, block_number).await
+    }
+
+    /// Takes one slot, although the inner service may send several requests concurrently
+    async fn get_account_info(&self, address: &Address) -> Result<AccountInfo, DomainError> {
+        self.acquire().await;
+        self.inner.get_account_info(address).await
+    }
+
+    /// Takes one slot, although the inner service needs a balance and a fee estimate
+    async fn max_sendable(&self, from: &Address, to: &Address) -> Result<MaxSendable, DomainError> {
+        self.acquire().await;
+        self.inner.max_sendable(from, to).await
+    }
+
+    async fn transfer(
+        &self,
+        from: &Address,
+        to: &Address,
+        amount: u128,
+        signer: &dyn Signer,
+    ) -> Result<TransactionHash, DomainError> {
+        self.acquire().await;
+        self.inner.transfer(from, to, amount, signer).await
+    }
+
+    async fn transfer_with_gas_price(
+        &self,
+        from: &Address,
+        to: &Address,
+        amount: u128,
+        signer: &dyn Signer,
+        gas_price: u128,
+    ) -> Result<TransactionHash, DomainError> {
+        self.acquire().await;
+        self.inner.transfer_with_gas_price(from, to, amount, signer, gas_price).await
+    }
+
+    /// Not rate limited: no request is made
+    fn signer_for_key(&self, private_key: &SecretKey) -> Result<Box<dyn Signer>, DomainError> {
+        self.inner.signer_for_key(private_key)
+    }
+
+    async fn estimate_fee(
+        &self,
+        from: &Address,
+        to: &Address,
+        amount: u128,
+    ) -> Result<FeeEstimate, DomainError> {
+        self.acquire().await;
+        self.inner.estimate_fee(from, to, amount).await
+    }
+
+    /// Takes one slot, although the inner service may need several requests
+    async fn estimate_confirmation_time(&self, fee: &FeeEstimate) -> Result<Duration, DomainError> {
+        self.acquire().await;
+        self.inner.estimate_confirmation_time(fee).await
+    }
+
+    async fn wait_for_receipt(
+        &self,
+        tx_hash: &TransactionHash,
+        timeout: Duration,
+    ) -> Result<TransactionReceipt, DomainError> {
+        self.acquire().await;
+        self.inner.wait_for_receipt(tx_hash, timeout).await
+    }
+
+    async fn get_transaction_status(&self, tx_hash: &TransactionHash) -> Result<TxStatus, DomainError> {
+        self.acquire().await;
+        self.inner.get_transaction_status(tx_hash).await
+    }
+
+    /// Wraps the inner sibling in a new limiter with the same rate and burst
+    ///
+    /// The sibling gets its own bucket, since the limit protects each endpoint separately.
+    async fn with_endpoint(&self, url: &str) -> Result<Arc<dyn BlockchainService>, DomainError> {
+        let (requests_per_second, burst) = {
+            let bucket = self.bucket.lock().await;
+            (bucket.refill_per_sec, bucket.capacity as u32)
+        };
+        let inner = self.inner.with_endpoint(url).await?;
+        Ok(Arc::new(Self::with_burst(inner, requests_per_second, burst)?))
+    }
+
+    async fn is_connected(&self) -> bool {
+        self.acquire().await;
+        self.inner.is_connected().await
+    }
+
+    async fn get_block_number(&self) -> Result<u64, DomainError> {
+        self.acquire().await;
+        self.inner.get_block_number().await
+    }
+
+    async fn get_height(&self, finality: Finality) -> Result<u64, DomainError> {
+        self.acquire().await;
+        self.inner.get_height(finality).await
+    }
+
+    async fn diagnose(&self) -> ConnectionDiagnostics {
+        self.acquire().await;
+        self.inner.diagnose().await
+    }
+
+    /// Not rate limited: subscribing is a one-off request, not one per tick
+    fn new_block_ticks(&self, fallback: BoxStream<'static, ()>) -> BoxStream<'static, ()> {
+        self.inner.new_block_ticks(fallback)
+    }
+
+    async fn shutdown(&self) {
+        self.inner.shutdown().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    struct CountingService {
+        calls: AtomicU32,
+    }
+
+    #[async_trait]
+    impl BlockchainService for CountingService {
+        fn chain_type(&self) -> ChainType {
+            ChainType::Ethereum
+        }
+
+        async fn get_balance(&self, _address: &Address) -> Result<Balance, DomainError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(Balance::zero())
+        }
+
+        /// One batched request, like Multicall3
+        async fn get_balances(&self, addresses: &[Address]) -> Vec<Result<Balance, DomainError>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            addresses.iter().map(|_| Ok(Balance::zero())).collect()
+        }
+
+        async fn transfer(
+            &self,
+            _from: &Address,
+            _to: &Address,
+            _amount: u128,
+            _signer: &dyn Signer,
+        ) -> Result<TransactionHash, DomainError> {
+            Err(DomainError::TransferFailed("not supported".to_string()))
+        }
+
+        async fn is_connected(&self) -> bool {
+            true
+        }
+
+        async fn get_block_number(&self) -> Result<u64, DomainError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(1)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_throttles_requests() {
+        let inner = Arc::new(CountingService { calls: AtomicU32::new(0) });
+        let service = RateLimitedBlockchainService::with_burst(inner.clone(), 20.0, 1).unwrap();
+
+        let start = Instant::now();
+        for _ in 0..5 {
+            service.get_block_number().await.unwrap();
+        }
+
+        // First call uses the burst token, the remaining 4 wait ~50ms each
+        assert!(start.elapsed() >= Duration::from_millis(190));
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 5);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_allows_burst() {
+        let inner = Arc::new(CountingService { calls: AtomicU32::new(0) });
+        let service = RateLimitedBlockchainService::with_burst(inner, 1.0, 3).unwrap();
+        let address = Address::new("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEbC".to_string()).unwrap();
+
+        let start = Instant::now();
+        for _ in 0..3 {
+            service.get_balance(&address).await.unwrap();
+        }
+        assert!(start.elapsed() < Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_keeps_balance_batches_whole() {
+        let inner = Arc::new(CountingService { calls: AtomicU32::new(0) });
+        let service = RateLimitedBlockchainService::with_burst(inner.clone(), 1.0, 1).unwrap();
+        let address = Address::new("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEbC".to_string()).unwrap();
+        let addresses = vec![address; 5];
+
+        // A single token covers the whole batch, sent as one request
+        let start = Instant::now();
+        let balances = service.get_balances(&addresses).await;
+        assert!(start.elapsed() < Duration::from_millis(500));
+        assert_eq!(balances.len(), 5);
+        assert!(balances.iter().all(|balance| balance.is_ok()));
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_rate_limiter_rejects_invalid_rate() {
+        let inner = Arc::new(CountingService { calls: AtomicU32::new(0) });
+        assert!(RateLimitedBlockchainService::new(inner.clone(), 0.0).is_err());
+        assert!(RateLimitedBlockchainService::with_burst(inner, 1.0, 0).is_err());
+    }
+}
@@ -0,0 +1,103 @@
+//! Human-readable reasons for reverted `eth_call` / `eth_estimateGas` requests
+
+use alloy::hex;
+use alloy::sol_types::{Panic, Revert, SolError};
+use super::evm_rpc::RpcFailure;
+
+/// Why the node refused a call or gas estimate because execution reverted,
+/// or `None` if the request failed for another reason
+///
+/// Decodes the revert data when the node returns it; otherwise falls back to
+/// the reason some nodes append to the message ("execution reverted: ...").
+pub(crate) fn revert_reason(error: &RpcFailure) -> Option<String> {
+    let RpcFailure::Response(response) = error else {
+        return None;
+    };
+    match &response.revert_data {
+        Some(data) => Some(decode_revert_data(data)),
+        None if response.message.contains("revert") => {
+            let reason = response
+                .message
+                .split_once("reverted")
+                .map_or("", |(_, reason)| reason.trim_start_matches(':').trim());
+            Some(if reason.is_empty() { "no reason given".to_string() } else { reason.to_string() })
+        }
+        None => None,
+    }
+}
+
+/// Reason encoded in revert data: the message of `Error(string)`, the kind
+/// of a Solidity `Panic(uint256)`, or the 4-byte selector of a custom error
+pub(crate) fn decode_revert_data(data: &[u8]) -> String {
+    let Some(selector) = data.get(..4) else {
+        return if data.is_empty() {
+            "no reason given".to_string()
+        } else {
+            format!("malformed revert data 0x{}", hex::encode(data))
+        };
+    };
+    if selector == Revert::SELECTOR {
+        if let Ok(revert) = Revert::abi_decode(data, true) {
+            return if revert.reason.is_empty() { "no reason given".to_string() } else { revert.reason };
+        }
+    }
+    if selector == Panic::SELECTOR {
+        if let Ok(panic) = Panic::abi_decode(data, true) {
+            return panic.to_string();
+        }
+    }
+    format!("custom error 0x{}", hex::encode(selector))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::evm_rpc::ErrorResponse;
+    use alloy::primitives::U256;
+
+    fn error_response(message: &'static str, revert_data: Option<Vec<u8>>) -> RpcFailure {
+        RpcFailure::Response(ErrorResponse { revert_data, ..ErrorResponse::new(3, message) })
+    }
+
+    #[test]
+    fn test_decode_revert_data() {
+        let revert = Revert::from("ERC20: transfer amount exceeds balance").abi_encode();
+        assert_eq!(&revert[..4], hex::decode("08c379a0").unwrap());
+        assert_eq!(decode_revert_data(&revert), "ERC20: transfer amount exceeds balance");
+
+        let panic = Panic { code: U256::from(0x11) }.abi_encode();
+        assert_eq!(decode_revert_data(&panic), "panic: arithmetic underflow or overflow (0x11)");
+
+        // ERC20InsufficientBalance(address,uint256,uint256) from OpenZeppelin 5
+        let custom = hex::decode("e450d38c000000000000000000000000000000000000000000000000000000000000002a").unwrap();
+        assert_eq!(decode_revert_data(&custom), "custom error 0xe450d38c");
+
+        assert_eq!(decode_revert_data(&[]), "no reason given");
+        assert_eq!(decode_revert_data(&[0x01, 0x02]), "malformed revert data 0x0102");
+        // A truncated Error(string) is reported by its selector
+        assert_eq!(decode_revert_data(&revert[..40]), "custom error 0x08c379a0");
+    }
+
+    #[test]
+    fn test_revert_reason() {
+        let data = Revert::from("Ownable: caller is not the owner").abi_encode();
+        assert_eq!(
+            revert_reason(&error_response("execution reverted", Some(data))).as_deref(),
+            Some("Ownable: caller is not the owner")
+        );
+        assert_eq!(
+            revert_reason(&error_response("execution reverted: Pausable: paused", None)).as_deref(),
+            Some("Pausable: paused")
+        );
+        assert_eq!(
+            revert_reason(&error_response("execution reverted", None)).as_deref(),
+            Some("no reason given")
+        );
+        assert_eq!(revert_reason(&error_response("insufficient funds for gas", None)), None);
+        let transport = RpcFailure::Transport {
+            message: "backend connection task has stopped".to_string(),
+            rate_limited: false,
+        };
+        assert_eq!(revert_reason(&transport), None);
+    }
+}
@@ -0,0 +1,110 @@
+//! L1 data fee lookups for rollups, via their fee-oracle precompiles
+
+use alloy::consensus::{SignableTransaction, TxEip1559};
+use alloy::primitives::{address, Address as AlloyAddress, Bytes, TxKind, U256};
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use crate::core::domain::errors::DomainError;
+
+/// OP Stack `GasPriceOracle` predeploy
+pub(crate) const GAS_PRICE_ORACLE_ADDRESS: AlloyAddress = address!("420000000000000000000000000000000000000F");
+/// Arbitrum `NodeInterface` (virtual contract, only answers `eth_call`)
+pub(crate) const NODE_INTERFACE_ADDRESS: AlloyAddress = address!("00000000000000000000000000000000000000C8");
+
+sol! {
+    function getL1Fee(bytes _data) external view returns (uint256);
+    function gasEstimateL1Component(address to, bool contractCreation, bytes data)
+        external
+        payable
+        returns (uint64 gasEstimateForL1, uint256 baseFee, uint256 l1BaseFeeEstimate);
+}
+
+/// Rollup families whose transactions also pay for L1 data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Rollup {
+    /// Optimism, Base and other OP Stack chains: L1 fee is charged on top of L2 gas
+    OpStack,
+    /// Arbitrum One/Nova: L1 fee is charged as extra L2 gas, already in `eth_estimateGas`
+    Arbitrum,
+}
+
+impl Rollup {
+    /// Rollup family of an EVM chain, or `None` for L1s and sidechains
+    pub(crate) fn for_chain_id(chain_id: u64) -> Option<Self> {
+        match chain_id {
+            // Optimism, OP Sepolia, Base, Base Sepolia
+            10 | 11155420 | 8453 | 84532 => Some(Rollup::OpStack),
+            // Arbitrum One, Arbitrum Nova, Arbitrum Sepolia
+            42161 | 42170 | 421614 => Some(Rollup::Arbitrum),
+            _ => None,
+        }
+    }
+}
+
+/// `getL1Fee` calldata for a transfer of `value` to `to` with `input`
+///
+/// The oracle prices the unsigned RLP encoding; nonce and fee fields are
+/// placeholders, which changes the size by at most a few bytes.
+pub(crate) fn encode_l1_fee_call(chain_id: u64, to: AlloyAddress, value: U256, input: Bytes, gas_limit: u64) -> Vec<u8> {
+    let tx = TxEip1559 {
+        chain_id,
+        gas_limit,
+        to: TxKind::Call(to),
+        value,
+        input,
+        ..Default::default()
+    };
+    getL1FeeCall { _data: tx.encoded_for_signing().into() }.abi_encode()
+}
+
+/// Decode the `getL1Fee` result (Wei)
+pub(crate) fn decode_l1_fee(output: &[u8]) -> Result<U256, DomainError> {
+    getL1FeeCall::abi_decode_returns(output, true)
+        .map(|decoded| decoded._0)
+        .map_err(|e| DomainError::BlockchainError(format!("Invalid getL1Fee response: {}", e)))
+}
+
+/// `gasEstimateL1Component` calldata for a call to `to` with `input`
+pub(crate) fn encode_l1_gas_call(to: AlloyAddress, input: Bytes) -> Vec<u8> {
+    gasEstimateL1ComponentCall {
+        to,
+        contractCreation: false,
+        data: input,
+    }
+    .abi_encode()
+}
+
+/// Decode the `gasEstimateL1Component` result: L2 gas spent on L1 data
+pub(crate) fn decode_l1_gas(output: &[u8]) -> Result<u64, DomainError> {
+    gasEstimateL1ComponentCall::abi_decode_returns(output, true)
+        .map(|decoded| decoded.gasEstimateForL1)
+        .map_err(|e| DomainError::BlockchainError(format!("Invalid gasEstimateL1Component response: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::sol_types::SolValue;
+
+    #[test]
+    fn test_rollup_for_chain_id() {
+        assert_eq!(Rollup::for_chain_id(10), Some(Rollup::OpStack));
+        assert_eq!(Rollup::for_chain_id(8453), Some(Rollup::OpStack));
+        assert_eq!(Rollup::for_chain_id(42161), Some(Rollup::Arbitrum));
+        assert_eq!(Rollup::for_chain_id(1), None);
+        assert_eq!(Rollup::for_chain_id(137), None);
+    }
+
+    #[test]
+    fn test_l1_fee_calls_round_trip() {
+        let to = AlloyAddress::repeat_byte(0x11);
+        let calldata = encode_l1_fee_call(10, to, U256::from(1u64), Bytes::new(), 21_000);
+        assert_eq!(&calldata[..4], &getL1FeeCall::SELECTOR);
+        assert_eq!(decode_l1_fee(&U256::from(7u64).abi_encode()).unwrap(), U256::from(7u64));
+
+        let calldata = encode_l1_gas_call(to, Bytes::new());
+        assert_eq!(&calldata[..4], &gasEstimateL1ComponentCall::SELECTOR);
+        let output = (100u64, U256::from(1u64), U256::from(2u64)).abi_encode_params();
+        assert_eq!(decode_l1_gas(&output).unwrap(), 100);
+    }
+}
@@ -0,0 +1,248 @@
+//! Gnosis Safe multisig proposals (`safe` feature)
+//!
+//! A proposal is a Safe transaction signed by one owner and handed to the
+//! Safe Transaction Service, where the other owners add their signatures
+//! until the threshold is met and someone executes it. Proposing never
+//! sends an Ethereum transaction, so it costs no gas.
+//!
+//! The Safe tx hash is the EIP-712 hash of the `SafeTx` struct under the
+//! Safe's own domain separator, read from the contract so every Safe
+//! version hashes the way it will verify.
+
+use alloy::primitives::{keccak256, Address as AlloyAddress, Bytes, B256, U256};
+use alloy::rpc::types::BlockId;
+use alloy::signers::SignerSync;
+use alloy::sol;
+use alloy::sol_types::{SolCall, SolStruct};
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
+use super::http::{build_http_client_with_timeout, error_chain, read_body_capped, DEFAULT_MAX_RESPONSE_SIZE};
+use super::AlloyBlockchainService;
+use crate::adapter::infrastructure::keys;
+use crate::core::domain::{
+    errors::{redact_payloads, DomainError},
+    value_objects::{Address, SecretKey},
+};
+
+/// Transaction Service requests give up after this long
+const SERVICE_TIMEOUT: Duration = Duration::from_secs(15);
+/// Longest service error body quoted in messages
+const MAX_ERROR_BODY_LEN: usize = 500;
+
+sol! {
+    function nonce() external view returns (uint256);
+    function domainSeparator() external view returns (bytes32);
+    function isOwner(address owner) external view returns (bool);
+
+    /// Field names and order define the type hash the Safe contract checks
+    struct SafeTx {
+        address to;
+        uint256 value;
+        bytes data;
+        uint8 operation;
+        uint256 safeTxGas;
+        uint256 baseGas;
+        uint256 gasPrice;
+        address gasToken;
+        address refundReceiver;
+        uint256 nonce;
+    }
+}
+
+/// EIP-712 hash identifying a Safe transaction (what the owners sign)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SafeTxHash(B256);
+
+impl SafeTxHash {
+    /// Raw 32-byte hash
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0 .0
+    }
+}
+
+impl fmt::Display for SafeTxHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#x}", self.0)
+    }
+}
+
+/// Safe Transaction Service for a chain, if Safe hosts one
+pub fn default_safe_service_url(chain_id: u64) -> Option<&'static str> {
+    Some(match chain_id {
+        1 => "https://safe-transaction-mainnet.safe.global",
+        10 => "https://safe-transaction-optimism.safe.global",
+        56 => "https://safe-transaction-bsc.safe.global",
+        100 => "https://safe-transaction-gnosis-chain.safe.global",
+        137 => "https://safe-transaction-polygon.safe.global",
+        8453 => "https://safe-transaction-base.safe.global",
+        42161 => "https://safe-transaction-arbitrum.safe.global",
+        84532 => "https://safe-transaction-base-sepolia.safe.global",
+        11155111 => "https://safe-transaction-sepolia.safe.global",
+        _ => return None,
+    })
+}
+
+impl AlloyBlockchainService {
+    /// Propose sending `value` Wei from `safe` to `to`, signed by one Safe owner
+    ///
+    /// Uses the Safe's current on-chain nonce, so a proposal already queued
+    /// at that nonce becomes an alternative to this one. The signer must be
+    /// an owner (`InvalidQuery` otherwise); the remaining owners confirm and
+    /// execute the proposal in their own Safe tooling.
+    pub async fn propose_safe_transaction(
+        &self,
+        safe: &Address,
+        to: &Address,
+        value: u128,
+        signer_key: &SecretKey,
+    ) -> Result<SafeTxHash, DomainError> {
+        let signer = keys::parse_evm_signer(signer_key.expose_secret())?;
+        let safe_address = parse_address(safe)?;
+        let to_address = parse_address(to)?;
+        let service_url = self.safe_service_url()?;
+
+        let is_owner = self.safe_call(safe, isOwnerCall { owner: signer.address() }).await?._0;
+        if !is_owner {
+            return Err(DomainError::InvalidQuery(format!(
+                "{} is not an owner of Safe {}",
+                signer.address().to_checksum(None),
+                safe
+            )));
+        }
+        let nonce = self.safe_call(safe, nonceCall {}).await?._0;
+        let domain_separator = self.safe_call(safe, domainSeparatorCall {}).await?._0;
+
+        let safe_tx = SafeTx {
+            to: to_address,
+            value: U256::from(value),
+            data: Bytes::new(),
+            operation: 0,
+            safeTxGas: U256::ZERO,
+            baseGas: U256::ZERO,
+            gasPrice: U256::ZERO,
+            gasToken: AlloyAddress::ZERO,
+            refundReceiver: AlloyAddress::ZERO,
+            nonce,
+        };
+        let safe_tx_hash = safe_tx_hash(domain_separator, &safe_tx);
+        let signature = signer
+            .sign_hash_sync(&safe_tx_hash.0)
+            .map_err(|e| DomainError::TransferFailed(format!("Failed to sign Safe transaction: {}", e)))?;
+
+        let body = serde_json::json!({
+            "safe": safe_address.to_checksum(None),
+            "to": to_address.to_checksum(None),
+            "value": value.to_string(),
+            "data": null,
+            "operation": 0,
+            "safeTxGas": "0",
+            "baseGas": "0",
+            "gasPrice": "0",
+            "gasToken": AlloyAddress::ZERO.to_checksum(None),
+            "refundReceiver": AlloyAddress::ZERO.to_checksum(None),
+            "nonce": nonce.to_string(),
+            "contractTransactionHash": safe_tx_hash.to_string(),
+            "sender": signer.address().to_checksum(None),
+            "signature": format!("0x{}", alloy::hex::encode(signature.as_bytes())),
+            "origin": "rustwallet",
+        });
+        let endpoint = format!(
+            "{}/api/v1/safes/{}/multisig-transactions/",
+            service_url.trim_end_matches('/'),
+            safe_address.to_checksum(None)
+        );
+
+        let http = build_http_client_with_timeout(&HashMap::new(), Some(SERVICE_TIMEOUT))?;
+        let response = http.post(&endpoint).json(&body).send().await.map_err(|e| {
+            DomainError::NetworkError(format!("Safe Transaction Service request failed: {}", error_chain(&e)))
+        })?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = read_body_capped(response, DEFAULT_MAX_RESPONSE_SIZE).await.unwrap_or_default();
+            let body: String = String::from_utf8_lossy(&body).chars().take(MAX_ERROR_BODY_LEN).collect();
+            let message = format!("Safe Transaction Service returned HTTP {}: {}", status, redact_payloads(&body));
+            return Err(if status.is_server_error() {
+                DomainError::NetworkError(message)
+            } else {
+                DomainError::TransferFailed(message)
+            });
+        }
+        Ok(safe_tx_hash)
+    }
+
+    /// Configured Transaction Service, or Safe's hosted one for this chain
+    fn safe_service_url(&self) -> Result<String, DomainError> {
+        match self.configured_safe_service_url() {
+            Some(url) => Ok(url.to_string()),
+            None => default_safe_service_url(self.network().chain_id())
+                .map(str::to_string)
+                .ok_or_else(|| {
+                    DomainError::ConfigurationError(format!(
+                        "No Safe Transaction Service known for {}; set one with `safe_service_url`",
+                        self.network().name()
+                    ))
+                }),
+        }
+    }
+
+    /// Call a view function on `safe`, reporting a non-Safe address clearly
+    async fn safe_call<C: SolCall>(&self, safe: &Address, call: C) -> Result<C::Return, DomainError> {
+        let output = self.call(safe, call.abi_encode(), BlockId::latest()).await?;
+        C::abi_decode_returns(&output, true)
+            .map_err(|_| DomainError::InvalidQuery(format!("{} is not a Safe contract", safe)))
+    }
+}
+
+/// `keccak256(0x19 ‖ 0x01 ‖ domainSeparator ‖ hashStruct(safeTx))`
+fn safe_tx_hash(domain_separator: B256, safe_tx: &SafeTx) -> SafeTxHash {
+    SafeTxHash(keccak256(
+        [&[0x19, 0x01], domain_separator.as_slice(), safe_tx.eip712_hash_struct().as_slice()].concat(),
+    ))
+}
+
+fn parse_address(address: &Address) -> Result<AlloyAddress, DomainError> {
+    address
+        .as_str()
+        .parse()
+        .map_err(|e| DomainError::BlockchainError(format!("Invalid address: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{address, b256};
+
+    #[test]
+    fn test_safe_tx_type_hash_matches_contract() {
+        // SAFE_TX_TYPEHASH in Safe.sol (v1.0.0 onwards)
+        assert_eq!(
+            keccak256(SafeTx::eip712_encode_type().as_bytes()),
+            b256!("bb8310d486368db6bd6f849402fdd73ad53d316b5a4b2644ad6efe0f941286d8")
+        );
+    }
+
+    #[test]
+    fn test_safe_tx_hash_is_deterministic_and_nonce_bound() {
+        let domain_separator = B256::repeat_byte(0x11);
+        let mut safe_tx = SafeTx {
+            to: address!("8894E0a0c962CB723c1976a4421c95949bE2D4E3"),
+            value: U256::from(1_000u64),
+            data: Bytes::new(),
+            operation: 0,
+            safeTxGas: U256::ZERO,
+            baseGas: U256::ZERO,
+            gasPrice: U256::ZERO,
+            gasToken: AlloyAddress::ZERO,
+            refundReceiver: AlloyAddress::ZERO,
+            nonce: U256::from(7u64),
+        };
+        let first = safe_tx_hash(domain_separator, &safe_tx);
+        assert_eq!(first, safe_tx_hash(domain_separator, &safe_tx));
+        assert_eq!(first.to_string().len(), 66);
+
+        safe_tx.nonce = U256::from(8u64);
+        assert_ne!(first, safe_tx_hash(domain_separator, &safe_tx));
+        assert_ne!(first, safe_tx_hash(B256::repeat_byte(0x22), &safe_tx));
+    }
+}
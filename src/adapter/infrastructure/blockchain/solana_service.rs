@@ -1,17 +1,78 @@
 use async_trait::async_trait;
+use base64::Engine;
+use futures::stream::{self, StreamExt};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use super::http::{
+    build_pooled_http_client, error_chain, http_status_error, read_body_capped, DEFAULT_MAX_RESPONSE_SIZE,
+};
+use super::solana_tx::{self, AddressLookupTable, Pubkey};
+use crate::adapter::infrastructure::{
+    keys::{parse_solana_signing_key, PrivateKeySigner},
+    resilience::{retry_with_backoff, RetryPolicy},
+};
 use crate::core::domain::{
-    errors::DomainError,
-    services::BlockchainService,
-    value_objects::{Address, Balance, Network, TransactionHash},
+    errors::{redact_payloads, DomainError},
+    services::{BlockchainService, Signer},
+    value_objects::{
+        AccountInfo, Address, Balance, ChainType, ConnectionDiagnostics, FeeDetails, FeeEstimate, Finality,
+        Network, SecretKey, TokenHolding, TransactionHash, TxStatus,
+    },
 };
 
+/// Requests in flight at once when falling back from a batch to per-address
+/// calls, and idle connections kept open for them
+const DEFAULT_MAX_CONNECTIONS: usize = 8;
+
+/// Base fee charged per transaction signature, in lamports
+const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+
+/// Target slot time of the Solana leader schedule
+const SLOT_TIME: Duration = Duration::from_millis(400);
+
+/// Wire format of transactions sent by [`SolanaBlockchainService`]
+///
+/// The default public endpoints (`api.mainnet-beta.solana.com`,
+/// `api.devnet.solana.com`, `api.testnet.solana.com`) accept both formats in
+/// `sendTransaction`. Legacy is the default because older self-hosted nodes,
+/// hardware-wallet flows and some indexers still only understand legacy
+/// transactions; use `V0` when a program or provider requires it, or to fit
+/// more accounts into one transaction via address lookup tables.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum SolanaTxVersion {
+    /// Original message format: every account is listed in full
+    #[default]
+    Legacy,
+    /// Versioned message (v0); accounts found in `lookup_tables` are
+    /// referenced by index instead of by address
+    V0 {
+        /// Active address lookup table accounts to draw accounts from
+        lookup_tables: Vec<Address>,
+    },
+}
+
 /// Solana blockchain service using JSON-RPC API
 pub struct SolanaBlockchainService {
     client: Client,
     network: Network,
     rpc_url: String,
+    /// Custom headers, kept so `with_endpoint` siblings send them too
+    headers: HashMap<String, String>,
+    /// Format of transactions built by `transfer_token`
+    tx_version: SolanaTxVersion,
+    /// Commitment for `get_balance`; `None` leaves it to the node (finalized)
+    commitment: Option<Finality>,
+    /// Concurrency limit and connection pool size
+    max_connections: usize,
+    /// Per-request timeout; `None` waits as long as the connection stays open
+    timeout: Option<Duration>,
+    /// Retries of requests that fail with a network error or rate limit
+    retry_policy: RetryPolicy,
+    /// Responses with larger bodies are rejected unread
+    max_response_size: usize,
 }
 
 #[derive(Serialize)]
@@ -24,10 +85,66 @@ struct JsonRpcRequest {
 
 #[derive(Deserialize, Debug)]
 struct JsonRpcResponse<T> {
+    /// Only needed to match up batch responses
+    id: Option<u64>,
     result: Option<T>,
     error: Option<JsonRpcError>,
 }
 
+impl<T> JsonRpcResponse<T> {
+    fn into_result(self) -> Result<T, DomainError> {
+        if let Some(error) = self.error {
+            return Err(DomainError::BlockchainError(format!(
+                "RPC error: {}",
+                redact_payloads(&error.message)
+            )));
+        }
+        self.result
+            .ok_or_else(|| DomainError::BlockchainError("No result in RPC response".to_string()))
+    }
+}
+
+/// Result wrapper used by context-bearing RPC methods (e.g. getBalance)
+#[derive(Deserialize, Debug)]
+struct RpcContextResponse<T> {
+    value: T,
+}
+
+/// `getAccountInfo` value; `data` is left untyped because its shape depends on the encoding
+#[derive(Deserialize, Debug)]
+struct RpcAccountInfo {
+    owner: String,
+    data: serde_json::Value,
+    #[serde(default)]
+    lamports: u64,
+    #[serde(default)]
+    executable: bool,
+    /// Full data length, even under a `dataSlice` (reported by newer nodes only)
+    #[serde(default)]
+    space: Option<u64>,
+}
+
+/// `getTokenAccountsByOwner` entry (its `pubkey` is not needed)
+#[derive(Deserialize, Debug)]
+struct RpcKeyedAccount {
+    account: RpcAccountInfo,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct LatestBlockhash {
+    blockhash: String,
+}
+
+/// `getSignatureStatuses` entry (`null` for signatures the node does not know)
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct SignatureStatus {
+    slot: u64,
+    err: Option<serde_json::Value>,
+    confirmation_status: Option<String>,
+}
+
 #[derive(Deserialize, Debug)]
 struct JsonRpcError {
     message: String,
@@ -36,26 +153,132 @@ struct JsonRpcError {
 impl SolanaBlockchainService {
     /// Create new Solana blockchain service
     pub async fn new(network: Network) -> Result<Self, DomainError> {
+        Self::new_with_headers(network, HashMap::new()).await
+    }
+
+    /// Create service that sends custom headers (e.g. `Authorization`, `x-api-key`)
+    /// with every JSON-RPC request
+    pub async fn new_with_headers(
+        network: Network,
+        headers: HashMap<String, String>,
+    ) -> Result<Self, DomainError> {
+        let rpc_url = network.default_rpc_url().to_string();
+        Self::new_with_rpc_url(network, &rpc_url, headers).await
+    }
+
+    /// Create service against a custom JSON-RPC endpoint (e.g. a paid provider
+    /// instead of the rate-limited public RPC)
+    pub async fn new_with_rpc(network: Network, rpc_url: &str) -> Result<Self, DomainError> {
+        Self::new_with_rpc_url(network, rpc_url, HashMap::new()).await
+    }
+
+    /// Create service against an explicit JSON-RPC endpoint
+    pub async fn new_with_rpc_url(
+        network: Network,
+        rpc_url: &str,
+        headers: HashMap<String, String>,
+    ) -> Result<Self, DomainError> {
         if !network.is_solana() {
             return Err(DomainError::ConfigurationError(
                 "Network must be a Solana network".to_string(),
             ));
         }
 
-        let rpc_url = network.default_rpc_url().to_string();
-
         Ok(Self {
-            client: Client::new(),
+            client: build_pooled_http_client(&headers, DEFAULT_MAX_CONNECTIONS, None)?,
             network,
-            rpc_url,
+            rpc_url: rpc_url.to_string(),
+            headers,
+            tx_version: SolanaTxVersion::default(),
+            commitment: None,
+            max_connections: DEFAULT_MAX_CONNECTIONS,
+            timeout: None,
+            retry_policy: RetryPolicy::none(),
+            max_response_size: DEFAULT_MAX_RESPONSE_SIZE,
         })
     }
 
+    /// Send at most `max_connections` requests at once and keep that many
+    /// connections open for reuse (default 8); 0 disables connection reuse
+    /// but still sends one request at a time
+    pub fn with_max_connections(mut self, max_connections: usize) -> Result<Self, DomainError> {
+        self.client = build_pooled_http_client(&self.headers, max_connections, self.timeout)?;
+        self.max_connections = max_connections;
+        Ok(self)
+    }
+
+    /// Fail requests that take longer than `timeout` with a `NetworkError`
+    pub fn with_timeout(mut self, timeout: Duration) -> Result<Self, DomainError> {
+        self.client = build_pooled_http_client(&self.headers, self.max_connections, Some(timeout))?;
+        self.timeout = Some(timeout);
+        Ok(self)
+    }
+
+    /// Retry requests that fail with a network error or rate limit (default: no retries)
+    ///
+    /// `sendTransaction` is retried too: the same signed transaction has the
+    /// same signature, so a resend that reaches the node twice lands once.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Reject response bodies larger than `max_response_size` bytes (default 4 MiB)
+    pub fn with_max_response_size(mut self, max_response_size: usize) -> Self {
+        self.max_response_size = max_response_size;
+        self
+    }
+
+    /// Serialize transfers in the given format (legacy by default)
+    pub fn with_tx_version(mut self, tx_version: SolanaTxVersion) -> Self {
+        self.tx_version = tx_version;
+        self
+    }
+
+    /// Read balances at the given commitment (`Latest` = processed,
+    /// `Safe` = confirmed, `Finalized` = finalized)
+    pub fn with_commitment(mut self, commitment: Finality) -> Self {
+        self.commitment = Some(commitment);
+        self
+    }
+
     /// Get the network this service is connected to
     pub fn network(&self) -> &Network {
         &self.network
     }
 
+    /// Sign `message` with an ed25519 key, returning the base58 signature
+    ///
+    /// For off-chain proofs of ownership ("Sign in with Solana"); nothing is
+    /// sent to the network. The bytes are signed as given, without a prefix,
+    /// like wallets' `signMessage`.
+    pub fn sign_message(&self, message: &[u8], private_key: &SecretKey) -> Result<String, DomainError> {
+        let signing_key = parse_solana_signing_key(private_key.expose_secret())?;
+        Ok(bs58::encode(ed25519_dalek::Signer::sign(&signing_key, message).to_bytes()).into_string())
+    }
+
+    /// Check a base58 ed25519 `signature` of `message` by the key `pubkey`
+    ///
+    /// `Ok(false)` for a well-formed signature that does not match; malformed
+    /// signatures and addresses that are not ed25519 public keys are errors.
+    pub fn verify_message(&self, message: &[u8], signature: &str, pubkey: &Address) -> Result<bool, DomainError> {
+        let signature: [u8; 64] = bs58::decode(signature.trim())
+            .into_vec()
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| DomainError::InvalidQuery("Signature must be 64 bytes of base58".to_string()))?;
+        let verifying_key = solana_tx::decode_pubkey(pubkey.as_str())
+            .ok()
+            .and_then(|key| ed25519_dalek::VerifyingKey::from_bytes(&key).ok())
+            .ok_or_else(|| DomainError::InvalidAddress {
+                chain: ChainType::Solana,
+                reason: "not an ed25519 public key".to_string(),
+            })?;
+        Ok(verifying_key
+            .verify_strict(message, &ed25519_dalek::Signature::from_bytes(&signature))
+            .is_ok())
+    }
+
     /// Make a JSON-RPC call
     async fn rpc_call<T>(&self, method: &str, params: Vec<serde_json::Value>) -> Result<T, DomainError>
     where
@@ -68,46 +291,372 @@ impl SolanaBlockchainService {
             params,
         };
 
-        let response = self
-            .client
-            .post(&self.rpc_url)
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| DomainError::NetworkError(format!("Failed to send RPC request: {}", e)))?;
+        let rpc_response: JsonRpcResponse<T> = self.post_json(&request).await?;
+        rpc_response.into_result()
+    }
+
+    /// Send several calls of `method` as one JSON-RPC batch
+    ///
+    /// Results are returned in the order of `params`, with errors kept per call.
+    async fn rpc_batch<T>(
+        &self,
+        method: &str,
+        params: Vec<Vec<serde_json::Value>>,
+    ) -> Result<Vec<Result<T, DomainError>>, DomainError>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let requests: Vec<JsonRpcRequest> = params
+            .into_iter()
+            .enumerate()
+            .map(|(id, params)| JsonRpcRequest {
+                jsonrpc: "2.0".to_string(),
+                id: id as u64,
+                method: method.to_string(),
+                params,
+            })
+            .collect();
+
+        // Responses may come back in any order; match them up by id
+        let responses: Vec<JsonRpcResponse<T>> = self.post_json(&requests).await?;
+        let mut results: Vec<Option<Result<T, DomainError>>> = requests.iter().map(|_| None).collect();
+        for response in responses {
+            if let Some(slot) = response.id.and_then(|id| results.get_mut(id as usize)) {
+                *slot = Some(response.into_result());
+            }
+        }
+        Ok(results
+            .into_iter()
+            .map(|result| {
+                result.unwrap_or_else(|| {
+                    Err(DomainError::BlockchainError("No response for call in RPC batch".to_string()))
+                })
+            })
+            .collect())
+    }
+
+    /// POST a JSON-RPC request (or batch) and parse the response body,
+    /// retrying under the service's retry policy
+    async fn post_json<R, T>(&self, request: &R) -> Result<T, DomainError>
+    where
+        R: Serialize + ?Sized,
+        T: for<'de> Deserialize<'de>,
+    {
+        retry_with_backoff(&self.retry_policy, DomainError::is_retryable, || async {
+            let response = self
+                .client
+                .post(&self.rpc_url)
+                .json(request)
+                .send()
+                .await
+                .map_err(|e| DomainError::NetworkError(format!("Failed to send RPC request: {}", error_chain(&e))))?;
 
-        let rpc_response: JsonRpcResponse<T> = response
-            .json()
+            let status = response.status();
+            let body = read_body_capped(response, self.max_response_size).await?;
+
+            // Some providers return JSON-RPC errors with a non-2xx status; only fall
+            // back to the HTTP status when the body is not JSON-RPC at all
+            serde_json::from_slice(&body).map_err(|e| {
+                if status.is_success() {
+                    DomainError::NetworkError(format!("Failed to parse RPC response: {}", e))
+                } else {
+                    http_status_error("RPC request failed", status)
+                }
+            })
+        })
+        .await
+    }
+
+    /// Query getBalance, optionally at an explicit commitment level
+    async fn query_balance(&self, address: &Address, commitment: Option<&str>) -> Result<Balance, DomainError> {
+        // getBalance returns an RpcResponse: {"context": {...}, "value": <lamports>}
+        let response: RpcContextResponse<u64> =
+            self.rpc_call("getBalance", balance_params(address, commitment)).await?;
+        let balance_lamports = response.value;
+
+        // Convert lamports to Wei format for consistency
+        // 1 SOL = 10^9 lamports
+        Ok(Balance::from_wei(balance_lamports as u128))
+    }
+
+    /// Decimals of an SPL token mint
+    pub async fn token_decimals(&self, mint: &Address) -> Result<u8, DomainError> {
+        self.mint_info(mint).await.map(|(decimals, _)| decimals)
+    }
+
+    /// All non-zero SPL token balances held by `owner`, one per token account
+    ///
+    /// Queries `getTokenAccountsByOwner` for both the Token and Token-2022
+    /// programs in one JSON-RPC batch. An owner with several accounts for the
+    /// same mint gets one entry per account.
+    pub async fn list_token_accounts(&self, owner: &Address) -> Result<Vec<TokenHolding>, DomainError> {
+        let mut config = serde_json::json!({ "encoding": "jsonParsed" });
+        if let Some(finality) = self.commitment {
+            config["commitment"] = serde_json::json!(commitment_level(finality));
+        }
+        let params = [solana_tx::TOKEN_PROGRAM_ID, solana_tx::TOKEN_2022_PROGRAM_ID]
+            .into_iter()
+            .map(|program| {
+                vec![
+                    serde_json::json!(owner.as_str()),
+                    serde_json::json!({ "programId": program }),
+                    config.clone(),
+                ]
+            })
+            .collect();
+
+        let mut holdings = Vec::new();
+        for response in self
+            .rpc_batch::<RpcContextResponse<Vec<RpcKeyedAccount>>>("getTokenAccountsByOwner", params)
+            .await?
+        {
+            for keyed in response?.value {
+                if let Some(holding) = token_holding(&keyed.account)? {
+                    holdings.push(holding);
+                }
+            }
+        }
+        Ok(holdings)
+    }
+
+    /// Send `amount` base units of the SPL token `mint` from `from` to `to`
+    ///
+    /// Tokens move between the owners' associated token accounts. If the
+    /// recipient's account does not exist yet, an idempotent create-ATA
+    /// instruction (paid by the sender) is prepended. Works for both the Token
+    /// and Token-2022 programs. The transaction is serialized according to the
+    /// service's [`SolanaTxVersion`]. Returns the transaction signature.
+    pub async fn transfer_token(
+        &self,
+        mint: &Address,
+        from: &Address,
+        to: &Address,
+        amount: u64,
+        private_key: &SecretKey,
+    ) -> Result<TransactionHash, DomainError> {
+        if amount == 0 {
+            return Err(DomainError::InvalidAmount);
+        }
+
+        let signer = parse_solana_signing_key(private_key.expose_secret())?;
+        let owner = solana_tx::address_pubkey(from)?;
+        if signer.verifying_key().to_bytes() != owner {
+            return Err(DomainError::InvalidPrivateKey(
+                "key does not control the sender address".to_string(),
+            ));
+        }
+        let recipient = solana_tx::address_pubkey(to)?;
+        let mint_key = solana_tx::address_pubkey(mint)?;
+
+        let (decimals, token_program) = self.mint_info(mint).await?;
+        let source = solana_tx::associated_token_address(&owner, &mint_key, &token_program)?;
+        let destination = solana_tx::associated_token_address(&recipient, &mint_key, &token_program)?;
+
+        let available = self.token_account_balance(&source).await?.ok_or_else(|| {
+            DomainError::TransferFailed(format!("{} has no token account for mint {}", from, mint))
+        })?;
+        if available < amount {
+            return Err(DomainError::InsufficientBalance {
+                required: amount as u128,
+                available: available as u128,
+            });
+        }
+
+        let mut instructions = Vec::with_capacity(2);
+        if !self.account_exists(&destination).await? {
+            tracing::debug!("Creating associated token account for {} (mint {})", to, mint);
+            instructions.push(solana_tx::create_associated_token_account_idempotent(
+                &owner,
+                &recipient,
+                &mint_key,
+                &token_program,
+            )?);
+        }
+        instructions.push(solana_tx::transfer_checked(
+            &source,
+            &mint_key,
+            &destination,
+            &owner,
+            amount,
+            decimals,
+            &token_program,
+        ));
+
+        let message = match &self.tx_version {
+            SolanaTxVersion::Legacy => {
+                let blockhash = self.latest_blockhash().await?;
+                solana_tx::compile_message(&owner, &instructions, &blockhash)
+            }
+            SolanaTxVersion::V0 { lookup_tables } => {
+                let mut tables = Vec::with_capacity(lookup_tables.len());
+                for table in lookup_tables {
+                    tables.push(self.lookup_table(table).await?);
+                }
+                let blockhash = self.latest_blockhash().await?;
+                solana_tx::compile_v0_message(&owner, &instructions, &blockhash, &tables)
+            }
+        };
+        let (signature, transaction) = solana_tx::sign_transaction(&message, &signer);
+
+        let encoded = base64::engine::general_purpose::STANDARD.encode(transaction);
+        let returned: String = self
+            .rpc_call(
+                "sendTransaction",
+                vec![serde_json::json!(encoded), serde_json::json!({ "encoding": "base64" })],
+            )
             .await
-            .map_err(|e| DomainError::NetworkError(format!("Failed to parse RPC response: {}", e)))?;
+            .map_err(|e| DomainError::TransferFailed(e.to_string()))?;
+        if returned != signature {
+            tracing::warn!("RPC returned signature {} for transaction {}", returned, signature);
+        }
 
-        if let Some(error) = rpc_response.error {
-            return Err(DomainError::BlockchainError(format!(
-                "RPC error: {}",
-                error.message
-            )));
+        // Solana transaction ids are base58 signatures, not 0x-prefixed hashes
+        Ok(TransactionHash::new_unchecked(signature))
+    }
+
+    /// Decimals and owning token program (Token or Token-2022) of a mint
+    async fn mint_info(&self, mint: &Address) -> Result<(u8, Pubkey), DomainError> {
+        let info = self
+            .account_info(mint.as_str(), serde_json::json!({ "encoding": "jsonParsed" }))
+            .await?
+            .ok_or_else(|| DomainError::InvalidQuery(format!("Mint {} does not exist", mint)))?;
+
+        if info.owner != solana_tx::TOKEN_PROGRAM_ID && info.owner != solana_tx::TOKEN_2022_PROGRAM_ID {
+            return Err(DomainError::InvalidQuery(format!("{} is not an SPL token mint", mint)));
         }
+        let decimals = info.data["parsed"]["info"]["decimals"]
+            .as_u64()
+            .and_then(|d| u8::try_from(d).ok())
+            .ok_or_else(|| DomainError::InvalidQuery(format!("{} is not an SPL token mint", mint)))?;
 
-        rpc_response
-            .result
-            .ok_or_else(|| DomainError::BlockchainError("No result in RPC response".to_string()))
+        Ok((decimals, solana_tx::decode_pubkey(&info.owner)?))
+    }
+
+    /// Raw token amount held by a token account, or `None` if the account does not exist
+    async fn token_account_balance(&self, account: &Pubkey) -> Result<Option<u64>, DomainError> {
+        let Some(info) = self
+            .account_info(&bs58::encode(account).into_string(), serde_json::json!({ "encoding": "jsonParsed" }))
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        info.data["parsed"]["info"]["tokenAmount"]["amount"]
+            .as_str()
+            .and_then(|amount| amount.parse().ok())
+            .map(Some)
+            .ok_or_else(|| DomainError::BlockchainError("Unexpected token account data".to_string()))
+    }
+
+    async fn account_exists(&self, account: &Pubkey) -> Result<bool, DomainError> {
+        // Zero-length data slice: only existence matters
+        let config = serde_json::json!({ "encoding": "base64", "dataSlice": { "offset": 0, "length": 0 } });
+        let info = self.account_info(&bs58::encode(account).into_string(), config).await?;
+        Ok(info.is_some())
+    }
+
+    async fn account_info(
+        &self,
+        account: &str,
+        config: serde_json::Value,
+    ) -> Result<Option<RpcAccountInfo>, DomainError> {
+        let response: RpcContextResponse<Option<RpcAccountInfo>> = self
+            .rpc_call("getAccountInfo", vec![serde_json::json!(account), config])
+            .await?;
+        Ok(response.value)
+    }
+
+    /// Fetch and parse an address lookup table account
+    async fn lookup_table(&self, table: &Address) -> Result<AddressLookupTable, DomainError> {
+        let info = self
+            .account_info(table.as_str(), serde_json::json!({ "encoding": "base64" }))
+            .await?
+            .ok_or_else(|| {
+                DomainError::InvalidQuery(format!("Address lookup table {} does not exist", table))
+            })?;
+        if info.owner != solana_tx::ADDRESS_LOOKUP_TABLE_PROGRAM_ID {
+            return Err(DomainError::InvalidQuery(format!("{} is not an address lookup table", table)));
+        }
+
+        // base64 account data is returned as ["<data>", "base64"]
+        let data = info.data[0]
+            .as_str()
+            .and_then(|data| base64::engine::general_purpose::STANDARD.decode(data).ok())
+            .ok_or_else(|| DomainError::BlockchainError("Unexpected lookup table data".to_string()))?;
+        solana_tx::parse_lookup_table(solana_tx::address_pubkey(table)?, &data)
+    }
+
+    async fn latest_blockhash(&self) -> Result<Pubkey, DomainError> {
+        let response: RpcContextResponse<LatestBlockhash> =
+            self.rpc_call("getLatestBlockhash", vec![]).await?;
+        solana_tx::decode_pubkey(&response.value.blockhash)
     }
 }
 
 #[async_trait]
 impl BlockchainService for SolanaBlockchainService {
+    fn chain_type(&self) -> ChainType {
+        ChainType::Solana
+    }
+
     async fn get_balance(&self, address: &Address) -> Result<Balance, DomainError> {
-        // Call getBalance RPC method
-        // params: [address (base58 string), optional config object]
-        let params = vec![
-            serde_json::json!(address.as_str()),
-        ];
+        self.query_balance(address, self.commitment.map(commitment_level)).await
+    }
 
-        let balance_lamports: u64 = self.rpc_call("getBalance", params).await?;
+    /// One JSON-RPC batch of `getBalance` calls
+    ///
+    /// Falls back to one request per address if the endpoint rejects batches.
+    async fn get_balances(&self, addresses: &[Address]) -> Vec<Result<Balance, DomainError>> {
+        if addresses.is_empty() {
+            return Vec::new();
+        }
 
-        // Convert lamports to Wei format for consistency
-        // 1 SOL = 10^9 lamports
-        Ok(Balance::from_wei(balance_lamports as u128))
+        let commitment = self.commitment.map(commitment_level);
+        let params = addresses
+            .iter()
+            .map(|address| balance_params(address, commitment))
+            .collect();
+        match self.rpc_batch::<RpcContextResponse<u64>>("getBalance", params).await {
+            Ok(results) => results
+                .into_iter()
+                .map(|result| result.map(|response| Balance::from_wei(response.value as u128)))
+                .collect(),
+            Err(e) => {
+                tracing::warn!("getBalance batch failed ({}), querying addresses one by one", e);
+                let requests: Vec<_> = addresses.iter().map(|address| self.get_balance(address)).collect();
+                stream::iter(requests)
+                    .buffered(self.max_connections.max(1))
+                    .collect()
+                    .await
+            }
+        }
+    }
+
+    /// One `getAccountInfo` call: lamports, the executable (program) flag and data length
+    ///
+    /// Account data itself is not downloaded, so the data length is only
+    /// known from nodes that report `space`. Accounts that do not exist yet
+    /// have a zero balance and no data.
+    async fn get_account_info(&self, address: &Address) -> Result<AccountInfo, DomainError> {
+        let mut config = serde_json::json!({ "encoding": "base64", "dataSlice": { "offset": 0, "length": 0 } });
+        if let Some(finality) = self.commitment {
+            config["commitment"] = serde_json::json!(commitment_level(finality));
+        }
+        let Some(account) = self.account_info(address.as_str(), config).await? else {
+            return Ok(AccountInfo::new(address.clone(), Balance::zero()).with_code_size(0));
+        };
+        let info = AccountInfo::new(address.clone(), Balance::from_wei(account.lamports as u128));
+        let info = match account.space {
+            Some(space) => info.with_code_size(space),
+            None => info,
+        };
+        // Plain data accounts hold bytes too; only programs are executable
+        Ok(info.with_is_contract(account.executable))
+    }
+
+    /// Uses `processed` commitment, which includes transactions not yet voted on
+    async fn get_pending_balance(&self, address: &Address) -> Result<Balance, DomainError> {
+        self.query_balance(address, Some("processed")).await
     }
 
     async fn transfer(
@@ -115,13 +664,95 @@ impl BlockchainService for SolanaBlockchainService {
         _from: &Address,
         _to: &Address,
         _amount: u128,
-        _private_key: &str,
+        _signer: &dyn Signer,
     ) -> Result<TransactionHash, DomainError> {
         Err(DomainError::TransferFailed(
             "Solana transfers not yet implemented".to_string(),
         ))
     }
 
+    fn signer_for_key(&self, private_key: &SecretKey) -> Result<Box<dyn Signer>, DomainError> {
+        Ok(Box::new(PrivateKeySigner::new(private_key, &self.network)?))
+    }
+
+    /// A native SOL transfer carries a single signature (the sender's)
+    async fn estimate_fee(
+        &self,
+        _from: &Address,
+        _to: &Address,
+        _amount: u128,
+    ) -> Result<FeeEstimate, DomainError> {
+        FeeEstimate::from_details(FeeDetails::Solana {
+            signatures: 1,
+            lamports_per_signature: LAMPORTS_PER_SIGNATURE,
+        })
+        .ok_or_else(|| DomainError::BlockchainError("Fee estimate overflows u128".to_string()))
+    }
+
+    /// Near-constant: the base fee is flat, so the wait is the slots until
+    /// the configured commitment (processed: 1, confirmed: ~2, finalized: 32)
+    async fn estimate_confirmation_time(&self, fee: &FeeEstimate) -> Result<Duration, DomainError> {
+        if !matches!(fee.details, FeeDetails::Solana { .. }) {
+            return Err(DomainError::ConfigurationError(
+                "Not a Solana fee estimate".to_string(),
+            ));
+        }
+        let slots = match self.commitment.unwrap_or(Finality::Finalized) {
+            Finality::Latest => 1,
+            Finality::Safe => 2,
+            Finality::Finalized => 32,
+        };
+        Ok(SLOT_TIME * slots)
+    }
+
+    async fn with_endpoint(&self, url: &str) -> Result<Arc<dyn BlockchainService>, DomainError> {
+        let mut sibling = Self::new_with_rpc_url(self.network.clone(), url, self.headers.clone())
+            .await?
+            .with_tx_version(self.tx_version.clone())
+            .with_max_connections(self.max_connections)?;
+        sibling.commitment = self.commitment;
+        sibling.max_response_size = self.max_response_size;
+        sibling.retry_policy = self.retry_policy;
+        Ok(Arc::new(sibling))
+    }
+
+    /// Adds the `solana-core` version from `getVersion`
+    async fn diagnose(&self) -> ConnectionDiagnostics {
+        let diagnostics = ConnectionDiagnostics::measure(self.get_block_number()).await;
+        if !diagnostics.reachable {
+            return diagnostics;
+        }
+
+        #[derive(Deserialize)]
+        struct Version {
+            #[serde(rename = "solana-core")]
+            solana_core: String,
+        }
+        let version: Option<Version> = self.rpc_call("getVersion", vec![]).await.ok();
+        diagnostics.with_rpc_version(version.map(|v| format!("solana-core {}", v.solana_core)))
+    }
+
+    /// `processed` counts as pending, since the slot may still be skipped;
+    /// `confirmed` and `finalized` are terminal
+    async fn get_transaction_status(&self, tx_hash: &TransactionHash) -> Result<TxStatus, DomainError> {
+        let statuses: RpcContextResponse<Vec<Option<SignatureStatus>>> = self
+            .rpc_call(
+                "getSignatureStatuses",
+                vec![
+                    serde_json::json!([tx_hash.as_str()]),
+                    serde_json::json!({ "searchTransactionHistory": true }),
+                ],
+            )
+            .await?;
+
+        Ok(match statuses.value.into_iter().next().flatten() {
+            None => TxStatus::NotFound,
+            Some(status) if status.confirmation_status.as_deref() == Some("processed") => TxStatus::Pending,
+            Some(status) if status.err.is_some() => TxStatus::Reverted { block: Some(status.slot) },
+            Some(status) => TxStatus::Confirmed { block: Some(status.slot) },
+        })
+    }
+
     async fn is_connected(&self) -> bool {
         // Try to get health status
         let result: Result<String, DomainError> = self.rpc_call("getHealth", vec![]).await;
@@ -129,8 +760,54 @@ impl BlockchainService for SolanaBlockchainService {
     }
 
     async fn get_block_number(&self) -> Result<u64, DomainError> {
-        // Get current slot
-        self.rpc_call("getSlot", vec![]).await
+        self.get_height(Finality::Latest).await
+    }
+
+    /// Current slot at the matching commitment: processed, confirmed or finalized
+    async fn get_height(&self, finality: Finality) -> Result<u64, DomainError> {
+        let commitment = commitment_level(finality);
+        self.rpc_call("getSlot", vec![serde_json::json!({ "commitment": commitment })]).await
+    }
+}
+
+/// getBalance params: [address (base58 string), optional config object]
+fn balance_params(address: &Address, commitment: Option<&str>) -> Vec<serde_json::Value> {
+    let mut params = vec![serde_json::json!(address.as_str())];
+    if let Some(commitment) = commitment {
+        params.push(serde_json::json!({ "commitment": commitment }));
+    }
+    params
+}
+
+/// Parse a `jsonParsed` token account; `None` when it holds nothing
+fn token_holding(account: &RpcAccountInfo) -> Result<Option<TokenHolding>, DomainError> {
+    let info = &account.data["parsed"]["info"];
+    let token_amount = &info["tokenAmount"];
+    let (Some(mint), Some(amount), Some(decimals)) = (
+        info["mint"].as_str(),
+        token_amount["amount"].as_str().and_then(|amount| amount.parse::<u128>().ok()),
+        token_amount["decimals"].as_u64().and_then(|d| u8::try_from(d).ok()),
+    ) else {
+        return Err(DomainError::BlockchainError(
+            "Unexpected token account data in RPC response".to_string(),
+        ));
+    };
+    if amount == 0 {
+        return Ok(None);
+    }
+    Ok(Some(TokenHolding {
+        mint: Address::new(mint.to_string())?,
+        amount,
+        decimals,
+    }))
+}
+
+/// Solana commitment level matching a finality
+fn commitment_level(finality: Finality) -> &'static str {
+    match finality {
+        Finality::Latest => "processed",
+        Finality::Safe => "confirmed",
+        Finality::Finalized => "finalized",
     }
 }
 
@@ -138,6 +815,32 @@ impl BlockchainService for SolanaBlockchainService {
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_sign_and_verify_message() {
+        // RFC 8032 section 7.1, test 2
+        let seed = alloy::hex::decode("4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb").unwrap();
+        let public_key = alloy::hex::decode("3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c").unwrap();
+        let expected = alloy::hex::decode(
+            "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da\
+             085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+        )
+        .unwrap();
+        let message = [0x72];
+
+        let service = SolanaBlockchainService::new(Network::SolanaDevnet).await.unwrap();
+        let key = SecretKey::from(bs58::encode(&seed).into_string());
+        let pubkey = Address::new(bs58::encode(&public_key).into_string()).unwrap();
+
+        let signature = service.sign_message(&message, &key).unwrap();
+        assert_eq!(signature, bs58::encode(&expected).into_string());
+        assert!(service.verify_message(&message, &signature, &pubkey).unwrap());
+        assert!(!service.verify_message(b"other", &signature, &pubkey).unwrap());
+
+        assert!(service.verify_message(&message, "not base58!", &pubkey).is_err());
+        let other_key = Address::new(bs58::encode([0u8; 32]).into_string()).unwrap();
+        assert!(!service.verify_message(&message, &signature, &other_key).unwrap_or(false));
+    }
+
     #[tokio::test]
     #[ignore] // Requires network connection
     async fn test_solana_service_creation() {
@@ -0,0 +1,483 @@
+//! Minimal Solana transaction building (legacy and v0 message formats)
+//!
+//! Just enough of the wire format to send SPL token transfers without pulling
+//! in the Solana SDK: program-derived addresses, instruction compilation,
+//! message serialization (including address lookup tables) and signing.
+
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use ed25519_dalek::{Signer, SigningKey};
+use sha2::{Digest, Sha256};
+use crate::core::domain::{errors::DomainError, value_objects::Address};
+
+/// 32-byte Solana account address
+pub(crate) type Pubkey = [u8; 32];
+
+pub(crate) const SYSTEM_PROGRAM_ID: &str = "11111111111111111111111111111111";
+pub(crate) const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+pub(crate) const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+pub(crate) const ASSOCIATED_TOKEN_PROGRAM_ID: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+pub(crate) const ADDRESS_LOOKUP_TABLE_PROGRAM_ID: &str = "AddressLookupTab1e1111111111111111111111111";
+
+/// SPL Token `TransferChecked` instruction index
+const TOKEN_TRANSFER_CHECKED: u8 = 12;
+/// Associated Token Account program `CreateIdempotent` instruction index
+const ATA_CREATE_IDEMPOTENT: u8 = 1;
+/// First byte of a versioned message: high bit set, low bits = version 0
+const V0_MESSAGE_PREFIX: u8 = 0x80;
+/// Size of the lookup table account header preceding its addresses
+const LOOKUP_TABLE_META_SIZE: usize = 56;
+
+/// Account referenced by an instruction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AccountMeta {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl AccountMeta {
+    fn writable(pubkey: Pubkey, is_signer: bool) -> Self {
+        Self { pubkey, is_signer, is_writable: true }
+    }
+
+    fn readonly(pubkey: Pubkey, is_signer: bool) -> Self {
+        Self { pubkey, is_signer, is_writable: false }
+    }
+}
+
+/// Program invocation inside a transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Instruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<AccountMeta>,
+    pub data: Vec<u8>,
+}
+
+/// On-chain address lookup table: a list of addresses v0 transactions can
+/// reference by 1-byte index instead of embedding all 32 bytes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AddressLookupTable {
+    pub key: Pubkey,
+    pub addresses: Vec<Pubkey>,
+}
+
+/// Parse the raw data of a lookup table account
+///
+/// Layout: `u32` type (1 = lookup table), `u64` deactivation slot
+/// (`u64::MAX` while active), `u64` last extended slot, `u8` start index,
+/// optional 32-byte authority, 2 bytes padding, then the addresses.
+pub(crate) fn parse_lookup_table(key: Pubkey, data: &[u8]) -> Result<AddressLookupTable, DomainError> {
+    let invalid = || {
+        DomainError::InvalidQuery(format!(
+            "{} is not an address lookup table",
+            bs58::encode(key).into_string()
+        ))
+    };
+    if data.len() < LOOKUP_TABLE_META_SIZE || !(data.len() - LOOKUP_TABLE_META_SIZE).is_multiple_of(32) {
+        return Err(invalid());
+    }
+    let account_type = u32::from_le_bytes(data[0..4].try_into().expect("4 bytes"));
+    if account_type != 1 {
+        return Err(invalid());
+    }
+    let deactivation_slot = u64::from_le_bytes(data[4..12].try_into().expect("8 bytes"));
+    if deactivation_slot != u64::MAX {
+        return Err(DomainError::InvalidQuery(format!(
+            "Address lookup table {} is deactivated",
+            bs58::encode(key).into_string()
+        )));
+    }
+
+    let addresses = data[LOOKUP_TABLE_META_SIZE..]
+        .chunks_exact(32)
+        .map(|chunk| chunk.try_into().expect("32-byte chunk"))
+        .collect();
+    Ok(AddressLookupTable { key, addresses })
+}
+
+/// Decode a base58 address into its 32 raw bytes
+pub(crate) fn decode_pubkey(address: &str) -> Result<Pubkey, DomainError> {
+    bs58::decode(address)
+        .into_vec()
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| {
+            DomainError::InvalidQuery(format!("{} is not a 32-byte Solana address", address))
+        })
+}
+
+/// Derive a program-derived address: the first off-curve `sha256(seeds, bump, program, marker)`
+/// counting the bump seed down from 255
+pub(crate) fn find_program_address(seeds: &[&[u8]], program_id: &Pubkey) -> Option<(Pubkey, u8)> {
+    (0..=u8::MAX).rev().find_map(|bump| {
+        let mut hasher = Sha256::new();
+        for seed in seeds {
+            hasher.update(seed);
+        }
+        hasher.update([bump]);
+        hasher.update(program_id);
+        hasher.update(b"ProgramDerivedAddress");
+        let candidate: Pubkey = hasher.finalize().into();
+
+        // A PDA must not be a valid ed25519 public key, so nobody holds its private key
+        let on_curve = CompressedEdwardsY(candidate).decompress().is_some();
+        (!on_curve).then_some((candidate, bump))
+    })
+}
+
+/// Associated token account of `owner` for `mint`, under the given token program
+pub(crate) fn associated_token_address(
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Result<Pubkey, DomainError> {
+    let ata_program = decode_pubkey(ASSOCIATED_TOKEN_PROGRAM_ID)?;
+    find_program_address(&[owner, token_program, mint], &ata_program)
+        .map(|(address, _)| address)
+        .ok_or_else(|| DomainError::BlockchainError("No valid associated token address".to_string()))
+}
+
+/// Create `owner`'s associated token account for `mint`, paid by `payer`;
+/// a no-op if it already exists
+pub(crate) fn create_associated_token_account_idempotent(
+    payer: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Result<Instruction, DomainError> {
+    Ok(Instruction {
+        program_id: decode_pubkey(ASSOCIATED_TOKEN_PROGRAM_ID)?,
+        accounts: vec![
+            AccountMeta::writable(*payer, true),
+            AccountMeta::writable(associated_token_address(owner, mint, token_program)?, false),
+            AccountMeta::readonly(*owner, false),
+            AccountMeta::readonly(*mint, false),
+            AccountMeta::readonly(decode_pubkey(SYSTEM_PROGRAM_ID)?, false),
+            AccountMeta::readonly(*token_program, false),
+        ],
+        data: vec![ATA_CREATE_IDEMPOTENT],
+    })
+}
+
+/// SPL `TransferChecked`: move `amount` base units between token accounts,
+/// failing on-chain if `decimals` does not match the mint
+pub(crate) fn transfer_checked(
+    source: &Pubkey,
+    mint: &Pubkey,
+    destination: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+    decimals: u8,
+    token_program: &Pubkey,
+) -> Instruction {
+    let mut data = vec![TOKEN_TRANSFER_CHECKED];
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.push(decimals);
+
+    Instruction {
+        program_id: *token_program,
+        accounts: vec![
+            AccountMeta::writable(*source, false),
+            AccountMeta::readonly(*mint, false),
+            AccountMeta::writable(*destination, false),
+            AccountMeta::readonly(*owner, true),
+        ],
+        data,
+    }
+}
+
+/// Compile instructions into a serialized legacy message
+pub(crate) fn compile_message(payer: &Pubkey, instructions: &[Instruction], recent_blockhash: &Pubkey) -> Vec<u8> {
+    let accounts = collect_accounts(payer, instructions);
+    let mut message = Vec::new();
+    write_message_body(&mut message, &accounts, &[], recent_blockhash, instructions);
+    message
+}
+
+/// Compile instructions into a serialized v0 message
+///
+/// Non-signer accounts that are not invoked programs and appear in one of
+/// `lookup_tables` (first match wins) are referenced through the table
+/// instead of the static key list. Loaded accounts are indexed after the
+/// static keys: writable ones from all tables first, then read-only ones.
+pub(crate) fn compile_v0_message(
+    payer: &Pubkey,
+    instructions: &[Instruction],
+    recent_blockhash: &Pubkey,
+    lookup_tables: &[AddressLookupTable],
+) -> Vec<u8> {
+    let mut accounts = collect_accounts(payer, instructions);
+    let program_ids: Vec<Pubkey> = instructions.iter().map(|i| i.program_id).collect();
+
+    let mut lookups = Vec::new();
+    let mut loaded_writable = Vec::new();
+    let mut loaded_readonly = Vec::new();
+    for table in lookup_tables {
+        let mut writable_indexes = Vec::new();
+        let mut readonly_indexes = Vec::new();
+        accounts.retain(|account| {
+            if account.is_signer || program_ids.contains(&account.pubkey) {
+                return true;
+            }
+            let Some(index) = table
+                .addresses
+                .iter()
+                .position(|address| address == &account.pubkey)
+                .and_then(|index| u8::try_from(index).ok())
+            else {
+                return true;
+            };
+            if account.is_writable {
+                writable_indexes.push(index);
+                loaded_writable.push(account.pubkey);
+            } else {
+                readonly_indexes.push(index);
+                loaded_readonly.push(account.pubkey);
+            }
+            false
+        });
+        if !writable_indexes.is_empty() || !readonly_indexes.is_empty() {
+            lookups.push((table.key, writable_indexes, readonly_indexes));
+        }
+    }
+    loaded_writable.extend(loaded_readonly);
+
+    let mut message = vec![V0_MESSAGE_PREFIX];
+    write_message_body(&mut message, &accounts, &loaded_writable, recent_blockhash, instructions);
+    write_compact_u16(&mut message, lookups.len());
+    for (key, writable_indexes, readonly_indexes) in lookups {
+        message.extend_from_slice(&key);
+        write_compact_u16(&mut message, writable_indexes.len());
+        message.extend(writable_indexes);
+        write_compact_u16(&mut message, readonly_indexes.len());
+        message.extend(readonly_indexes);
+    }
+    message
+}
+
+/// Every account referenced by `instructions`, deduplicated (merging
+/// signer/writable flags) and ordered as the runtime expects: fee payer first,
+/// then writable signers, read-only signers, writable non-signers and
+/// read-only non-signers
+fn collect_accounts(payer: &Pubkey, instructions: &[Instruction]) -> Vec<AccountMeta> {
+    let mut accounts: Vec<AccountMeta> = vec![AccountMeta::writable(*payer, true)];
+    let mut add = |meta: AccountMeta| match accounts.iter_mut().find(|a| a.pubkey == meta.pubkey) {
+        Some(existing) => {
+            existing.is_signer |= meta.is_signer;
+            existing.is_writable |= meta.is_writable;
+        }
+        None => accounts.push(meta),
+    };
+    for instruction in instructions {
+        for meta in &instruction.accounts {
+            add(meta.clone());
+        }
+        add(AccountMeta::readonly(instruction.program_id, false));
+    }
+    // Stable sort keeps the payer first among writable signers
+    accounts.sort_by_key(|a| (!a.is_signer, !a.is_writable));
+    accounts
+}
+
+/// Header, static keys, blockhash and instructions (shared by both formats)
+///
+/// Instruction account indexes refer to `static_accounts` followed by `loaded`.
+fn write_message_body(
+    message: &mut Vec<u8>,
+    static_accounts: &[AccountMeta],
+    loaded: &[Pubkey],
+    recent_blockhash: &Pubkey,
+    instructions: &[Instruction],
+) {
+    let index_of = |pubkey: &Pubkey| {
+        static_accounts
+            .iter()
+            .map(|a| &a.pubkey)
+            .chain(loaded)
+            .position(|key| key == pubkey)
+            .expect("account was collected") as u8
+    };
+
+    let num_signers = static_accounts.iter().filter(|a| a.is_signer).count();
+    let num_readonly_signed = static_accounts.iter().filter(|a| a.is_signer && !a.is_writable).count();
+    let num_readonly_unsigned = static_accounts.iter().filter(|a| !a.is_signer && !a.is_writable).count();
+
+    message.extend([num_signers as u8, num_readonly_signed as u8, num_readonly_unsigned as u8]);
+    write_compact_u16(message, static_accounts.len());
+    for account in static_accounts {
+        message.extend_from_slice(&account.pubkey);
+    }
+    message.extend_from_slice(recent_blockhash);
+
+    write_compact_u16(message, instructions.len());
+    for instruction in instructions {
+        message.push(index_of(&instruction.program_id));
+        write_compact_u16(message, instruction.accounts.len());
+        message.extend(instruction.accounts.iter().map(|a| index_of(&a.pubkey)));
+        write_compact_u16(message, instruction.data.len());
+        message.extend_from_slice(&instruction.data);
+    }
+}
+
+/// Sign a single-signer message, returning the base58 signature (the transaction id)
+/// and the serialized transaction
+pub(crate) fn sign_transaction(message: &[u8], signer: &SigningKey) -> (String, Vec<u8>) {
+    let signature = signer.sign(message).to_bytes();
+    let mut transaction = Vec::with_capacity(1 + signature.len() + message.len());
+    write_compact_u16(&mut transaction, 1);
+    transaction.extend_from_slice(&signature);
+    transaction.extend_from_slice(message);
+    (bs58::encode(signature).into_string(), transaction)
+}
+
+/// Raw bytes of a domain address
+pub(crate) fn address_pubkey(address: &Address) -> Result<Pubkey, DomainError> {
+    decode_pubkey(address.as_str())
+}
+
+/// Solana "shortvec" length prefix: 7 bits per byte, high bit = continuation
+fn write_compact_u16(buffer: &mut Vec<u8>, len: usize) {
+    let mut remaining = len as u16;
+    loop {
+        let byte = (remaining & 0x7f) as u8;
+        remaining >>= 7;
+        if remaining == 0 {
+            buffer.push(byte);
+            return;
+        }
+        buffer.push(byte | 0x80);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compact_u16_encoding() {
+        for (len, expected) in [(0, vec![0x00]), (127, vec![0x7f]), (128, vec![0x80, 0x01]), (16_384, vec![0x80, 0x80, 0x01])] {
+            let mut buffer = Vec::new();
+            write_compact_u16(&mut buffer, len);
+            assert_eq!(buffer, expected, "len {}", len);
+        }
+    }
+
+    #[test]
+    fn test_associated_token_address_is_off_curve_and_deterministic() {
+        let owner = [7u8; 32];
+        let mint = decode_pubkey("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v").unwrap();
+        let token_program = decode_pubkey(TOKEN_PROGRAM_ID).unwrap();
+
+        let ata = associated_token_address(&owner, &mint, &token_program).unwrap();
+        assert_eq!(ata, associated_token_address(&owner, &mint, &token_program).unwrap());
+        assert!(CompressedEdwardsY(ata).decompress().is_none());
+
+        // Different token program => different account
+        let token_2022 = decode_pubkey(TOKEN_2022_PROGRAM_ID).unwrap();
+        assert_ne!(ata, associated_token_address(&owner, &mint, &token_2022).unwrap());
+    }
+
+    #[test]
+    fn test_compile_message_orders_and_deduplicates_accounts() {
+        let payer = [1u8; 32];
+        let source = [2u8; 32];
+        let mint = [3u8; 32];
+        let destination = [4u8; 32];
+        let token_program = decode_pubkey(TOKEN_PROGRAM_ID).unwrap();
+        let blockhash = [9u8; 32];
+
+        let instruction = transfer_checked(&source, &mint, &destination, &payer, 1_500_000, 6, &token_program);
+        let message = compile_message(&payer, &[instruction], &blockhash);
+
+        // Header: 1 signer (payer), 0 read-only signers, 2 read-only non-signers (mint, program)
+        assert_eq!(&message[..3], &[1, 0, 2]);
+        assert_eq!(message[3], 5);
+        let keys: Vec<&[u8]> = message[4..4 + 5 * 32].chunks(32).collect();
+        assert_eq!(keys[0], payer);
+        assert_eq!(keys[1], source);
+        assert_eq!(keys[2], destination);
+        assert_eq!(keys[3], mint);
+        assert_eq!(keys[4], token_program);
+
+        let rest = &message[4 + 5 * 32..];
+        assert_eq!(&rest[..32], &blockhash);
+        // 1 instruction: program index 4, accounts [source, mint, destination, payer], 10 data bytes
+        assert_eq!(&rest[32..39], &[1, 4, 4, 1, 3, 2, 0]);
+        assert_eq!(rest[39], 10);
+        assert_eq!(rest[40], TOKEN_TRANSFER_CHECKED);
+        assert_eq!(&rest[41..49], &1_500_000u64.to_le_bytes());
+        assert_eq!(rest[49], 6);
+    }
+
+    #[test]
+    fn test_compile_v0_message_moves_table_accounts_to_lookups() {
+        let payer = [1u8; 32];
+        let source = [2u8; 32];
+        let mint = [3u8; 32];
+        let destination = [4u8; 32];
+        let token_program = decode_pubkey(TOKEN_PROGRAM_ID).unwrap();
+        let blockhash = [9u8; 32];
+        // The program and payer are listed too, but must stay static
+        let table = AddressLookupTable {
+            key: [8u8; 32],
+            addresses: vec![[0u8; 32], mint, token_program, destination, payer],
+        };
+
+        let instruction = transfer_checked(&source, &mint, &destination, &payer, 1_500_000, 6, &token_program);
+        let message = compile_v0_message(&payer, &[instruction], &blockhash, &[table]);
+
+        assert_eq!(message[0], V0_MESSAGE_PREFIX);
+        // Static keys: payer, source, program (1 read-only non-signer)
+        assert_eq!(&message[1..4], &[1, 0, 1]);
+        assert_eq!(message[4], 3);
+        let keys: Vec<&[u8]> = message[5..5 + 3 * 32].chunks(32).collect();
+        assert_eq!(keys, [&payer[..], &source[..], &token_program[..]]);
+
+        let rest = &message[5 + 3 * 32..];
+        assert_eq!(&rest[..32], &blockhash);
+        // Loaded indexes: destination (writable) = 3, mint (read-only) = 4
+        assert_eq!(&rest[32..39], &[1, 2, 4, 1, 4, 3, 0]);
+        let lookups = &rest[39 + 1 + 10..];
+        assert_eq!(lookups[0], 1);
+        assert_eq!(&lookups[1..33], &[8u8; 32]);
+        assert_eq!(&lookups[33..], &[1, 3, 1, 1]);
+
+        // Without tables a v0 message is the legacy body plus the prefix and an empty lookup list
+        let instruction = transfer_checked(&source, &mint, &destination, &payer, 1_500_000, 6, &token_program);
+        let legacy = compile_message(&payer, std::slice::from_ref(&instruction), &blockhash);
+        let v0 = compile_v0_message(&payer, &[instruction], &blockhash, &[]);
+        assert_eq!(v0, [&[V0_MESSAGE_PREFIX][..], &legacy, &[0]].concat());
+    }
+
+    #[test]
+    fn test_parse_lookup_table() {
+        let key = [6u8; 32];
+        let mut data = vec![0u8; LOOKUP_TABLE_META_SIZE];
+        data[0..4].copy_from_slice(&1u32.to_le_bytes());
+        data[4..12].copy_from_slice(&u64::MAX.to_le_bytes());
+        data.extend_from_slice(&[1u8; 32]);
+        data.extend_from_slice(&[2u8; 32]);
+
+        let table = parse_lookup_table(key, &data).unwrap();
+        assert_eq!(table.addresses, vec![[1u8; 32], [2u8; 32]]);
+
+        assert!(parse_lookup_table(key, &data[..70]).is_err());
+        data[4..12].copy_from_slice(&1_000u64.to_le_bytes());
+        assert!(parse_lookup_table(key, &data).unwrap_err().to_string().contains("deactivated"));
+    }
+
+    #[test]
+    fn test_signed_transaction_verifies() {
+        use ed25519_dalek::{Signature, Verifier};
+
+        let signer = SigningKey::from_bytes(&[5u8; 32]);
+        let message = b"message bytes";
+        let (signature, transaction) = sign_transaction(message, &signer);
+
+        assert_eq!(transaction[0], 1);
+        let raw: [u8; 64] = transaction[1..65].try_into().unwrap();
+        assert_eq!(bs58::encode(raw).into_string(), signature);
+        assert_eq!(&transaction[65..], message);
+        assert!(signer.verifying_key().verify(message, &Signature::from_bytes(&raw)).is_ok());
+    }
+}
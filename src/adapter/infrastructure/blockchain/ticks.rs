@@ -0,0 +1,15 @@
+//! Timers that drive polling streams such as `BlockchainService::watch_balance`
+
+use futures::stream::{self, BoxStream, StreamExt};
+use std::time::Duration;
+
+/// Ticks immediately, then every `period`
+pub fn interval_ticks(period: Duration) -> BoxStream<'static, ()> {
+    stream::unfold(true, move |first| async move {
+        if !first {
+            tokio::time::sleep(period).await;
+        }
+        Some(((), false))
+    })
+    .boxed()
+}
@@ -0,0 +1,198 @@
+//! BIP-39 / BIP-32 hierarchical deterministic wallet
+//!
+//! Derivation paths (the defaults used by MetaMask, Electrum/BIP-84 wallets and Phantom):
+//!
+//! | Chain    | Path                      | Scheme                 |
+//! |----------|---------------------------|------------------------|
+//! | Ethereum | `m/44'/60'/0'/0/{i}`      | BIP-32 secp256k1       |
+//! | Bitcoin  | `m/84'/{0,1}'/0'/0/{i}`   | BIP-32 secp256k1, P2WPKH |
+//! | Solana   | `m/44'/501'/{i}'/0'`      | SLIP-10 ed25519        |
+
+use bip39::Mnemonic;
+use hmac::{Hmac, Mac};
+use k256::{elliptic_curve::sec1::ToEncodedPoint, NonZeroScalar, SecretKey as K256SecretKey};
+use sha2::Sha512;
+use zeroize::{Zeroize, Zeroizing};
+use crate::core::domain::{
+    errors::DomainError,
+    services::AddressDerivation,
+    value_objects::{Address, BitcoinAddressType, ChainType, Network},
+};
+use super::{bitcoin_address_from_private_key, evm_address_from_private_key, solana_address_from_private_key};
+
+type HmacSha512 = Hmac<Sha512>;
+
+/// Offset marking a hardened BIP-32 child index
+const HARDENED: u32 = 0x8000_0000;
+
+/// HD wallet holding a BIP-39 seed; derives per-chain account addresses by index
+pub struct HdWallet {
+    seed: Zeroizing<[u8; 64]>,
+    bitcoin_network: Network,
+}
+
+impl HdWallet {
+    /// Create from a BIP-39 mnemonic phrase and optional passphrase ("25th word")
+    pub fn from_mnemonic(phrase: &str, passphrase: &str) -> Result<Self, DomainError> {
+        let mnemonic = Mnemonic::parse_normalized(phrase.trim())
+            .map_err(|e| DomainError::InvalidPrivateKey(format!("invalid mnemonic: {}", e)))?;
+        Ok(Self {
+            seed: Zeroizing::new(mnemonic.to_seed_normalized(passphrase)),
+            bitcoin_network: Network::BitcoinMainnet,
+        })
+    }
+
+    /// Derive Bitcoin addresses for testnet (coin type 1, `tb1` addresses) instead of mainnet
+    pub fn with_bitcoin_network(mut self, network: Network) -> Result<Self, DomainError> {
+        if !network.is_bitcoin() {
+            return Err(DomainError::ConfigurationError(
+                "Network must be a Bitcoin network".to_string(),
+            ));
+        }
+        self.bitcoin_network = network;
+        Ok(self)
+    }
+
+    /// Derive a secp256k1 private key along a BIP-32 path
+    fn derive_secp256k1(&self, path: &[u32]) -> Result<Zeroizing<[u8; 32]>, DomainError> {
+        let (mut key, mut chain_code) = split_hmac(b"Bitcoin seed", &[self.seed.as_slice()]);
+
+        for &index in path {
+            let parent = K256SecretKey::from_slice(key.as_slice()).map_err(|_| invalid_derivation())?;
+            let index_bytes = index.to_be_bytes();
+            let public_key;
+            let data: [&[u8]; 3] = if index >= HARDENED {
+                [&[0u8], key.as_slice(), &index_bytes]
+            } else {
+                public_key = parent.public_key().to_encoded_point(true);
+                [public_key.as_bytes(), &[], &index_bytes]
+            };
+
+            let (tweak, child_chain_code) = split_hmac(chain_code.as_slice(), &data);
+            // A tweak >= n is invalid (probability < 2^-127); BIP-32 says skip to the next index
+            let tweak = K256SecretKey::from_slice(tweak.as_slice()).map_err(|_| invalid_derivation())?;
+            let child = *tweak.to_nonzero_scalar().as_ref() + parent.to_nonzero_scalar().as_ref();
+            let child: Option<NonZeroScalar> = NonZeroScalar::new(child).into();
+            let child = child.ok_or_else(invalid_derivation)?;
+
+            key = Zeroizing::new(child.to_bytes().into());
+            chain_code = child_chain_code;
+        }
+        Ok(key)
+    }
+
+    /// Derive an ed25519 seed along a SLIP-10 path (hardened indices only)
+    fn derive_ed25519(&self, path: &[u32]) -> Zeroizing<[u8; 32]> {
+        let (mut key, mut chain_code) = split_hmac(b"ed25519 seed", &[self.seed.as_slice()]);
+        for &index in path {
+            let index_bytes = (index | HARDENED).to_be_bytes();
+            let (child_key, child_chain_code) =
+                split_hmac(chain_code.as_slice(), &[&[0u8], key.as_slice(), &index_bytes]);
+            key = child_key;
+            chain_code = child_chain_code;
+        }
+        key
+    }
+}
+
+impl AddressDerivation for HdWallet {
+    fn derive_address(&self, chain_type: ChainType, index: u32) -> Result<Address, DomainError> {
+        if index >= HARDENED {
+            return Err(DomainError::InvalidQuery(format!(
+                "Account index {} is out of range",
+                index
+            )));
+        }
+
+        match chain_type {
+            ChainType::Ethereum => {
+                let key = self.derive_secp256k1(&[44 | HARDENED, 60 | HARDENED, HARDENED, 0, index])?;
+                evm_address_from_private_key(&hex_key(&key))
+            }
+            ChainType::Bitcoin => {
+                let coin = if self.bitcoin_network == Network::BitcoinTestnet { 1 } else { 0 };
+                let key =
+                    self.derive_secp256k1(&[84 | HARDENED, coin | HARDENED, HARDENED, 0, index])?;
+                bitcoin_address_from_private_key(
+                    &hex_key(&key),
+                    &self.bitcoin_network,
+                    BitcoinAddressType::SegwitV0,
+                )
+            }
+            ChainType::Solana => {
+                let seed = self.derive_ed25519(&[44, 501, index, 0]);
+                let encoded = Zeroizing::new(bs58::encode(seed.as_slice()).into_string());
+                solana_address_from_private_key(&encoded)
+            }
+        }
+    }
+}
+
+/// HMAC-SHA512 over the concatenated `data`, split into (key, chain code)
+fn split_hmac(key: &[u8], data: &[&[u8]]) -> (Zeroizing<[u8; 32]>, Zeroizing<[u8; 32]>) {
+    let mut mac = HmacSha512::new_from_slice(key).expect("HMAC accepts keys of any length");
+    for part in data {
+        mac.update(part);
+    }
+    let mut output: [u8; 64] = mac.finalize().into_bytes().into();
+
+    let mut left = Zeroizing::new([0u8; 32]);
+    let mut right = Zeroizing::new([0u8; 32]);
+    left.copy_from_slice(&output[..32]);
+    right.copy_from_slice(&output[32..]);
+    output.zeroize();
+    (left, right)
+}
+
+fn hex_key(key: &[u8; 32]) -> Zeroizing<String> {
+    Zeroizing::new(alloy::hex::encode(key))
+}
+
+fn invalid_derivation() -> DomainError {
+    DomainError::InvalidPrivateKey("derived key is invalid for this index".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // BIP-39 test mnemonic; its first accounts are published by every major wallet
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn test_derive_evm_addresses() {
+        let wallet = HdWallet::from_mnemonic(MNEMONIC, "").unwrap();
+        assert_eq!(
+            wallet.derive_address(ChainType::Ethereum, 0).unwrap().as_str(),
+            "0x9858EfFD232B4033E47d90003D41EC34EcaEda94"
+        );
+        assert_eq!(
+            wallet.derive_address(ChainType::Ethereum, 1).unwrap().as_str(),
+            "0x6Fac4D18c912343BF86fa7049364Dd4E424Ab9C0"
+        );
+    }
+
+    #[test]
+    fn test_derive_bitcoin_bip84_address() {
+        let wallet = HdWallet::from_mnemonic(MNEMONIC, "").unwrap();
+        assert_eq!(
+            wallet.derive_address(ChainType::Bitcoin, 0).unwrap().as_str(),
+            "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu"
+        );
+    }
+
+    #[test]
+    fn test_derive_solana_address() {
+        let wallet = HdWallet::from_mnemonic(MNEMONIC, "").unwrap();
+        assert_eq!(
+            wallet.derive_address(ChainType::Solana, 0).unwrap().as_str(),
+            "HAgk14JpMQLgt6rVgv7cBQFJWFto5Dqxi472uT3DKpqk"
+        );
+    }
+
+    #[test]
+    fn test_invalid_mnemonic_rejected() {
+        let result = HdWallet::from_mnemonic("abandon abandon abandon", "");
+        assert!(matches!(result, Err(DomainError::InvalidPrivateKey(_))));
+    }
+}
@@ -0,0 +1,115 @@
+//! Ledger hardware-wallet signer (`ledger` feature)
+//!
+//! The key never leaves the device: the wallet sends the unsigned transaction
+//! over USB, the user checks recipient and amount on the Ledger's screen and
+//! approves, and the device returns the signature. Requires the Ethereum app
+//! to be open on an unlocked device.
+
+use alloy::signers::Signer as _;
+use alloy_signer_ledger::HDPath;
+use async_trait::async_trait;
+use crate::core::domain::{errors::DomainError, services::Signer, value_objects::Address};
+
+/// BIP-44 path of the first Ethereum account (Ledger Live and MetaMask)
+pub const DEFAULT_LEDGER_PATH: &str = "m/44'/60'/0'/0/0";
+
+/// Signs EVM transactions on a connected Ledger device
+pub struct LedgerSigner {
+    device: alloy_signer_ledger::LedgerSigner,
+    path: String,
+    address: Address,
+}
+
+impl LedgerSigner {
+    /// Connect to the device and read the address at `path` (e.g. `m/44'/60'/0'/0/0`)
+    pub async fn connect(path: &str) -> Result<Self, DomainError> {
+        let path = normalize_path(path)?;
+        let device = alloy_signer_ledger::LedgerSigner::new(HDPath::Other(path.clone()), None)
+            .await
+            .map_err(|e| {
+                DomainError::ConfigurationError(format!(
+                    "Failed to connect to Ledger (is it unlocked with the Ethereum app open?): {}",
+                    e
+                ))
+            })?;
+        let address = Address::new(device.address().to_checksum(None))?;
+        Ok(Self { device, path, address })
+    }
+
+    /// BIP-44 derivation path this signer uses
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+#[async_trait]
+impl Signer for LedgerSigner {
+    fn address(&self) -> Address {
+        self.address.clone()
+    }
+
+    /// Blocks until the user approves or rejects the transaction on the device
+    async fn sign_transaction(&self, payload: &[u8]) -> Result<Vec<u8>, DomainError> {
+        let signature = self
+            .device
+            .sign_tx_rlp(payload)
+            .await
+            .map_err(|e| DomainError::TransferFailed(format!("Ledger did not sign the transaction: {}", e)))?;
+        Ok(signature.as_bytes().to_vec())
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Vec<u8>, DomainError> {
+        let signature = self
+            .device
+            .sign_message(message)
+            .await
+            .map_err(|e| DomainError::BlockchainError(format!("Ledger did not sign the message: {}", e)))?;
+        Ok(signature.as_bytes().to_vec())
+    }
+}
+
+/// Check `path` is a BIP-32 path such as `m/44'/60'/0'/0/0` and spell
+/// hardened levels with `'` (`44h` is accepted too)
+fn normalize_path(path: &str) -> Result<String, DomainError> {
+    let invalid = || DomainError::ConfigurationError(format!("Invalid derivation path '{}'", path));
+    let mut components = path.trim().split('/');
+    if components.next() != Some("m") {
+        return Err(invalid());
+    }
+    let mut normalized = String::from("m");
+    let mut depth = 0;
+    for component in components {
+        let (index, hardened) = match component.strip_suffix(['\'', 'h']) {
+            Some(index) => (index, true),
+            None => (component, false),
+        };
+        if index.is_empty() || !index.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        // The top bit marks hardened indices, so it cannot be part of the index
+        let index: u32 = index.parse().ok().filter(|index| *index < 0x8000_0000).ok_or_else(invalid)?;
+        normalized.push_str(&format!("/{}{}", index, if hardened { "'" } else { "" }));
+        depth += 1;
+    }
+    // The Ledger Ethereum app accepts at most 10 levels
+    if depth == 0 || depth > 10 {
+        return Err(invalid());
+    }
+    Ok(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(normalize_path(DEFAULT_LEDGER_PATH).unwrap(), DEFAULT_LEDGER_PATH);
+        assert_eq!(normalize_path("m/44h/60h/3h/0/7").unwrap(), "m/44'/60'/3'/0/7");
+        assert!(normalize_path("44'/60'/0'/0/0").is_err());
+        assert!(normalize_path("m").is_err());
+        assert!(normalize_path("m/44'/x/0").is_err());
+        assert!(normalize_path("m/44''/60").is_err());
+        assert!(normalize_path("m/2147483648/0").is_err());
+    }
+}
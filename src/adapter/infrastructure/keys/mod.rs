@@ -0,0 +1,319 @@
+//! Private key parsing and address derivation for each supported chain
+//!
+//! Error messages never include key material.
+
+pub mod hd;
+#[cfg(feature = "ledger")]
+pub mod ledger;
+pub mod signer;
+
+pub use hd::HdWallet;
+#[cfg(feature = "ledger")]
+pub use ledger::LedgerSigner;
+pub use signer::PrivateKeySigner;
+
+use alloy::signers::local::PrivateKeySigner as EvmKey;
+use bech32::{hrp, segwit};
+use k256::ecdsa::SigningKey;
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+use crate::core::domain::{
+    errors::DomainError,
+    value_objects::{Address, BitcoinAddressType, ChainType, Network},
+};
+
+/// Derive the address controlled by `private_key` on the given network
+///
+/// Bitcoin networks derive a native SegWit (P2WPKH) address; use
+/// [`bitcoin_address_from_private_key`] to pick another script type.
+pub fn address_from_private_key(private_key: &str, network: &Network) -> Result<Address, DomainError> {
+    match network.chain_type() {
+        ChainType::Ethereum => evm_address_from_private_key(private_key),
+        ChainType::Bitcoin => {
+            bitcoin_address_from_private_key(private_key, network, BitcoinAddressType::SegwitV0)
+        }
+        ChainType::Solana => solana_address_from_private_key(private_key),
+    }
+}
+
+/// Derive the EIP-55 checksummed EOA address for a secp256k1 private key
+pub fn evm_address_from_private_key(private_key: &str) -> Result<Address, DomainError> {
+    let signer = parse_evm_signer(private_key)?;
+    Address::new(signer.address().to_checksum(None))
+}
+
+/// Parse a hex private key (optional `0x`, surrounding whitespace allowed) into a signer
+pub(crate) fn parse_evm_signer(private_key: &str) -> Result<EvmKey, DomainError> {
+    normalize_hex_private_key(private_key)?
+        .parse()
+        .map_err(|_| DomainError::InvalidPrivateKey("not a valid secp256k1 secret key".to_string()))
+}
+
+/// Trim whitespace and an optional `0x` prefix, then require exactly 64 hex characters
+pub(crate) fn normalize_hex_private_key(private_key: &str) -> Result<&str, DomainError> {
+    let trimmed = private_key.trim();
+    let hex = trimmed
+        .strip_prefix("0x")
+        .or_else(|| trimmed.strip_prefix("0X"))
+        .unwrap_or(trimmed);
+
+    if hex.len() != 64 {
+        return Err(DomainError::InvalidPrivateKey(format!(
+            "expected 64 hex characters (optionally 0x-prefixed), got {}",
+            hex.len()
+        )));
+    }
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(DomainError::InvalidPrivateKey(
+            "must contain only hexadecimal characters".to_string(),
+        ));
+    }
+    Ok(hex)
+}
+
+/// Derive a Bitcoin address of the given script type from a WIF or hex private key
+///
+/// Supports P2PKH, P2SH-wrapped P2WPKH and native P2WPKH. Taproot derivation is
+/// not supported yet.
+pub fn bitcoin_address_from_private_key(
+    private_key: &str,
+    network: &Network,
+    address_type: BitcoinAddressType,
+) -> Result<Address, DomainError> {
+    let testnet = match network {
+        Network::BitcoinMainnet => false,
+        Network::BitcoinTestnet => true,
+        _ => {
+            return Err(DomainError::ConfigurationError(
+                "Network must be a Bitcoin network".to_string(),
+            ))
+        }
+    };
+
+    let (signing_key, compressed) = parse_bitcoin_private_key(private_key, testnet)?;
+    let public_key = signing_key.verifying_key().to_encoded_point(compressed);
+    let pubkey_hash = hash160(public_key.as_bytes());
+
+    if !compressed && address_type != BitcoinAddressType::P2pkh {
+        return Err(DomainError::InvalidPrivateKey(
+            "SegWit addresses require a compressed public key".to_string(),
+        ));
+    }
+
+    let address = match address_type {
+        BitcoinAddressType::P2pkh => {
+            base58check(if testnet { 0x6f } else { 0x00 }, &pubkey_hash)
+        }
+        BitcoinAddressType::P2sh => {
+            // P2SH-P2WPKH: redeem script is OP_0 <20-byte pubkey hash>
+            let mut redeem_script = vec![0x00, 0x14];
+            redeem_script.extend_from_slice(&pubkey_hash);
+            base58check(if testnet { 0xc4 } else { 0x05 }, &hash160(&redeem_script))
+        }
+        BitcoinAddressType::SegwitV0 => {
+            let hrp = if testnet { hrp::TB } else { hrp::BC };
+            segwit::encode(hrp, segwit::VERSION_0, &pubkey_hash).map_err(|e| {
+                DomainError::BlockchainError(format!("Failed to encode Bech32 address: {}", e))
+            })?
+        }
+        BitcoinAddressType::Taproot => {
+            return Err(DomainError::ConfigurationError(
+                "Taproot address derivation is not supported yet".to_string(),
+            ))
+        }
+    };
+
+    Address::new(address)
+}
+
+/// Parse a WIF (compressed or uncompressed) or 64-hex private key
+///
+/// Returns the signing key and whether the public key should be compressed.
+/// A WIF key encodes its network, which must match `testnet`; hex keys fit either.
+pub(crate) fn parse_bitcoin_private_key(private_key: &str, testnet: bool) -> Result<(SigningKey, bool), DomainError> {
+    let trimmed = private_key.trim();
+
+    let (secret, compressed) = if let Ok(hex) = normalize_hex_private_key(trimmed) {
+        (Zeroizing::new(decode_hex(hex)?), true)
+    } else {
+        let payload = Zeroizing::new(
+            bs58::decode(trimmed)
+                .with_check(None)
+                .into_vec()
+                .map_err(|_| DomainError::InvalidPrivateKey("not a valid WIF or hex key".to_string()))?,
+        );
+
+        // 0x80 marks a mainnet key, 0xef a testnet one
+        let wrong_network = match (payload.first(), testnet) {
+            (Some(0x80), true) => Some("WIF key is for Bitcoin mainnet, not testnet"),
+            (Some(0xef), false) => Some("WIF key is for Bitcoin testnet, not mainnet"),
+            _ => None,
+        };
+        if let Some(reason) = wrong_network {
+            return Err(DomainError::InvalidPrivateKey(reason.to_string()));
+        }
+
+        match payload.as_slice() {
+            [0x80 | 0xef, key @ .., 0x01] if key.len() == 32 => (Zeroizing::new(key.to_vec()), true),
+            [0x80 | 0xef, key @ ..] if key.len() == 32 => (Zeroizing::new(key.to_vec()), false),
+            _ => {
+                return Err(DomainError::InvalidPrivateKey(
+                    "unexpected WIF version or length".to_string(),
+                ))
+            }
+        }
+    };
+
+    let signing_key = SigningKey::from_slice(&secret)
+        .map_err(|_| DomainError::InvalidPrivateKey("not a valid secp256k1 secret key".to_string()))?;
+    Ok((signing_key, compressed))
+}
+
+/// Derive the base58 Solana public key from a keypair or secret seed
+///
+/// Accepts a base58 string or a JSON byte array (the `solana-keygen` file format),
+/// holding either a 64-byte keypair or a 32-byte secret seed.
+pub fn solana_address_from_private_key(private_key: &str) -> Result<Address, DomainError> {
+    let signing_key = parse_solana_signing_key(private_key)?;
+    Address::new(bs58::encode(signing_key.verifying_key().to_bytes()).into_string())
+}
+
+/// Parse a Solana keypair or secret seed (see [`solana_address_from_private_key`]) into a signing key
+pub(crate) fn parse_solana_signing_key(private_key: &str) -> Result<ed25519_dalek::SigningKey, DomainError> {
+    let trimmed = private_key.trim();
+
+    let bytes = Zeroizing::new(if trimmed.starts_with('[') {
+        serde_json::from_str::<Vec<u8>>(trimmed)
+            .map_err(|_| DomainError::InvalidPrivateKey("invalid JSON keypair array".to_string()))?
+    } else {
+        bs58::decode(trimmed)
+            .into_vec()
+            .map_err(|_| DomainError::InvalidPrivateKey("invalid base58 keypair".to_string()))?
+    });
+
+    let seed: [u8; 32] = match bytes.len() {
+        32 | 64 => bytes[..32].try_into().expect("length checked"),
+        n => {
+            return Err(DomainError::InvalidPrivateKey(format!(
+                "expected a 32-byte seed or 64-byte keypair, got {} bytes",
+                n
+            )))
+        }
+    };
+
+    let signing_key = ed25519_dalek::SigningKey::from_bytes(&seed);
+
+    // A 64-byte keypair embeds the public key; make sure it is consistent
+    if bytes.len() == 64 && bytes[32..] != signing_key.verifying_key().to_bytes() {
+        return Err(DomainError::InvalidPrivateKey(
+            "keypair public half does not match its secret".to_string(),
+        ));
+    }
+
+    Ok(signing_key)
+}
+
+pub(crate) fn hash160(data: &[u8]) -> [u8; 20] {
+    Ripemd160::digest(Sha256::digest(data)).into()
+}
+
+fn base58check(version: u8, payload: &[u8]) -> String {
+    bs58::encode(payload)
+        .with_check_version(version)
+        .into_string()
+}
+
+fn decode_hex(hex: &str) -> Result<Vec<u8>, DomainError> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&hex[i..i + 2], 16)
+                .map_err(|_| DomainError::InvalidPrivateKey("invalid hex".to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Private key 1 (the generator point) has well-known addresses on every chain
+    const KEY_ONE: &str = "0000000000000000000000000000000000000000000000000000000000000001";
+
+    #[test]
+    fn test_evm_address_from_private_key() {
+        let address = evm_address_from_private_key(KEY_ONE).unwrap();
+        assert_eq!(address.as_str(), "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf");
+
+        let prefixed = evm_address_from_private_key(&format!(" 0x{} ", KEY_ONE)).unwrap();
+        assert_eq!(prefixed, address);
+    }
+
+    #[test]
+    fn test_bitcoin_address_from_private_key() {
+        let mainnet = Network::BitcoinMainnet;
+
+        let p2pkh = bitcoin_address_from_private_key(KEY_ONE, &mainnet, BitcoinAddressType::P2pkh).unwrap();
+        assert_eq!(p2pkh.as_str(), "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH");
+
+        let p2sh = bitcoin_address_from_private_key(KEY_ONE, &mainnet, BitcoinAddressType::P2sh).unwrap();
+        assert_eq!(p2sh.as_str(), "3JvL6Ymt8MVWiCNHC7oWU6nLeHNJKLZGLN");
+
+        let segwit = bitcoin_address_from_private_key(KEY_ONE, &mainnet, BitcoinAddressType::SegwitV0).unwrap();
+        assert_eq!(segwit.as_str(), "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+
+        // Same key as compressed WIF
+        let wif = "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn";
+        let from_wif = bitcoin_address_from_private_key(wif, &mainnet, BitcoinAddressType::P2pkh).unwrap();
+        assert_eq!(from_wif, p2pkh);
+
+        // Uncompressed WIF only supports legacy P2PKH
+        let uncompressed = "5HpHagT65TZzG1PH3CSu63k8DbpvD8s5ip4nEB3kEsreAnchuDf";
+        let legacy = bitcoin_address_from_private_key(uncompressed, &mainnet, BitcoinAddressType::P2pkh).unwrap();
+        assert_eq!(legacy.as_str(), "1EHNa6Q4Jz2uvNExL497mE43ikXhwF6kZm");
+        assert!(bitcoin_address_from_private_key(uncompressed, &mainnet, BitcoinAddressType::SegwitV0).is_err());
+    }
+
+    #[test]
+    fn test_bitcoin_wif_must_match_network() {
+        let (mainnet, testnet) = (Network::BitcoinMainnet, Network::BitcoinTestnet);
+        let mut payload = decode_hex(KEY_ONE).unwrap();
+        payload.push(0x01);
+        let testnet_wif = base58check(0xef, &payload);
+        let mainnet_wif = "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn";
+
+        let from_hex = bitcoin_address_from_private_key(KEY_ONE, &testnet, BitcoinAddressType::P2pkh).unwrap();
+        let from_wif = bitcoin_address_from_private_key(&testnet_wif, &testnet, BitcoinAddressType::P2pkh).unwrap();
+        assert_eq!(from_wif, from_hex);
+
+        assert!(matches!(
+            bitcoin_address_from_private_key(&testnet_wif, &mainnet, BitcoinAddressType::P2pkh),
+            Err(DomainError::InvalidPrivateKey(_))
+        ));
+        assert!(matches!(
+            bitcoin_address_from_private_key(mainnet_wif, &testnet, BitcoinAddressType::SegwitV0),
+            Err(DomainError::InvalidPrivateKey(_))
+        ));
+    }
+
+    #[test]
+    fn test_solana_address_from_private_key() {
+        let seed = [1u8; 32];
+        let public_key = ed25519_dalek::SigningKey::from_bytes(&seed).verifying_key().to_bytes();
+        let expected = bs58::encode(public_key).into_string();
+
+        let mut keypair = seed.to_vec();
+        keypair.extend_from_slice(&public_key);
+
+        let from_base58 = solana_address_from_private_key(&bs58::encode(&keypair).into_string()).unwrap();
+        assert_eq!(from_base58.as_str(), expected);
+
+        let json = serde_json::to_string(&keypair).unwrap();
+        assert_eq!(solana_address_from_private_key(&json).unwrap(), from_base58);
+
+        // Tampered public half is rejected
+        keypair[40] ^= 0xff;
+        assert!(solana_address_from_private_key(&bs58::encode(&keypair).into_string()).is_err());
+    }
+}
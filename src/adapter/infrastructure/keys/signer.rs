@@ -0,0 +1,118 @@
+use alloy::{
+    primitives::keccak256,
+    signers::{local::PrivateKeySigner as EvmKey, SignerSync},
+};
+use async_trait::async_trait;
+use ed25519_dalek::Signer as _;
+use crate::core::domain::{
+    errors::DomainError,
+    services::Signer,
+    value_objects::{Address, ChainType, Network, SecretKey},
+};
+use super::{parse_evm_signer, parse_solana_signing_key};
+
+/// [`Signer`] backed by a private key held in memory
+///
+/// Accepts the same key formats as the rest of the wallet: hex on EVM
+/// chains, a base58 or JSON keypair on Solana. Bitcoin keys are not
+/// supported yet, since the wallet cannot build Bitcoin transactions.
+pub struct PrivateKeySigner {
+    key: LocalKey,
+    address: Address,
+}
+
+enum LocalKey {
+    Evm(EvmKey),
+    Solana(ed25519_dalek::SigningKey),
+}
+
+impl PrivateKeySigner {
+    /// Parse `private_key` for the chain `network` belongs to
+    pub fn new(private_key: &SecretKey, network: &Network) -> Result<Self, DomainError> {
+        match network.chain_type() {
+            ChainType::Ethereum => {
+                let key = parse_evm_signer(private_key.expose_secret())?;
+                let address = Address::new(key.address().to_checksum(None))?;
+                Ok(Self {
+                    key: LocalKey::Evm(key),
+                    address,
+                })
+            }
+            ChainType::Solana => {
+                let key = parse_solana_signing_key(private_key.expose_secret())?;
+                let address = Address::new(bs58::encode(key.verifying_key().to_bytes()).into_string())?;
+                Ok(Self {
+                    key: LocalKey::Solana(key),
+                    address,
+                })
+            }
+            ChainType::Bitcoin => Err(DomainError::ConfigurationError(
+                "Signing Bitcoin transactions is not supported yet".to_string(),
+            )),
+        }
+    }
+}
+
+#[async_trait]
+impl Signer for PrivateKeySigner {
+    fn address(&self) -> Address {
+        self.address.clone()
+    }
+
+    async fn sign_transaction(&self, payload: &[u8]) -> Result<Vec<u8>, DomainError> {
+        match &self.key {
+            LocalKey::Evm(key) => key
+                .sign_hash_sync(&keccak256(payload))
+                .map(|signature| signature.as_bytes().to_vec())
+                .map_err(|e| DomainError::TransferFailed(format!("Failed to sign transaction: {}", e))),
+            LocalKey::Solana(key) => Ok(key.sign(payload).to_bytes().to_vec()),
+        }
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Vec<u8>, DomainError> {
+        match &self.key {
+            LocalKey::Evm(key) => key
+                .sign_message_sync(message)
+                .map(|signature| signature.as_bytes().to_vec())
+                .map_err(|e| DomainError::BlockchainError(format!("Failed to sign message: {}", e))),
+            LocalKey::Solana(key) => Ok(key.sign(message).to_bytes().to_vec()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::PrimitiveSignature;
+
+    const EVM_KEY: &str = "0000000000000000000000000000000000000000000000000000000000000001";
+
+    #[tokio::test]
+    async fn test_private_key_signer_signs_for_its_address() {
+        let signer = PrivateKeySigner::new(&SecretKey::from(EVM_KEY), &Network::Mainnet).unwrap();
+        assert_eq!(signer.address().as_str(), "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf");
+
+        let payload = b"unsigned transaction";
+        let signature = signer.sign_transaction(payload).await.unwrap();
+        assert_eq!(signature.len(), 65);
+        let recovered = PrimitiveSignature::try_from(signature.as_slice())
+            .unwrap()
+            .recover_address_from_prehash(&keccak256(payload))
+            .unwrap();
+        assert_eq!(recovered.to_checksum(None), signer.address().as_str());
+
+        let solana = PrivateKeySigner::new(&SecretKey::from(bs58::encode([7u8; 32]).into_string()), &Network::SolanaMainnet)
+            .unwrap();
+        let signature = solana.sign_message(b"hello").await.unwrap();
+        assert_eq!(signature.len(), 64);
+
+        assert!(matches!(
+            PrivateKeySigner::new(&SecretKey::from(EVM_KEY), &Network::BitcoinMainnet),
+            Err(DomainError::ConfigurationError(_))
+        ));
+        assert!(matches!(
+            PrivateKeySigner::new(&SecretKey::from("not a key"), &Network::Mainnet),
+            Err(DomainError::InvalidPrivateKey(_))
+        ));
+    }
+}
@@ -1,3 +1,9 @@
+pub mod addressbook;
+pub mod audit;
 pub mod blockchain;
+pub mod keys;
+pub mod prices;
+pub mod provider_keys;
+pub mod resilience;
 
 pub use blockchain::AlloyBlockchainService;
@@ -0,0 +1,193 @@
+//! Fiat prices for native currencies and tokens
+//!
+//! Prices are informational (portfolio views); nothing in the wallet depends
+//! on them, so callers should degrade gracefully when a provider is down.
+
+use async_trait::async_trait;
+use reqwest::{Client, StatusCode};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use crate::adapter::infrastructure::blockchain::http::build_http_client_with_timeout;
+use crate::core::domain::{
+    errors::DomainError,
+    value_objects::{Address, ChainType},
+};
+
+/// Public CoinGecko API (no key required, ~30 requests/minute)
+const COINGECKO_API_URL: &str = "https://api.coingecko.com/api/v3";
+/// Prices are reused for this long before being fetched again
+const DEFAULT_PRICE_TTL: Duration = Duration::from_secs(60);
+/// A price is never worth waiting long for
+const PRICE_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Source of USD prices
+#[async_trait]
+pub trait PriceProvider: Send + Sync {
+    /// USD price of one unit of `chain`'s native currency, or of `token` on `chain`
+    async fn get_price(&self, chain: ChainType, token: Option<&Address>) -> Result<f64, DomainError>;
+}
+
+/// [`PriceProvider`] backed by CoinGecko's `simple/price` endpoints
+///
+/// Native currencies map to CoinGecko coin ids (`ethereum`, `bitcoin`,
+/// `solana`); tokens are looked up by contract address (ERC-20) or mint
+/// (SPL). Prices are cached per asset for a short TTL, so a portfolio view
+/// over many addresses costs one request per asset.
+pub struct CoinGeckoPriceProvider {
+    client: Client,
+    api_base_url: String,
+    ttl: Duration,
+    cache: Mutex<HashMap<String, (f64, Instant)>>,
+}
+
+impl CoinGeckoPriceProvider {
+    /// Create a provider for the public CoinGecko API
+    pub fn new() -> Result<Self, DomainError> {
+        Self::new_with_base_url(COINGECKO_API_URL)
+    }
+
+    /// Create a provider against an explicit CoinGecko-compatible API URL
+    pub fn new_with_base_url(api_base_url: &str) -> Result<Self, DomainError> {
+        Ok(Self {
+            client: build_http_client_with_timeout(&HashMap::new(), Some(PRICE_REQUEST_TIMEOUT))?,
+            api_base_url: api_base_url.trim_end_matches('/').to_string(),
+            ttl: DEFAULT_PRICE_TTL,
+            cache: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Reuse fetched prices for `ttl` (default 60 s; zero disables caching)
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    fn cached(&self, key: &str) -> Option<f64> {
+        let cache = self.cache.lock().expect("price cache lock poisoned");
+        cache
+            .get(key)
+            .filter(|(_, fetched_at)| fetched_at.elapsed() < self.ttl)
+            .map(|(price, _)| *price)
+    }
+
+    async fn fetch(&self, endpoint: &str, params: &[(&str, &str)], key: &str) -> Result<f64, DomainError> {
+        let response = self
+            .client
+            .get(format!("{}/{}", self.api_base_url, endpoint))
+            .query(params)
+            .send()
+            .await
+            .map_err(|e| DomainError::NetworkError(format!("Failed to query price: {}", e)))?;
+
+        match response.status() {
+            StatusCode::TOO_MANY_REQUESTS => {
+                return Err(DomainError::RateLimited("CoinGecko rate limit exceeded".to_string()))
+            }
+            status if !status.is_success() => {
+                return Err(DomainError::NetworkError(format!("CoinGecko API returned error: {}", status)))
+            }
+            _ => {}
+        }
+
+        let body = response
+            .text()
+            .await
+            .map_err(|e| DomainError::NetworkError(format!("Failed to read response: {}", e)))?;
+        parse_price_response(&body, key)
+    }
+}
+
+#[async_trait]
+impl PriceProvider for CoinGeckoPriceProvider {
+    async fn get_price(&self, chain: ChainType, token: Option<&Address>) -> Result<f64, DomainError> {
+        // EVM contract addresses are case-insensitive; CoinGecko keys them lowercased
+        let key = match token {
+            None => native_coin_id(chain).to_string(),
+            Some(token) if chain == ChainType::Ethereum => token.as_str().to_lowercase(),
+            Some(token) => token.as_str().to_string(),
+        };
+        if let Some(price) = self.cached(&key) {
+            return Ok(price);
+        }
+
+        let price = match token {
+            None => {
+                let params = [("ids", key.as_str()), ("vs_currencies", "usd")];
+                self.fetch("simple/price", &params, &key).await?
+            }
+            Some(_) => {
+                let platform = token_platform(chain)?;
+                let params = [("contract_addresses", key.as_str()), ("vs_currencies", "usd")];
+                self.fetch(&format!("simple/token_price/{}", platform), &params, &key).await?
+            }
+        };
+
+        self.cache
+            .lock()
+            .expect("price cache lock poisoned")
+            .insert(key, (price, Instant::now()));
+        Ok(price)
+    }
+}
+
+/// CoinGecko coin id of a chain's native currency
+fn native_coin_id(chain: ChainType) -> &'static str {
+    match chain {
+        ChainType::Ethereum => "ethereum",
+        ChainType::Bitcoin => "bitcoin",
+        ChainType::Solana => "solana",
+    }
+}
+
+/// CoinGecko asset platform that token addresses on `chain` belong to
+fn token_platform(chain: ChainType) -> Result<&'static str, DomainError> {
+    match chain {
+        ChainType::Ethereum => Ok("ethereum"),
+        ChainType::Solana => Ok("solana"),
+        ChainType::Bitcoin => Err(DomainError::InvalidQuery(
+            "Bitcoin has no token prices".to_string(),
+        )),
+    }
+}
+
+/// Extract `body[key].usd` from a `simple/price` or `simple/token_price` response
+fn parse_price_response(body: &str, key: &str) -> Result<f64, DomainError> {
+    let response: serde_json::Value = serde_json::from_str(body)
+        .map_err(|e| DomainError::BlockchainError(format!("Failed to parse price response: {}", e)))?;
+
+    // Unknown ids and contracts are answered with an empty object rather than an error
+    response
+        .get(key)
+        .and_then(|asset| asset.get("usd"))
+        .and_then(serde_json::Value::as_f64)
+        .ok_or_else(|| DomainError::InvalidQuery(format!("No USD price for {}", key)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_price_response() {
+        assert_eq!(
+            parse_price_response(r#"{"ethereum":{"usd":3012.5}}"#, "ethereum").unwrap(),
+            3012.5
+        );
+        assert!(matches!(
+            parse_price_response("{}", "ethereum"),
+            Err(DomainError::InvalidQuery(_))
+        ));
+        assert!(matches!(
+            parse_price_response("<html>", "ethereum"),
+            Err(DomainError::BlockchainError(_))
+        ));
+    }
+
+    #[test]
+    fn test_token_platform() {
+        assert_eq!(token_platform(ChainType::Ethereum).unwrap(), "ethereum");
+        assert_eq!(token_platform(ChainType::Solana).unwrap(), "solana");
+        assert!(token_platform(ChainType::Bitcoin).is_err());
+    }
+}
@@ -0,0 +1,58 @@
+//! API keys for hosted RPC providers, read from the environment
+//!
+//! The domain's [`Network::rpc_url_with_keys`] knows where each provider
+//! serves a network; this module decides where the keys come from, so the
+//! domain never touches process state.
+
+use crate::core::domain::value_objects::Network;
+
+/// Environment variable holding an Infura project ID for the default EVM endpoints
+pub const INFURA_KEY_ENV: &str = "INFURA_KEY";
+
+/// Environment variable holding an Alchemy API key for the default EVM endpoints
+pub const ALCHEMY_KEY_ENV: &str = "ALCHEMY_KEY";
+
+/// Infura project ID and Alchemy API key, either of which may be missing
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct ProviderKeys {
+    pub infura: Option<String>,
+    pub alchemy: Option<String>,
+}
+
+impl ProviderKeys {
+    /// Keys from `INFURA_KEY` and `ALCHEMY_KEY`; blank values count as unset
+    pub fn from_env() -> Self {
+        let key = |name| std::env::var(name).ok().filter(|key: &String| !key.trim().is_empty());
+        Self {
+            infura: key(INFURA_KEY_ENV),
+            alchemy: key(ALCHEMY_KEY_ENV),
+        }
+    }
+
+    /// Default RPC URL of `network` with these keys substituted in
+    pub fn rpc_url(&self, network: &Network) -> String {
+        network
+            .rpc_url_with_keys(self.infura.as_deref(), self.alchemy.as_deref())
+            .into_owned()
+    }
+}
+
+// Keys stay out of logs and panic messages
+impl std::fmt::Debug for ProviderKeys {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProviderKeys")
+            .field("infura", &self.infura.as_ref().map(|_| "***"))
+            .field("alchemy", &self.alchemy.as_ref().map(|_| "***"))
+            .finish()
+    }
+}
+
+/// Default RPC URL of `network` with provider keys from the environment
+///
+/// Infura endpoints need a project ID after `/v3/`, so the bare defaults
+/// for Goerli, Sepolia and Holesky do not answer. `INFURA_KEY` (preferred)
+/// or `ALCHEMY_KEY` switches every network either provider serves to that
+/// provider's authenticated endpoint.
+pub fn default_rpc_url(network: &Network) -> String {
+    ProviderKeys::from_env().rpc_url(network)
+}
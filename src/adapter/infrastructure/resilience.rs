@@ -0,0 +1,161 @@
+//! Retry configuration and backoff shared by the blockchain services
+
+use std::future::Future;
+use std::time::Duration;
+use crate::core::domain::errors::DomainError;
+
+/// Upper bound on a single backoff, however many retries have passed
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// How often and how patiently to retry transient RPC failures (rate limits, 5xx)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt (0 disables retrying)
+    pub max_retries: u32,
+    /// Wait before each retry, unless the endpoint sends a backoff hint
+    pub initial_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Create a policy with an explicit retry count and backoff
+    pub fn new(max_retries: u32, initial_backoff: Duration) -> Self {
+        Self {
+            max_retries,
+            initial_backoff,
+        }
+    }
+
+    /// Never retry
+    pub fn none() -> Self {
+        Self::new(0, Duration::ZERO)
+    }
+}
+
+impl Default for RetryPolicy {
+    /// 3 retries, 500 ms apart
+    fn default() -> Self {
+        Self::new(3, Duration::from_millis(500))
+    }
+}
+
+/// Run `op`, retrying failures that `is_retryable` accepts according to `policy`
+///
+/// The wait before retry `n` (1-based) is `initial_backoff * 2^(n-1)`, capped
+/// at 30 s, of which a random half is skipped ("equal jitter") so clients
+/// throttled together do not retry in lockstep. Non-retryable errors and the
+/// error of the last attempt are returned as-is.
+///
+/// Pass [`DomainError::is_retryable`] as `is_retryable` to retry every
+/// transient failure, or a narrower predicate (e.g. rate limits only).
+pub async fn retry_with_backoff<F, Fut, T>(
+    policy: &RetryPolicy,
+    is_retryable: impl Fn(&DomainError) -> bool,
+    mut op: F,
+) -> Result<T, DomainError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, DomainError>>,
+{
+    let mut retry = 0;
+    loop {
+        match op().await {
+            Err(e) if retry < policy.max_retries && is_retryable(&e) => {
+                retry += 1;
+                let delay = backoff_delay(policy, retry, random_fraction());
+                tracing::debug!("Retry {}/{} in {:?} after: {}", retry, policy.max_retries, delay, e);
+                tokio::time::sleep(delay).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Wait before retry `retry` (1-based); `jitter` in `[0, 1)` picks within the upper half
+fn backoff_delay(policy: &RetryPolicy, retry: u32, jitter: f64) -> Duration {
+    let exponential = policy
+        .initial_backoff
+        .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)))
+        .min(MAX_BACKOFF);
+    exponential / 2 + exponential.mul_f64(jitter / 2.0)
+}
+
+/// Uniform random number in `[0, 1)`; 0 (no jitter) if the OS source fails
+fn random_fraction() -> f64 {
+    let mut bytes = [0u8; 4];
+    match getrandom::getrandom(&mut bytes) {
+        Ok(()) => u32::from_le_bytes(bytes) as f64 / (u32::MAX as f64 + 1.0),
+        Err(_) => 0.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Fails with `error` the first `failures` times, then returns the attempt number
+    fn flaky(
+        failures: u32,
+        error: DomainError,
+        attempts: &AtomicU32,
+    ) -> impl FnMut() -> std::future::Ready<Result<u32, DomainError>> + '_ {
+        move || {
+            let attempt = attempts.fetch_add(1, Ordering::SeqCst) + 1;
+            std::future::ready(if attempt <= failures { Err(error.clone()) } else { Ok(attempt) })
+        }
+    }
+
+    fn rate_limited(e: &DomainError) -> bool {
+        matches!(e, DomainError::RateLimited(_))
+    }
+
+    #[tokio::test]
+    async fn test_retry_succeeds_after_transient_failures() {
+        let policy = RetryPolicy::new(3, Duration::from_millis(1));
+        let attempts = AtomicU32::new(0);
+        let op = flaky(2, DomainError::RateLimited("429".to_string()), &attempts);
+
+        assert_eq!(retry_with_backoff(&policy, rate_limited, op).await.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_gives_up_after_max_retries() {
+        let policy = RetryPolicy::new(2, Duration::from_millis(1));
+        let attempts = AtomicU32::new(0);
+        let op = flaky(5, DomainError::RateLimited("429".to_string()), &attempts);
+
+        assert!(matches!(
+            retry_with_backoff(&policy, rate_limited, op).await,
+            Err(DomainError::RateLimited(_))
+        ));
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_skips_non_retryable_errors() {
+        let policy = RetryPolicy::new(3, Duration::from_millis(1));
+        let attempts = AtomicU32::new(0);
+        let op = flaky(1, DomainError::InvalidAmount, &attempts);
+
+        assert!(matches!(
+            retry_with_backoff(&policy, DomainError::is_retryable, op).await,
+            Err(DomainError::InvalidAmount)
+        ));
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_backoff_delay_doubles_with_jitter_and_cap() {
+        let policy = RetryPolicy::new(10, Duration::from_millis(100));
+
+        assert_eq!(backoff_delay(&policy, 1, 0.0), Duration::from_millis(50));
+        assert_eq!(backoff_delay(&policy, 1, 0.5), Duration::from_millis(75));
+        assert_eq!(backoff_delay(&policy, 3, 0.0), Duration::from_millis(200));
+        // 100 ms * 2^19 is far past the cap
+        assert_eq!(backoff_delay(&policy, 20, 0.0), MAX_BACKOFF / 2);
+        assert!(backoff_delay(&policy, 20, 0.999) < MAX_BACKOFF);
+
+        let fraction = random_fraction();
+        assert!((0.0..1.0).contains(&fraction));
+    }
+}
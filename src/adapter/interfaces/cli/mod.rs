@@ -1,15 +1,142 @@
+use alloy::rpc::types::BlockId;
 use clap::{Parser, Subcommand};
+use futures::StreamExt;
+use serde::Serialize;
+use std::io::Write;
 use std::sync::Arc;
+use std::time::Duration;
 use crate::{
-    core::application::GetBalanceHandler,
+    core::application::{
+        handlers::{record_audit, GetBalancesHandler, TransferHandler},
+        unix_now, GetBalanceHandler, TransactionTracker,
+    },
     core::domain::{
-        queries::GetBalanceQuery,
-        services::QueryHandler,
-        value_objects::{Address, Network},
+        commands::TransferCommand,
+        errors::{mask_url, redact_payloads, DomainError},
+        queries::{BalanceQueryResult, GetBalanceQuery, GetBalancesQuery},
+        services::{balance_changes, CommandHandler, QueryHandler},
+        value_objects::{
+            Address, Amount, Balance, BalanceChange, BalanceFormat, BalanceSnapshotEntry, BitcoinAddressType, ChainType,
+            FeeDetails, FeeEstimate, Finality, Network, PaymentUri, SecretKey, TransactionHash, TransferAuditRecord,
+            TransferOutcome, TxStatus,
+        },
+    },
+};
+use crate::adapter::infrastructure::{
+    addressbook::AddressBook,
+    audit::JsonlAuditSink,
+    blockchain::{
+        interval_ticks, BitcoinBlockchainService, CachedBlockchainService, EnsResolver, NoopBlockchainService,
+        SolanaBlockchainService, SolanaTxVersion,
     },
+    keys,
+    prices::{CoinGeckoPriceProvider, PriceProvider},
+    provider_keys::default_rpc_url,
+    AlloyBlockchainService,
 };
-use crate::adapter::infrastructure::AlloyBlockchainService;
-use crate::core::domain::services::BlockchainService;
+use crate::core::domain::services::{BlockchainService, NameResolver};
+
+/// How often `transfer --wait` and `--track` poll the chain
+const CONFIRMATION_POLL_INTERVAL: Duration = Duration::from_secs(3);
+/// Longer unresolvable `--to` / `--address` values are not repeated in errors
+const MAX_ECHOED_INPUT_LEN: usize = 40;
+/// Environment variable overriding the Ethereum mainnet endpoint used for ENS lookups
+const ENS_RPC_URL_ENV: &str = "RUSTWALLET_ENS_RPC_URL";
+/// Endpoint of a local Anvil node started with default options
+const ANVIL_RPC_URL: &str = "http://127.0.0.1:8545";
+
+/// `println!` for progress and decoration, silenced by the context's `--quiet`
+macro_rules! status {
+    ($ctx:expr) => {
+        if !$ctx.quiet {
+            println!();
+        }
+    };
+    ($ctx:expr, $($arg:tt)*) => {
+        if !$ctx.quiet {
+            println!($($arg)*);
+        }
+    };
+}
+
+/// Global options shared by every command, parsed once by [`Cli::run`] and
+/// passed to the command handlers
+#[derive(Debug, Clone, Copy)]
+struct CliContext {
+    /// `--quiet`: print results only, without progress or decoration
+    quiet: bool,
+    /// `--anvil`: EVM commands default to a local Anvil node
+    anvil: bool,
+    /// Number layout from `--locale` / `--grouping` for `balance` and `balances`
+    balance_format: BalanceFormat,
+}
+
+impl CliContext {
+    /// The local Anvil endpoint under `--anvil`, for EVM networks
+    fn anvil_url(&self, network: &Network) -> Option<String> {
+        (self.anvil && network.is_evm()).then(|| ANVIL_RPC_URL.to_string())
+    }
+
+    /// Endpoint for `network`: `--rpc-url`, else the Anvil node under `--anvil`,
+    /// else the network's default
+    fn endpoint(&self, rpc_url: Option<String>, network: &Network) -> String {
+        rpc_url
+            .or_else(|| self.anvil_url(network))
+            .unwrap_or_else(|| default_rpc_url(network))
+    }
+
+    /// Report a sent transaction; `--quiet` prints the bare hash
+    fn print_tx_sent(&self, tx_hash: &TransactionHash) {
+        if self.quiet {
+            println!("{}", tx_hash);
+        } else {
+            println!("✅ Transaction sent: {}", tx_hash);
+        }
+    }
+
+    /// Run `future` while drawing a spinner and `message` on stderr
+    async fn with_spinner<F: std::future::Future>(&self, message: &str, future: F) -> F::Output {
+        const FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+        if self.quiet {
+            return future.await;
+        }
+        tokio::pin!(future);
+        let mut ticker = tokio::time::interval(Duration::from_millis(100));
+        let mut frame = 0;
+        let output = loop {
+            tokio::select! {
+                output = &mut future => break output,
+                _ = ticker.tick() => {
+                    eprint!("\r{} {}", FRAMES[frame % FRAMES.len()], message);
+                    frame += 1;
+                }
+            }
+        };
+        // Clear the spinner line
+        eprint!("\r{}\r", " ".repeat(message.chars().count() + 2));
+        output
+    }
+}
+
+/// Invalid command-line input that clap cannot check (unknown network or
+/// option values, malformed amounts and dates); exits with code 2
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+struct InputError(String);
+
+/// `anyhow!` for invalid input, classified by [`exit_code`] as a usage error
+macro_rules! input_error {
+    ($($arg:tt)*) => {
+        anyhow::Error::new(InputError(format!($($arg)*)))
+    };
+}
+
+/// `bail!` for invalid input
+macro_rules! bail_input {
+    ($($arg:tt)*) => {
+        return Err(input_error!($($arg)*))
+    };
+}
 
 #[derive(Parser)]
 #[command(name = "rustwallet")]
@@ -17,101 +144,2408 @@ use crate::core::domain::services::BlockchainService;
 pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
+
+    /// Print the full error chain instead of a short message when a command fails
+    #[arg(short, long, global = true)]
+    pub verbose: bool,
+
+    /// Print only the essential result (a balance, a transaction hash) or nothing
+    #[arg(short, long, global = true)]
+    pub quiet: bool,
+
+    /// EVM chain definition in `wallet_addEthereumChain` (EIP-3085) JSON, used instead of --network
+    #[arg(long, global = true, value_name = "PATH")]
+    pub network_file: Option<String>,
+
+    /// Send EVM requests to a local Anvil node (http://127.0.0.1:8545) unless --rpc-url is given;
+    /// keeps --network's chain, e.g. for a fork of it (chain ID verification is skipped)
+    #[arg(long, global = true)]
+    pub anvil: bool,
+
+    /// Show balances with a locale's separators (e.g. en: 1,234.5; de: 1.234,5; fr, de-CH)
+    #[arg(long, global = true)]
+    pub locale: Option<String>,
+
+    /// Group thousands in balances (1,234.5); implied by --locale
+    #[arg(long, global = true)]
+    pub grouping: bool,
 }
 
 #[derive(Subcommand)]
 pub enum Commands {
     /// Get balance of an Ethereum address
     Balance {
+        /// Ethereum or Solana address, or address-book name
+        #[arg(short, long)]
+        address: String,
+
+        /// Network (mainnet, sepolia, goerli, holesky, bsc, bsc-testnet, polygon,
+        /// sol, sol-devnet, sol-testnet)
+        #[arg(short, long, default_value = "sepolia")]
+        network: String,
+
+        /// Custom RPC URL (EVM and Solana; optional)
+        #[arg(short, long)]
+        rpc_url: Option<String>,
+
+        /// Solana commitment level (finalized, confirmed, processed)
+        #[arg(long)]
+        commitment: Option<String>,
+
+        /// Query the balance as of a block number (old blocks need an archive node)
+        #[arg(long, conflicts_with = "at_date")]
+        block: Option<u64>,
+
+        /// Query the balance as of 00:00 UTC on a date (YYYY-MM-DD)
+        #[arg(long)]
+        at_date: Option<String>,
+
+        /// Use a fixed stub instead of the network (for smoke tests and demos)
+        #[arg(long, conflicts_with_all = ["rpc_url", "block", "at_date"])]
+        offline: bool,
+
+        /// Units to show the balance in (all, native, raw)
+        #[arg(long, default_value = "all")]
+        units: String,
+
+        /// Also show the value at the current market price (usd)
+        #[arg(long, conflicts_with_all = ["offline", "block", "at_date"])]
+        fiat: Option<String>,
+    },
+
+    /// Query the balances of many addresses at once
+    Balances {
+        /// Address or address-book name (repeatable)
+        #[arg(short, long = "address")]
+        addresses: Vec<String>,
+
+        /// File with one address or name per line (blank lines and # comments ignored)
+        #[arg(short, long)]
+        file: Option<String>,
+
+        /// Network (mainnet, sepolia, goerli, holesky, bsc, bsc-testnet, polygon,
+        /// sol, sol-devnet, sol-testnet)
+        #[arg(short, long, default_value = "sepolia")]
+        network: String,
+
+        /// Custom RPC URL (optional)
+        #[arg(short, long)]
+        rpc_url: Option<String>,
+
+        /// Units to show balances in (all, native, raw)
+        #[arg(long, default_value = "all")]
+        units: String,
+
+        /// Also show values at the current market price (usd)
+        #[arg(long)]
+        fiat: Option<String>,
+
+        /// Output format (text, or ndjson: one JSON event per line)
+        #[arg(long, default_value = "text")]
+        output: String,
+    },
+
+    /// Save the balances of several addresses to a JSON file for a later `diff`
+    Snapshot {
+        /// Address or address-book name (repeatable)
+        #[arg(short, long = "address")]
+        addresses: Vec<String>,
+
+        /// File with one address or name per line (blank lines and # comments ignored)
+        #[arg(long)]
+        addresses_file: Option<String>,
+
+        /// Network (mainnet, sepolia, goerli, holesky, bsc, bsc-testnet, polygon,
+        /// sol, sol-devnet, sol-testnet)
+        #[arg(short, long, default_value = "sepolia")]
+        network: String,
+
+        /// Custom RPC URL (optional)
+        #[arg(short, long)]
+        rpc_url: Option<String>,
+
+        /// File to write the snapshot to
+        #[arg(short, long)]
+        out: String,
+    },
+
+    /// Re-query the addresses of a snapshot and show how each balance changed
+    Diff {
+        /// Snapshot written by `rustwallet snapshot`
+        snapshot: String,
+
+        /// Custom RPC URL (optional)
+        #[arg(short, long)]
+        rpc_url: Option<String>,
+    },
+
+    /// Poll an address's balance and report changes (Ctrl-C to stop)
+    Watch {
         /// Ethereum address (0x...)
         #[arg(short, long)]
         address: String,
 
-        /// Network (mainnet, sepolia, goerli, holesky, bsc, bsc-testnet)
+        /// Network (mainnet, sepolia, goerli, holesky, bsc, bsc-testnet, polygon)
+        #[arg(short, long, default_value = "sepolia")]
+        network: String,
+
+        /// Custom RPC URL (optional)
+        #[arg(short, long)]
+        rpc_url: Option<String>,
+
+        /// WebSocket RPC URL: check on every new block instead of polling
+        #[arg(long)]
+        ws_url: Option<String>,
+
+        /// Seconds between balance checks (without --ws-url)
+        #[arg(short, long, default_value_t = 15)]
+        interval: u64,
+
+        /// Warn when the balance drops below this amount (in native units, e.g. 0.1)
+        #[arg(long)]
+        alert_below: Option<f64>,
+
+        /// Exit with a non-zero status when the --alert-below threshold is crossed
+        #[arg(long, requires = "alert_below")]
+        exit_on_alert: bool,
+
+        /// Output format (text, or ndjson: one JSON event per line, flushed as it happens)
+        #[arg(long, default_value = "text")]
+        output: String,
+    },
+
+    /// Send native coins (EVM), or SPL tokens on Solana with --token
+    Transfer {
+        /// Sender address
+        #[arg(long)]
+        from: String,
+
+        /// Recipient address or address-book name
+        #[arg(long, required_unless_present = "uri")]
+        to: Option<String>,
+
+        /// Amount in whole units (e.g. 0.5 ETH, or 1.25 tokens with --token)
+        #[arg(long, required_unless_present_any = ["all", "uri"])]
+        amount: Option<String>,
+
+        /// Payment URI giving the recipient and amount (e.g. ethereum:0x...?value=1e18)
+        #[arg(long, conflicts_with_all = ["to", "all", "token"])]
+        uri: Option<String>,
+
+        /// Send the whole balance minus the network fee (EVM only)
+        #[arg(long, conflicts_with_all = ["amount", "token", "gas_price_gwei"])]
+        all: bool,
+
+        /// Allow sending an amount of zero, which is otherwise rejected as a likely mistake (EVM only)
+        #[arg(long, conflicts_with_all = ["all", "token"])]
+        allow_zero: bool,
+
+        /// Allow sending to the sender's own address, which is otherwise rejected
+        #[arg(long)]
+        allow_self: bool,
+
+        /// Network (mainnet, sepolia, ..., bsc, polygon; sol, sol-devnet, sol-testnet with --token)
+        #[arg(short, long, default_value = "sepolia")]
+        network: String,
+
+        /// Custom RPC URL (optional)
+        #[arg(short, long)]
+        rpc_url: Option<String>,
+
+        /// Name of the environment variable holding the sender's private key
+        #[cfg_attr(feature = "ledger", arg(long, required_unless_present = "ledger"))]
+        #[cfg_attr(not(feature = "ledger"), arg(long, required = true))]
+        private_key_env: Option<String>,
+
+        /// Sign on a Ledger device instead of with a private key (EVM only)
+        #[cfg(feature = "ledger")]
+        #[arg(long, conflicts_with_all = ["private_key_env", "token"])]
+        ledger: bool,
+
+        /// BIP-44 path of the Ledger account to send from
+        #[cfg(feature = "ledger")]
+        #[arg(long, default_value = keys::ledger::DEFAULT_LEDGER_PATH)]
+        hd_path: String,
+
+        /// SPL token mint address (Solana networks only)
+        #[arg(long)]
+        token: Option<String>,
+
+        /// Legacy gas price in gwei (EVM only; default: provider estimate)
+        #[arg(long, conflicts_with = "token")]
+        gas_price_gwei: Option<u64>,
+
+        /// Solana transaction format (legacy, v0)
+        #[arg(long, default_value = "legacy", requires = "token")]
+        tx_version: String,
+
+        /// Address lookup table to reference accounts through (v0 only; repeatable)
+        #[arg(long = "lookup-table", requires = "token")]
+        lookup_tables: Vec<String>,
+
+        /// After broadcasting, wait for this many confirmations and print the receipt
+        /// (EVM only; without a value, the network's recommended depth)
+        #[arg(long, value_name = "CONFIRMATIONS", num_args = 0..=1, conflicts_with = "token")]
+        wait: Option<Option<u64>>,
+
+        /// After broadcasting, track the transaction until it is included in a block
+        /// and print whether it succeeded (EVM and Solana)
+        #[arg(long, conflicts_with = "wait")]
+        track: bool,
+
+        /// Give up waiting after this many seconds with --wait or --track
+        /// (the transaction stays pending)
+        #[arg(long, default_value_t = 300)]
+        wait_timeout: u64,
+    },
+
+    /// Show the most an address can send after the network fee (what `transfer --all` sends on EVM)
+    MaxSend {
+        /// Sender address or address-book name
+        #[arg(long)]
+        from: String,
+
+        /// Recipient address or address-book name
+        #[arg(long)]
+        to: String,
+
+        /// Network (mainnet, sepolia, bsc, polygon, btc, btc-testnet, sol, sol-devnet, ...)
+        #[arg(short, long, default_value = "sepolia")]
+        network: String,
+
+        /// Custom RPC URL (EVM and Solana; optional)
+        #[arg(short, long)]
+        rpc_url: Option<String>,
+    },
+
+    /// Derive the address controlled by a private key
+    Address {
+        /// Name of the environment variable holding the private key
+        #[arg(long)]
+        private_key_env: String,
+
+        /// Chain (eth, btc, btc-testnet, sol)
+        #[arg(short, long, default_value = "eth")]
+        chain: String,
+
+        /// Bitcoin address type (p2pkh, p2sh, segwit)
+        #[arg(long, default_value = "segwit")]
+        address_type: String,
+    },
+
+    /// List the supported networks and their `--network` names
+    Networks {
+        /// Output format (table, json)
+        #[arg(long, default_value = "table")]
+        output: String,
+    },
+
+    /// Run the REST API (GET /balance/:network/:address, POST /transfer)
+    #[cfg(feature = "http")]
+    Serve {
+        /// Port to listen on
+        #[arg(long, default_value_t = 8080)]
+        port: u16,
+
+        /// Interface to bind (transfers carry private keys: keep this local
+        /// unless the server sits behind TLS and authentication)
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+    },
+
+    /// Diagnose connectivity to a network's RPC endpoint (latency, height, node version)
+    Health {
+        /// Network (mainnet, sepolia, bsc, polygon, btc, btc-testnet, sol, sol-devnet, ...)
+        #[arg(short, long, default_value = "sepolia")]
+        network: String,
+
+        /// Custom RPC URL (EVM and Solana; optional)
+        #[arg(short, long)]
+        rpc_url: Option<String>,
+    },
+
+    /// Manage named addresses (usable as `balance --address` / `transfer --to`)
+    Book {
+        #[command(subcommand)]
+        action: BookCommand,
+    },
+
+    /// Call a contract's read-only function with raw calldata (eth_call)
+    Call {
+        /// Contract address (0x...)
+        #[arg(long)]
+        to: String,
+
+        /// ABI-encoded calldata in hex (0x-prefixed selector + arguments)
+        #[arg(long)]
+        data: String,
+
+        /// Network (mainnet, sepolia, goerli, holesky, bsc, bsc-testnet, polygon)
+        #[arg(short, long, default_value = "sepolia")]
+        network: String,
+
+        /// Custom RPC URL (optional)
+        #[arg(short, long)]
+        rpc_url: Option<String>,
+
+        /// Block number to call at (default: latest)
+        #[arg(long)]
+        block: Option<u64>,
+    },
+
+    /// Read a raw 32-byte storage slot of a contract (eth_getStorageAt)
+    Storage {
+        /// Contract address (0x...)
+        #[arg(long)]
+        address: String,
+
+        /// Slot index, decimal or 0x-prefixed hex (e.g. an EIP-1967 proxy slot)
+        #[arg(long)]
+        slot: String,
+
+        /// Network (mainnet, sepolia, goerli, holesky, bsc, bsc-testnet, polygon)
         #[arg(short, long, default_value = "sepolia")]
         network: String,
 
         /// Custom RPC URL (optional)
         #[arg(short, long)]
         rpc_url: Option<String>,
+
+        /// Block number to read at (default: latest)
+        #[arg(long)]
+        block: Option<u64>,
+    },
+
+    /// Check an address before sending funds (format, chain, EIP-55 checksum)
+    ValidateAddress {
+        /// Address to validate (EVM, Bitcoin or Solana)
+        #[arg(short, long)]
+        address: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum BookCommand {
+    /// Save (or replace) a named address
+    Add {
+        /// Name to refer to the address by
+        name: String,
+
+        /// Address (EVM, Bitcoin or Solana)
+        address: String,
+
+        /// Network the address is for (default: the chain's mainnet)
+        #[arg(short, long)]
+        network: Option<String>,
+    },
+
+    /// List saved addresses
+    List,
+
+    /// Remove a named address
+    Rm {
+        /// Name to remove
+        name: String,
     },
 }
 
 impl Cli {
     pub async fn run(self) -> anyhow::Result<()> {
+        let network_file = match &self.network_file {
+            Some(path) => {
+                let json = std::fs::read_to_string(path)
+                    .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path, e))?;
+                Some(Network::from_chain_config_json(&json)?)
+            }
+            None => None,
+        };
+        let network_file = network_file.as_ref();
+        let ctx = &CliContext {
+            quiet: self.quiet,
+            anvil: self.anvil,
+            balance_format: parse_balance_format(self.locale.as_deref(), self.grouping)?,
+        };
         match self.command {
             Commands::Balance {
                 address,
                 network,
                 rpc_url,
+                commitment,
+                block,
+                at_date,
+                offline,
+                units,
+                fiat,
+            } => {
+                let request = BalanceRequest {
+                    address,
+                    network: Self::parse_balances_network(&network, network_file)?,
+                    rpc_url,
+                    commitment,
+                    block,
+                    at_date,
+                    offline,
+                    units: parse_display_units(&units)?,
+                    fiat: fiat.as_deref().map(parse_fiat).transpose()?,
+                };
+                Self::handle_balance_static(ctx, request).await?;
+            }
+            Commands::Balances {
+                addresses,
+                file,
+                network,
+                rpc_url,
+                units,
+                fiat,
+                output,
+            } => {
+                let units = parse_display_units(&units)?;
+                let fiat = fiat.as_deref().map(parse_fiat).transpose()?;
+                let output = parse_output_format(&output)?;
+                let network = Self::parse_balances_network(&network, network_file)?;
+                Self::handle_balances(ctx, addresses, file, network, rpc_url, (units, fiat, output)).await?;
+            }
+            Commands::Snapshot {
+                addresses,
+                addresses_file,
+                network,
+                rpc_url,
+                out,
+            } => {
+                let network = Self::parse_balances_network(&network, network_file)?;
+                Self::handle_snapshot(ctx, addresses, addresses_file, network, rpc_url, out).await?;
+            }
+            Commands::Diff { snapshot, rpc_url } => {
+                Self::handle_diff(ctx, snapshot, rpc_url).await?;
+            }
+            Commands::Watch {
+                address,
+                network,
+                rpc_url,
+                ws_url,
+                interval,
+                alert_below,
+                exit_on_alert,
+                output,
+            } => {
+                let output = parse_output_format(&output)?;
+                let alert = (alert_below, exit_on_alert);
+                let network = Self::parse_evm_network(&network, network_file)?;
+                let endpoints = (rpc_url, ws_url);
+                Self::handle_watch(ctx, address, network, endpoints, interval, alert, output).await?;
+            }
+            Commands::Transfer {
+                from,
+                to,
+                amount,
+                uri,
+                all: _,
+                allow_zero,
+                allow_self,
+                network,
+                rpc_url,
+                private_key_env,
+                #[cfg(feature = "ledger")]
+                ledger,
+                #[cfg(feature = "ledger")]
+                hd_path,
+                token,
+                gas_price_gwei,
+                tx_version,
+                lookup_tables,
+                wait,
+                track,
+                wait_timeout,
+            } => {
+                let signer = match private_key_env {
+                    Some(env_var) => SignerSource::PrivateKeyEnv(env_var),
+                    #[cfg(feature = "ledger")]
+                    None if ledger => SignerSource::Ledger(hd_path),
+                    None => unreachable!("clap requires --private-key-env"),
+                };
+                let network = match &token {
+                    Some(_) => Self::parse_token_network(&network)?,
+                    None => Self::parse_evm_network(&network, network_file)?,
+                };
+                let (to, amount) = match uri {
+                    Some(uri) => payment_uri_target(&uri, &network, amount)?,
+                    None => (to.expect("clap requires --to without --uri"), amount),
+                };
+                let request = TransferRequest {
+                    from,
+                    to,
+                    amount,
+                    network,
+                    rpc_url,
+                    signer,
+                    gas_price_gwei,
+                    allow_zero,
+                    allow_self,
+                    wait: wait.map(|confirmations| (confirmations, Duration::from_secs(wait_timeout))),
+                    track: track.then(|| Duration::from_secs(wait_timeout)),
+                };
+                match token {
+                    Some(mint) => {
+                        let tx_version = parse_solana_tx_version(&tx_version, lookup_tables)?;
+                        Self::handle_token_transfer(ctx, request, mint, tx_version).await?
+                    }
+                    None => Self::handle_transfer(ctx, request).await?,
+                }
+            }
+            Commands::MaxSend {
+                from,
+                to,
+                network,
+                rpc_url,
+            } => {
+                let network = Self::parse_network(&network, network_file)?;
+                Self::handle_max_send(ctx, from, to, network, rpc_url).await?;
+            }
+            Commands::Address {
+                private_key_env,
+                chain,
+                address_type,
+            } => {
+                Self::handle_address(private_key_env, chain, address_type)?;
+            }
+            Commands::Networks { output } => {
+                Self::handle_networks(&output)?;
+            }
+            #[cfg(feature = "http")]
+            Commands::Serve { port, host } => {
+                Self::handle_serve(&host, port).await?;
+            }
+            Commands::Health { network, rpc_url } => {
+                Self::handle_health(ctx, Self::parse_network(&network, network_file)?, rpc_url).await?;
+            }
+            Commands::Book { action } => {
+                Self::handle_book(ctx, action)?;
+            }
+            Commands::Call {
+                to,
+                data,
+                network,
+                rpc_url,
+                block,
+            } => {
+                let network = Self::parse_evm_network(&network, network_file)?;
+                Self::handle_call(ctx, to, data, network, rpc_url, block).await?;
+            }
+            Commands::Storage {
+                address,
+                slot,
+                network,
+                rpc_url,
+                block,
             } => {
-                Self::handle_balance_static(address, network, rpc_url).await?;
+                let network = Self::parse_evm_network(&network, network_file)?;
+                Self::handle_storage(ctx, address, slot, network, rpc_url, block).await?;
+            }
+            Commands::ValidateAddress { address } => {
+                Self::handle_validate_address(ctx, address)?;
             }
         }
         Ok(())
     }
 
-    async fn handle_balance_static(
-        address_str: String,
-        network_str: String,
-        rpc_url: Option<String>,
-    ) -> anyhow::Result<()> {
-        // Parse address
-        let address = Address::new(address_str)?;
+    async fn handle_balance_static(ctx: &CliContext, request: BalanceRequest) -> anyhow::Result<()> {
+        let BalanceRequest {
+            address: address_str,
+            network,
+            rpc_url,
+            commitment,
+            block,
+            at_date,
+            offline,
+            units,
+            fiat,
+        } = request;
 
-        // Parse network
-        let network = match network_str.to_lowercase().as_str() {
-            "mainnet" | "eth" => Network::Mainnet,
-            "sepolia" => Network::Sepolia,
-            "goerli" => Network::Goerli,
-            "holesky" => Network::Holesky,
-            "bsc" | "bsc-mainnet" => Network::BscMainnet,
-            "bsc-testnet" => Network::BscTestnet,
-            _ => {
-                return Err(anyhow::anyhow!(
-                    "Unknown network: {}. Use mainnet, sepolia, goerli, holesky, bsc, or bsc-testnet",
-                    network_str
-                ));
-            }
-        };
+        // Resolve the address (or address-book name) for the network
+        let address = resolve_address(ctx, &address_str, &network).await?;
 
-        println!("🔍 Querying balance...");
-        println!("   Address: {}", address);
-        println!("   Network: {}", network);
+        // Parse options before touching the network
+        let at_timestamp = at_date.as_deref().map(parse_date_to_unix).transpose()?;
+        let finality = commitment.as_deref().map(parse_commitment).transpose()?;
+        if finality.is_some() && !network.is_solana() {
+            bail_input!("--commitment only applies to Solana networks");
+        }
+        if network.is_solana() && (block.is_some() || at_timestamp.is_some()) {
+            bail_input!("--block and --at-date are not supported on Solana networks");
+        }
 
-        // Create blockchain service
-        let blockchain_service: Arc<dyn BlockchainService> = if let Some(rpc) = rpc_url {
-            println!("   RPC URL: {}", rpc);
-            Arc::new(AlloyBlockchainService::new(network.clone(), &rpc).await?)
-        } else {
-            let default_rpc = network.default_rpc_url();
-            println!("   RPC URL: {}", default_rpc);
-            Arc::new(AlloyBlockchainService::new_with_default_rpc(network.clone()).await?)
-        };
+        status!(ctx, "🔍 Querying balance...");
+        status!(ctx, "   Address: {}", address);
+        status!(ctx, "   Network: {}", network);
 
-        // Test connection
-        if !blockchain_service.is_connected().await {
-            return Err(anyhow::anyhow!("Failed to connect to network"));
+        if offline {
+            status!(ctx, "   RPC URL: (offline)");
+            let blockchain_service: Arc<dyn BlockchainService> =
+                Arc::new(NoopBlockchainService::new().with_chain_type(network.chain_type()));
+            return Self::run_balance_query(ctx, blockchain_service, address, network, units, None).await;
         }
 
-        let block_number = blockchain_service.get_block_number().await?;
-        println!("   Current Block: #{}", block_number);
-        println!();
+        if network.is_solana() {
+            let rpc = ctx.endpoint(rpc_url, &network);
+            status!(ctx, "   RPC URL: {}", mask_url(&rpc));
+            let mut service = SolanaBlockchainService::new_with_rpc(network.clone(), &rpc).await?;
+            if let (Some(commitment), Some(finality)) = (commitment, finality) {
+                status!(ctx, "   Commitment: {}", commitment.to_lowercase());
+                service = service.with_commitment(finality);
+            }
+            return Self::run_balance_query(ctx, Arc::new(service), address, network, units, fiat).await;
+        }
 
-        // Create query handler
-        let handler = GetBalanceHandler::new(blockchain_service);
+        // Create blockchain service
+        let rpc = ctx.endpoint(rpc_url, &network);
+        status!(ctx, "   RPC URL: {}", mask_url(&rpc));
+        let alloy_service = Arc::new(AlloyBlockchainService::new(network.clone(), &rpc).await?);
+        let blockchain_service: Arc<dyn BlockchainService> = alloy_service.clone();
 
-        // Execute query
-        let query = GetBalanceQuery::new(address, network);
-        let result = handler.handle(query).await?;
+        // Run the query, aborting cleanly on Ctrl-C
+        let query = async {
+            if block.is_some() || at_timestamp.is_some() {
+                let at = (block, at_timestamp);
+                Self::run_historical_balance_query(ctx, &alloy_service, &address, &network, at, units).await
+            } else {
+                let service = blockchain_service.clone();
+                Self::run_balance_query(ctx, service, address.clone(), network.clone(), units, fiat).await
+            }
+        };
+        let outcome = tokio::select! {
+            result = query => result,
+            _ = tokio::signal::ctrl_c() => {
+                status!(ctx, "\n⚠️  Interrupted, shutting down...");
+                Ok(())
+            }
+        };
 
-        // Display result
-        println!("✅ Balance Query Result:");
-        println!("   Address:  {}", result.address);
-        println!("   Network:  {}", result.network);
-        println!("   Balance:  {}", result.balance.format_ether(6));
-        println!("   Wei:      {} Wei", result.balance.to_wei());
+        // Always release connections before exiting
+        blockchain_service.shutdown().await;
 
+        outcome
+    }
+
+    async fn handle_balances(
+        ctx: &CliContext,
+        inputs: Vec<String>,
+        file: Option<String>,
+        network: Network,
+        rpc_url: Option<String>,
+        (units, fiat, output): (DisplayUnits, Option<FiatCurrency>, OutputFormat),
+    ) -> anyhow::Result<()> {
+        let addresses = read_address_list(ctx, inputs, file.as_deref(), &network).await?;
+
+        let rpc = ctx.endpoint(rpc_url, &network);
+        if output == OutputFormat::Ndjson {
+            let results = Self::query_balances(&addresses, &network, &rpc).await?;
+            for (address, result) in addresses.iter().zip(results) {
+                match result {
+                    Ok(result) => emit_event(&CliEvent::balance(address, &network, result.balance))?,
+                    Err(e) => emit_event(&CliEvent::Error { address, message: e.user_message() })?,
+                }
+            }
+            return Ok(());
+        }
+
+        status!(ctx, "🔍 Querying {} balances on {} ({})", addresses.len(), network, mask_url(&rpc));
+        let results = Self::query_balances(&addresses, &network, &rpc).await?;
+
+        // One price for the whole list; without it, balances are still shown
+        let price = match fiat {
+            Some(fiat) => match fiat.native_price(&network).await {
+                Ok(price) => Some((fiat, price)),
+                Err(e) => {
+                    status!(ctx, "   ⚠️  {} value unavailable: {}", fiat, e.user_message());
+                    None
+                }
+            },
+            None => None,
+        };
+
+        status!(ctx);
+        for (address, result) in addresses.iter().zip(results) {
+            match result {
+                Ok(result) => match price {
+                    Some((fiat, price)) => println!(
+                        "   {}  {}  ({})",
+                        address,
+                        units.render(&result, &ctx.balance_format),
+                        fiat.format_value(&result, price)
+                    ),
+                    None => println!("   {}  {}", address, units.render(&result, &ctx.balance_format)),
+                },
+                Err(e) => println!("   {}  ❌ {}", address, e.user_message()),
+            }
+        }
+        Ok(())
+    }
+
+    async fn handle_snapshot(
+        ctx: &CliContext,
+        inputs: Vec<String>,
+        addresses_file: Option<String>,
+        network: Network,
+        rpc_url: Option<String>,
+        out: String,
+    ) -> anyhow::Result<()> {
+        let addresses = read_address_list(ctx, inputs, addresses_file.as_deref(), &network).await?;
+
+        let rpc = ctx.endpoint(rpc_url, &network);
+        status!(ctx, "📸 Snapshotting {} balances on {} ({})", addresses.len(), network, mask_url(&rpc));
+        let results = Self::query_balances(&addresses, &network, &rpc).await?;
+
+        // A snapshot with holes would report bogus changes later, so fail instead
+        let timestamp = unix_now();
+        let entries = addresses
+            .iter()
+            .zip(results)
+            .map(|(address, result)| {
+                result
+                    .map(|result| BalanceSnapshotEntry::new(address.clone(), network.clone(), result.balance, timestamp))
+                    .map_err(|e| anyhow::Error::new(e).context(format!("Failed to query {}", address)))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        std::fs::write(&out, serde_json::to_string_pretty(&entries)?)
+            .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", out, e))?;
+        status!(ctx, "✅ Saved {} balances to {}", entries.len(), out);
+        Ok(())
+    }
+
+    async fn handle_diff(ctx: &CliContext, snapshot: String, rpc_url: Option<String>) -> anyhow::Result<()> {
+        let contents = std::fs::read_to_string(&snapshot)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", snapshot, e))?;
+        let entries: Vec<BalanceSnapshotEntry> = serde_json::from_str(&contents)
+            .map_err(|e| input_error!("{} is not a balance snapshot: {}", snapshot, e))?;
+
+        // One batch per network, in the order networks first appear
+        let mut networks: Vec<Network> = Vec::new();
+        for entry in &entries {
+            if !networks.contains(&entry.network) {
+                networks.push(entry.network.clone());
+            }
+        }
+
+        let (mut increased, mut decreased, mut unchanged) = (0, 0, 0);
+        for network in networks {
+            let snapshotted: Vec<_> = entries.iter().filter(|entry| entry.network == network).collect();
+            let addresses: Vec<_> = snapshotted.iter().map(|entry| entry.address.clone()).collect();
+            let rpc = ctx.endpoint(rpc_url.clone(), &network);
+            status!(ctx, "🔍 Re-querying {} balances on {} ({})", addresses.len(), network, mask_url(&rpc));
+            let results = Self::query_balances(&addresses, &network, &rpc).await?;
+
+            let format = |balance: Balance| {
+                format!("{} {}", balance.format_exact(network.native_decimals()), network.native_symbol())
+            };
+            status!(ctx);
+            for (entry, result) in snapshotted.into_iter().zip(results) {
+                let current = match result {
+                    Ok(result) => result.balance,
+                    Err(e) if ctx.quiet => {
+                        eprintln!("{}: {}", entry.address, e.user_message());
+                        continue;
+                    }
+                    Err(e) => {
+                        println!("   {}  ❌ {}", entry.address, e.user_message());
+                        continue;
+                    }
+                };
+                let since = format!("since {}", Self::format_unix_date(entry.timestamp));
+                // `--quiet` prints "<address> <signed change>" per line
+                match entry.change_to(current) {
+                    BalanceChange::Increased(delta) => {
+                        increased += 1;
+                        if ctx.quiet {
+                            println!("{} +{}", entry.address, format(delta));
+                        } else {
+                            println!("   {}  📈 +{}  ({} → {}, {})", entry.address, format(delta), format(entry.balance), format(current), since);
+                        }
+                    }
+                    BalanceChange::Decreased(delta) => {
+                        decreased += 1;
+                        if ctx.quiet {
+                            println!("{} -{}", entry.address, format(delta));
+                        } else {
+                            println!("   {}  📉 -{}  ({} → {}, {})", entry.address, format(delta), format(entry.balance), format(current), since);
+                        }
+                    }
+                    BalanceChange::Unchanged => {
+                        unchanged += 1;
+                        if ctx.quiet {
+                            println!("{} 0", entry.address);
+                        } else {
+                            println!("   {}  ➖ unchanged  ({}, {})", entry.address, format(current), since);
+                        }
+                    }
+                }
+            }
+            status!(ctx);
+        }
+
+        status!(ctx, "{} increased, {} decreased, {} unchanged", increased, decreased, unchanged);
+        Ok(())
+    }
+
+    /// Parse the network of a balance command (EVM or Solana)
+    fn parse_balances_network(network_str: &str, network_file: Option<&Network>) -> anyhow::Result<Network> {
+        match Network::from_name(network_str).filter(Network::is_solana) {
+            Some(network) => Ok(network),
+            None => Self::parse_evm_network(network_str, network_file),
+        }
+    }
+
+    /// Parse any supported network name (EVM, Bitcoin or Solana)
+    ///
+    /// A network loaded with `--network-file` takes precedence over the name.
+    fn parse_network(network_str: &str, network_file: Option<&Network>) -> anyhow::Result<Network> {
+        match network_file {
+            Some(network) => Ok(network.clone()),
+            None => Network::from_name(network_str).ok_or_else(|| input_error!("Unknown network: {}", network_str)),
+        }
+    }
+
+    /// Parse the Solana network of a `transfer --token`
+    fn parse_token_network(network_str: &str) -> anyhow::Result<Network> {
+        Network::from_name(network_str).filter(Network::is_solana).ok_or_else(|| {
+            input_error!(
+                "--token requires a Solana network (sol, sol-devnet, sol-testnet), got {}",
+                network_str
+            )
+        })
+    }
+
+    /// Query `addresses` in one batch, showing progress on stderr
+    async fn query_balances(
+        addresses: &[Address],
+        network: &Network,
+        rpc: &str,
+    ) -> anyhow::Result<Vec<Result<BalanceQueryResult, DomainError>>> {
+        let service: Arc<dyn BlockchainService> = if network.is_solana() {
+            Arc::new(SolanaBlockchainService::new_with_rpc(network.clone(), rpc).await?)
+        } else {
+            Arc::new(AlloyBlockchainService::new(network.clone(), rpc).await?)
+        };
+
+        let handler = GetBalancesHandler::new(service).with_progress(Box::new(|done, total| {
+            eprint!("\r   {}/{} done", done, total);
+            if done == total {
+                eprintln!();
+            }
+        }));
+        Ok(handler.handle(GetBalancesQuery::new(addresses.to_vec(), network.clone())).await?)
+    }
+
+    /// Parse an EVM network name as accepted by the balance/watch commands
+    ///
+    /// A network loaded with `--network-file` takes precedence over the name.
+    fn parse_evm_network(network_str: &str, network_file: Option<&Network>) -> anyhow::Result<Network> {
+        if let Some(network) = network_file {
+            return Ok(network.clone());
+        }
+        let network = match network_str.to_lowercase().as_str() {
+            "mainnet" | "eth" => Network::Mainnet,
+            "sepolia" => Network::Sepolia,
+            "goerli" => Network::Goerli,
+            "holesky" => Network::Holesky,
+            "bsc" | "bsc-mainnet" => Network::BscMainnet,
+            "bsc-testnet" => Network::BscTestnet,
+            "polygon" | "matic" => Network::Polygon,
+            _ => {
+                return Err(input_error!(
+                    "Unknown network: {}. Use mainnet, sepolia, goerli, holesky, bsc, bsc-testnet, or polygon",
+                    network_str
+                ));
+            }
+        };
+        Ok(network)
+    }
+
+    async fn handle_watch(
+        ctx: &CliContext,
+        address_str: String,
+        network: Network,
+        (rpc_url, ws_url): (Option<String>, Option<String>),
+        interval_secs: u64,
+        (alert_below, exit_on_alert): (Option<f64>, bool),
+        output: OutputFormat,
+    ) -> anyhow::Result<()> {
+        let address = Address::new(address_str)?;
+        let threshold = alert_below.map(Balance::from_ether);
+        let ndjson = output == OutputFormat::Ndjson;
+
+        let rpc = ctx.endpoint(rpc_url, &network);
+        let mut builder = AlloyBlockchainService::builder().network(network.clone()).rpc_urls([rpc]);
+        if let Some(ws_url) = &ws_url {
+            builder = builder.ws_url(ws_url);
+        }
+        let blockchain_service: Arc<dyn BlockchainService> = Arc::new(builder.build().await?);
+        let handler = GetBalanceHandler::new(blockchain_service.clone());
+
+        if !ndjson {
+            match &ws_url {
+                Some(ws_url) => {
+                    status!(ctx, "👀 Watching balance (every new block via {}, Ctrl-C to stop)...", mask_url(ws_url))
+                }
+                None => status!(ctx, "👀 Watching balance (every {}s, Ctrl-C to stop)...", interval_secs),
+            }
+            status!(ctx, "   Address: {}", address);
+            status!(ctx, "   Network: {}", network);
+            if let Some(threshold) = &threshold {
+                status!(ctx, "   Alert:   below {}", threshold.format_native(6, &network));
+            }
+            status!(ctx);
+        }
+
+        let watch = async {
+            let ticks = blockchain_service.new_block_ticks(interval_ticks(Duration::from_secs(interval_secs.max(1))));
+            let reads = ticks.then(|_| async {
+                let query = GetBalanceQuery::new(address.clone(), network.clone());
+                handler.handle(query).await.map(|result| result.balance)
+            });
+            let mut balances = balance_changes(reads);
+            let mut alerting = false;
+
+            while let Some(read) = balances.next().await {
+                let balance = match read {
+                    Ok(balance) => balance,
+                    Err(e) if ndjson => {
+                        emit_event(&CliEvent::Error { address: &address, message: e.user_message() })?;
+                        continue;
+                    }
+                    Err(e) => {
+                        eprintln!("⚠️  Query failed: {}", e.user_message());
+                        continue;
+                    }
+                };
+                if ndjson {
+                    emit_event(&CliEvent::balance(&address, &network, balance))?;
+                } else if ctx.quiet {
+                    println!("{}", balance.format_native(6, &network));
+                } else {
+                    println!("💰 {}", balance.format_native(6, &network));
+                }
+
+                let Some(threshold) = &threshold else { continue };
+                let below = balance.is_below(threshold.to_wei());
+                if below && !alerting {
+                    if ndjson {
+                        emit_event(&CliEvent::Alert {
+                            address: &address,
+                            network: network_name(&network),
+                            balance: balance.to_wei().to_string(),
+                            threshold: threshold.to_wei().to_string(),
+                        })?;
+                    } else if ctx.quiet {
+                        println!(
+                            "ALERT: balance {} is below {}",
+                            balance.format_native(6, &network),
+                            threshold.format_native(6, &network),
+                        );
+                    } else {
+                        println!();
+                        println!(
+                            "🚨🚨🚨 ALERT: balance {} is below {} 🚨🚨🚨",
+                            balance.format_native(6, &network),
+                            threshold.format_native(6, &network),
+                        );
+                        println!();
+                    }
+                    if exit_on_alert {
+                        return Err(anyhow::anyhow!("Balance dropped below alert threshold"));
+                    }
+                } else if !below && alerting {
+                    if ndjson {
+                        emit_event(&CliEvent::Recovered {
+                            address: &address,
+                            network: network_name(&network),
+                            balance: balance.to_wei().to_string(),
+                        })?;
+                    } else {
+                        status!(ctx, "✅ Balance recovered above threshold");
+                    }
+                }
+                alerting = below;
+            }
+            Ok(())
+        };
+
+        let outcome = tokio::select! {
+            result = watch => result,
+            _ = tokio::signal::ctrl_c() => {
+                eprintln!("\n⚠️  Interrupted, shutting down...");
+                Ok(())
+            }
+        };
+
+        blockchain_service.shutdown().await;
+        outcome
+    }
+
+    async fn run_historical_balance_query(
+        ctx: &CliContext,
+        service: &AlloyBlockchainService,
+        address: &Address,
+        network: &Network,
+        (block, at_timestamp): (Option<u64>, Option<u64>),
+        units: DisplayUnits,
+    ) -> anyhow::Result<()> {
+        let block_number = match (block, at_timestamp) {
+            (Some(block), _) => block,
+            (None, Some(ts)) => {
+                status!(ctx, "   Date:    {} (unix {})", Self::format_unix_date(ts), ts);
+                service.block_at_timestamp(ts).await?
+            }
+            (None, None) => unreachable!("historical query without block or date"),
+        };
+        status!(ctx, "   Block:   #{}", block_number);
+        status!(ctx);
+
+        let balance = service.get_balance_at_block(address, block_number).await?;
+        let result = BalanceQueryResult::new(address.clone(), network.clone(), balance);
+
+        if ctx.quiet {
+            println!("{}", units.render(&result, &ctx.balance_format));
+            return Ok(());
+        }
+        println!("✅ Historical Balance:");
+        println!("   Address:  {}", address);
+        println!("   Network:  {}", network);
+        println!("   Block:    #{}", block_number);
+        println!("   Balance:  {}", units.render(&result, &ctx.balance_format));
+
+        Ok(())
+    }
+
+    async fn handle_transfer(ctx: &CliContext, request: TransferRequest) -> anyhow::Result<()> {
+        let from = Address::new(request.from)?;
+        let network = request.network.clone();
+        let to = resolve_address(ctx, &request.to, &network).await?;
+        if from == to && !request.allow_self {
+            return Err(DomainError::SelfTransfer(from.to_string()).into());
+        }
+
+        let rpc = ctx.endpoint(request.rpc_url, &network);
+        let alloy_service = Arc::new(AlloyBlockchainService::new(network.clone(), &rpc).await?);
+        let blockchain_service: Arc<dyn BlockchainService> = alloy_service.clone();
+
+        let symbol = network.native_symbol();
+        match &request.amount {
+            Some(amount) => status!(ctx, "💸 Sending {} {}...", amount, symbol),
+            None => status!(ctx, "💸 Sending entire {} balance...", symbol),
+        }
+        status!(ctx, "   From:    {}", from);
+        status!(ctx, "   To:      {}", to);
+        status!(ctx, "   Network: {}", network);
+
+        let amount = request.amount.as_deref().map(|amount| parse_units(amount, 18)).transpose()?;
+        let with_options = |mut command: TransferCommand| {
+            if let Some(gwei) = request.gas_price_gwei {
+                command = command.with_gas_price_gwei(gwei);
+            }
+            if request.allow_zero {
+                command = command.with_allow_zero();
+            }
+            if request.allow_self {
+                command = command.with_allow_self();
+            }
+            command
+        };
+        let transfer_handler = || {
+            let handler = TransferHandler::new(blockchain_service.clone());
+            match JsonlAuditSink::from_env() {
+                Some(sink) => handler.with_audit_sink(Arc::new(sink)),
+                None => handler,
+            }
+        };
+        let tx_hash = match request.signer {
+            SignerSource::PrivateKeyEnv(env_var) => {
+                let private_key = read_private_key(&env_var)?;
+                match amount {
+                    Some(amount) => {
+                        let amount = Amount::from_wei(amount);
+                        let command = TransferCommand::new(from, to, amount, network.clone(), private_key);
+                        transfer_handler().handle(with_options(command)).await?.tx_hash
+                    }
+                    None => {
+                        let signer = keys::PrivateKeySigner::new(&private_key, &network)?;
+                        let result = alloy_service.sweep(&from, &to, &signer).await;
+                        audit_sweep(&from, &to, &network, &result).await;
+                        result?.0
+                    }
+                }
+            }
+            #[cfg(feature = "ledger")]
+            SignerSource::Ledger(path) => {
+                use crate::core::domain::services::Signer as _;
+
+                let signer = keys::LedgerSigner::connect(&path).await?;
+                let device_address = signer.address();
+                if device_address != from {
+                    bail_input!(
+                        "Ledger account {} at {} does not match --from {}",
+                        device_address,
+                        signer.path(),
+                        from
+                    );
+                }
+                status!(ctx, "🔐 Confirm the transaction on your Ledger ({})...", signer.path());
+                match amount {
+                    Some(amount) => {
+                        let amount = Amount::from_wei(amount);
+                        let command = TransferCommand::without_key(from, to, amount, network.clone());
+                        let handler = transfer_handler().with_signer(Arc::new(signer));
+                        handler.handle(with_options(command)).await?.tx_hash
+                    }
+                    None => {
+                        let result = alloy_service.sweep(&from, &to, &signer).await;
+                        audit_sweep(&from, &to, &network, &result).await;
+                        result?.0
+                    }
+                }
+            }
+        };
+
+        ctx.print_tx_sent(&tx_hash);
+        if let Some(explorer) = network.explorer_url() {
+            status!(ctx, "   Explorer: {}/tx/{}", explorer, tx_hash);
+        }
+
+        if let Some(timeout) = request.track {
+            return track_transaction(ctx, blockchain_service, &network, tx_hash, timeout).await;
+        }
+        let Some((confirmations, timeout)) = request.wait else {
+            return Ok(());
+        };
+        let confirmations = confirmations.unwrap_or_else(|| network.recommended_confirmations());
+        let service = polling_service(blockchain_service, &network);
+        let ticks = interval_ticks(CONFIRMATION_POLL_INTERVAL);
+        let wait = service.wait_for_confirmations(&tx_hash, confirmations, ticks);
+        let message = format!("Waiting for {} confirmation(s)...", confirmations);
+        match tokio::time::timeout(timeout, ctx.with_spinner(&message, wait)).await {
+            Ok(receipt) => {
+                let receipt = receipt?;
+                let status = if receipt.success { "✅ Confirmed" } else { "❌ Reverted" };
+                status!(ctx, "{} in block #{}", status, receipt.block_number.unwrap_or_default());
+                status!(ctx, "   Gas used:  {}", receipt.gas_used);
+                match receipt.fee_paid() {
+                    Some(fee) => status!(
+                        ctx,
+                        "   Fee:       {}",
+                        Balance::from_wei(fee.to_wei()).format_native(8, &network)
+                    ),
+                    None => status!(ctx, "   Fee:       (overflow)"),
+                }
+            }
+            Err(_) => {
+                status!(
+                    ctx,
+                    "⏳ Not confirmed after {}s; {} is still pending",
+                    timeout.as_secs(),
+                    tx_hash
+                );
+            }
+        }
+        Ok(())
+    }
+
+    async fn handle_token_transfer(
+        ctx: &CliContext,
+        request: TransferRequest,
+        mint: String,
+        tx_version: SolanaTxVersion,
+    ) -> anyhow::Result<()> {
+        let from = Address::new(request.from)?;
+        let mint = Address::new(mint)?;
+        let network = request.network.clone();
+        let to = resolve_address(ctx, &request.to, &network).await?;
+        if from == to && !request.allow_self {
+            return Err(DomainError::SelfTransfer(from.to_string()).into());
+        }
+        let private_key = request.signer.private_key()?;
+
+        let rpc = ctx.endpoint(request.rpc_url, &network);
+        let service = SolanaBlockchainService::new_with_rpc(network.clone(), &rpc)
+            .await?
+            .with_tx_version(tx_version);
+
+        let amount_str = request
+            .amount
+            .ok_or_else(|| input_error!("--all is not supported with --token; pass --amount"))?;
+        let decimals = service.token_decimals(&mint).await?;
+        let amount = u64::try_from(parse_units(&amount_str, decimals)?)
+            .map_err(|_| input_error!("Amount {} is too large for an SPL token", amount_str))?;
+
+        status!(ctx, "💸 Sending {} tokens...", amount_str);
+        status!(ctx, "   Mint:    {} ({} decimals)", mint, decimals);
+        status!(ctx, "   From:    {}", from);
+        status!(ctx, "   To:      {}", to);
+        status!(ctx, "   Network: {}", network);
+
+        let result = service.transfer_token(&mint, &from, &to, amount, &private_key).await;
+        let outcome = TransferOutcome::from_result(result.as_ref());
+        let record = TransferAuditRecord::new(unix_now(), from, to, Some(Amount::from_wei(amount.into())), network.clone(), outcome);
+        audit_transfer(record.with_token(mint)).await;
+        let signature = result?;
+
+        ctx.print_tx_sent(&signature);
+        if let Some(timeout) = request.track {
+            return track_transaction(ctx, Arc::new(service), &network, signature, timeout).await;
+        }
+        Ok(())
+    }
+
+    fn format_unix_date(ts: u64) -> String {
+        let days = (ts / 86_400) as i64;
+        let (year, month, day) = civil_from_days(days);
+        format!("{:04}-{:02}-{:02}", year, month, day)
+    }
+
+    fn handle_address(
+        private_key_env: String,
+        chain_str: String,
+        address_type_str: String,
+    ) -> anyhow::Result<()> {
+        let private_key = read_private_key(&private_key_env)?;
+        let private_key = private_key.expose_secret();
+
+        let address = match chain_str.to_lowercase().as_str() {
+            "eth" | "evm" | "bsc" => keys::evm_address_from_private_key(private_key)?,
+            "sol" | "solana" => keys::solana_address_from_private_key(private_key)?,
+            "btc" | "bitcoin" | "btc-testnet" => {
+                let network = if chain_str.eq_ignore_ascii_case("btc-testnet") {
+                    Network::BitcoinTestnet
+                } else {
+                    Network::BitcoinMainnet
+                };
+                let address_type = match address_type_str.to_lowercase().as_str() {
+                    "p2pkh" | "legacy" => BitcoinAddressType::P2pkh,
+                    "p2sh" => BitcoinAddressType::P2sh,
+                    "segwit" | "p2wpkh" | "bech32" => BitcoinAddressType::SegwitV0,
+                    _ => {
+                        return Err(input_error!(
+                            "Unknown address type: {}. Use p2pkh, p2sh, or segwit",
+                            address_type_str
+                        ));
+                    }
+                };
+                keys::bitcoin_address_from_private_key(private_key, &network, address_type)?
+            }
+            _ => {
+                return Err(input_error!(
+                    "Unknown chain: {}. Use eth, btc, btc-testnet, or sol",
+                    chain_str
+                ));
+            }
+        };
+
+        println!("{}", address);
+        Ok(())
+    }
+
+    fn handle_book(ctx: &CliContext, action: BookCommand) -> anyhow::Result<()> {
+        let mut book = AddressBook::open_default()?;
+
+        match action {
+            BookCommand::Add { name, address, network } => {
+                let address = Address::new(address)?;
+                let network = match network {
+                    Some(network_str) => Network::from_name(&network_str)
+                        .ok_or_else(|| input_error!("Unknown network: {}", network_str))?,
+                    None => match address.chain_type() {
+                        ChainType::Ethereum => Network::Mainnet,
+                        ChainType::Bitcoin => Network::BitcoinMainnet,
+                        ChainType::Solana => Network::SolanaMainnet,
+                    },
+                };
+                book.add(&name, address.clone(), network.clone())?;
+                book.save()?;
+                status!(ctx, "📒 Saved {} = {} ({})", name, address, network.name());
+            }
+            BookCommand::List => {
+                if book.is_empty() {
+                    status!(ctx, "📒 Address book is empty ({})", book.path().display());
+                    return Ok(());
+                }
+                status!(ctx, "📒 Address book ({}):", book.path().display());
+                // `--quiet` prints "<name> <address>" per valid entry
+                for (name, entry) in book.entries() {
+                    match entry {
+                        Ok(entry) if ctx.quiet => println!("{} {}", name, entry.address),
+                        Ok(entry) => println!("   {:<16} {}  ({})", name, entry.address, entry.network.name()),
+                        Err(e) if ctx.quiet => eprintln!("{}: {}", name, e.user_message()),
+                        Err(e) => println!("   {:<16} ⚠️  {}", name, e.user_message()),
+                    }
+                }
+            }
+            BookCommand::Rm { name } => {
+                if !book.remove(&name) {
+                    return Err(input_error!("No address named '{}' in the address book", name));
+                }
+                book.save()?;
+                status!(ctx, "🗑️  Removed {}", name);
+            }
+        }
+        Ok(())
+    }
+
+    async fn handle_call(
+        ctx: &CliContext,
+        to_str: String,
+        data: String,
+        network: Network,
+        rpc_url: Option<String>,
+        block: Option<u64>,
+    ) -> anyhow::Result<()> {
+        let to = Address::new(to_str)?;
+        let calldata = alloy::hex::decode(data.trim())
+            .map_err(|e| input_error!("Invalid calldata hex: {}", e))?;
+        let block_id = block.map(BlockId::number).unwrap_or_else(BlockId::latest);
+
+        let rpc = ctx.endpoint(rpc_url, &network);
+        let service = AlloyBlockchainService::new(network, &rpc).await?;
+        let output = service.call(&to, calldata, block_id).await?;
+
+        println!("0x{}", alloy::hex::encode(output));
+        Ok(())
+    }
+
+    async fn handle_storage(
+        ctx: &CliContext,
+        address_str: String,
+        slot: String,
+        network: Network,
+        rpc_url: Option<String>,
+        block: Option<u64>,
+    ) -> anyhow::Result<()> {
+        let contract = Address::new(address_str)?;
+        let slot: alloy::primitives::U256 = slot
+            .trim()
+            .parse()
+            .map_err(|e| input_error!("Invalid storage slot '{}': {}", slot, e))?;
+        let block_id = block.map(BlockId::number).unwrap_or_else(BlockId::latest);
+
+        let rpc = ctx.endpoint(rpc_url, &network);
+        let service = AlloyBlockchainService::new(network, &rpc).await?;
+        let value = service.get_storage_at(&contract, slot, block_id).await?;
+
+        println!("{}", value);
+        Ok(())
+    }
+
+    #[cfg(feature = "http")]
+    async fn handle_serve(host: &str, port: u16) -> anyhow::Result<()> {
+        use crate::adapter::interfaces::http::{self, HttpState};
+
+        let listener = tokio::net::TcpListener::bind((host, port))
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to bind {}:{}: {}", host, port, e))?;
+        println!("🌐 REST API listening on http://{}", listener.local_addr()?);
+        println!("   GET  /balance/:network/:address");
+        println!("   POST /transfer");
+
+        tokio::select! {
+            result = http::serve(listener, HttpState::new()) => result?,
+            _ = tokio::signal::ctrl_c() => println!("\n⚠️  Interrupted, shutting down..."),
+        }
+        Ok(())
+    }
+
+    fn handle_networks(output: &str) -> anyhow::Result<()> {
+        let networks = Network::built_in();
+        match output.to_lowercase().as_str() {
+            "table" => {
+                println!(
+                    "{:<12} {:<17} {:<9} {:>9}  {:<7} {:<6} DEFAULT RPC",
+                    "--network", "NAME", "CHAIN", "CHAIN ID", "TESTNET", "SYMBOL"
+                );
+                for network in &networks {
+                    let chain_id = if network.is_evm() {
+                        network.chain_id().to_string()
+                    } else {
+                        "N/A".to_string()
+                    };
+                    println!(
+                        "{:<12} {:<17} {:<9} {:>9}  {:<7} {:<6} {}",
+                        network.short_name().unwrap_or_default(),
+                        network.name(),
+                        network.chain_type().name(),
+                        chain_id,
+                        if network.is_testnet() { "yes" } else { "no" },
+                        network.native_symbol(),
+                        network.default_rpc_url()
+                    );
+                }
+            }
+            "json" => {
+                let entries: Vec<_> = networks
+                    .iter()
+                    .map(|network| {
+                        serde_json::json!({
+                            "network": network.short_name(),
+                            "name": network.name(),
+                            "chain_type": network.chain_type().name(),
+                            "chain_id": network.is_evm().then(|| network.chain_id()),
+                            "default_rpc_url": network.default_rpc_url(),
+                            "testnet": network.is_testnet(),
+                            "kind": network.kind().to_string(),
+                            "native_symbol": network.native_symbol(),
+                        })
+                    })
+                    .collect();
+                println!("{}", serde_json::to_string_pretty(&entries)?);
+            }
+            other => bail_input!("Unknown output format: {}. Use table or json", other),
+        }
+        Ok(())
+    }
+
+    async fn handle_max_send(
+        ctx: &CliContext,
+        from: String,
+        to: String,
+        network: Network,
+        rpc_url: Option<String>,
+    ) -> anyhow::Result<()> {
+        let from = resolve_address(ctx, &from, &network).await?;
+        let to = resolve_address(ctx, &to, &network).await?;
+        let rpc = ctx.endpoint(rpc_url, &network);
+
+        let service: Arc<dyn BlockchainService> = match network.chain_type() {
+            ChainType::Ethereum => Arc::new(AlloyBlockchainService::new(network.clone(), &rpc).await?),
+            ChainType::Bitcoin => Arc::new(BitcoinBlockchainService::new(network.clone()).await?),
+            ChainType::Solana => Arc::new(
+                SolanaBlockchainService::new_with_rpc(network.clone(), &rpc).await?,
+            ),
+        };
+        let max = ctx.with_spinner("Estimating fee...", service.max_sendable(&from, &to)).await;
+        // Best effort: not every chain or endpoint can estimate the wait
+        let eta = match &max {
+            Ok(max) if !ctx.quiet => service.estimate_confirmation_time(&max.fee).await.ok(),
+            _ => None,
+        };
+        service.shutdown().await;
+        let max = max?;
+
+        if ctx.quiet {
+            println!("{}", Balance::from_wei(max.amount.to_wei()).format_exact(network.native_decimals()));
+            return Ok(());
+        }
+        status!(ctx, "🧮 Max sendable on {}", network);
+        status!(ctx, "   From:    {}", from);
+        status!(ctx, "   To:      {}", to);
+        status!(ctx, "   Balance: {}", native_amount(max.balance.to_wei(), &network, &ctx.balance_format));
+        status!(
+            ctx,
+            "   Fee:     {} ({})",
+            native_amount(max.fee.total_native.to_wei(), &network, &ctx.balance_format),
+            fee_breakdown(&max.fee)
+        );
+        if let Some(l1_data_fee) = &max.fee.l1_data_fee {
+            let l1_data_fee = native_amount(l1_data_fee.to_wei(), &network, &ctx.balance_format);
+            status!(ctx, "            incl. {} L1 data fee", l1_data_fee);
+        }
+        if let Some(eta) = eta {
+            status!(ctx, "   ETA:     {} at this fee", approx_duration(eta));
+        }
+        status!(
+            ctx,
+            "   Max:     {} ({} {})",
+            native_amount(max.amount.to_wei(), &network, &ctx.balance_format),
+            max.amount.to_wei(),
+            network.chain_type().smallest_unit()
+        );
+        Ok(())
+    }
+
+    async fn handle_health(ctx: &CliContext, network: Network, rpc_url: Option<String>) -> anyhow::Result<()> {
+        let rpc = ctx.endpoint(rpc_url, &network);
+
+        status!(ctx, "🩺 Checking {}...", network);
+        status!(ctx, "   RPC URL: {}", mask_url(&rpc));
+
+        let service: Arc<dyn BlockchainService> = match network.chain_type() {
+            ChainType::Ethereum => Arc::new(AlloyBlockchainService::new(network.clone(), &rpc).await?),
+            ChainType::Bitcoin => Arc::new(BitcoinBlockchainService::new(network.clone()).await?),
+            ChainType::Solana => Arc::new(
+                SolanaBlockchainService::new_with_rpc(network.clone(), &rpc).await?,
+            ),
+        };
+        let diagnostics = service.diagnose().await;
+        service.shutdown().await;
+
+        if diagnostics.throttled {
+            status!(ctx, "⚠️  Up but rate limited after {} ms", diagnostics.latency_ms);
+            status!(ctx, "   {}", diagnostics.error.unwrap_or_else(|| "unknown error".to_string()));
+            return Ok(());
+        }
+        if !diagnostics.reachable {
+            status!(ctx, "❌ Unreachable after {} ms", diagnostics.latency_ms);
+            return Err(anyhow::anyhow!(
+                "{}",
+                diagnostics.error.unwrap_or_else(|| "unknown error".to_string())
+            ));
+        }
+
+        status!(ctx, "✅ Reachable");
+        status!(ctx, "   Latency: {} ms", diagnostics.latency_ms);
+        if let Some(height) = diagnostics.block_height {
+            status!(ctx, "   Height:  #{}", height);
+        }
+        if let Some(version) = &diagnostics.rpc_version {
+            status!(ctx, "   Node:    {}", version);
+        }
+        Ok(())
+    }
+
+    fn handle_validate_address(ctx: &CliContext, address_str: String) -> anyhow::Result<()> {
+        let address = Address::new(address_str).map_err(|e| input_error!("❌ {}", e.user_message()))?;
+        let chain_type = address.chain_type();
+
+        status!(ctx, "🔍 Address: {}", address);
+        status!(ctx, "   Chain:   {}", chain_type);
+
+        match chain_type {
+            ChainType::Ethereum => {
+                let checksummed: alloy::primitives::Address = address
+                    .as_str()
+                    .parse()
+                    .map_err(|e| input_error!("❌ Invalid address: {}", e))?;
+                let checksummed = checksummed.to_checksum(None);
+                let hex = &address.as_str()[2..];
+
+                if hex == hex.to_lowercase() || hex == hex.to_uppercase() {
+                    status!(ctx, "   EIP-55:  ⚠️  not checksummed (single case)");
+                } else if address.as_str() == checksummed {
+                    status!(ctx, "   EIP-55:  ✅ valid checksum");
+                } else {
+                    status!(ctx, "   EIP-55:  ❌ checksum mismatch");
+                    status!(ctx, "   Correct: {}", checksummed);
+                    return Err(input_error!(
+                        "Checksum mismatch - the address may have been mistyped"
+                    ));
+                }
+                status!(ctx, "   Correct: {}", checksummed);
+            }
+            ChainType::Bitcoin => {
+                let address_type = address.bitcoin_address_type()?;
+                status!(ctx, "   Type:    {}", address_type);
+            }
+            ChainType::Solana => {
+                status!(ctx, "   Type:    Ed25519 public key (Base58)");
+            }
+        }
+
+        status!(ctx, "✅ Address is valid");
         Ok(())
     }
+
+    async fn run_balance_query(
+        ctx: &CliContext,
+        blockchain_service: Arc<dyn BlockchainService>,
+        address: Address,
+        network: Network,
+        units: DisplayUnits,
+        fiat: Option<FiatCurrency>,
+    ) -> anyhow::Result<()> {
+        // Test connection, keeping the reason if it fails
+        let diagnostics = blockchain_service.diagnose().await;
+        if !diagnostics.is_up() {
+            return Err(anyhow::anyhow!("Failed to connect to network: {}", diagnostics));
+        }
+        if diagnostics.throttled {
+            // Throttled, not down: the balance request may still get through
+            status!(ctx, "   ⚠️  Endpoint is rate limiting requests; continuing");
+        }
+
+        if let Some(block_number) = diagnostics.block_height {
+            status!(ctx, "   Current Block: #{}", block_number);
+        }
+        status!(ctx);
+
+        // Create query handler
+        let handler = GetBalanceHandler::new(blockchain_service);
+
+        // Execute query
+        let query = GetBalanceQuery::new(address, network);
+        let result = handler.handle(query).await?;
+
+        // Display result
+        if ctx.quiet {
+            println!("{}", units.render(&result, &ctx.balance_format));
+            return Ok(());
+        }
+        println!("✅ Balance Query Result:");
+        println!("   Address:  {}", result.address);
+        println!("   Network:  {}", result.network);
+        println!("   Balance:  {}", units.render(&result, &ctx.balance_format));
+        if let Some(fiat) = fiat {
+            // A price feed outage must not hide the balance itself
+            match fiat.native_price(&result.network).await {
+                Ok(price) => println!("   {}:      {}", fiat, fiat.format_value(&result, price)),
+                Err(e) => println!("   {}:      unavailable ({})", fiat, e.user_message()),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Arguments of the `balance` command
+struct BalanceRequest {
+    address: String,
+    network: Network,
+    rpc_url: Option<String>,
+    commitment: Option<String>,
+    block: Option<u64>,
+    at_date: Option<String>,
+    offline: bool,
+    units: DisplayUnits,
+    fiat: Option<FiatCurrency>,
+}
+
+/// Which units `balance` / `balances` print amounts in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DisplayUnits {
+    /// Native currency and smallest unit: "1.5 ETH (1500000000000000000 Wei)"
+    All,
+    /// Native currency only: "1.5 ETH"
+    Native,
+    /// Smallest unit only: "1500000000000000000 Wei"
+    Raw,
+}
+
+impl DisplayUnits {
+    /// Native amounts follow `--locale` / `--grouping`; raw amounts stay plain digits
+    fn render(self, result: &BalanceQueryResult, format: &BalanceFormat) -> String {
+        let native = || {
+            format!(
+                "{} {}",
+                result.balance.format(result.network.native_decimals(), format),
+                result.network.native_symbol()
+            )
+        };
+        match self {
+            DisplayUnits::All => format!("{} ({})", native(), result.display_raw()),
+            DisplayUnits::Native => native(),
+            DisplayUnits::Raw => result.display_raw(),
+        }
+    }
+}
+
+/// `amount` (smallest unit) in `network`'s native currency, laid out per `--locale` / `--grouping`
+fn native_amount(amount: u128, network: &Network, format: &BalanceFormat) -> String {
+    format!(
+        "{} {}",
+        Balance::from_wei(amount).format(network.native_decimals(), format),
+        network.native_symbol()
+    )
+}
+
+/// Rounded duration for estimates, e.g. "~24s", "~30 min", "~2 h", "~1 day"
+fn approx_duration(duration: Duration) -> String {
+    let secs = duration.as_secs_f64();
+    match secs {
+        s if s < 90.0 => format!("~{}s", s.round().max(1.0)),
+        s if s < 90.0 * 60.0 => format!("~{} min", (s / 60.0).round()),
+        s if s < 24.0 * 3600.0 => format!("~{} h", (s / 3600.0).round()),
+        s => {
+            let days = (s / 86_400.0).round();
+            format!("~{} day{}", days, if days == 1.0 { "" } else { "s" })
+        }
+    }
+}
+
+/// How a fee was computed, e.g. "21000 gas × 12.5 gwei"
+fn fee_breakdown(fee: &FeeEstimate) -> String {
+    match &fee.details {
+        FeeDetails::Evm { gas_limit, gas_price } => {
+            format!("{} gas × {} gwei", gas_limit, Balance::from_wei(*gas_price).format_exact(9))
+        }
+        FeeDetails::Bitcoin { vsize, sat_per_vbyte } => format!("{} vB × {} sat/vB", vsize, sat_per_vbyte),
+        FeeDetails::Solana { signatures, lamports_per_signature } => {
+            format!("{} signature(s) × {} lamports", signatures, lamports_per_signature)
+        }
+    }
+}
+
+/// Number layout for `--locale` and `--grouping` (`,` between thousands without a locale)
+fn parse_balance_format(locale: Option<&str>, grouping: bool) -> anyhow::Result<BalanceFormat> {
+    let format = match locale {
+        Some(locale) => BalanceFormat::for_locale(locale)
+            .ok_or_else(|| input_error!("Unsupported locale: {}. Use e.g. en, de, fr or de-CH", locale))?,
+        None => BalanceFormat::default(),
+    };
+    Ok(match format.thousands_separator {
+        None if grouping => format.with_thousands_separator(','),
+        _ => format,
+    })
+}
+
+/// Parse `--units` (all, native, raw)
+fn parse_display_units(units: &str) -> anyhow::Result<DisplayUnits> {
+    match units.to_lowercase().as_str() {
+        "all" => Ok(DisplayUnits::All),
+        "native" => Ok(DisplayUnits::Native),
+        "raw" => Ok(DisplayUnits::Raw),
+        other => bail_input!("Unknown units: {}. Use all, native or raw", other),
+    }
+}
+
+/// `--output` of the long-running commands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    /// Human-readable lines
+    Text,
+    /// One JSON event per line
+    Ndjson,
+}
+
+/// Parse `--output` (text, ndjson)
+fn parse_output_format(output: &str) -> anyhow::Result<OutputFormat> {
+    match output.to_lowercase().as_str() {
+        "text" => Ok(OutputFormat::Text),
+        "ndjson" => Ok(OutputFormat::Ndjson),
+        other => bail_input!("Unknown output format: {}. Use text or ndjson", other),
+    }
+}
+
+/// One line of `--output ndjson`
+///
+/// Balances are in the smallest unit (wei, lamports), as strings since they
+/// may exceed what JSON numbers can hold exactly.
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum CliEvent<'a> {
+    /// Current balance of an address
+    Balance {
+        address: &'a Address,
+        network: String,
+        balance: String,
+        formatted: String,
+        timestamp: u64,
+    },
+    /// The balance dropped below `--alert-below`
+    Alert {
+        address: &'a Address,
+        network: String,
+        balance: String,
+        threshold: String,
+    },
+    /// The balance is back above `--alert-below`
+    Recovered {
+        address: &'a Address,
+        network: String,
+        balance: String,
+    },
+    /// Querying an address failed
+    Error { address: &'a Address, message: String },
+}
+
+impl<'a> CliEvent<'a> {
+    fn balance(address: &'a Address, network: &Network, balance: Balance) -> Self {
+        CliEvent::Balance {
+            address,
+            network: network_name(network),
+            balance: balance.to_wei().to_string(),
+            formatted: format!("{} {}", balance.format_exact(network.native_decimals()), network.native_symbol()),
+            timestamp: unix_now(),
+        }
+    }
+}
+
+/// Write `event` as a single line and flush, so a reading process sees it immediately
+fn emit_event(event: &CliEvent) -> anyhow::Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    serde_json::to_writer(&mut stdout, event)?;
+    writeln!(stdout)?;
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Short name for built-in networks, as accepted by `--network`
+fn network_name(network: &Network) -> String {
+    network.short_name().unwrap_or(network.name()).to_string()
+}
+
+/// Fiat currency for `--fiat`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FiatCurrency {
+    Usd,
+}
+
+impl FiatCurrency {
+    /// Current price of `network`'s native currency
+    ///
+    /// Testnet coins and EVM gas tokens other than ETH (BNB, MATIC) have no
+    /// feed here, so they are reported as unavailable rather than mispriced.
+    async fn native_price(self, network: &Network) -> Result<f64, DomainError> {
+        if network.is_testnet() {
+            return Err(DomainError::InvalidQuery("testnet coins have no market price".to_string()));
+        }
+        if network.native_symbol() != network.chain_type().native_currency() {
+            return Err(DomainError::InvalidQuery(format!("no price feed for {}", network.native_symbol())));
+        }
+        match self {
+            FiatCurrency::Usd => CoinGeckoPriceProvider::new()?.get_price(network.chain_type(), None).await,
+        }
+    }
+
+    /// `result`'s balance valued at `price`, e.g. "$4512.30"
+    fn format_value(self, result: &BalanceQueryResult, price: f64) -> String {
+        let value = result.balance.to_units(result.network.native_decimals()) * price;
+        match self {
+            FiatCurrency::Usd => format!("${:.2}", value),
+        }
+    }
+}
+
+impl std::fmt::Display for FiatCurrency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FiatCurrency::Usd => write!(f, "USD"),
+        }
+    }
+}
+
+/// Parse `--fiat` (usd)
+fn parse_fiat(fiat: &str) -> anyhow::Result<FiatCurrency> {
+    match fiat.to_lowercase().as_str() {
+        "usd" => Ok(FiatCurrency::Usd),
+        other => bail_input!("Unsupported fiat currency: {}. Use usd", other),
+    }
+}
+
+/// Arguments shared by the native and token transfer paths
+struct TransferRequest {
+    from: String,
+    to: String,
+    /// `None` sweeps the whole balance (`--all`)
+    amount: Option<String>,
+    network: Network,
+    rpc_url: Option<String>,
+    signer: SignerSource,
+    gas_price_gwei: Option<u64>,
+    /// Send even if the amount is zero (`--allow-zero`)
+    allow_zero: bool,
+    /// Send even if the recipient is the sender (`--allow-self`)
+    allow_self: bool,
+    /// Confirmations to wait for after broadcasting (`None`: the network's
+    /// recommended depth), and for how long
+    wait: Option<(Option<u64>, Duration)>,
+    /// Track the transaction until it is included, for at most this long
+    track: Option<Duration>,
+}
+
+/// Where `transfer` gets the sender's signing key from
+enum SignerSource {
+    /// Environment variable holding a private key (`--private-key-env`)
+    PrivateKeyEnv(String),
+    /// Ledger account at a BIP-44 path (`--ledger`, `--hd-path`)
+    #[cfg(feature = "ledger")]
+    Ledger(String),
+}
+
+impl SignerSource {
+    /// Read the private key, failing for sources that sign elsewhere
+    fn private_key(&self) -> anyhow::Result<SecretKey> {
+        match self {
+            Self::PrivateKeyEnv(env_var) => read_private_key(env_var),
+            #[cfg(feature = "ledger")]
+            Self::Ledger(_) => bail_input!("--ledger cannot be used here; pass --private-key-env"),
+        }
+    }
+}
+
+/// Recipient and amount (whole units) of a `transfer --uri` payment request
+///
+/// The URI's amount wins; `--amount` is only accepted when the URI has none.
+fn payment_uri_target(
+    uri: &str,
+    network: &Network,
+    amount: Option<String>,
+) -> anyhow::Result<(String, Option<String>)> {
+    let uri = PaymentUri::parse(uri)?;
+    if uri.chain != ChainType::Ethereum {
+        bail_input!("{} payment URIs are not supported by transfer yet", uri.chain);
+    }
+    if let Some(chain_id) = uri.chain_id.filter(|id| *id != network.chain_id()) {
+        bail_input!(
+            "Payment URI is for chain ID {}, but {} has chain ID {}",
+            chain_id,
+            network.name(),
+            network.chain_id()
+        );
+    }
+
+    let amount = match (uri.amount, amount) {
+        (Some(_), Some(_)) => bail_input!("--amount conflicts with the amount in the payment URI"),
+        (Some(requested), None) => Balance::from_wei(requested.to_wei()).format_exact(network.native_decimals()),
+        (None, Some(amount)) => amount,
+        (None, None) => bail_input!("Payment URI has no amount: pass --amount"),
+    };
+    Ok((uri.address.as_str().to_string(), Some(amount)))
+}
+
+/// Track `tx_hash` until it is included in a block and print its final status
+async fn track_transaction(
+    ctx: &CliContext,
+    service: Arc<dyn BlockchainService>,
+    network: &Network,
+    tx_hash: TransactionHash,
+    timeout: Duration,
+) -> anyhow::Result<()> {
+    let tracker = TransactionTracker::new().with_service(network.clone(), polling_service(service, network));
+    tracker.track(tx_hash.clone(), network.clone())?;
+
+    let mut completions = tracker.completions(interval_ticks(CONFIRMATION_POLL_INTERVAL));
+    match tokio::time::timeout(timeout, ctx.with_spinner("Tracking transaction...", completions.next())).await {
+        Ok(Some((_, Ok(status @ TxStatus::Reverted { .. })))) => {
+            status!(ctx, "❌ {}", status);
+            anyhow::bail!("Transaction {} reverted", tx_hash)
+        }
+        Ok(Some((_, Ok(status)))) => status!(ctx, "✅ {}", status),
+        Ok(Some((_, Err(e)))) => return Err(e.into()),
+        Ok(None) | Err(_) => status!(
+            ctx,
+            "⏳ Not included after {}s; {} is still pending",
+            timeout.as_secs(),
+            tx_hash
+        ),
+    }
+    Ok(())
+}
+
+/// `service` for polling a transaction: once its receipt or final status is
+/// known, repeated reads are answered from cache instead of the node
+fn polling_service(service: Arc<dyn BlockchainService>, network: &Network) -> Arc<dyn BlockchainService> {
+    Arc::new(CachedBlockchainService::new(service, network.clone()))
+}
+
+/// Parse `--tx-version` and `--lookup-table` into a Solana transaction format
+fn parse_solana_tx_version(version: &str, lookup_tables: Vec<String>) -> anyhow::Result<SolanaTxVersion> {
+    match version.to_lowercase().as_str() {
+        "legacy" if lookup_tables.is_empty() => Ok(SolanaTxVersion::Legacy),
+        "legacy" => bail_input!("--lookup-table requires --tx-version v0"),
+        "v0" | "0" => Ok(SolanaTxVersion::V0 {
+            lookup_tables: lookup_tables
+                .into_iter()
+                .map(Address::new)
+                .collect::<Result<_, _>>()?,
+        }),
+        _ => bail_input!("Invalid transaction version: {} (use legacy or v0)", version),
+    }
+}
+
+/// Parse `--commitment` into the finality it corresponds to
+fn parse_commitment(commitment: &str) -> anyhow::Result<Finality> {
+    match commitment.to_lowercase().as_str() {
+        "processed" => Ok(Finality::Latest),
+        "confirmed" => Ok(Finality::Safe),
+        "finalized" => Ok(Finality::Finalized),
+        _ => bail_input!(
+            "Invalid commitment: {} (use finalized, confirmed or processed)",
+            commitment
+        ),
+    }
+}
+
+/// Accept a raw address, a name saved with `book add`, or (on EVM networks) an ENS name
+///
+/// A name saved for another chain is rejected; one saved for another network
+/// of the same chain is used with a warning. ENS names are always resolved
+/// on Ethereum mainnet (`RUSTWALLET_ENS_RPC_URL` overrides the endpoint) and
+/// the address is used on whichever EVM network was asked for.
+async fn resolve_address(ctx: &CliContext, input: &str, network: &Network) -> anyhow::Result<Address> {
+    if let Ok(address) = Address::new(input.to_string()) {
+        let detected = ChainType::detect_from_address(&address);
+        if detected != network.chain_type() {
+            return Err(DomainError::ChainMismatch {
+                expected: network.chain_type(),
+                detected,
+            }
+            .into());
+        }
+        return Ok(address);
+    }
+
+    let book = AddressBook::open_default()?;
+    let entry = book.resolve(input)?;
+    if entry.is_none() && input.contains('.') && network.chain_type() == ChainType::Ethereum {
+        let rpc = std::env::var(ENS_RPC_URL_ENV).unwrap_or_else(|_| default_rpc_url(&Network::Mainnet));
+        let resolver = EnsResolver::new(AlloyBlockchainService::new(Network::Mainnet, &rpc).await?)?;
+        let address = resolver.resolve_name(input).await?;
+        if !ctx.quiet {
+            eprintln!("   🔗 {} = {} (ENS)", input, address);
+        }
+        return Ok(address);
+    }
+    let entry = entry.ok_or_else(|| {
+        // Explain what an address on this network should look like
+        let reason = match Address::for_chain(input.to_string(), network.chain_type()) {
+            Err(e) => e.user_message(),
+            Ok(_) => format!("not a {} address", network.chain_type()),
+        };
+        // Names are short; anything longer may be a private key pasted into the wrong flag
+        if input.len() > MAX_ECHOED_INPUT_LEN {
+            input_error!("The given value is not a name in the address book ({})", reason)
+        } else {
+            input_error!("{} is not a name in the address book ({})", input, reason)
+        }
+    })?;
+    if entry.network.chain_type() != network.chain_type() {
+        return Err(input_error!(
+            "'{}' is a {} address, but {} is a {} network",
+            input,
+            entry.network.chain_type(),
+            network.name(),
+            network.chain_type()
+        ));
+    }
+    if !ctx.quiet {
+        if &entry.network != network {
+            eprintln!("   ⚠️  '{}' was saved for {}, using it on {}", input, entry.network.name(), network.name());
+        }
+        eprintln!("   📒 {} = {}", input, entry.address);
+    }
+    Ok(entry.address)
+}
+
+/// Message printed when a command fails
+///
+/// Each cause in the chain is shown, domain errors via their sanitized
+/// `user_message`; `verbose` prints the full chain (`Debug`) instead.
+pub fn error_report(error: &anyhow::Error, verbose: bool) -> String {
+    if verbose {
+        return format!("{:?}", error);
+    }
+    error
+        .chain()
+        .map(|cause| match cause.downcast_ref::<DomainError>() {
+            Some(domain_error) => domain_error.user_message(),
+            None => redact_payloads(&cause.to_string()),
+        })
+        .collect::<Vec<_>>()
+        .join(": ")
+}
+
+/// Process exit code for a failed command
+///
+/// The contract scripts can rely on:
+///
+/// | Code | Meaning |
+/// |------|---------|
+/// | 0 | Success |
+/// | 1 | Any other failure |
+/// | 2 | Invalid input (address, amount, key, hash, query, unknown network or option value; also clap usage errors) |
+/// | 3 | Network error (unreachable or rate-limited endpoint, malformed response) |
+/// | 4 | Insufficient balance |
+///
+/// The category comes from the first [`DomainError`] in the error chain;
+/// input the CLI rejects itself is always 2.
+pub fn exit_code(error: &anyhow::Error) -> i32 {
+    if error.chain().any(|cause| cause.is::<InputError>()) {
+        return 2;
+    }
+    let Some(domain_error) = error.chain().find_map(|cause| cause.downcast_ref::<DomainError>()) else {
+        return 1;
+    };
+    match domain_error {
+        DomainError::InvalidAddress { .. }
+        | DomainError::ChainMismatch { .. }
+        | DomainError::InvalidBalance
+        | DomainError::InvalidTransactionHash
+        | DomainError::InvalidTransactionHashLength
+        | DomainError::InvalidTransactionHashCharacters
+        | DomainError::InvalidAmount
+        | DomainError::SelfTransfer(_)
+        | DomainError::SignerMismatch { .. }
+        | DomainError::InvalidQuery(_)
+        | DomainError::InvalidPrivateKey(_) => 2,
+        DomainError::NetworkError(_) | DomainError::RateLimited(_) | DomainError::InvalidResponse(_) => 3,
+        DomainError::InsufficientBalance { .. } => 4,
+        DomainError::BlockchainError(_)
+        | DomainError::ConfigurationError(_)
+        | DomainError::TransferFailed(_)
+        | DomainError::WouldRevert(_)
+        | DomainError::NonceTooLow(_)
+        | DomainError::AlreadyKnown(_)
+        | DomainError::ReplacementUnderpriced(_) => 1,
+    }
+}
+
+
+/// Addresses from `--address` values plus the lines of `file`, resolved for `network`
+///
+/// Blank lines and `#` comments in the file are ignored.
+async fn read_address_list(
+    ctx: &CliContext,
+    mut inputs: Vec<String>,
+    file: Option<&str>,
+    network: &Network,
+) -> anyhow::Result<Vec<Address>> {
+    if let Some(path) = file {
+        let contents =
+            std::fs::read_to_string(path).map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path, e))?;
+        inputs.extend(
+            contents
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(str::to_string),
+        );
+    }
+    if inputs.is_empty() {
+        bail_input!("No addresses given: use --address or an address file");
+    }
+    let mut addresses = Vec::with_capacity(inputs.len());
+    for input in &inputs {
+        addresses.push(resolve_address(ctx, input, network).await?);
+    }
+    Ok(addresses)
+}
+
+/// Append a transfer that bypasses `TransferHandler` (sweeps and token
+/// transfers) to the audit log at `$RUSTWALLET_AUDIT_LOG`, if set
+async fn audit_transfer(record: TransferAuditRecord) {
+    if let Some(sink) = JsonlAuditSink::from_env() {
+        record_audit(&sink, &record).await;
+    }
+}
+
+/// Audit a sweep; the amount is only known once it has been sent
+async fn audit_sweep(
+    from: &Address,
+    to: &Address,
+    network: &Network,
+    result: &Result<(TransactionHash, Amount), DomainError>,
+) {
+    let outcome = TransferOutcome::from_result(result.as_ref().map(|(tx_hash, _)| tx_hash));
+    let amount = result.as_ref().ok().map(|(_, amount)| *amount);
+    audit_transfer(TransferAuditRecord::new(unix_now(), from.clone(), to.clone(), amount, network.clone(), outcome)).await;
+}
+
+/// Read a private key from the named environment variable
+fn read_private_key(env_var: &str) -> anyhow::Result<SecretKey> {
+    std::env::var(env_var)
+        .map(SecretKey::new)
+        .map_err(|_| input_error!("Environment variable {} is not set", env_var))
+}
+
+/// Parse a decimal amount such as `1.25` into base units with `decimals` places
+///
+/// Integer arithmetic only, so there is no float rounding; rejects more
+/// fractional digits than the unit supports.
+fn parse_units(amount: &str, decimals: u8) -> anyhow::Result<u128> {
+    let invalid = || input_error!("Invalid amount: {}", amount);
+    let (whole, fraction) = amount.trim().split_once('.').unwrap_or((amount.trim(), ""));
+    if (whole.is_empty() && fraction.is_empty())
+        || !whole.bytes().chain(fraction.bytes()).all(|b| b.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+    if fraction.len() > decimals as usize {
+        return Err(input_error!(
+            "Amount {} has more than {} decimal places",
+            amount,
+            decimals
+        ));
+    }
+
+    let digits = format!("{}{:0<width$}", whole, fraction, width = decimals as usize);
+    digits.parse::<u128>().map_err(|_| invalid())
+}
+
+/// Parse `YYYY-MM-DD` into the unix timestamp of 00:00:00 UTC on that day
+fn parse_date_to_unix(date: &str) -> anyhow::Result<u64> {
+    let invalid = || input_error!("Invalid date: {}. Use YYYY-MM-DD", date);
+    let parts: Vec<&str> = date.split('-').collect();
+    let [year, month, day] = parts.as_slice() else {
+        return Err(invalid());
+    };
+    let year: i64 = year.parse().map_err(|_| invalid())?;
+    let month: u32 = month.parse().map_err(|_| invalid())?;
+    let day: u32 = day.parse().map_err(|_| invalid())?;
+
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return Err(invalid());
+    }
+    let days = days_from_civil(year, month, day);
+    if days < 0 {
+        return Err(input_error!("Date {} is before 1970-01-01", date));
+    }
+    Ok(days as u64 * 86_400)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date (Howard Hinnant's algorithm)
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let m = month as i64;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Inverse of `days_from_civil`
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_report() {
+        let signed_tx = format!("0x{}", "ab".repeat(100));
+        let error = anyhow::Error::new(DomainError::TransferFailed(format!("rejected {}", signed_tx)))
+            .context("Sending the transfer");
+
+        let report = error_report(&error, false);
+        assert_eq!(report, "Sending the transfer: Transfer failed: rejected <202 chars redacted>");
+        assert!(error_report(&error, true).contains(&signed_tx));
+    }
+
+    #[test]
+    fn test_exit_code_by_error_category() {
+        let code = |error: DomainError| exit_code(&anyhow::Error::new(error).context("Running the command"));
+        assert_eq!(code(DomainError::InvalidAmount), 2);
+        assert_eq!(code(DomainError::SelfTransfer("0xabc".to_string())), 2);
+        assert_eq!(code(DomainError::RateLimited("429".to_string())), 3);
+        assert_eq!(code(DomainError::InsufficientBalance { required: 2, available: 1 }), 4);
+        assert_eq!(code(DomainError::TransferFailed("rejected".to_string())), 1);
+        assert_eq!(exit_code(&input_error!("Unknown network: foo")), 2);
+        assert_eq!(exit_code(&Cli::parse_evm_network("foo", None).unwrap_err()), 2);
+        assert_eq!(exit_code(&parse_units("1.5x", 18).unwrap_err().context("Parsing --amount")), 2);
+        assert_eq!(exit_code(&anyhow::anyhow!("Transaction 0xabc reverted")), 1);
+    }
+
+    #[test]
+    fn test_network_file_takes_precedence_over_network_name() {
+        let file = Network::from_chain_config_json(
+            r#"{"chainId": "0xa4b1", "chainName": "Arbitrum One", "rpcUrls": ["https://arb1.arbitrum.io/rpc"]}"#,
+        )
+        .unwrap();
+        assert_eq!(Cli::parse_evm_network("sepolia", Some(&file)).unwrap(), file);
+        assert_eq!(Cli::parse_network("bitcoin", Some(&file)).unwrap(), file);
+        assert_eq!(Cli::parse_balances_network("sol", Some(&file)).unwrap(), Network::SolanaMainnet);
+        assert_eq!(Cli::parse_network("sepolia", None).unwrap(), Network::Sepolia);
+        assert!(Cli::parse_network("foo", None).is_err());
+    }
+
+    #[test]
+    fn test_ndjson_events_are_single_lines() {
+        let address = Address::new("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".to_string()).unwrap();
+        let event = CliEvent::balance(&address, &Network::Sepolia, Balance::from_wei(1_500_000_000_000_000_000));
+        let line = serde_json::to_string(&event).unwrap();
+        assert!(!line.contains('\n'));
+
+        let json: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(json["event"], "balance");
+        assert_eq!(json["address"], address.as_str());
+        assert_eq!(json["network"], "sepolia");
+        assert_eq!(json["balance"], "1500000000000000000");
+        assert_eq!(json["formatted"], "1.5 ETH");
+
+        let error = CliEvent::Error { address: &address, message: "boom".to_string() };
+        assert_eq!(
+            serde_json::to_string(&error).unwrap(),
+            format!(r#"{{"event":"error","address":"{}","message":"boom"}}"#, address)
+        );
+        assert!(parse_output_format("xml").is_err());
+    }
+
+    #[test]
+    fn test_parse_date_to_unix() {
+        assert_eq!(parse_date_to_unix("1970-01-01").unwrap(), 0);
+        assert_eq!(parse_date_to_unix("2024-01-01").unwrap(), 1_704_067_200);
+        assert_eq!(parse_date_to_unix("2024-02-29").unwrap(), 1_709_164_800);
+        assert!(parse_date_to_unix("2023-02-29").is_err());
+        assert!(parse_date_to_unix("2024-13-01").is_err());
+        assert!(parse_date_to_unix("2024/01/01").is_err());
+    }
+
+    #[test]
+    fn test_approx_duration() {
+        assert_eq!(approx_duration(Duration::from_millis(400)), "~1s");
+        assert_eq!(approx_duration(Duration::from_secs(24)), "~24s");
+        assert_eq!(approx_duration(Duration::from_secs(1_800)), "~30 min");
+        assert_eq!(approx_duration(Duration::from_secs(3_600)), "~60 min");
+        assert_eq!(approx_duration(Duration::from_secs(7_200)), "~2 h");
+        assert_eq!(approx_duration(Duration::from_secs(86_400)), "~1 day");
+        assert_eq!(approx_duration(Duration::from_secs(3 * 86_400)), "~3 days");
+    }
+
+    #[test]
+    fn test_fee_breakdown() {
+        let evm = FeeEstimate::from_details(FeeDetails::Evm { gas_limit: 21_000, gas_price: 12_500_000_000 }).unwrap();
+        assert_eq!(fee_breakdown(&evm), "21000 gas × 12.5 gwei");
+        let btc = FeeEstimate::from_details(FeeDetails::Bitcoin { vsize: 110, sat_per_vbyte: 4 }).unwrap();
+        assert_eq!(fee_breakdown(&btc), "110 vB × 4 sat/vB");
+    }
+
+    #[test]
+    fn test_parse_balance_format() {
+        assert_eq!(parse_balance_format(None, false).unwrap(), BalanceFormat::default());
+        assert_eq!(parse_balance_format(None, true).unwrap().thousands_separator, Some(','));
+        let de = parse_balance_format(Some("de"), false).unwrap();
+        assert_eq!((de.thousands_separator, de.decimal_separator), (Some('.'), ','));
+        assert!(parse_balance_format(Some("klingon"), true).is_err());
+    }
+
+    #[test]
+    fn test_context_picks_endpoint_and_balance_format() {
+        let ctx = CliContext {
+            quiet: false,
+            anvil: true,
+            balance_format: parse_balance_format(Some("de"), false).unwrap(),
+        };
+        assert_eq!(ctx.endpoint(None, &Network::Mainnet), ANVIL_RPC_URL);
+        assert_eq!(ctx.endpoint(Some("http://node:8545".to_string()), &Network::Mainnet), "http://node:8545");
+        // Anvil only stands in for EVM networks
+        assert_eq!(ctx.endpoint(None, &Network::BitcoinMainnet), default_rpc_url(&Network::BitcoinMainnet));
+        let ctx = CliContext { anvil: false, ..ctx };
+        assert_eq!(ctx.endpoint(None, &Network::Mainnet), default_rpc_url(&Network::Mainnet));
+
+        assert_eq!(native_amount(1_500_000_000_000_000_000, &Network::Mainnet, &ctx.balance_format), "1,5 ETH");
+        assert_eq!(native_amount(1_500_000_000_000_000_000, &Network::Mainnet, &BalanceFormat::default()), "1.5 ETH");
+    }
+
+    #[test]
+    fn test_parse_units() {
+        assert_eq!(parse_units("1.25", 6).unwrap(), 1_250_000);
+        assert_eq!(parse_units("0.5", 18).unwrap(), 500_000_000_000_000_000);
+        assert_eq!(parse_units("3", 0).unwrap(), 3);
+        assert_eq!(parse_units(".1", 2).unwrap(), 10);
+        assert!(parse_units("0.0000001", 6).is_err());
+        assert!(parse_units("1e18", 18).is_err());
+        assert!(parse_units("-1", 6).is_err());
+        assert!(parse_units(".", 6).is_err());
+    }
+
+    #[test]
+    fn test_format_unix_date_round_trip() {
+        let ts = parse_date_to_unix("2024-07-15").unwrap();
+        assert_eq!(Cli::format_unix_date(ts), "2024-07-15");
+    }
 }
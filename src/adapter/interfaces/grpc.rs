@@ -0,0 +1,208 @@
+//! gRPC API (`grpc` feature)
+//!
+//! Implements the `rustwallet.v1.Wallet` service from `proto/wallet.proto`
+//! on top of the same handlers as the CLI and REST API: requests are mapped
+//! to `GetBalanceQuery` / `TransferCommand`, results back to proto messages.
+//! Domain errors become gRPC statuses (`INVALID_ARGUMENT` for bad input,
+//! including a key that does not control the sender, `FAILED_PRECONDITION`
+//! for missing funds or a transfer that would revert, `ABORTED` for nonce conflicts,
+//! `RESOURCE_EXHAUSTED` / `UNAVAILABLE` for upstream RPC trouble).
+//!
+//! `Transfer` carries the sender's private key, so serve it on a trusted
+//! interface or behind TLS.
+
+use tokio::net::TcpListener;
+use tonic::{transport::server::TcpIncoming, Request, Response, Status};
+use std::sync::Arc;
+use super::resolver::{default_resolver, ServiceResolver};
+use crate::adapter::infrastructure::audit::JsonlAuditSink;
+use crate::core::application::handlers::{GetBalanceHandler, TransferHandler};
+use crate::core::domain::{
+    commands::{TransferCommand, TransferResult},
+    errors::DomainError,
+    queries::{BalanceQueryResult, GetBalanceQuery},
+    services::{AuditSink, BlockchainService, CommandHandler, QueryHandler},
+    value_objects::{Address, Amount, Network, SecretKey},
+};
+
+/// Messages, server and client generated from `proto/wallet.proto`
+pub mod proto {
+    tonic::include_proto!("rustwallet.v1");
+}
+
+use proto::wallet_server::{Wallet, WalletServer};
+
+/// `rustwallet.v1.Wallet` implementation
+#[derive(Clone)]
+pub struct WalletGrpcService {
+    resolve: ServiceResolver,
+    audit_sink: Option<Arc<dyn AuditSink>>,
+}
+
+impl WalletGrpcService {
+    /// Serve every network from its default public endpoint, auditing
+    /// transfers to `$RUSTWALLET_AUDIT_LOG` when it is set
+    pub fn new() -> Self {
+        let state = Self::with_resolver(default_resolver());
+        match JsonlAuditSink::from_env() {
+            Some(sink) => state.with_audit_sink(Arc::new(sink)),
+            None => state,
+        }
+    }
+
+    /// Serve networks from the services `resolve` builds (e.g. paid RPCs, or stubs in tests)
+    pub fn with_resolver(resolve: ServiceResolver) -> Self {
+        Self {
+            resolve,
+            audit_sink: None,
+        }
+    }
+
+    /// Record every transfer, successful or not, in `audit_sink`
+    pub fn with_audit_sink(mut self, audit_sink: Arc<dyn AuditSink>) -> Self {
+        self.audit_sink = Some(audit_sink);
+        self
+    }
+
+    /// Transfer handler for `service`, auditing to the configured sink
+    fn transfer_handler(&self, service: Arc<dyn BlockchainService>) -> TransferHandler {
+        let handler = TransferHandler::new(service);
+        match &self.audit_sink {
+            Some(sink) => handler.with_audit_sink(sink.clone()),
+            None => handler,
+        }
+    }
+
+    /// Wrap in the generated tonic server, ready for `Server::add_service`
+    pub fn into_server(self) -> WalletServer<Self> {
+        WalletServer::new(self)
+    }
+}
+
+impl Default for WalletGrpcService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Serve the gRPC API on `listener` until the task is cancelled
+pub async fn serve(listener: TcpListener, service: WalletGrpcService) -> Result<(), DomainError> {
+    let incoming = TcpIncoming::from_listener(listener, true, None)
+        .map_err(|e| DomainError::ConfigurationError(format!("Failed to listen for gRPC connections: {}", e)))?;
+    tonic::transport::Server::builder()
+        .add_service(service.into_server())
+        .serve_with_incoming(incoming)
+        .await
+        .map_err(|e| DomainError::NetworkError(format!("gRPC server failed: {}", e)))
+}
+
+#[tonic::async_trait]
+impl Wallet for WalletGrpcService {
+    async fn get_balance(
+        &self,
+        request: Request<proto::GetBalanceRequest>,
+    ) -> Result<Response<proto::GetBalanceResponse>, Status> {
+        let request = request.into_inner();
+        let network = parse_network(&request.network).map_err(to_status)?;
+        let address = Address::new(request.address).map_err(to_status)?;
+
+        let service = (self.resolve)(network.clone()).await.map_err(to_status)?;
+        let result = GetBalanceHandler::new(service)
+            .handle(GetBalanceQuery::new(address, network))
+            .await
+            .map_err(to_status)?;
+        Ok(Response::new(result.into()))
+    }
+
+    async fn transfer(
+        &self,
+        request: Request<proto::TransferRequest>,
+    ) -> Result<Response<proto::TransferResponse>, Status> {
+        let command = TransferCommand::try_from(request.into_inner()).map_err(to_status)?;
+
+        let service = (self.resolve)(command.network.clone()).await.map_err(to_status)?;
+        let result = self.transfer_handler(service).handle(command).await.map_err(to_status)?;
+        Ok(Response::new(result.into()))
+    }
+}
+
+impl From<BalanceQueryResult> for proto::GetBalanceResponse {
+    fn from(result: BalanceQueryResult) -> Self {
+        Self {
+            address: result.address.as_str().to_string(),
+            network: network_name(&result.network),
+            chain_type: result.chain_type.name().to_string(),
+            balance: result.balance.to_wei().to_string(),
+            formatted: result.display_native(),
+        }
+    }
+}
+
+impl TryFrom<proto::TransferRequest> for TransferCommand {
+    type Error = DomainError;
+
+    fn try_from(request: proto::TransferRequest) -> Result<Self, DomainError> {
+        let amount = parse_u128("amount", &request.amount)?;
+        let mut command = TransferCommand::new(
+            Address::new(request.from_address)?,
+            Address::new(request.to_address)?,
+            Amount::from_wei(amount),
+            parse_network(&request.network)?,
+            SecretKey::new(request.private_key),
+        );
+        if let Some(gas_price) = request.gas_price {
+            command = command.with_gas_price(parse_u128("gas_price", &gas_price)?);
+        }
+        if request.wait_for_receipt {
+            command = command.with_wait_for_receipt();
+        }
+        Ok(command)
+    }
+}
+
+impl From<TransferResult> for proto::TransferResponse {
+    fn from(result: TransferResult) -> Self {
+        Self {
+            tx_hash: result.tx_hash.as_str().to_string(),
+            from_address: result.from_address.as_str().to_string(),
+            to_address: result.to_address.as_str().to_string(),
+            amount: result.amount.to_wei().to_string(),
+            network: network_name(&result.network),
+            gas_used: result.gas_used.map(|gas| gas.to_string()),
+            effective_gas_price: result.effective_gas_price.map(|price| price.to_string()),
+            fee_paid: result.fee_paid.map(|fee| fee.to_wei().to_string()),
+        }
+    }
+}
+
+/// Short name for built-in networks (so responses can be fed back into requests)
+fn network_name(network: &Network) -> String {
+    network.short_name().unwrap_or(network.name()).to_string()
+}
+
+fn parse_network(name: &str) -> Result<Network, DomainError> {
+    Network::from_name(name).ok_or_else(|| DomainError::InvalidQuery(format!("Unknown network: {}", name)))
+}
+
+fn parse_u128(field: &str, value: &str) -> Result<u128, DomainError> {
+    value
+        .parse()
+        .map_err(|_| DomainError::InvalidQuery(format!("Invalid {} '{}': expected an integer", field, value)))
+}
+
+/// gRPC status for a domain error
+fn to_status(err: DomainError) -> Status {
+    let message = err.user_message();
+    match err {
+        DomainError::RateLimited(_) => Status::resource_exhausted(message),
+        DomainError::NetworkError(_) | DomainError::InvalidResponse(_) | DomainError::TransferFailed(_) => {
+            Status::unavailable(message)
+        }
+        DomainError::BlockchainError(_) | DomainError::ConfigurationError(_) => Status::internal(message),
+        DomainError::InsufficientBalance { .. } | DomainError::WouldRevert(_) => Status::failed_precondition(message),
+        DomainError::NonceTooLow(_) | DomainError::AlreadyKnown(_) | DomainError::ReplacementUnderpriced(_) => {
+            Status::aborted(message)
+        }
+        _ => Status::invalid_argument(message),
+    }
+}
@@ -0,0 +1,154 @@
+//! REST API (`http` feature)
+//!
+//! Runs the same handlers as the CLI behind an axum router:
+//!
+//! | Route                             | Body                | Response              |
+//! |-----------------------------------|---------------------|-----------------------|
+//! | `GET /balance/:network/:address`  | -                   | `BalanceQueryResult`  |
+//! | `POST /transfer`                  | `TransferCommand`   | `TransferResult`      |
+//!
+//! `:network` is a short name such as "mainnet", "bsc", "btc" or "sol-devnet".
+//! Errors are returned as `{"error": "<message>"}` with a status derived
+//! from the `DomainError` (400 for bad input, including a key that does not
+//! control the sender, 409 for nonce conflicts, 422 for a transfer that would
+//! revert, 429 when the upstream RPC is throttling, 502 when it fails or is
+//! unreachable).
+//!
+//! `POST /transfer` takes the sender's private key in the request body, so
+//! only bind the server to a trusted interface (the CLI defaults to
+//! 127.0.0.1) or put it behind TLS and authentication.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use serde_json::json;
+use tokio::net::TcpListener;
+use std::sync::Arc;
+use super::resolver::{default_resolver, ServiceResolver};
+use crate::adapter::infrastructure::audit::JsonlAuditSink;
+use crate::core::application::handlers::{GetBalanceHandler, TransferHandler};
+use crate::core::domain::{
+    commands::{TransferCommand, TransferResult},
+    errors::DomainError,
+    queries::{BalanceQueryResult, GetBalanceQuery},
+    services::{AuditSink, BlockchainService, CommandHandler, QueryHandler},
+    value_objects::{Address, Network},
+};
+
+/// Shared state of the REST API
+#[derive(Clone)]
+pub struct HttpState {
+    resolve: ServiceResolver,
+    audit_sink: Option<Arc<dyn AuditSink>>,
+}
+
+impl HttpState {
+    /// Serve every network from its default public endpoint, auditing
+    /// transfers to `$RUSTWALLET_AUDIT_LOG` when it is set
+    pub fn new() -> Self {
+        let state = Self::with_resolver(default_resolver());
+        match JsonlAuditSink::from_env() {
+            Some(sink) => state.with_audit_sink(Arc::new(sink)),
+            None => state,
+        }
+    }
+
+    /// Serve networks from the services `resolve` builds (e.g. paid RPCs, or stubs in tests)
+    pub fn with_resolver(resolve: ServiceResolver) -> Self {
+        Self {
+            resolve,
+            audit_sink: None,
+        }
+    }
+
+    /// Record every transfer, successful or not, in `audit_sink`
+    pub fn with_audit_sink(mut self, audit_sink: Arc<dyn AuditSink>) -> Self {
+        self.audit_sink = Some(audit_sink);
+        self
+    }
+
+    /// Transfer handler for `service`, auditing to the configured sink
+    fn transfer_handler(&self, service: Arc<dyn BlockchainService>) -> TransferHandler {
+        let handler = TransferHandler::new(service);
+        match &self.audit_sink {
+            Some(sink) => handler.with_audit_sink(sink.clone()),
+            None => handler,
+        }
+    }
+}
+
+impl Default for HttpState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Router exposing the REST API
+pub fn router(state: HttpState) -> Router {
+    Router::new()
+        .route("/balance/:network/:address", get(get_balance))
+        .route("/transfer", post(transfer))
+        .with_state(state)
+}
+
+/// Serve the REST API on `listener` until the task is cancelled
+pub async fn serve(listener: TcpListener, state: HttpState) -> std::io::Result<()> {
+    axum::serve(listener, router(state)).await
+}
+
+async fn get_balance(
+    State(state): State<HttpState>,
+    Path((network, address)): Path<(String, String)>,
+) -> Result<Json<BalanceQueryResult>, ApiError> {
+    let network = Network::from_name(&network)
+        .ok_or_else(|| DomainError::InvalidQuery(format!("Unknown network: {}", network)))?;
+    let address = Address::new(address)?;
+
+    let service = (state.resolve)(network.clone()).await?;
+    let result = GetBalanceHandler::new(service)
+        .handle(GetBalanceQuery::new(address, network))
+        .await?;
+    Ok(Json(result))
+}
+
+async fn transfer(
+    State(state): State<HttpState>,
+    Json(command): Json<TransferCommand>,
+) -> Result<Json<TransferResult>, ApiError> {
+    let service = (state.resolve)(command.network.clone()).await?;
+    let result = state.transfer_handler(service).handle(command).await?;
+    Ok(Json(result))
+}
+
+/// A `DomainError` rendered as a JSON error response
+struct ApiError(DomainError);
+
+impl From<DomainError> for ApiError {
+    fn from(err: DomainError) -> Self {
+        Self(err)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = match &self.0 {
+            DomainError::RateLimited(_) => StatusCode::TOO_MANY_REQUESTS,
+            DomainError::NetworkError(_)
+            | DomainError::InvalidResponse(_)
+            | DomainError::TransferFailed(_) => StatusCode::BAD_GATEWAY,
+            DomainError::BlockchainError(_) | DomainError::ConfigurationError(_) => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+            DomainError::NonceTooLow(_)
+            | DomainError::AlreadyKnown(_)
+            | DomainError::ReplacementUnderpriced(_) => StatusCode::CONFLICT,
+            DomainError::WouldRevert(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            _ => StatusCode::BAD_REQUEST,
+        };
+        (status, Json(json!({ "error": self.0.user_message() }))).into_response()
+    }
+}
@@ -1,3 +1,13 @@
+#[cfg(feature = "cli")]
 pub mod cli;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+#[cfg(feature = "http")]
+pub mod http;
+#[cfg(any(feature = "http", feature = "grpc"))]
+pub mod resolver;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
-pub use cli::Cli;
+#[cfg(feature = "cli")]
+pub use cli::{error_report, exit_code, Cli};
@@ -0,0 +1,32 @@
+//! How the server interfaces (REST, gRPC) pick a blockchain service per request
+
+use futures::future::BoxFuture;
+use std::sync::Arc;
+use crate::adapter::infrastructure::blockchain::{
+    AlloyBlockchainService, BitcoinBlockchainService, SolanaBlockchainService,
+};
+use crate::core::domain::{
+    errors::DomainError,
+    services::BlockchainService,
+    value_objects::{ChainType, Network},
+};
+
+/// Builds the service that requests for a network are run against
+pub type ServiceResolver = Arc<
+    dyn Fn(Network) -> BoxFuture<'static, Result<Arc<dyn BlockchainService>, DomainError>> + Send + Sync,
+>;
+
+/// Resolver serving every network from its default public endpoint
+pub fn default_resolver() -> ServiceResolver {
+    Arc::new(|network| Box::pin(create_service(network)))
+}
+
+/// Pick the chain-specific service for `network`, on its default endpoint
+async fn create_service(network: Network) -> Result<Arc<dyn BlockchainService>, DomainError> {
+    let service: Arc<dyn BlockchainService> = match network.chain_type() {
+        ChainType::Ethereum => Arc::new(AlloyBlockchainService::new_with_default_rpc(network).await?),
+        ChainType::Bitcoin => Arc::new(BitcoinBlockchainService::new(network).await?),
+        ChainType::Solana => Arc::new(SolanaBlockchainService::new(network).await?),
+    };
+    Ok(service)
+}
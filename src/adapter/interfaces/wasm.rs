@@ -0,0 +1,71 @@
+//! Browser entry points (`wasm` feature)
+//!
+//! Exposes the balance query to JavaScript through wasm-bindgen. The same
+//! `GetBalanceHandler` as the CLI runs underneath; HTTP goes through the
+//! browser's `fetch` (reqwest's wasm32 backend).
+//!
+//! ```js
+//! import init, { getBalance } from "./pkg/rustwallet.js";
+//! await init();
+//! const result = JSON.parse(await getBalance("0xd8dA...6045", "mainnet"));
+//! ```
+
+use std::sync::Arc;
+use wasm_bindgen::prelude::*;
+use crate::adapter::infrastructure::blockchain::{
+    AlloyBlockchainService, BitcoinBlockchainService, SolanaBlockchainService,
+};
+use crate::core::application::GetBalanceHandler;
+use crate::core::domain::{
+    errors::DomainError,
+    queries::GetBalanceQuery,
+    services::{BlockchainService, QueryHandler},
+    value_objects::{Address, ChainType, Network},
+};
+
+/// Query a balance and return the `BalanceQueryResult` as a JSON string
+///
+/// `network` is a short name such as "mainnet", "bsc", "btc" or "sol-devnet".
+/// `rpc_url` overrides the network's default endpoint for EVM and Solana.
+#[wasm_bindgen(js_name = getBalance)]
+pub async fn get_balance(
+    address: String,
+    network: String,
+    rpc_url: Option<String>,
+) -> Result<String, JsError> {
+    let network = Network::from_name(&network)
+        .ok_or_else(|| JsError::new(&format!("Unknown network: {}", network)))?;
+    let service = create_service(&network, rpc_url.as_deref())
+        .await
+        .map_err(to_js_error)?;
+
+    let address = Address::new(address).map_err(to_js_error)?;
+    let handler = GetBalanceHandler::new(service);
+    let result = handler
+        .handle(GetBalanceQuery::new(address, network))
+        .await
+        .map_err(to_js_error)?;
+
+    serde_json::to_string(&result).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Pick the chain-specific service for `network`
+async fn create_service(
+    network: &Network,
+    rpc_url: Option<&str>,
+) -> Result<Arc<dyn BlockchainService>, DomainError> {
+    let rpc_url = rpc_url.unwrap_or(network.default_rpc_url());
+    let service: Arc<dyn BlockchainService> = match network.chain_type() {
+        ChainType::Ethereum => Arc::new(AlloyBlockchainService::new(network.clone(), rpc_url).await?),
+        ChainType::Bitcoin => Arc::new(BitcoinBlockchainService::new(network.clone()).await?),
+        ChainType::Solana => Arc::new(
+            SolanaBlockchainService::new_with_rpc_url(network.clone(), rpc_url, Default::default())
+                .await?,
+        ),
+    };
+    Ok(service)
+}
+
+fn to_js_error(err: DomainError) -> JsError {
+    JsError::new(&err.to_string())
+}
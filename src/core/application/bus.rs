@@ -0,0 +1,286 @@
+//! Query and command buses: route a request to the handler registered for its type
+//!
+//! An interface layer (HTTP, gRPC, ...) registers its handlers once and then
+//! dispatches incoming requests without naming the concrete handler:
+//!
+//! ```text
+//! GetBalanceQuery  ──┐                ┌──> GetBalanceHandler
+//! GetBalancesQuery ──┼──> QueryBus ───┼──> GetBalancesHandler
+//!                    │  (by TypeId)   │
+//! ```
+
+use async_trait::async_trait;
+use std::any::{type_name, Any, TypeId};
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use crate::core::domain::{
+    errors::DomainError,
+    services::{Command, CommandHandler, Query, QueryHandler},
+};
+
+/// A request or result whose concrete type is only known at runtime
+pub type AnyMessage = Box<dyn Any + Send>;
+
+/// Handler with its request and result types erased
+#[async_trait]
+trait ErasedHandler: Send + Sync {
+    async fn handle_any(&self, message: AnyMessage) -> Result<AnyMessage, DomainError>;
+}
+
+/// Adapts a `QueryHandler<Q>` to [`ErasedHandler`]
+struct ErasedQueryHandler<Q, H> {
+    handler: H,
+    _query: PhantomData<fn(Q)>,
+}
+
+#[async_trait]
+impl<Q, H> ErasedHandler for ErasedQueryHandler<Q, H>
+where
+    Q: Query,
+    H: QueryHandler<Q, Output = Q::Output>,
+{
+    async fn handle_any(&self, message: AnyMessage) -> Result<AnyMessage, DomainError> {
+        let query = message.downcast::<Q>().map_err(|_| wrong_type::<Q>())?;
+        Ok(Box::new(self.handler.handle(*query).await?))
+    }
+}
+
+/// Adapts a `CommandHandler<C>` to [`ErasedHandler`]
+struct ErasedCommandHandler<C, H> {
+    handler: H,
+    _command: PhantomData<fn(C)>,
+}
+
+#[async_trait]
+impl<C, H> ErasedHandler for ErasedCommandHandler<C, H>
+where
+    C: Command,
+    H: CommandHandler<C, Output = C::Output>,
+{
+    async fn handle_any(&self, message: AnyMessage) -> Result<AnyMessage, DomainError> {
+        let command = message.downcast::<C>().map_err(|_| wrong_type::<C>())?;
+        Ok(Box::new(self.handler.handle(*command).await?))
+    }
+}
+
+fn wrong_type<T>() -> DomainError {
+    DomainError::ConfigurationError(format!("Message is not a {}", type_name::<T>()))
+}
+
+/// Handlers keyed by the `TypeId` of the message they accept
+#[derive(Clone, Default)]
+struct Registry {
+    handlers: HashMap<TypeId, Arc<dyn ErasedHandler>>,
+}
+
+impl Registry {
+    async fn dispatch_any(&self, message: AnyMessage) -> Result<AnyMessage, DomainError> {
+        // `type_id` on the boxed value itself, not on the `Box`
+        let handler = self.handlers.get(&(*message).type_id()).ok_or_else(|| {
+            DomainError::ConfigurationError("No handler registered for this message type".to_string())
+        })?;
+        handler.handle_any(message).await
+    }
+
+    async fn dispatch<M: 'static + Send, O: 'static>(&self, message: M) -> Result<O, DomainError> {
+        if !self.handlers.contains_key(&TypeId::of::<M>()) {
+            return Err(DomainError::ConfigurationError(format!(
+                "No handler registered for {}",
+                type_name::<M>()
+            )));
+        }
+        let output = self.dispatch_any(Box::new(message)).await?;
+        output.downcast::<O>().map(|output| *output).map_err(|_| wrong_type::<O>())
+    }
+}
+
+/// Routes queries to the [`QueryHandler`] registered for their type
+///
+/// ```no_run
+/// # use std::sync::Arc;
+/// # use rustwallet::adapter::infrastructure::blockchain::NoopBlockchainService;
+/// # use rustwallet::core::application::{handlers::GetBalancesHandler, GetBalanceHandler, QueryBus};
+/// # use rustwallet::core::domain::{queries::GetBalanceQuery, services::BlockchainService};
+/// # use rustwallet::core::domain::value_objects::{Address, Network};
+/// # async fn example(address: Address) -> Result<(), rustwallet::core::domain::errors::DomainError> {
+/// let service: Arc<dyn BlockchainService> = Arc::new(NoopBlockchainService::new());
+/// let bus = QueryBus::new()
+///     .register(GetBalanceHandler::new(service.clone()))
+///     .register(GetBalancesHandler::new(service));
+/// let result = bus.dispatch(GetBalanceQuery::new(address, Network::Mainnet)).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct QueryBus {
+    registry: Registry,
+}
+
+impl QueryBus {
+    /// Create a bus with no handlers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Route queries of type `Q` to `handler`, replacing any earlier registration
+    pub fn register<Q, H>(mut self, handler: H) -> Self
+    where
+        Q: Query,
+        H: QueryHandler<Q, Output = Q::Output> + 'static,
+    {
+        let erased = ErasedQueryHandler {
+            handler,
+            _query: PhantomData,
+        };
+        self.registry.handlers.insert(TypeId::of::<Q>(), Arc::new(erased));
+        self
+    }
+
+    /// Whether a handler is registered for `Q`
+    pub fn handles<Q: Query>(&self) -> bool {
+        self.registry.handlers.contains_key(&TypeId::of::<Q>())
+    }
+
+    /// Run `query` through its registered handler
+    pub async fn dispatch<Q: Query>(&self, query: Q) -> Result<Q::Output, DomainError> {
+        self.registry.dispatch(query).await
+    }
+
+    /// Run a type-erased query; the result is the handler's `Output`, boxed
+    ///
+    /// Fails with `ConfigurationError` if no handler accepts the query's type.
+    pub async fn dispatch_any(&self, query: AnyMessage) -> Result<AnyMessage, DomainError> {
+        self.registry.dispatch_any(query).await
+    }
+}
+
+/// Routes commands to the [`CommandHandler`] registered for their type
+#[derive(Clone, Default)]
+pub struct CommandBus {
+    registry: Registry,
+}
+
+impl CommandBus {
+    /// Create a bus with no handlers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Route commands of type `C` to `handler`, replacing any earlier registration
+    pub fn register<C, H>(mut self, handler: H) -> Self
+    where
+        C: Command,
+        H: CommandHandler<C, Output = C::Output> + 'static,
+    {
+        let erased = ErasedCommandHandler {
+            handler,
+            _command: PhantomData,
+        };
+        self.registry.handlers.insert(TypeId::of::<C>(), Arc::new(erased));
+        self
+    }
+
+    /// Whether a handler is registered for `C`
+    pub fn handles<C: Command>(&self) -> bool {
+        self.registry.handlers.contains_key(&TypeId::of::<C>())
+    }
+
+    /// Run `command` through its registered handler
+    pub async fn dispatch<C: Command>(&self, command: C) -> Result<C::Output, DomainError> {
+        self.registry.dispatch(command).await
+    }
+
+    /// Run a type-erased command; the result is the handler's `Output`, boxed
+    pub async fn dispatch_any(&self, command: AnyMessage) -> Result<AnyMessage, DomainError> {
+        self.registry.dispatch_any(command).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::application::handlers::{GetBalanceHandler, GetBalancesHandler};
+    use crate::core::domain::{
+        queries::{BalanceQueryResult, GetBalanceQuery, GetBalancesQuery, GetTransactionHistoryQuery},
+        services::{BlockchainService, Signer},
+        value_objects::{Address, Balance, ChainType, Network, TransactionHash},
+    };
+
+    /// Reports 7 wei for every address
+    struct FixedService;
+
+    #[async_trait]
+    impl BlockchainService for FixedService {
+        fn chain_type(&self) -> ChainType {
+            ChainType::Ethereum
+        }
+
+        async fn get_balance(&self, _address: &Address) -> Result<Balance, DomainError> {
+            Ok(Balance::from_wei(7))
+        }
+
+        async fn transfer(
+            &self,
+            _from: &Address,
+            _to: &Address,
+            _amount: u128,
+            _signer: &dyn Signer,
+        ) -> Result<TransactionHash, DomainError> {
+            Err(DomainError::BlockchainError("FixedService cannot send".to_string()))
+        }
+
+        async fn is_connected(&self) -> bool {
+            true
+        }
+
+        async fn get_block_number(&self) -> Result<u64, DomainError> {
+            Ok(1)
+        }
+    }
+
+    fn address() -> Address {
+        Address::new("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".to_string()).unwrap()
+    }
+
+    fn bus() -> QueryBus {
+        let service: Arc<dyn BlockchainService> = Arc::new(FixedService);
+        QueryBus::new()
+            .register(GetBalanceHandler::new(service.clone()))
+            .register(GetBalancesHandler::new(service))
+    }
+
+    #[tokio::test]
+    async fn test_query_bus_routes_by_query_type() {
+        let bus = bus();
+        assert!(bus.handles::<GetBalanceQuery>());
+        assert!(!bus.handles::<GetTransactionHistoryQuery>());
+
+        let result = bus.dispatch(GetBalanceQuery::new(address(), Network::Mainnet)).await.unwrap();
+        assert_eq!(result.balance.to_wei(), 7);
+
+        let results = bus
+            .dispatch(GetBalancesQuery::new(vec![address(), address()], Network::Mainnet))
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 2);
+
+        let history = GetTransactionHistoryQuery::new(address(), Network::Mainnet);
+        assert!(matches!(bus.dispatch(history).await, Err(DomainError::ConfigurationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_query_bus_dispatches_type_erased_queries() {
+        let bus = bus();
+
+        let query: AnyMessage = Box::new(GetBalanceQuery::new(address(), Network::Mainnet));
+        let output = bus.dispatch_any(query).await.unwrap();
+        let result = output.downcast::<BalanceQueryResult>().unwrap();
+        assert_eq!(result.balance.to_wei(), 7);
+
+        assert!(matches!(
+            bus.dispatch_any(Box::new("not a query")).await,
+            Err(DomainError::ConfigurationError(_))
+        ));
+    }
+}
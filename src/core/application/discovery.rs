@@ -0,0 +1,153 @@
+use futures::future::join_all;
+use std::sync::Arc;
+use crate::core::domain::{
+    errors::DomainError,
+    services::{AddressDerivation, BlockchainService},
+    value_objects::{Address, Balance, ChainType},
+};
+
+/// Maximum balance queries in flight at once during discovery
+const DISCOVERY_CONCURRENCY: u32 = 5;
+
+/// Discover funded accounts of an HD wallet (BIP-44 gap-limit scan)
+///
+/// Derives addresses at index 0, 1, 2, ... and queries their balances in
+/// batches of `DISCOVERY_CONCURRENCY`, stopping after `gap_limit` consecutive
+/// empty addresses (BIP-44 recommends 20). Returns `(index, address, balance)`
+/// for every non-empty account, in index order.
+///
+/// A failed balance query aborts discovery rather than counting as "empty",
+/// so an RPC hiccup cannot hide funded accounts. Wrap `service` in
+/// `RateLimitedBlockchainService` for strict per-second limits.
+pub async fn discover_accounts(
+    hd: &dyn AddressDerivation,
+    chain: ChainType,
+    service: Arc<dyn BlockchainService>,
+    gap_limit: u32,
+) -> Result<Vec<(u32, Address, Balance)>, DomainError> {
+    if gap_limit == 0 {
+        return Err(DomainError::InvalidQuery("gap_limit must be greater than zero".to_string()));
+    }
+
+    let mut found = Vec::new();
+    let mut consecutive_empty = 0;
+    let mut next_index: u32 = 0;
+
+    while consecutive_empty < gap_limit {
+        let batch_size = DISCOVERY_CONCURRENCY.min(gap_limit - consecutive_empty);
+        let batch = (next_index..next_index.saturating_add(batch_size))
+            .map(|index| Ok((index, hd.derive_address(chain, index)?)))
+            .collect::<Result<Vec<(u32, Address)>, DomainError>>()?;
+        if batch.is_empty() {
+            break;
+        }
+        next_index = next_index.saturating_add(batch_size);
+
+        let balances = join_all(batch.iter().map(|(_, address)| service.get_balance(address))).await;
+
+        for ((index, address), balance) in batch.into_iter().zip(balances) {
+            let balance = balance?;
+            if balance.is_zero() {
+                consecutive_empty += 1;
+                if consecutive_empty >= gap_limit {
+                    break;
+                }
+            } else {
+                tracing::debug!("Discovered account #{} {} with {} Wei", index, address, balance.to_wei());
+                consecutive_empty = 0;
+                found.push((index, address, balance));
+            }
+        }
+    }
+
+    Ok(found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::domain::{services::Signer, value_objects::TransactionHash};
+    use async_trait::async_trait;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    /// Derives `0x000...{index}` so tests can map indices to balances
+    struct IndexDerivation;
+
+    impl AddressDerivation for IndexDerivation {
+        fn derive_address(&self, _chain_type: ChainType, index: u32) -> Result<Address, DomainError> {
+            Address::new(format!("0x{:040x}", index))
+        }
+    }
+
+    struct FundedIndices {
+        funded: HashMap<String, u128>,
+        queried: Mutex<Vec<String>>,
+    }
+
+    impl FundedIndices {
+        fn new(funded: &[(u32, u128)]) -> Self {
+            Self {
+                funded: funded
+                    .iter()
+                    .map(|(i, wei)| (format!("0x{:040x}", i), *wei))
+                    .collect(),
+                queried: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl BlockchainService for FundedIndices {
+        fn chain_type(&self) -> ChainType {
+            ChainType::Ethereum
+        }
+
+        async fn get_balance(&self, address: &Address) -> Result<Balance, DomainError> {
+            self.queried.lock().unwrap().push(address.to_string());
+            Ok(Balance::from_wei(*self.funded.get(address.as_str()).unwrap_or(&0)))
+        }
+
+        async fn transfer(
+            &self,
+            _from: &Address,
+            _to: &Address,
+            _amount: u128,
+            _signer: &dyn Signer,
+        ) -> Result<TransactionHash, DomainError> {
+            Err(DomainError::BlockchainError("FundedIndices cannot send".to_string()))
+        }
+
+        async fn is_connected(&self) -> bool {
+            true
+        }
+
+        async fn get_block_number(&self) -> Result<u64, DomainError> {
+            Ok(1)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_discovery_stops_after_gap_limit() {
+        // Index 7 is within the gap after index 0; index 30 is beyond it
+        let service = Arc::new(FundedIndices::new(&[(0, 5), (7, 9), (30, 1)]));
+        let found = discover_accounts(&IndexDerivation, ChainType::Ethereum, service.clone(), 10)
+            .await
+            .unwrap();
+
+        let indices: Vec<u32> = found.iter().map(|(i, _, _)| *i).collect();
+        assert_eq!(indices, vec![0, 7]);
+        assert_eq!(found[1].2, Balance::from_wei(9));
+        // Stopped at index 17 (10 empty after #7), never reaching #30
+        assert_eq!(service.queried.lock().unwrap().len(), 18);
+    }
+
+    #[tokio::test]
+    async fn test_discovery_empty_wallet() {
+        let service = Arc::new(FundedIndices::new(&[]));
+        let found = discover_accounts(&IndexDerivation, ChainType::Ethereum, service, 3)
+            .await
+            .unwrap();
+        assert!(found.is_empty());
+    }
+}
@@ -0,0 +1,118 @@
+use crate::core::domain::{
+    errors::DomainError,
+    queries::GetAccountInfoQuery,
+    services::{BlockchainService, QueryHandler},
+    value_objects::{AccountInfo, ChainType},
+};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Account overview query handler - balance, transaction count and code in one query
+pub struct GetAccountInfoHandler {
+    blockchain_service: Arc<dyn BlockchainService>,
+}
+
+impl GetAccountInfoHandler {
+    /// Create new GetAccountInfoHandler with a blockchain service
+    pub fn new(blockchain_service: Arc<dyn BlockchainService>) -> Self {
+        Self { blockchain_service }
+    }
+}
+
+#[async_trait]
+impl QueryHandler<GetAccountInfoQuery> for GetAccountInfoHandler {
+    type Output = AccountInfo;
+
+    async fn handle(&self, query: GetAccountInfoQuery) -> Result<Self::Output, DomainError> {
+        let detected = ChainType::detect_from_address(&query.address);
+        if detected != query.chain_type {
+            return Err(DomainError::ChainMismatch {
+                expected: query.chain_type,
+                detected,
+            });
+        }
+        let served = self.blockchain_service.chain_type();
+        if served != query.chain_type {
+            return Err(DomainError::ChainMismatch {
+                expected: served,
+                detected: query.chain_type,
+            });
+        }
+
+        tracing::info!(
+            "Querying account info for {} on network {}",
+            query.address,
+            query.network.name()
+        );
+
+        let info = self.blockchain_service.get_account_info(&query.address).await?;
+
+        tracing::info!(
+            "Account info query successful: {} has {} {}, {:?} transactions, contract={:?}",
+            query.address,
+            info.balance.to_wei(),
+            query.chain_type.smallest_unit(),
+            info.transaction_count,
+            info.is_contract
+        );
+
+        Ok(info)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::domain::{
+        services::Signer,
+        value_objects::{Address, Balance, Network, TransactionHash},
+    };
+
+    /// Reports a fixed balance only, relying on the default `get_account_info`
+    struct BalanceOnlyService;
+
+    #[async_trait]
+    impl BlockchainService for BalanceOnlyService {
+        fn chain_type(&self) -> ChainType {
+            ChainType::Ethereum
+        }
+
+        async fn get_balance(&self, _address: &Address) -> Result<Balance, DomainError> {
+            Ok(Balance::from_wei(42))
+        }
+
+        async fn transfer(
+            &self,
+            _from: &Address,
+            _to: &Address,
+            _amount: u128,
+            _signer: &dyn Signer,
+        ) -> Result<TransactionHash, DomainError> {
+            Err(DomainError::BlockchainError("BalanceOnlyService cannot send".to_string()))
+        }
+
+        async fn is_connected(&self) -> bool {
+            true
+        }
+
+        async fn get_block_number(&self) -> Result<u64, DomainError> {
+            Ok(1)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_account_info_handler_defaults_to_balance_only() {
+        let handler = GetAccountInfoHandler::new(Arc::new(BalanceOnlyService));
+        let address = Address::new("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEbC".to_string()).unwrap();
+
+        let info = handler
+            .handle(GetAccountInfoQuery::new(address.clone(), Network::Mainnet))
+            .await
+            .unwrap();
+        assert_eq!(info, AccountInfo::new(address, Balance::from_wei(42)));
+
+        let sol = Address::new("DRpbCBMxVnDK7maPM5tGv6MvB3v1sRMC86PZ8okm21hy".to_string()).unwrap();
+        let mismatch = handler.handle(GetAccountInfoQuery::new(sol, Network::Mainnet)).await;
+        assert!(matches!(mismatch, Err(DomainError::ChainMismatch { .. })));
+    }
+}
@@ -2,6 +2,7 @@ use crate::core::domain::{
     errors::DomainError,
     queries::{BalanceQueryResult, GetBalanceQuery},
     services::{BlockchainService, GetBalanceQueryHandler, QueryHandler},
+    value_objects::ChainType,
 };
 use async_trait::async_trait;
 use std::sync::Arc;
@@ -23,6 +24,23 @@ impl QueryHandler<GetBalanceQuery> for GetBalanceHandler {
     type Output = BalanceQueryResult;
 
     async fn handle(&self, query: GetBalanceQuery) -> Result<Self::Output, DomainError> {
+        // Reject e.g. a Solana address on an EVM network before any RPC call
+        let detected = ChainType::detect_from_address(&query.address);
+        if detected != query.chain_type {
+            return Err(DomainError::ChainMismatch {
+                expected: query.chain_type,
+                detected,
+            });
+        }
+        // ...and a query for one chain sent to another chain's service
+        let served = self.blockchain_service.chain_type();
+        if served != query.chain_type {
+            return Err(DomainError::ChainMismatch {
+                expected: served,
+                detected: query.chain_type,
+            });
+        }
+
         tracing::info!(
             "Querying {} balance for address {} on network {}",
             query.chain_type.name(),
@@ -32,21 +50,20 @@ impl QueryHandler<GetBalanceQuery> for GetBalanceHandler {
 
         tracing::debug!(
             "Chain details: currency={}, unit={}, decimals={}",
-            query.chain_type.native_currency(),
+            query.network.native_symbol(),
             query.chain_type.smallest_unit(),
-            query.chain_type.decimals()
+            query.network.native_decimals()
         );
 
         // Get balance from blockchain service
         let balance = self.blockchain_service.get_balance(&query.address).await?;
 
         tracing::info!(
-            "Balance query successful: {} has {} {} ({} {})",
+            "Balance query successful: {} has {} {} ({})",
             query.address,
             balance.to_wei(),
             query.chain_type.smallest_unit(),
-            balance.to_ether(),
-            query.chain_type.native_currency()
+            balance.format_native(query.network.native_decimals() as usize, &query.network)
         );
 
         // Return result
@@ -64,14 +81,22 @@ impl GetBalanceQueryHandler for GetBalanceHandler {}
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::domain::value_objects::{Address, Balance, ChainType, Network, TransactionHash};
+    use crate::core::domain::{
+        services::Signer,
+        value_objects::{Address, Balance, Network, TransactionHash},
+    };
 
     struct MockBlockchainService {
         balance: Balance,
+        chain_type: ChainType,
     }
 
     #[async_trait]
     impl BlockchainService for MockBlockchainService {
+        fn chain_type(&self) -> ChainType {
+            self.chain_type
+        }
+
         async fn get_balance(&self, _address: &Address) -> Result<Balance, DomainError> {
             Ok(self.balance)
         }
@@ -81,7 +106,7 @@ mod tests {
             _from: &Address,
             _to: &Address,
             _amount: u128,
-            _private_key: &str,
+            _signer: &dyn Signer,
         ) -> Result<TransactionHash, DomainError> {
             // Mock implementation for testing
             TransactionHash::new("0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef".to_string())
@@ -100,6 +125,7 @@ mod tests {
     async fn test_get_balance_handler() {
         let mock_service = Arc::new(MockBlockchainService {
             balance: Balance::from_ether(10.5),
+            chain_type: ChainType::Ethereum,
         });
 
         let handler = GetBalanceHandler::new(mock_service);
@@ -124,6 +150,7 @@ mod tests {
 
         let mock_service = Arc::new(MockBlockchainService {
             balance: Balance::from_wei(100_000_000), // 1 BTC in satoshis
+            chain_type: ChainType::Bitcoin,
         });
 
         let handler = GetBalanceHandler::new(mock_service);
@@ -148,4 +175,54 @@ mod tests {
         assert_eq!(balance_result.chain_type.smallest_unit(), "Satoshi");
         assert_eq!(balance_result.chain_type.decimals(), 8);
     }
+
+    #[tokio::test]
+    async fn test_get_balance_handler_rejects_cross_chain_addresses() {
+        let handler = GetBalanceHandler::new(Arc::new(MockBlockchainService {
+            balance: Balance::zero(),
+            chain_type: ChainType::Ethereum,
+        }));
+        let eth = "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEbC";
+        let btc = "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa";
+        let sol = "DRpbCBMxVnDK7maPM5tGv6MvB3v1sRMC86PZ8okm21hy";
+
+        let cases = [
+            (sol, Network::Mainnet, ChainType::Ethereum, ChainType::Solana),
+            (btc, Network::Mainnet, ChainType::Ethereum, ChainType::Bitcoin),
+            (eth, Network::BitcoinMainnet, ChainType::Bitcoin, ChainType::Ethereum),
+            (sol, Network::BitcoinMainnet, ChainType::Bitcoin, ChainType::Solana),
+            (eth, Network::SolanaMainnet, ChainType::Solana, ChainType::Ethereum),
+            (btc, Network::SolanaMainnet, ChainType::Solana, ChainType::Bitcoin),
+        ];
+        for (address, network, expected, detected) in cases {
+            let query = GetBalanceQuery::new(Address::new(address.to_string()).unwrap(), network);
+            match handler.handle(query).await {
+                Err(DomainError::ChainMismatch { expected: e, detected: d }) => {
+                    assert_eq!((e, d), (expected, detected), "{}", address);
+                }
+                other => panic!("Expected ChainMismatch for {}, got {:?}", address, other),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_balance_handler_rejects_query_for_another_chains_service() {
+        // A Bitcoin query routed to an Ethereum service would print satoshis as wei
+        let handler = GetBalanceHandler::new(Arc::new(MockBlockchainService {
+            balance: Balance::from_wei(100_000_000),
+            chain_type: ChainType::Ethereum,
+        }));
+        let query = GetBalanceQuery::new(
+            Address::new("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa".to_string()).unwrap(),
+            Network::BitcoinMainnet,
+        );
+
+        assert!(matches!(
+            handler.handle(query).await,
+            Err(DomainError::ChainMismatch {
+                expected: ChainType::Ethereum,
+                detected: ChainType::Bitcoin
+            })
+        ));
+    }
 }
@@ -0,0 +1,181 @@
+use crate::core::domain::{
+    errors::DomainError,
+    queries::{BalanceQueryResult, GetBalancesQuery},
+    services::{BlockchainService, QueryHandler},
+    value_objects::ChainType,
+};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Addresses per `get_balances` call when reporting progress
+const PROGRESS_CHUNK_SIZE: usize = 25;
+
+/// Called with `(completed, total)` as balances come in
+pub type ProgressCallback = Box<dyn Fn(usize, usize) + Send + Sync>;
+
+/// Batch balance query handler - one result per address, in input order
+///
+/// Errors stay per address: an address for another chain or a failed read
+/// does not affect the others.
+pub struct GetBalancesHandler {
+    blockchain_service: Arc<dyn BlockchainService>,
+    progress: Option<ProgressCallback>,
+}
+
+impl GetBalancesHandler {
+    /// Create new GetBalancesHandler with a blockchain service
+    pub fn new(blockchain_service: Arc<dyn BlockchainService>) -> Self {
+        Self {
+            blockchain_service,
+            progress: None,
+        }
+    }
+
+    /// Report progress while querying
+    ///
+    /// Addresses are then sent in chunks of 25 and `progress` fires after
+    /// each chunk. Without a callback all addresses go out in a single
+    /// `get_balances` call.
+    pub fn with_progress(mut self, progress: ProgressCallback) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+}
+
+#[async_trait]
+impl QueryHandler<GetBalancesQuery> for GetBalancesHandler {
+    type Output = Vec<Result<BalanceQueryResult, DomainError>>;
+
+    async fn handle(&self, query: GetBalancesQuery) -> Result<Self::Output, DomainError> {
+        let served = self.blockchain_service.chain_type();
+        if served != query.chain_type {
+            return Err(DomainError::ChainMismatch {
+                expected: served,
+                detected: query.chain_type,
+            });
+        }
+
+        tracing::info!(
+            "Querying {} {} balances on network {}",
+            query.addresses.len(),
+            query.chain_type.name(),
+            query.network.name()
+        );
+
+        // Only addresses for the network's chain are sent to the service
+        let mut results: Vec<Option<Result<BalanceQueryResult, DomainError>>> = query
+            .addresses
+            .iter()
+            .map(|address| {
+                let detected = ChainType::detect_from_address(address);
+                (detected != query.chain_type).then_some(Err(DomainError::ChainMismatch {
+                    expected: query.chain_type,
+                    detected,
+                }))
+            })
+            .collect();
+        let pending: Vec<usize> = (0..results.len()).filter(|&i| results[i].is_none()).collect();
+
+        let total = query.addresses.len();
+        let mut completed = total - pending.len();
+        let chunk_size = match self.progress {
+            Some(_) => PROGRESS_CHUNK_SIZE,
+            None => pending.len().max(1),
+        };
+        if let Some(progress) = &self.progress {
+            progress(completed, total);
+        }
+
+        for chunk in pending.chunks(chunk_size) {
+            let addresses: Vec<_> = chunk.iter().map(|&i| query.addresses[i].clone()).collect();
+            let balances = self.blockchain_service.get_balances(&addresses).await;
+            for ((&i, address), balance) in chunk.iter().zip(addresses).zip(balances) {
+                results[i] = Some(balance.map(|balance| {
+                    BalanceQueryResult::new(address, query.network.clone(), balance)
+                }));
+            }
+
+            completed += chunk.len();
+            if let Some(progress) = &self.progress {
+                progress(completed, total);
+            }
+        }
+
+        Ok(results
+            .into_iter()
+            .map(|result| {
+                result.unwrap_or_else(|| {
+                    Err(DomainError::BlockchainError("No balance returned".to_string()))
+                })
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::domain::{
+        services::Signer,
+        value_objects::{Address, Balance, Network, TransactionHash},
+    };
+    use std::sync::Mutex;
+
+    /// Reports each address's last byte as its balance
+    struct LastByteService;
+
+    #[async_trait]
+    impl BlockchainService for LastByteService {
+        fn chain_type(&self) -> ChainType {
+            ChainType::Ethereum
+        }
+
+        async fn get_balance(&self, address: &Address) -> Result<Balance, DomainError> {
+            let last = u128::from_str_radix(&address.as_str()[40..], 16).unwrap();
+            Ok(Balance::from_wei(last))
+        }
+
+        async fn transfer(
+            &self,
+            _from: &Address,
+            _to: &Address,
+            _amount: u128,
+            _signer: &dyn Signer,
+        ) -> Result<TransactionHash, DomainError> {
+            Err(DomainError::BlockchainError("LastByteService cannot send".to_string()))
+        }
+
+        async fn is_connected(&self) -> bool {
+            true
+        }
+
+        async fn get_block_number(&self) -> Result<u64, DomainError> {
+            Ok(1)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_balances_reports_progress_in_order() {
+        let calls: Arc<Mutex<Vec<(usize, usize)>>> = Arc::default();
+        let recorded = calls.clone();
+        let handler = GetBalancesHandler::new(Arc::new(LastByteService))
+            .with_progress(Box::new(move |done, total| recorded.lock().unwrap().push((done, total))));
+
+        let mut addresses: Vec<Address> = (0..60)
+            .map(|i| Address::new(format!("0x{:040x}", i)).unwrap())
+            .collect();
+        addresses.insert(1, Address::new("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa".to_string()).unwrap());
+
+        let results = handler
+            .handle(GetBalancesQuery::new(addresses, Network::Mainnet))
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 61);
+        assert_eq!(results[0].as_ref().unwrap().balance.to_wei(), 0);
+        assert!(matches!(results[1], Err(DomainError::ChainMismatch { .. })));
+        assert_eq!(results[60].as_ref().unwrap().balance.to_wei(), 59);
+        // The mismatched address counts as done up front, then chunks of 25
+        assert_eq!(*calls.lock().unwrap(), vec![(1, 61), (26, 61), (51, 61), (61, 61)]);
+    }
+}
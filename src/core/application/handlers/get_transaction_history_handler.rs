@@ -0,0 +1,145 @@
+use crate::core::domain::{
+    errors::DomainError,
+    queries::{GetTransactionHistoryQuery, Page, TransactionSummary},
+    services::{QueryHandler, TransactionHistoryService},
+};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Transaction history query handler - pages through an address's history
+pub struct GetTransactionHistoryHandler {
+    history_service: Arc<dyn TransactionHistoryService>,
+}
+
+impl GetTransactionHistoryHandler {
+    /// Create new GetTransactionHistoryHandler with a history service
+    pub fn new(history_service: Arc<dyn TransactionHistoryService>) -> Self {
+        Self { history_service }
+    }
+}
+
+#[async_trait]
+impl QueryHandler<GetTransactionHistoryQuery> for GetTransactionHistoryHandler {
+    type Output = Page<TransactionSummary>;
+
+    async fn handle(&self, query: GetTransactionHistoryQuery) -> Result<Self::Output, DomainError> {
+        if query.page == 0 {
+            return Err(DomainError::InvalidQuery("page must be >= 1".to_string()));
+        }
+        if query.page_size == 0 {
+            return Err(DomainError::InvalidQuery("page_size must be >= 1".to_string()));
+        }
+        if let (Some(from), Some(to)) = (query.from_block, query.to_block) {
+            if from > to {
+                return Err(DomainError::InvalidQuery(format!(
+                    "from_block ({}) must not exceed to_block ({})",
+                    from, to
+                )));
+            }
+        }
+
+        tracing::info!(
+            "Querying transaction history for {} on {} (page {}, size {})",
+            query.address,
+            query.network.name(),
+            query.page,
+            query.page_size
+        );
+
+        let page = self.history_service.get_transaction_history(&query).await?;
+
+        tracing::info!(
+            "History query returned {} transactions (has_more={})",
+            page.items.len(),
+            page.has_more
+        );
+
+        Ok(page)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::domain::value_objects::{Address, Amount, Network, TransactionHash};
+
+    /// Serves a fixed list of transactions, paginated by the query
+    struct MockHistoryService {
+        transactions: Vec<TransactionSummary>,
+    }
+
+    #[async_trait]
+    impl TransactionHistoryService for MockHistoryService {
+        async fn get_transaction_history(
+            &self,
+            query: &GetTransactionHistoryQuery,
+        ) -> Result<Page<TransactionSummary>, DomainError> {
+            let items = self
+                .transactions
+                .iter()
+                .filter(|tx| query.from_block.is_none_or(|from| tx.block_number >= from))
+                .filter(|tx| query.to_block.is_none_or(|to| tx.block_number <= to))
+                .skip(((query.page - 1) * query.page_size) as usize)
+                .take(query.page_size as usize)
+                .cloned()
+                .collect();
+            Ok(Page::new(items, query.page, query.page_size))
+        }
+    }
+
+    fn summary(block_number: u64) -> TransactionSummary {
+        TransactionSummary {
+            hash: TransactionHash::new(format!("0x{:064x}", block_number)).unwrap(),
+            block_number,
+            timestamp: 1_700_000_000 + block_number,
+            from: Address::new("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEbC".to_string()).unwrap(),
+            to: Some(Address::new("0x8894E0a0c962CB723c1976a4421c95949bE2D4E3".to_string()).unwrap()),
+            value: Amount::from_wei(1),
+            success: true,
+        }
+    }
+
+    fn handler() -> GetTransactionHistoryHandler {
+        GetTransactionHistoryHandler::new(Arc::new(MockHistoryService {
+            transactions: (1..=5).map(summary).collect(),
+        }))
+    }
+
+    fn query() -> GetTransactionHistoryQuery {
+        GetTransactionHistoryQuery::new(
+            Address::new("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEbC".to_string()).unwrap(),
+            Network::Mainnet,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_history_pagination() {
+        let handler = handler();
+
+        let first = handler.handle(query().with_page(1, 2)).await.unwrap();
+        assert_eq!(first.items.len(), 2);
+        assert!(first.has_more);
+
+        let last = handler.handle(query().with_page(3, 2)).await.unwrap();
+        assert_eq!(last.items.len(), 1);
+        assert!(!last.has_more);
+    }
+
+    #[tokio::test]
+    async fn test_history_block_range_and_validation() {
+        let handler = handler();
+
+        let ranged = handler
+            .handle(query().with_block_range(Some(2), Some(3)))
+            .await
+            .unwrap();
+        let blocks: Vec<u64> = ranged.items.iter().map(|tx| tx.block_number).collect();
+        assert_eq!(blocks, vec![2, 3]);
+
+        let inverted = handler.handle(query().with_block_range(Some(4), Some(1))).await;
+        assert!(matches!(inverted, Err(DomainError::InvalidQuery(_))));
+
+        let zero_page = handler.handle(query().with_page(0, 10)).await;
+        assert!(matches!(zero_page, Err(DomainError::InvalidQuery(_))));
+    }
+}
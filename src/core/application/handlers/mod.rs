@@ -1,5 +1,11 @@
+pub mod get_account_info_handler;
 pub mod get_balance_handler;
+pub mod get_balances_handler;
+pub mod get_transaction_history_handler;
 pub mod transfer_handler;
 
+pub use get_account_info_handler::GetAccountInfoHandler;
 pub use get_balance_handler::GetBalanceHandler;
-pub use transfer_handler::TransferHandler;
+pub use get_balances_handler::{GetBalancesHandler, ProgressCallback};
+pub use get_transaction_history_handler::GetTransactionHistoryHandler;
+pub use transfer_handler::{record_audit, TransferHandler};
@@ -1,10 +1,16 @@
+use crate::core::application::unix_now;
 use crate::core::domain::{
     commands::{TransferCommand, TransferResult},
     errors::DomainError,
-    services::{BlockchainService, CommandHandler},
+    services::{AuditSink, BlockchainService, CommandHandler, Signer},
+    value_objects::{TransferAuditRecord, TransferOutcome},
 };
 use async_trait::async_trait;
 use std::sync::Arc;
+use std::time::Duration;
+
+/// How long to wait for a receipt when `TransferCommand::wait_for_receipt` is set
+const RECEIPT_TIMEOUT: Duration = Duration::from_secs(30);
 
 /// Transfer command handler - orchestrates the transfer use case
 ///
@@ -20,6 +26,9 @@ use std::sync::Arc;
 /// ```
 pub struct TransferHandler {
     blockchain_service: Arc<dyn BlockchainService>,
+    audit_sink: Option<Arc<dyn AuditSink>>,
+    /// Signs every transfer instead of a key from the command
+    signer: Option<Arc<dyn Signer>>,
 }
 
 impl TransferHandler {
@@ -27,7 +36,83 @@ impl TransferHandler {
     pub fn new(blockchain_service: Arc<dyn BlockchainService>) -> Self {
         Self {
             blockchain_service,
+            audit_sink: None,
+            signer: None,
+        }
+    }
+
+    /// Sign with `signer` (e.g. a Ledger) rather than the command's private
+    /// key, which is then ignored; see [`TransferCommand::without_key`]
+    pub fn with_signer(mut self, signer: Arc<dyn Signer>) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    /// Record every handled transfer, successful or not, in `audit_sink`
+    pub fn with_audit_sink(mut self, audit_sink: Arc<dyn AuditSink>) -> Self {
+        self.audit_sink = Some(audit_sink);
+        self
+    }
+
+    /// Validate, send and optionally wait for `command`
+    async fn execute(&self, command: TransferCommand) -> Result<TransferResult, DomainError> {
+        // A zero-value transfer only burns gas, so it is almost always a mistake
+        if command.amount.is_zero() && !command.allow_zero {
+            return Err(DomainError::InvalidAmount);
         }
+        // Address equality ignores EVM checksum case, so mixed-case copies still match
+        if command.from_address == command.to_address && !command.allow_self {
+            return Err(DomainError::SelfTransfer(command.from_address.to_string()));
+        }
+
+        // Execute transfer via blockchain service, signed by the handler's
+        // signer or else with the command's key
+        let key_signer;
+        let signer = match &self.signer {
+            Some(signer) => signer.as_ref(),
+            None => {
+                key_signer = self.blockchain_service.signer_for_key(&command.private_key)?;
+                key_signer.as_ref()
+            }
+        };
+        let (from, to, amount) = (&command.from_address, &command.to_address, command.amount.to_wei());
+        let tx_hash = match command.gas_price {
+            Some(gas_price) => {
+                self.blockchain_service
+                    .transfer_with_gas_price(from, to, amount, signer, gas_price)
+                    .await?
+            }
+            None => self.blockchain_service.transfer(from, to, amount, signer).await?,
+        };
+
+        let receipt = if command.wait_for_receipt {
+            match self
+                .blockchain_service
+                .wait_for_receipt(&tx_hash, RECEIPT_TIMEOUT)
+                .await
+            {
+                Ok(receipt) => Some(receipt),
+                Err(e) => {
+                    tracing::warn!("Transfer {} sent but receipt unavailable: {}", tx_hash, e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        // Build and return result
+        let result = TransferResult::new(
+            tx_hash,
+            command.from_address,
+            command.to_address,
+            command.amount,
+            command.network,
+        );
+        Ok(match &receipt {
+            Some(receipt) => result.with_receipt(receipt),
+            None => result,
+        })
     }
 }
 
@@ -41,48 +126,99 @@ impl CommandHandler<TransferCommand> for TransferHandler {
     ///
     /// 1. Extract command parameters
     /// 2. Delegate to blockchain service for execution
-    /// 3. Build and return transfer result
+    /// 3. Optionally wait for the receipt to report gas used and fee paid
+    /// 4. With an audit sink, record the attempt and its outcome
+    /// 5. Build and return transfer result
+    ///
+    /// The transaction is already broadcast by step 2, so a receipt that is
+    /// unsupported or not mined in time leaves the cost fields as `None`
+    /// instead of failing the transfer. Likewise a failed audit write is
+    /// logged rather than returned.
     ///
     /// # Errors
     ///
+    /// - `InvalidAmount`: Zero amount without `allow_zero` (checked before any network call)
+    /// - `SelfTransfer`: Sender and recipient are the same address without `allow_self`
     /// - `InvalidPrivateKey`: Private key format invalid
-    /// - `InsufficientBalance`: Not enough balance for transfer
+    /// - `InsufficientBalance`: Not enough balance for amount plus gas
+    /// - `ConfigurationError`: A gas price was set for a chain without one
     /// - `TransferFailed`: Transaction submission failed
     /// - `NetworkError`: Network communication issues
     async fn handle(&self, command: TransferCommand) -> Result<Self::Output, DomainError> {
-        // Execute transfer via blockchain service
-        let tx_hash = self
-            .blockchain_service
-            .transfer(
-                &command.from_address,
-                &command.to_address,
-                command.amount.to_wei(),
-                &command.private_key,
-            )
-            .await?;
+        let Some(audit) = &self.audit_sink else {
+            return self.execute(command).await;
+        };
 
-        // Build and return result
-        Ok(TransferResult::new(
-            tx_hash,
-            command.from_address,
-            command.to_address,
-            command.amount,
-            command.network,
-        ))
+        let from = command.from_address.clone();
+        let to = command.to_address.clone();
+        let (amount, network) = (command.amount, command.network.clone());
+        let result = self.execute(command).await;
+
+        let outcome = TransferOutcome::from_result(result.as_ref().map(|result| &result.tx_hash));
+        let record = TransferAuditRecord::new(unix_now(), from, to, Some(amount), network, outcome);
+        record_audit(audit.as_ref(), &record).await;
+        result
+    }
+}
+
+/// Write `record` to `sink`, logging a failed write instead of returning it
+///
+/// The transfer may already be broadcast, so its outcome still has to reach
+/// the caller. Also used for transfers that bypass [`TransferHandler`]
+/// (sweeps, token transfers).
+pub async fn record_audit(sink: &dyn AuditSink, record: &TransferAuditRecord) {
+    if let Err(e) = sink.record_transfer(record).await {
+        tracing::error!("Failed to write transfer audit record: {}", e);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::domain::value_objects::{Address, Amount, Balance, Network, TransactionHash};
+    use crate::core::domain::{
+        value_objects::{
+            Address, Amount, Balance, ChainType, Network, SecretKey, TransactionHash, TransactionReceipt,
+        },
+    };
+
+    /// Signer whose signatures are never checked by the mocks
+    struct StubSigner;
+
+    #[async_trait]
+    impl Signer for StubSigner {
+        fn address(&self) -> Address {
+            Address::new("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEbC".to_string()).unwrap()
+        }
+
+        async fn sign_transaction(&self, _payload: &[u8]) -> Result<Vec<u8>, DomainError> {
+            Ok(vec![0; 65])
+        }
+
+        async fn sign_message(&self, _message: &[u8]) -> Result<Vec<u8>, DomainError> {
+            Ok(vec![0; 65])
+        }
+    }
 
     struct MockBlockchainService {
         expected_tx_hash: String,
     }
 
+    fn transfer_command() -> TransferCommand {
+        TransferCommand::new(
+            Address::new("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEbC".to_string()).unwrap(),
+            Address::new("0x8894E0a0c962CB723c1976a4421c95949bE2D4E3".to_string()).unwrap(),
+            Amount::from_ether(0.001),
+            Network::Sepolia,
+            SecretKey::from("test_private_key"),
+        )
+    }
+
     #[async_trait]
     impl BlockchainService for MockBlockchainService {
+        fn chain_type(&self) -> ChainType {
+            ChainType::Ethereum
+        }
+
         async fn get_balance(&self, _address: &Address) -> Result<Balance, DomainError> {
             Ok(Balance::from_ether(10.0))
         }
@@ -92,11 +228,30 @@ mod tests {
             _from: &Address,
             _to: &Address,
             _amount: u128,
-            _private_key: &str,
+            _signer: &dyn Signer,
         ) -> Result<TransactionHash, DomainError> {
             TransactionHash::new(self.expected_tx_hash.clone())
         }
 
+        fn signer_for_key(&self, _private_key: &SecretKey) -> Result<Box<dyn Signer>, DomainError> {
+            Ok(Box::new(StubSigner))
+        }
+
+        async fn wait_for_receipt(
+            &self,
+            tx_hash: &TransactionHash,
+            _timeout: Duration,
+        ) -> Result<TransactionReceipt, DomainError> {
+            Ok(TransactionReceipt {
+                tx_hash: tx_hash.clone(),
+                block_number: Some(12346),
+                success: true,
+                gas_used: 21_000,
+                effective_gas_price: 2_000_000_000,
+                logs: vec![],
+            })
+        }
+
         async fn is_connected(&self) -> bool {
             true
         }
@@ -128,7 +283,7 @@ mod tests {
             to_address.clone(),
             amount,
             Network::Sepolia,
-            "test_private_key".to_string(),
+            SecretKey::from("test_private_key"),
         );
 
         // Execute
@@ -144,6 +299,172 @@ mod tests {
         assert_eq!(transfer_result.network, Network::Sepolia);
     }
 
+    #[tokio::test]
+    async fn test_transfer_handler_rejects_zero_amount() {
+        let handler = TransferHandler::new(Arc::new(MockBlockchainService {
+            expected_tx_hash: format!("0x{}", "ab".repeat(32)),
+        }));
+        let mut command = transfer_command();
+        command.amount = Amount::from_wei(0);
+
+        let result = handler.handle(command.clone()).await;
+        assert!(matches!(result, Err(DomainError::InvalidAmount)));
+
+        let result = handler.handle(command.with_allow_zero()).await.unwrap();
+        assert!(result.amount.is_zero());
+    }
+
+    #[tokio::test]
+    async fn test_transfer_handler_rejects_self_transfer_despite_case() {
+        let handler = TransferHandler::new(Arc::new(MockBlockchainService {
+            expected_tx_hash: format!("0x{}", "ab".repeat(32)),
+        }));
+        let mut command = transfer_command();
+        command.from_address = Address::new("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEbC".to_string()).unwrap();
+        command.to_address = Address::new("0x742d35cc6634c0532925a3b844bc9e7595f0bebc".to_string()).unwrap();
+
+        let result = handler.handle(command.clone()).await;
+        assert!(matches!(result, Err(DomainError::SelfTransfer(_))));
+
+        assert!(handler.handle(command.with_allow_self()).await.is_ok());
+    }
+
+    /// Keeps audit records in memory
+    #[derive(Default)]
+    struct RecordingSink {
+        records: std::sync::Mutex<Vec<TransferAuditRecord>>,
+    }
+
+    #[async_trait]
+    impl AuditSink for RecordingSink {
+        async fn record_transfer(&self, record: &TransferAuditRecord) -> Result<(), DomainError> {
+            self.records.lock().unwrap().push(record.clone());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transfer_handler_audits_successes_and_failures() {
+        let expected_hash = format!("0x{}", "ab".repeat(32));
+        let sink = Arc::new(RecordingSink::default());
+        let handler = TransferHandler::new(Arc::new(MockBlockchainService {
+            expected_tx_hash: expected_hash.clone(),
+        }))
+        .with_audit_sink(sink.clone());
+
+        handler.handle(transfer_command()).await.unwrap();
+        let mut zero = transfer_command();
+        zero.amount = Amount::from_wei(0);
+        assert!(handler.handle(zero).await.is_err());
+
+        let records = sink.records.lock().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].from, transfer_command().from_address);
+        assert_eq!(records[0].amount, Some(Amount::from_ether(0.001)));
+        assert_eq!(
+            records[0].outcome,
+            TransferOutcome::Sent { tx_hash: TransactionHash::new(expected_hash).unwrap() }
+        );
+        assert!(matches!(&records[1].outcome, TransferOutcome::Failed { error } if error.contains("Invalid amount")));
+        assert!(records[1].timestamp > 0);
+        // The signing key never reaches the audit trail
+        let json = serde_json::to_string(&*records).unwrap();
+        assert!(!json.contains("test_private_key"));
+    }
+
+    #[tokio::test]
+    async fn test_transfer_handler_reports_cost_when_waiting() {
+        let handler = TransferHandler::new(Arc::new(MockBlockchainService {
+            expected_tx_hash: format!("0x{}", "ab".repeat(32)),
+        }));
+
+        let result = handler
+            .handle(transfer_command().with_wait_for_receipt())
+            .await
+            .unwrap();
+        assert_eq!(result.gas_used, Some(21_000));
+        assert_eq!(result.effective_gas_price, Some(2_000_000_000));
+        assert_eq!(result.fee_paid, Some(Amount::from_wei(42_000_000_000_000)));
+
+        // Without waiting the cost is unknown
+        let result = handler.handle(transfer_command()).await.unwrap();
+        assert_eq!(result.gas_used, None);
+        assert_eq!(result.fee_paid, None);
+    }
+
+    #[tokio::test]
+    async fn test_transfer_handler_submits_gas_price() {
+        /// Remembers the gas price of the last transfer (`None` for the node's suggestion)
+        #[derive(Default)]
+        struct PriceRecordingService {
+            gas_price: std::sync::Mutex<Option<Option<u128>>>,
+        }
+
+        #[async_trait]
+        impl BlockchainService for PriceRecordingService {
+            fn chain_type(&self) -> ChainType {
+                ChainType::Ethereum
+            }
+
+            async fn get_balance(&self, _address: &Address) -> Result<Balance, DomainError> {
+                Ok(Balance::from_ether(10.0))
+            }
+
+            async fn transfer(
+                &self,
+                _from: &Address,
+                _to: &Address,
+                _amount: u128,
+                _signer: &dyn Signer,
+            ) -> Result<TransactionHash, DomainError> {
+                *self.gas_price.lock().unwrap() = Some(None);
+                TransactionHash::new(format!("0x{}", "ab".repeat(32)))
+            }
+
+            async fn transfer_with_gas_price(
+                &self,
+                _from: &Address,
+                _to: &Address,
+                _amount: u128,
+                _signer: &dyn Signer,
+                gas_price: u128,
+            ) -> Result<TransactionHash, DomainError> {
+                *self.gas_price.lock().unwrap() = Some(Some(gas_price));
+                TransactionHash::new(format!("0x{}", "ab".repeat(32)))
+            }
+
+            fn signer_for_key(&self, _private_key: &SecretKey) -> Result<Box<dyn Signer>, DomainError> {
+                Ok(Box::new(StubSigner))
+            }
+
+            async fn is_connected(&self) -> bool {
+                true
+            }
+
+            async fn get_block_number(&self) -> Result<u64, DomainError> {
+                Ok(1)
+            }
+        }
+
+        let service = Arc::new(PriceRecordingService::default());
+        let handler = TransferHandler::new(service.clone());
+
+        handler.handle(transfer_command().with_gas_price_gwei(30)).await.unwrap();
+        assert_eq!(*service.gas_price.lock().unwrap(), Some(Some(30_000_000_000)));
+
+        handler.handle(transfer_command()).await.unwrap();
+        assert_eq!(*service.gas_price.lock().unwrap(), Some(None));
+
+        // A service without fixed gas prices refuses rather than ignoring it
+        let handler = TransferHandler::new(Arc::new(MockBlockchainService {
+            expected_tx_hash: format!("0x{}", "ab".repeat(32)),
+        }));
+        assert!(matches!(
+            handler.handle(transfer_command().with_gas_price(1)).await,
+            Err(DomainError::ConfigurationError(_))
+        ));
+    }
+
     #[tokio::test]
     async fn test_transfer_handler_error_propagation() {
         // Test that errors from blockchain service are properly propagated
@@ -151,6 +472,10 @@ mod tests {
 
         #[async_trait]
         impl BlockchainService for FailingBlockchainService {
+            fn chain_type(&self) -> ChainType {
+                ChainType::Ethereum
+            }
+
             async fn get_balance(&self, _address: &Address) -> Result<Balance, DomainError> {
                 Ok(Balance::from_ether(10.0))
             }
@@ -160,9 +485,16 @@ mod tests {
                 _from: &Address,
                 _to: &Address,
                 _amount: u128,
-                _private_key: &str,
+                _signer: &dyn Signer,
             ) -> Result<TransactionHash, DomainError> {
-                Err(DomainError::InsufficientBalance)
+                Err(DomainError::InsufficientBalance {
+                    required: 1_000,
+                    available: 0,
+                })
+            }
+
+            fn signer_for_key(&self, _private_key: &SecretKey) -> Result<Box<dyn Signer>, DomainError> {
+                Ok(Box::new(StubSigner))
             }
 
             async fn is_connected(&self) -> bool {
@@ -187,11 +519,79 @@ mod tests {
             to_address,
             Amount::from_ether(0.001),
             Network::Sepolia,
-            "test_key".to_string(),
+            SecretKey::from("test_key"),
         );
 
         let result = handler.handle(command).await;
         assert!(result.is_err());
-        assert!(matches!(result.unwrap_err(), DomainError::InsufficientBalance));
+        assert!(matches!(result.unwrap_err(), DomainError::InsufficientBalance { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_transfer_handler_signs_with_its_signer() {
+        /// Has no keys of its own: only transfers signed by the sender succeed
+        struct KeylessService;
+
+        #[async_trait]
+        impl BlockchainService for KeylessService {
+            fn chain_type(&self) -> ChainType {
+                ChainType::Ethereum
+            }
+
+            async fn get_balance(&self, _address: &Address) -> Result<Balance, DomainError> {
+                Ok(Balance::from_ether(10.0))
+            }
+
+            async fn transfer(
+                &self,
+                from: &Address,
+                _to: &Address,
+                _amount: u128,
+                signer: &dyn Signer,
+            ) -> Result<TransactionHash, DomainError> {
+                if signer.address() != *from {
+                    return Err(DomainError::SignerMismatch {
+                        signer: signer.address().to_string(),
+                        from: from.to_string(),
+                    });
+                }
+                TransactionHash::new(format!("0x{}", "cd".repeat(32)))
+            }
+
+            fn signer_for_key(&self, _private_key: &SecretKey) -> Result<Box<dyn Signer>, DomainError> {
+                Err(DomainError::InvalidPrivateKey("empty".to_string()))
+            }
+
+            async fn is_connected(&self) -> bool {
+                true
+            }
+
+            async fn get_block_number(&self) -> Result<u64, DomainError> {
+                Ok(1)
+            }
+        }
+
+        let command = transfer_command();
+        let command = TransferCommand::without_key(
+            command.from_address,
+            command.to_address,
+            command.amount,
+            command.network,
+        );
+
+        let handler = TransferHandler::new(Arc::new(KeylessService));
+        assert!(matches!(
+            handler.handle(command.clone()).await,
+            Err(DomainError::InvalidPrivateKey(_))
+        ));
+
+        let handler = handler.with_signer(Arc::new(StubSigner));
+        let result = handler.handle(command.clone()).await.unwrap();
+        assert_eq!(result.tx_hash.as_str(), format!("0x{}", "cd".repeat(32)));
+
+        // The handler's checks still apply to externally signed transfers
+        let mut zero = command;
+        zero.amount = Amount::from_wei(0);
+        assert!(matches!(handler.handle(zero).await, Err(DomainError::InvalidAmount)));
     }
 }
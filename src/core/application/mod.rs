@@ -1,3 +1,17 @@
+pub mod bus;
+pub mod discovery;
 pub mod handlers;
+pub mod tracker;
 
+pub use bus::{CommandBus, QueryBus};
+pub use discovery::discover_accounts;
 pub use handlers::GetBalanceHandler;
+pub use tracker::TransactionTracker;
+
+/// Current Unix time in seconds
+pub(crate) fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
@@ -0,0 +1,293 @@
+//! Track several sent transactions until each is included in a block
+
+use futures::future::join_all;
+use futures::stream::{self, BoxStream, StreamExt};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use crate::core::domain::{
+    errors::DomainError,
+    services::BlockchainService,
+    value_objects::{Network, TransactionHash, TxStatus},
+};
+
+/// Default number of failed status lookups in a row before a transaction is given up on
+const DEFAULT_MAX_FAILURES: u32 = 3;
+
+/// Pending transactions of a session, possibly spread over several networks
+///
+/// Each network needs a service (`with_service`) before its transactions can
+/// be tracked. Statuses are polled with `get_transaction_status`; a failed
+/// lookup keeps the transaction tracked, so an RPC hiccup does not end
+/// tracking, but after `max_failures` failures in a row (an outage, or a
+/// service that cannot look statuses up) the last error is reported instead.
+pub struct TransactionTracker {
+    services: Vec<(Network, Arc<dyn BlockchainService>)>,
+    pending: Mutex<Vec<Tracked>>,
+    max_failures: u32,
+}
+
+/// A tracked transaction and its failed lookups in a row
+#[derive(Clone)]
+struct Tracked {
+    tx_hash: TransactionHash,
+    network: Network,
+    failures: u32,
+}
+
+impl TransactionTracker {
+    pub fn new() -> Self {
+        Self {
+            services: Vec::new(),
+            pending: Mutex::new(Vec::new()),
+            max_failures: DEFAULT_MAX_FAILURES,
+        }
+    }
+
+    /// Use `service` to look up transactions on `network`
+    pub fn with_service(mut self, network: Network, service: Arc<dyn BlockchainService>) -> Self {
+        self.services.retain(|(known, _)| *known != network);
+        self.services.push((network, service));
+        self
+    }
+
+    /// Give up on a transaction after `max_failures` failed lookups in a row (default 3, at least 1)
+    pub fn with_max_failures(mut self, max_failures: u32) -> Self {
+        self.max_failures = max_failures.max(1);
+        self
+    }
+
+    /// Start tracking `tx_hash`; tracking the same transaction twice has no effect
+    pub fn track(&self, tx_hash: TransactionHash, network: Network) -> Result<(), DomainError> {
+        if self.service_for(&network).is_none() {
+            return Err(DomainError::ConfigurationError(format!(
+                "No service to track transactions on {}",
+                network.name()
+            )));
+        }
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        if !pending.iter().any(|tracked| tracked.tx_hash == tx_hash && tracked.network == network) {
+            pending.push(Tracked { tx_hash, network, failures: 0 });
+        }
+        Ok(())
+    }
+
+    /// Number of transactions still being tracked
+    pub fn len(&self) -> usize {
+        self.pending.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Current status of every tracked transaction, in the order they were added
+    pub async fn poll_all(&self) -> Vec<(TransactionHash, Result<TxStatus, DomainError>)> {
+        let pending = self.pending.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        let lookups = pending.iter().map(|tracked| async move {
+            let service = self.service_for(&tracked.network).expect("tracked networks have a service");
+            service.get_transaction_status(&tracked.tx_hash).await
+        });
+        let statuses = join_all(lookups).await;
+        pending.into_iter().map(|tracked| tracked.tx_hash).zip(statuses).collect()
+    }
+
+    /// Each transaction's terminal status (confirmed or reverted) as it is
+    /// reached, polling on every tick of `ticks`
+    ///
+    /// A transaction whose lookups fail `max_failures` times in a row is
+    /// reported with the last error. Finished transactions stop being
+    /// tracked; the stream ends once none are left, or when `ticks` ends.
+    pub fn completions(
+        &self,
+        ticks: BoxStream<'static, ()>,
+    ) -> BoxStream<'_, (TransactionHash, Result<TxStatus, DomainError>)> {
+        stream::unfold((VecDeque::new(), ticks), move |(mut ready, mut ticks)| async move {
+            loop {
+                if let Some(completion) = ready.pop_front() {
+                    return Some((completion, (ready, ticks)));
+                }
+                if self.is_empty() {
+                    return None;
+                }
+                ticks.next().await?;
+
+                for (tx_hash, status) in self.poll_all().await {
+                    if let Some(completion) = self.record(tx_hash, status) {
+                        ready.push_back(completion);
+                    }
+                }
+            }
+        })
+        .boxed()
+    }
+
+    /// Update `tx_hash`'s failure count, and stop tracking it if `status` ends tracking
+    fn record(
+        &self,
+        tx_hash: TransactionHash,
+        status: Result<TxStatus, DomainError>,
+    ) -> Option<(TransactionHash, Result<TxStatus, DomainError>)> {
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        let index = pending.iter().position(|tracked| tracked.tx_hash == tx_hash)?;
+        let done = match &status {
+            Ok(status) => {
+                pending[index].failures = 0;
+                status.is_terminal()
+            }
+            Err(e) => {
+                pending[index].failures += 1;
+                tracing::warn!(
+                    "Failed to get the status of {} on {} ({}/{}): {}",
+                    tx_hash,
+                    pending[index].network.name(),
+                    pending[index].failures,
+                    self.max_failures,
+                    e
+                );
+                pending[index].failures >= self.max_failures
+            }
+        };
+        if !done {
+            return None;
+        }
+        pending.remove(index);
+        Some((tx_hash, status))
+    }
+
+    fn service_for(&self, network: &Network) -> Option<&Arc<dyn BlockchainService>> {
+        self.services
+            .iter()
+            .find(|(known, _)| known == network)
+            .map(|(_, service)| service)
+    }
+}
+
+impl Default for TransactionTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::domain::{
+        services::Signer,
+        value_objects::{Address, Balance, ChainType},
+    };
+    use async_trait::async_trait;
+    use std::collections::HashMap;
+
+    /// Reports each hash's scripted statuses in turn, repeating the last one
+    struct ScriptedStatuses {
+        scripts: Mutex<HashMap<String, VecDeque<Result<TxStatus, DomainError>>>>,
+    }
+
+    #[async_trait]
+    impl BlockchainService for ScriptedStatuses {
+        fn chain_type(&self) -> ChainType {
+            ChainType::Ethereum
+        }
+
+        async fn get_balance(&self, _address: &Address) -> Result<Balance, DomainError> {
+            Ok(Balance::from_wei(0))
+        }
+
+        async fn transfer(
+            &self,
+            _from: &Address,
+            _to: &Address,
+            _amount: u128,
+            _signer: &dyn Signer,
+        ) -> Result<TransactionHash, DomainError> {
+            Err(DomainError::BlockchainError("ScriptedStatuses cannot send".to_string()))
+        }
+
+        async fn get_transaction_status(&self, tx_hash: &TransactionHash) -> Result<TxStatus, DomainError> {
+            let mut scripts = self.scripts.lock().unwrap();
+            let script = scripts.get_mut(tx_hash.as_str()).expect("unknown hash");
+            if script.len() > 1 {
+                script.pop_front().unwrap()
+            } else {
+                script.front().cloned().unwrap()
+            }
+        }
+
+        async fn is_connected(&self) -> bool {
+            true
+        }
+
+        async fn get_block_number(&self) -> Result<u64, DomainError> {
+            Ok(0)
+        }
+    }
+
+    fn hash(byte: u8) -> TransactionHash {
+        TransactionHash::new(format!("0x{}", format!("{:02x}", byte).repeat(32))).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_tracker_yields_terminal_statuses_as_they_arrive() {
+        let scripts = HashMap::from([
+            (
+                hash(1).as_str().to_string(),
+                VecDeque::from([Ok(TxStatus::Pending), Ok(TxStatus::Pending), Ok(TxStatus::Confirmed { block: Some(12) })]),
+            ),
+            (
+                hash(2).as_str().to_string(),
+                VecDeque::from([
+                    Err(DomainError::NetworkError("timed out".to_string())),
+                    Ok(TxStatus::Reverted { block: Some(11) }),
+                ]),
+            ),
+        ]);
+        let service = Arc::new(ScriptedStatuses { scripts: Mutex::new(scripts) });
+        let tracker = TransactionTracker::new().with_service(Network::Sepolia, service);
+
+        tracker.track(hash(1), Network::Sepolia).unwrap();
+        tracker.track(hash(2), Network::Sepolia).unwrap();
+        tracker.track(hash(2), Network::Sepolia).unwrap();
+        assert_eq!(tracker.len(), 2);
+        assert!(tracker.track(hash(3), Network::Mainnet).is_err());
+
+        let statuses = tracker.poll_all().await;
+        assert!(matches!(statuses[0], (_, Ok(TxStatus::Pending))));
+        assert!(matches!(statuses[1], (_, Err(DomainError::NetworkError(_)))));
+
+        // A single failed lookup keeps the transaction tracked
+        let completions: Vec<_> = tracker
+            .completions(stream::repeat(()).boxed())
+            .map(|(tx_hash, status)| (tx_hash, status.unwrap()))
+            .collect()
+            .await;
+        assert_eq!(
+            completions,
+            vec![
+                (hash(2), TxStatus::Reverted { block: Some(11) }),
+                (hash(1), TxStatus::Confirmed { block: Some(12) }),
+            ]
+        );
+        assert!(tracker.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_tracker_gives_up_after_repeated_failures() {
+        let failing = VecDeque::from([Err(DomainError::NetworkError("timed out".to_string()))]);
+        let scripts = HashMap::from([
+            (hash(1).as_str().to_string(), failing),
+            (hash(2).as_str().to_string(), VecDeque::from([Ok(TxStatus::Pending)])),
+        ]);
+        let service = Arc::new(ScriptedStatuses { scripts: Mutex::new(scripts) });
+        let tracker = TransactionTracker::new()
+            .with_service(Network::Sepolia, service)
+            .with_max_failures(2);
+        tracker.track(hash(1), Network::Sepolia).unwrap();
+        tracker.track(hash(2), Network::Sepolia).unwrap();
+
+        // Two ticks: the failing lookup gives up, the pending one stays tracked
+        let completions: Vec<_> = tracker.completions(stream::iter([(), ()]).boxed()).collect().await;
+        assert_eq!(completions.len(), 1);
+        assert!(matches!(&completions[0], (tx_hash, Err(DomainError::NetworkError(_))) if *tx_hash == hash(1)));
+        assert_eq!(tracker.len(), 1);
+    }
+}
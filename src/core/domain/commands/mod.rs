@@ -1,4 +1,7 @@
-use crate::core::domain::value_objects::{Address, Amount, Network, TransactionHash};
+use crate::core::domain::services::Command;
+use crate::core::domain::value_objects::{
+    Address, Amount, Network, SecretKey, TransactionHash, TransactionReceipt,
+};
 use serde::{Deserialize, Serialize};
 
 /// Transfer command - initiate a transfer
@@ -12,10 +15,20 @@ pub struct TransferCommand {
     pub amount: Amount,
     /// Network to use
     pub network: Network,
-    /// Private key for signing (should be handled securely)
-    pub private_key: String,
+    /// Private key for signing (zeroized on drop, redacted in Debug, never serialized)
+    #[serde(skip_serializing)]
+    pub private_key: SecretKey,
     /// Optional gas price (in Wei)
     pub gas_price: Option<u128>,
+    /// Wait for the receipt after broadcasting so the result reports the actual cost
+    #[serde(default)]
+    pub wait_for_receipt: bool,
+    /// Send even if `amount` is zero (e.g. to cancel a stuck nonce)
+    #[serde(default)]
+    pub allow_zero: bool,
+    /// Send even if `to_address` is the sender itself
+    #[serde(default)]
+    pub allow_self: bool,
 }
 
 impl TransferCommand {
@@ -24,7 +37,7 @@ impl TransferCommand {
         to_address: Address,
         amount: Amount,
         network: Network,
-        private_key: String,
+        private_key: SecretKey,
     ) -> Self {
         Self {
             from_address,
@@ -33,13 +46,49 @@ impl TransferCommand {
             network,
             private_key,
             gas_price: None,
+            wait_for_receipt: false,
+            allow_zero: false,
+            allow_self: false,
         }
     }
 
+    /// Command without a private key, for a handler that signs with its own
+    /// signer (e.g. a hardware wallet, see `TransferHandler::with_signer`)
+    pub fn without_key(from_address: Address, to_address: Address, amount: Amount, network: Network) -> Self {
+        Self::new(from_address, to_address, amount, network, SecretKey::from(""))
+    }
+
     pub fn with_gas_price(mut self, gas_price: u128) -> Self {
         self.gas_price = Some(gas_price);
         self
     }
+
+    /// Wait for the transaction to be mined and include its cost in the result
+    pub fn with_wait_for_receipt(mut self) -> Self {
+        self.wait_for_receipt = true;
+        self
+    }
+
+    /// Accept a zero amount, which the handler otherwise rejects as a likely mistake
+    pub fn with_allow_zero(mut self) -> Self {
+        self.allow_zero = true;
+        self
+    }
+
+    /// Accept a transfer to the sending address, which the handler otherwise rejects
+    pub fn with_allow_self(mut self) -> Self {
+        self.allow_self = true;
+        self
+    }
+
+    /// Set the gas price in Gwei (e.g. `with_gas_price_gwei(30)`)
+    pub fn with_gas_price_gwei(self, gwei: u64) -> Self {
+        self.with_gas_price(Amount::from_gwei(gwei).to_wei())
+    }
+}
+
+impl Command for TransferCommand {
+    type Output = TransferResult;
 }
 
 /// Transfer result
@@ -55,6 +104,12 @@ pub struct TransferResult {
     pub amount: Amount,
     /// Network used
     pub network: Network,
+    /// Gas consumed (set when the receipt was awaited)
+    pub gas_used: Option<u128>,
+    /// Price paid per gas in Wei (set when the receipt was awaited)
+    pub effective_gas_price: Option<u128>,
+    /// Total fee paid (set when the receipt was awaited)
+    pub fee_paid: Option<Amount>,
 }
 
 impl TransferResult {
@@ -71,6 +126,17 @@ impl TransferResult {
             to_address,
             amount,
             network,
+            gas_used: None,
+            effective_gas_price: None,
+            fee_paid: None,
         }
     }
+
+    /// Fill in the actual cost from a mined transaction's receipt
+    pub fn with_receipt(mut self, receipt: &TransactionReceipt) -> Self {
+        self.gas_used = Some(receipt.gas_used);
+        self.effective_gas_price = Some(receipt.effective_gas_price);
+        self.fee_paid = receipt.fee_paid();
+        self
+    }
 }
@@ -1,9 +1,16 @@
-use crate::core::domain::value_objects::{Address, Balance, ChainType, Network};
+use crate::core::domain::errors::DomainError;
+use crate::core::domain::services::Query;
+use crate::core::domain::value_objects::{
+    AccountInfo, Address, Amount, Balance, ChainType, Network, TransactionHash,
+};
 use serde::{Deserialize, Serialize};
 
 /// Query to get balance of a blockchain address
 /// Supports multiple chains: Ethereum (EVM), Bitcoin, and Solana
+///
+/// Serialized through a versioned wire format (see `GetBalanceQueryWire`).
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "GetBalanceQueryWire", into = "GetBalanceQueryWire")]
 pub struct GetBalanceQuery {
     /// The blockchain address to query
     pub address: Address,
@@ -40,8 +47,71 @@ impl GetBalanceQuery {
     }
 }
 
+impl Query for GetBalanceQuery {
+    type Output = BalanceQueryResult;
+}
+
+/// Query to get the balances of several addresses on one network
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetBalancesQuery {
+    /// The addresses to query, in the order results are returned
+    pub addresses: Vec<Address>,
+    /// The network to query on
+    pub network: Network,
+    /// The blockchain type (Ethereum/Bitcoin/Solana)
+    pub chain_type: ChainType,
+}
+
+impl GetBalancesQuery {
+    /// Create a batch balance query; the chain_type is derived from the network
+    pub fn new(addresses: Vec<Address>, network: Network) -> Self {
+        let chain_type = network.chain_type();
+        Self {
+            addresses,
+            network,
+            chain_type,
+        }
+    }
+}
+
+impl Query for GetBalancesQuery {
+    /// One result per address, in input order
+    type Output = Vec<Result<BalanceQueryResult, DomainError>>;
+}
+
+/// Query for an account overview: balance, transaction count, contract flag and code size
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetAccountInfoQuery {
+    /// The address to describe
+    pub address: Address,
+    /// The network to query on
+    pub network: Network,
+    /// The blockchain type (Ethereum/Bitcoin/Solana)
+    pub chain_type: ChainType,
+}
+
+impl GetAccountInfoQuery {
+    /// Create an account info query; the chain_type is derived from the network
+    pub fn new(address: Address, network: Network) -> Self {
+        let chain_type = network.chain_type();
+        Self {
+            address,
+            network,
+            chain_type,
+        }
+    }
+}
+
+impl Query for GetAccountInfoQuery {
+    type Output = AccountInfo;
+}
+
 /// Result of balance query
+///
+/// Serialized through a versioned wire format (see `BalanceQueryResultWire`),
+/// so persisted values stay readable as the struct evolves.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "BalanceQueryResultWire", into = "BalanceQueryResultWire")]
 pub struct BalanceQueryResult {
     /// The queried address
     pub address: Address,
@@ -78,4 +148,321 @@ impl BalanceQueryResult {
             balance,
         }
     }
+
+    /// Exact balance in the network's native currency, e.g. `"1.5 ETH"`
+    pub fn display_native(&self) -> String {
+        format!(
+            "{} {}",
+            self.balance.format_exact(self.network.native_decimals()),
+            self.network.native_symbol()
+        )
+    }
+
+    /// Balance in the chain's smallest unit, e.g. `"1500000000000000000 Wei"`
+    pub fn display_raw(&self) -> String {
+        format!("{} {}", self.balance.to_wei(), self.chain_type.smallest_unit())
+    }
+
+    /// Native and smallest-unit balance together, e.g.
+    /// `"1.5 ETH (1500000000000000000 Wei)"` or `"0.5 BTC (50000000 Satoshi)"`
+    pub fn display_multi(&self) -> String {
+        format!("{} ({})", self.display_native(), self.display_raw())
+    }
+}
+
+/// Current wire schema version for `GetBalanceQuery` and `BalanceQueryResult`
+///
+/// Bump this (and handle the previous version in `try_from`) whenever a field
+/// is added, removed or changes representation.
+///
+/// - v1: `balance` as a JSON number
+/// - v2: `balance` as a decimal string (v1 numbers are still read)
+pub const WIRE_VERSION: u32 = 2;
+
+fn check_wire_version(version: u32) -> Result<(), String> {
+    if (1..=WIRE_VERSION).contains(&version) {
+        Ok(())
+    } else {
+        Err(format!(
+            "unsupported wire version {} (expected {})",
+            version, WIRE_VERSION
+        ))
+    }
+}
+
+/// Versioned wire format for `GetBalanceQuery`
+#[derive(Serialize, Deserialize)]
+struct GetBalanceQueryWire {
+    version: u32,
+    address: Address,
+    network: Network,
+    chain_type: ChainType,
+}
+
+impl From<GetBalanceQuery> for GetBalanceQueryWire {
+    fn from(query: GetBalanceQuery) -> Self {
+        Self {
+            version: WIRE_VERSION,
+            address: query.address,
+            network: query.network,
+            chain_type: query.chain_type,
+        }
+    }
+}
+
+impl TryFrom<GetBalanceQueryWire> for GetBalanceQuery {
+    type Error = String;
+
+    fn try_from(wire: GetBalanceQueryWire) -> Result<Self, Self::Error> {
+        check_wire_version(wire.version)?;
+        Ok(GetBalanceQuery::new_with_chain_type(
+            wire.address,
+            wire.network,
+            wire.chain_type,
+        ))
+    }
+}
+
+/// Versioned wire format for `BalanceQueryResult`
+#[derive(Serialize, Deserialize)]
+struct BalanceQueryResultWire {
+    version: u32,
+    address: Address,
+    network: Network,
+    chain_type: ChainType,
+    balance: Balance,
+}
+
+impl From<BalanceQueryResult> for BalanceQueryResultWire {
+    fn from(result: BalanceQueryResult) -> Self {
+        Self {
+            version: WIRE_VERSION,
+            address: result.address,
+            network: result.network,
+            chain_type: result.chain_type,
+            balance: result.balance,
+        }
+    }
+}
+
+impl TryFrom<BalanceQueryResultWire> for BalanceQueryResult {
+    type Error = String;
+
+    fn try_from(wire: BalanceQueryResultWire) -> Result<Self, Self::Error> {
+        check_wire_version(wire.version)?;
+        Ok(BalanceQueryResult::new_with_chain_type(
+            wire.address,
+            wire.network,
+            wire.chain_type,
+            wire.balance,
+        ))
+    }
+}
+
+/// Query to page through the transaction history of an address
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetTransactionHistoryQuery {
+    /// The address whose history to fetch
+    pub address: Address,
+    /// The network to query on
+    pub network: Network,
+    /// First block to include (inclusive), `None` for genesis
+    pub from_block: Option<u64>,
+    /// Last block to include (inclusive), `None` for latest
+    pub to_block: Option<u64>,
+    /// Page number (1-based)
+    pub page: u32,
+    /// Number of transactions per page
+    pub page_size: u32,
+}
+
+impl GetTransactionHistoryQuery {
+    /// Default number of transactions per page
+    pub const DEFAULT_PAGE_SIZE: u32 = 100;
+
+    /// Create a history query for the first page over the full block range
+    pub fn new(address: Address, network: Network) -> Self {
+        Self {
+            address,
+            network,
+            from_block: None,
+            to_block: None,
+            page: 1,
+            page_size: Self::DEFAULT_PAGE_SIZE,
+        }
+    }
+
+    /// Restrict the query to an inclusive block range
+    pub fn with_block_range(mut self, from_block: Option<u64>, to_block: Option<u64>) -> Self {
+        self.from_block = from_block;
+        self.to_block = to_block;
+        self
+    }
+
+    /// Select a page (1-based) and page size
+    pub fn with_page(mut self, page: u32, page_size: u32) -> Self {
+        self.page = page;
+        self.page_size = page_size;
+        self
+    }
+
+    /// Build the query for the following page
+    pub fn next_page(&self) -> Self {
+        let mut next = self.clone();
+        next.page += 1;
+        next
+    }
+}
+
+impl Query for GetTransactionHistoryQuery {
+    type Output = Page<TransactionSummary>;
+}
+
+/// Summary of a single transaction in an address's history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionSummary {
+    /// Transaction hash
+    pub hash: TransactionHash,
+    /// Block the transaction was included in
+    pub block_number: u64,
+    /// Block timestamp (unix seconds)
+    pub timestamp: u64,
+    /// Sender address
+    pub from: Address,
+    /// Recipient address (`None` for contract creation)
+    pub to: Option<Address>,
+    /// Value transferred (in smallest unit)
+    pub value: Amount,
+    /// Whether the transaction executed successfully
+    pub success: bool,
+}
+
+/// A single page of results
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Page<T> {
+    /// Items on this page
+    pub items: Vec<T>,
+    /// Page number (1-based)
+    pub page: u32,
+    /// Requested page size
+    pub page_size: u32,
+    /// Whether another page may follow
+    pub has_more: bool,
+}
+
+impl<T> Page<T> {
+    /// Build a page, inferring `has_more` from whether the page was filled
+    pub fn new(items: Vec<T>, page: u32, page_size: u32) -> Self {
+        let has_more = items.len() as u32 >= page_size;
+        Self {
+            items,
+            page,
+            page_size,
+            has_more,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::domain::value_objects::NetworkKind;
+
+    #[test]
+    fn test_balance_query_result_display_multi_uses_chain_units() {
+        let eth = BalanceQueryResult::new(
+            Address::new("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".to_string()).unwrap(),
+            Network::Mainnet,
+            Balance::from_wei(1_500_000_000_000_000_000),
+        );
+        assert_eq!(eth.display_multi(), "1.5 ETH (1500000000000000000 Wei)");
+
+        let btc = BalanceQueryResult::new(
+            Address::new("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa".to_string()).unwrap(),
+            Network::BitcoinMainnet,
+            Balance::from_wei(50_000_000),
+        );
+        assert_eq!(btc.display_multi(), "0.5 BTC (50000000 Satoshi)");
+
+        let sol = BalanceQueryResult::new(
+            Address::new("DRpbCBMxVnDK7maPM5tGv6MvB3v1sRMC86PZ8okm21hy".to_string()).unwrap(),
+            Network::SolanaDevnet,
+            Balance::from_wei(1),
+        );
+        assert_eq!(sol.display_native(), "0.000000001 SOL");
+        assert_eq!(sol.display_raw(), "1 Lamport");
+    }
+
+    #[test]
+    fn test_balance_query_result_v1_fixture() {
+        let fixture = r#"{
+            "version": 1,
+            "address": "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEbC",
+            "network": "Mainnet",
+            "chain_type": "Ethereum",
+            "balance": 1500000000000000000
+        }"#;
+
+        let result: BalanceQueryResult = serde_json::from_str(fixture).unwrap();
+        assert_eq!(result.address.as_str(), "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEbC");
+        assert_eq!(result.network, Network::Mainnet);
+        assert_eq!(result.chain_type, ChainType::Ethereum);
+        assert_eq!(result.balance.to_wei(), 1_500_000_000_000_000_000);
+    }
+
+    #[test]
+    fn test_balance_query_result_round_trip() {
+        let result = BalanceQueryResult::new(
+            Address::new("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEbC".to_string()).unwrap(),
+            Network::Custom {
+                name: "Local".to_string(),
+                chain_id: 31337,
+                rpc_url: "http://localhost:8545".to_string(),
+                kind: NetworkKind::Local,
+                symbol: None,
+                explorer_url: None,
+            },
+            Balance::from_wei(42),
+        );
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["version"], WIRE_VERSION);
+        assert_eq!(json["balance"], "42");
+        assert_eq!(json["network"]["Custom"]["chain_id"], 31337);
+
+        let decoded: BalanceQueryResult = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded.address, result.address);
+        assert_eq!(decoded.network, result.network);
+        assert_eq!(decoded.balance, result.balance);
+    }
+
+    #[test]
+    fn test_get_balance_query_v1_fixture() {
+        let fixture = r#"{
+            "version": 1,
+            "address": "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa",
+            "network": "BitcoinMainnet",
+            "chain_type": "Bitcoin"
+        }"#;
+
+        let query: GetBalanceQuery = serde_json::from_str(fixture).unwrap();
+        assert_eq!(query.network, Network::BitcoinMainnet);
+        assert_eq!(query.chain_type, ChainType::Bitcoin);
+
+        let round_trip = serde_json::to_string(&query).unwrap();
+        let decoded: GetBalanceQuery = serde_json::from_str(&round_trip).unwrap();
+        assert_eq!(decoded.address, query.address);
+    }
+
+    #[test]
+    fn test_unknown_wire_version_rejected() {
+        let fixture = r#"{
+            "version": 99,
+            "address": "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEbC",
+            "network": "Mainnet",
+            "chain_type": "Ethereum",
+            "balance": 1
+        }"#;
+        assert!(serde_json::from_str::<BalanceQueryResult>(fixture).is_err());
+    }
 }
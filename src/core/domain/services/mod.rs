@@ -1,10 +1,27 @@
 use async_trait::async_trait;
+use futures::future;
+use futures::stream::{BoxStream, Stream, StreamExt};
+use std::sync::Arc;
+use std::time::Duration;
 use crate::core::domain::{
     errors::DomainError,
-    queries::{BalanceQueryResult, GetBalanceQuery},
-    value_objects::{Address, Balance, TransactionHash},
+    queries::{BalanceQueryResult, GetBalanceQuery, GetTransactionHistoryQuery, Page, TransactionSummary},
+    value_objects::{
+        AccountInfo, Address, Balance, ChainType, ConnectionDiagnostics, FeeEstimate, Finality, MaxSendable, SecretKey,
+        TransactionHash, TransactionReceipt, TransferAuditRecord, TxStatus,
+    },
 };
 
+/// A read request with a fixed result type, so a `QueryBus` can route it
+pub trait Query: Send + 'static {
+    type Output: Send + 'static;
+}
+
+/// A write request with a fixed result type, so a `CommandBus` can route it
+pub trait Command: Send + 'static {
+    type Output: Send + 'static;
+}
+
 /// Query handler trait - processes read operations (CQRS Query)
 #[async_trait]
 pub trait QueryHandler<Q>: Send + Sync {
@@ -24,23 +41,332 @@ pub trait CommandHandler<C>: Send + Sync {
 /// Blockchain service interface for Ethereum/BSC operations
 #[async_trait]
 pub trait BlockchainService: Send + Sync {
+    /// Chain family this service talks to, so holders of a trait object can
+    /// format amounts in the right unit
+    fn chain_type(&self) -> ChainType;
+
     /// Get balance of an address
     async fn get_balance(&self, address: &Address) -> Result<Balance, DomainError>;
 
-    /// Transfer funds from one address to another
+    /// Get the balances of several addresses, in input order
+    ///
+    /// Errors stay per address. Default implementation calls `get_balance`
+    /// for every address concurrently; services override it with a batched
+    /// request (EVM Multicall3, Bitcoin multi-address lookup, Solana JSON-RPC
+    /// batch).
+    async fn get_balances(&self, addresses: &[Address]) -> Vec<Result<Balance, DomainError>> {
+        future::join_all(addresses.iter().map(|address| self.get_balance(address))).await
+    }
+
+    /// Get balance of an address including pending (not yet mined) transactions
+    ///
+    /// EVM queries the `pending` block tag. Bitcoin and Solana map this to their
+    /// mempool-inclusive / lowest-commitment balance where the backend exposes one.
+    /// Default implementation falls back to `get_balance`.
+    async fn get_pending_balance(&self, address: &Address) -> Result<Balance, DomainError> {
+        self.get_balance(address).await
+    }
+
+    /// Get balance of an address as of a historical block (or slot)
+    ///
+    /// Default implementation reports that historical queries are
+    /// unsupported; EVM services override it (old blocks need an archive node).
+    async fn get_balance_at_block(&self, _address: &Address, _block_number: u64) -> Result<Balance, DomainError> {
+        Err(DomainError::ConfigurationError(
+            "Historical balance queries are not supported by this service".to_string(),
+        ))
+    }
+
+    /// Balance plus activity and code details of an address, in one logical query
+    ///
+    /// Default implementation only fills the balance; services add what
+    /// their chain exposes (EVM nonce and bytecode, Bitcoin transaction
+    /// count, Solana executable flag and data length).
+    async fn get_account_info(&self, address: &Address) -> Result<AccountInfo, DomainError> {
+        Ok(AccountInfo::new(address.clone(), self.get_balance(address).await?))
+    }
+
+    /// Transfer funds from one address to another, signed by `signer`
+    ///
+    /// Fails with `TransferFailed` if `signer` does not control `from`.
     async fn transfer(
         &self,
         from: &Address,
         to: &Address,
         amount: u128,
-        private_key: &str,
+        signer: &dyn Signer,
     ) -> Result<TransactionHash, DomainError>;
 
+    /// Like [`transfer`](Self::transfer), but paying `gas_price` wei per gas
+    /// instead of the fee the node suggests
+    ///
+    /// Default implementation reports that a fixed gas price is unsupported,
+    /// so a price meant for the transaction is never silently dropped.
+    async fn transfer_with_gas_price(
+        &self,
+        _from: &Address,
+        _to: &Address,
+        _amount: u128,
+        _signer: &dyn Signer,
+        _gas_price: u128,
+    ) -> Result<TransactionHash, DomainError> {
+        Err(DomainError::ConfigurationError(
+            "A fixed gas price is not supported by this service".to_string(),
+        ))
+    }
+
+    /// Signer for a raw private key on this service's chain
+    ///
+    /// Lets callers that only hold a key (e.g. a `TransferCommand`) sign
+    /// without depending on a concrete key implementation. Default
+    /// implementation reports that local keys are unsupported.
+    fn signer_for_key(&self, _private_key: &SecretKey) -> Result<Box<dyn Signer>, DomainError> {
+        Err(DomainError::ConfigurationError(
+            "Signing with a private key is not supported by this service".to_string(),
+        ))
+    }
+
+    /// Estimate the network fee for transferring `amount` from `from` to `to`
+    ///
+    /// Default implementation reports that fee estimation is unsupported.
+    async fn estimate_fee(
+        &self,
+        _from: &Address,
+        _to: &Address,
+        _amount: u128,
+    ) -> Result<FeeEstimate, DomainError> {
+        Err(DomainError::BlockchainError(
+            "Fee estimation is not supported by this service".to_string(),
+        ))
+    }
+
+    /// Rough time until a transaction paying `fee` is confirmed
+    ///
+    /// Based on how recent blocks priced their transactions, so it is a
+    /// guide for choosing a fee level rather than a promise. Default
+    /// implementation reports that the estimate is unsupported.
+    async fn estimate_confirmation_time(&self, _fee: &FeeEstimate) -> Result<Duration, DomainError> {
+        Err(DomainError::BlockchainError(
+            "Confirmation time estimation is not supported by this service".to_string(),
+        ))
+    }
+
+    /// Most `from` can send to `to` in one transfer after the network fee
+    ///
+    /// Default implementation subtracts the fee for a zero-value transfer from
+    /// the balance (estimating with the full balance would fail for lack of
+    /// funds for the fee). Chains whose fee depends on the funds spent, such
+    /// as Bitcoin's UTXO inputs, override this.
+    async fn max_sendable(&self, from: &Address, to: &Address) -> Result<MaxSendable, DomainError> {
+        let (balance, fee) = future::try_join(self.get_balance(from), self.estimate_fee(from, to, 0)).await?;
+        MaxSendable::new(balance, fee)
+    }
+
+    /// Wait until a transaction is mined and return its receipt (including logs)
+    ///
+    /// Returns an error if no receipt appears within `timeout`.
+    /// Default implementation reports that receipts are unsupported.
+    async fn wait_for_receipt(
+        &self,
+        _tx_hash: &TransactionHash,
+        _timeout: Duration,
+    ) -> Result<TransactionReceipt, DomainError> {
+        Err(DomainError::BlockchainError(
+            "Transaction receipts are not supported by this service".to_string(),
+        ))
+    }
+
+    /// Wait until `tx_hash` is included in a block and has `confirmations`
+    /// confirmations (the including block counts as the first), then return its receipt
+    ///
+    /// Checks the status, then the chain height, on every tick of
+    /// [`new_block_ticks`](Self::new_block_ticks) over the caller's `ticks`.
+    /// Fails if `ticks` ends first; drop the future to give up earlier.
+    async fn wait_for_confirmations(
+        &self,
+        tx_hash: &TransactionHash,
+        confirmations: u64,
+        ticks: BoxStream<'static, ()>,
+    ) -> Result<TransactionReceipt, DomainError> {
+        let mut ticks = self.new_block_ticks(ticks);
+        while ticks.next().await.is_some() {
+            let confirmed = match self.get_transaction_status(tx_hash).await? {
+                TxStatus::Confirmed { block: Some(block) } | TxStatus::Reverted { block: Some(block) } => {
+                    self.get_block_number().await?.saturating_sub(block) + 1 >= confirmations
+                }
+                status => status.is_terminal(),
+            };
+            if confirmed {
+                // Already mined, so the receipt is there without waiting
+                return self.wait_for_receipt(tx_hash, Duration::ZERO).await;
+            }
+        }
+        Err(DomainError::BlockchainError(format!(
+            "Stopped waiting before {} had {} confirmation(s)",
+            tx_hash, confirmations
+        )))
+    }
+
+    /// Current status of a sent transaction, without waiting
+    ///
+    /// Default implementation reports that status lookups are unsupported.
+    async fn get_transaction_status(&self, _tx_hash: &TransactionHash) -> Result<TxStatus, DomainError> {
+        Err(DomainError::BlockchainError(
+            "Transaction status lookups are not supported by this service".to_string(),
+        ))
+    }
+
     /// Check if connected to the network
     async fn is_connected(&self) -> bool;
 
-    /// Get current block number
+    /// Get current block number (alias for `get_height(Finality::Latest)`)
     async fn get_block_number(&self) -> Result<u64, DomainError>;
+
+    /// Get the chain height at the requested finality
+    ///
+    /// The unit is chain-specific: EVM block number, Bitcoin block height,
+    /// Solana slot. Default implementation only supports `Latest`.
+    async fn get_height(&self, finality: Finality) -> Result<u64, DomainError> {
+        match finality {
+            Finality::Latest => self.get_block_number().await,
+            _ => Err(DomainError::ConfigurationError(format!(
+                "Finality '{}' is not supported by this service",
+                finality
+            ))),
+        }
+    }
+
+    /// Probe the endpoint and explain the outcome (latency, height, node version, error)
+    ///
+    /// Default implementation times `get_block_number`; services override it to
+    /// add the node version.
+    async fn diagnose(&self) -> ConnectionDiagnostics {
+        ConnectionDiagnostics::measure(self.get_block_number()).await
+    }
+
+    /// Create a sibling service for the same network, pointed at another endpoint
+    ///
+    /// Lets a holder of an `Arc<dyn BlockchainService>` fan out to several
+    /// providers of the same chain (failover, load spreading). Other settings,
+    /// such as custom headers, carry over. Default implementation reports that
+    /// switching endpoints is unsupported.
+    async fn with_endpoint(&self, _url: &str) -> Result<Arc<dyn BlockchainService>, DomainError> {
+        Err(DomainError::ConfigurationError(
+            "Switching endpoints is not supported by this service".to_string(),
+        ))
+    }
+
+    /// Ticks on every new block, for services that can subscribe to them
+    ///
+    /// Default implementation returns `fallback` (typically a timer supplied
+    /// by the caller) unchanged.
+    fn new_block_ticks(&self, fallback: BoxStream<'static, ()>) -> BoxStream<'static, ()> {
+        fallback
+    }
+
+    /// Stream of `address`'s balance, yielding the current balance first and
+    /// then only when it differs from the last one yielded
+    ///
+    /// Checks on every tick of [`new_block_ticks`](Self::new_block_ticks)
+    /// over the caller's `ticks`. Failed reads are yielded as errors and
+    /// retried on the next check. The stream ends when `ticks` does; drop it
+    /// to stop watching.
+    fn watch_balance<'a>(
+        &'a self,
+        address: &'a Address,
+        ticks: BoxStream<'static, ()>,
+    ) -> BoxStream<'a, Result<Balance, DomainError>> {
+        balance_changes(self.new_block_ticks(ticks).then(move |_| self.get_balance(address)))
+    }
+
+    /// Release long-lived resources (subscriptions, sockets) before the service is dropped
+    ///
+    /// Default implementation is a no-op for stateless HTTP-backed services.
+    async fn shutdown(&self) {}
+}
+
+/// Drop successful balance reads equal to the last successful one
+///
+/// Building block for [`BlockchainService::watch_balance`] and for callers
+/// that read balances another way; errors are passed through.
+pub fn balance_changes<'a>(
+    reads: impl Stream<Item = Result<Balance, DomainError>> + Send + 'a,
+) -> BoxStream<'a, Result<Balance, DomainError>> {
+    reads
+        .scan(None, |last: &mut Option<Balance>, read| {
+            let changed = match &read {
+                Ok(balance) if *last == Some(*balance) => false,
+                Ok(balance) => {
+                    *last = Some(*balance);
+                    true
+                }
+                Err(_) => true,
+            };
+            async move { Some(changed.then_some(read)) }
+        })
+        .filter_map(|changed| async move { changed })
+        .boxed()
+}
+
+/// Holder of an account's signing key: a local private key, a hardware
+/// wallet, a keystore or a remote signing service
+///
+/// Signers only sign; building and broadcasting transactions stays with the
+/// `BlockchainService`. Payloads and signatures are in the chain's native
+/// encoding:
+///
+/// | Chain    | `sign_transaction` payload               | Signature                |
+/// |----------|------------------------------------------|--------------------------|
+/// | Ethereum | EIP-2718 signing encoding of the tx      | 65 bytes `r ‖ s ‖ v`     |
+/// | Solana   | serialized transaction message           | 64-byte ed25519          |
+///
+/// EVM signers hash the payload themselves (keccak256), so devices can
+/// show the transaction before signing it.
+#[async_trait]
+pub trait Signer: Send + Sync {
+    /// Address whose key this signer holds
+    fn address(&self) -> Address;
+
+    /// Sign an unsigned transaction payload
+    async fn sign_transaction(&self, payload: &[u8]) -> Result<Vec<u8>, DomainError>;
+
+    /// Sign an arbitrary message (EIP-191 `personal_sign` on EVM chains)
+    async fn sign_message(&self, message: &[u8]) -> Result<Vec<u8>, DomainError>;
+}
+
+/// Resolves human-readable names (e.g. ENS `vitalik.eth`) to addresses
+///
+/// Implementations are pluggable so other naming systems can sit beside ENS.
+#[async_trait]
+pub trait NameResolver: Send + Sync {
+    /// Address `name` points to; `InvalidQuery` if it is unregistered or has no address
+    async fn resolve_name(&self, name: &str) -> Result<Address, DomainError>;
+}
+
+/// Append-only destination for transfer audit records (a file, a database, syslog)
+///
+/// Unlike tracing output, records are meant to be kept: implementations
+/// should persist each one before returning.
+#[async_trait]
+pub trait AuditSink: Send + Sync {
+    /// Persist `record`
+    async fn record_transfer(&self, record: &TransferAuditRecord) -> Result<(), DomainError>;
+}
+
+/// Deterministic address derivation (e.g. a BIP-32/44 HD wallet), used for account discovery
+pub trait AddressDerivation: Send + Sync {
+    /// Derive the receiving address at `index` for the given chain
+    fn derive_address(&self, chain_type: ChainType, index: u32) -> Result<Address, DomainError>;
+}
+
+/// Transaction history interface (typically backed by a block explorer / indexer)
+#[async_trait]
+pub trait TransactionHistoryService: Send + Sync {
+    /// Fetch one page of an address's transactions, filtered by block range
+    async fn get_transaction_history(
+        &self,
+        query: &GetTransactionHistoryQuery,
+    ) -> Result<Page<TransactionSummary>, DomainError>;
 }
 
 /// Get balance query handler (Query side of CQRS)
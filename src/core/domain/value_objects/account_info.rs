@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+use super::{Address, Balance};
+
+/// Overview of one account: balance plus what the chain says about its activity and code
+///
+/// Fields a chain has no notion of stay `None`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountInfo {
+    pub address: Address,
+    pub balance: Balance,
+    /// EVM: the nonce (transactions sent); Bitcoin: transactions involving the address
+    pub transaction_count: Option<u64>,
+    /// EVM: the address has code; Solana: the account is an executable program
+    pub is_contract: Option<bool>,
+    /// EVM: bytecode length; Solana: account data length (bytes)
+    pub code_size: Option<u64>,
+}
+
+impl AccountInfo {
+    /// Account with only its balance known
+    pub fn new(address: Address, balance: Balance) -> Self {
+        Self {
+            address,
+            balance,
+            transaction_count: None,
+            is_contract: None,
+            code_size: None,
+        }
+    }
+
+    pub fn with_transaction_count(mut self, transaction_count: u64) -> Self {
+        self.transaction_count = Some(transaction_count);
+        self
+    }
+
+    /// Record the code size; any code at all makes the account a contract
+    pub fn with_code_size(mut self, code_size: u64) -> Self {
+        self.code_size = Some(code_size);
+        self.is_contract = Some(code_size > 0);
+        self
+    }
+
+    /// Override the contract flag (chains where it does not follow from the code size)
+    pub fn with_is_contract(mut self, is_contract: bool) -> Self {
+        self.is_contract = Some(is_contract);
+        self
+    }
+}
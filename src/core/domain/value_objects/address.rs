@@ -1,12 +1,18 @@
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::hash::{Hash, Hasher};
+use super::{BitcoinAddressType, ChainType};
 use crate::DomainError;
 
 /// Multi-chain address (supports Ethereum, Bitcoin, Solana)
 /// - Ethereum: 0x + 40 hex characters (42 total)
 /// - Bitcoin: 26-62 characters, starts with 1, 3, bc1, m, n, or tb1
 /// - Solana: 32-44 characters, Base58 encoded
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+///
+/// Equality and hashing are case-insensitive for EVM addresses (so checksummed
+/// and lowercase forms compare equal) and Bech32 addresses (which may be written
+/// all-uppercase), and case-sensitive for Base58 encodings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Address(String);
 
 impl Address {
@@ -22,48 +28,102 @@ impl Address {
         Self(addr)
     }
 
+    /// Create an address for `chain`, rejecting it with that chain's expected format
+    ///
+    /// Unlike [`Address::new`], which guesses the chain from the address itself,
+    /// this checks against the chain the caller intends to use (e.g. the
+    /// network's), so a mistyped Solana address is reported as such.
+    pub fn for_chain(addr: String, chain: ChainType) -> Result<Self, DomainError> {
+        let instance = Self(addr);
+        match chain {
+            ChainType::Ethereum => instance.validate_evm()?,
+            ChainType::Bitcoin => {
+                instance.bitcoin_address_type()?;
+            }
+            ChainType::Solana => instance.validate_solana()?,
+        }
+        Ok(instance)
+    }
+
     /// Validate address format (supports Ethereum, Bitcoin, Solana)
     pub fn validate(&self) -> Result<(), DomainError> {
         // Basic validation: address should not be empty
         if self.0.is_empty() {
-            return Err(DomainError::InvalidAddressFormat);
+            return Err(invalid(ChainType::Ethereum, "address is empty".to_string()));
         }
 
         // Ethereum address: 0x + 40 hex characters
         if self.0.starts_with("0x") {
-            if self.0.len() != 42 {
-                return Err(DomainError::InvalidAddressLength);
-            }
-            if !self.0[2..].chars().all(|c| c.is_ascii_hexdigit()) {
-                return Err(DomainError::InvalidAddressCharacters);
-            }
-            return Ok(());
+            return self.validate_evm();
         }
 
         // Bitcoin address: 26-35 characters, alphanumeric
         // Starts with 1, 3, or bc1 (mainnet) or m, n, tb1 (testnet)
-        if self.0.len() >= 26 && self.0.len() <= 62 {
-            if self.0.starts_with('1')
+        if (26..=62).contains(&self.0.len())
+            && (self.0.starts_with('1')
                 || self.0.starts_with('3')
                 || self.0.starts_with("bc1")
                 || self.0.starts_with('m')
                 || self.0.starts_with('n')
-                || self.0.starts_with("tb1")
-            {
-                // Basic alphanumeric check (Bitcoin uses Base58)
-                return Ok(());
-            }
+                || self.0.starts_with("tb1"))
+        {
+            // Basic alphanumeric check (Bitcoin uses Base58)
+            return Ok(());
         }
 
-        // Solana address: 32-44 characters, Base58 encoded
-        if self.0.len() >= 32 && self.0.len() <= 44 {
-            // Solana addresses are Base58 encoded (no 0, O, I, l)
-            if self.0.chars().all(|c| c.is_ascii_alphanumeric()) {
-                return Ok(());
-            }
+        // Bech32 prefixes are Bitcoin-only, so report the Bitcoin format for them
+        let lower = self.0.to_ascii_lowercase();
+        if lower.starts_with("bc1") || lower.starts_with("tb1") {
+            return self.bitcoin_address_type().map(|_| ());
+        }
+
+        // Anything else that looks like Base58 was most likely meant for Solana;
+        // hex without the prefix or other text gets the EVM format
+        if self.0.chars().all(is_base58) {
+            self.validate_solana()
+        } else {
+            self.validate_evm()
         }
+    }
 
-        Err(DomainError::InvalidAddressFormat)
+    /// 0x + 40 hex characters
+    fn validate_evm(&self) -> Result<(), DomainError> {
+        if !self.0.starts_with("0x") {
+            return Err(invalid(
+                ChainType::Ethereum,
+                "expected 0x followed by 40 hex characters".to_string(),
+            ));
+        }
+        if self.0.len() != 42 {
+            return Err(invalid(
+                ChainType::Ethereum,
+                format!("expected 0x followed by 40 hex characters (42 in total), got {}", self.0.len()),
+            ));
+        }
+        if !self.0[2..].chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(invalid(
+                ChainType::Ethereum,
+                "expected hexadecimal characters (0-9, a-f) after 0x".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Base58 public key: 32-44 characters
+    fn validate_solana(&self) -> Result<(), DomainError> {
+        if !(32..=44).contains(&self.0.len()) {
+            return Err(invalid(
+                ChainType::Solana,
+                format!("expected a Base58 public key of 32-44 characters, got {}", self.0.len()),
+            ));
+        }
+        if !self.0.chars().all(is_base58) {
+            return Err(invalid(
+                ChainType::Solana,
+                "expected Base58 characters only (no 0, O, I or l)".to_string(),
+            ));
+        }
+        Ok(())
     }
 
     /// Get address as string slice
@@ -71,10 +131,116 @@ impl Address {
         &self.0
     }
 
-    /// Convert to lowercase checksum format
-    pub fn to_checksum(&self) -> String {
+    /// Convert to lowercase (not an EIP-55 checksum; the domain has no keccak256)
+    pub fn to_lowercase(&self) -> String {
         self.0.to_lowercase()
     }
+
+    /// Check if this is an EVM (0x-prefixed hex) address
+    pub fn is_evm(&self) -> bool {
+        self.0.starts_with("0x")
+    }
+
+    /// EVM hex and Bech32 encodings are case-insensitive; Base58 is not
+    fn is_case_insensitive(&self) -> bool {
+        let prefix = self.0.get(..3).unwrap_or_default();
+        self.is_evm() || prefix.eq_ignore_ascii_case("bc1") || prefix.eq_ignore_ascii_case("tb1")
+    }
+
+    /// Detect which chain family this address belongs to
+    ///
+    /// 0x-prefixed hex is EVM, well-formed Bitcoin encodings are Bitcoin, and any
+    /// other Base58 string is treated as a Solana public key.
+    pub fn chain_type(&self) -> ChainType {
+        if self.is_evm() {
+            ChainType::Ethereum
+        } else if self.bitcoin_address_type().is_ok() {
+            ChainType::Bitcoin
+        } else {
+            ChainType::Solana
+        }
+    }
+
+    /// Classify a Bitcoin address into its script type (mainnet and testnet)
+    ///
+    /// Returns `InvalidAddress` if the address is not a well-formed Bitcoin address.
+    pub fn bitcoin_address_type(&self) -> Result<BitcoinAddressType, DomainError> {
+        const BECH32_CHARSET: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+        let bitcoin = |reason: &str| invalid(ChainType::Bitcoin, reason.to_string());
+
+        // Bech32 addresses may be all-uppercase, but never mixed case
+        let lower = self.0.to_ascii_lowercase();
+        if lower.starts_with("bc1") || lower.starts_with("tb1") {
+            if self.0 != lower && self.0 != self.0.to_ascii_uppercase() {
+                return Err(bitcoin("Bech32 addresses cannot mix upper and lower case"));
+            }
+            let data = &lower[3..];
+            if !data.chars().all(|c| BECH32_CHARSET.contains(c)) {
+                return Err(bitcoin("contains characters outside the Bech32 alphabet (no 1, b, i or o)"));
+            }
+            return match (data.as_bytes().first(), lower.len()) {
+                // P2WPKH (20-byte program) or P2WSH (32-byte program)
+                (Some(b'q'), 42 | 62) => Ok(BitcoinAddressType::SegwitV0),
+                (Some(b'p'), 62) => Ok(BitcoinAddressType::Taproot),
+                _ => Err(bitcoin(
+                    "expected a 42- or 62-character bc1q/tb1q address or a 62-character bc1p/tb1p address",
+                )),
+            };
+        }
+
+        // Legacy Base58Check addresses
+        if !(26..=35).contains(&self.0.len()) || !self.0.chars().all(is_base58) {
+            return Err(bitcoin(
+                "expected 26-35 Base58 characters, or a Bech32 address starting with bc1 or tb1",
+            ));
+        }
+        // Verify the checksum, so e.g. a Solana pubkey starting with 1 is not taken for Bitcoin
+        let payload = bs58::decode(&self.0)
+            .with_check(None)
+            .into_vec()
+            .map_err(|_| bitcoin("invalid Base58Check checksum"))?;
+        // Version byte + 20-byte hash: 0x00/0x6f P2PKH, 0x05/0xc4 P2SH (mainnet/testnet)
+        match (payload.len(), payload.first()) {
+            (21, Some(0x00 | 0x6f)) => Ok(BitcoinAddressType::P2pkh),
+            (21, Some(0x05 | 0xc4)) => Ok(BitcoinAddressType::P2sh),
+            _ => Err(bitcoin("expected a Base58 address starting with 1, 3, m, n or 2")),
+        }
+    }
+}
+
+fn invalid(chain: ChainType, reason: String) -> DomainError {
+    DomainError::InvalidAddress { chain, reason }
+}
+
+/// Base58 alphabet: alphanumerics without 0, O, I and l
+fn is_base58(c: char) -> bool {
+    c.is_ascii_alphanumeric() && !matches!(c, '0' | 'O' | 'I' | 'l')
+}
+
+impl PartialEq for Address {
+    fn eq(&self, other: &Self) -> bool {
+        if self.is_case_insensitive() && other.is_case_insensitive() {
+            self.0.eq_ignore_ascii_case(&other.0)
+        } else {
+            self.0 == other.0
+        }
+    }
+}
+
+impl Eq for Address {}
+
+impl Hash for Address {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        if self.is_case_insensitive() {
+            // Hash the case-folded form so it stays consistent with `PartialEq`
+            for b in self.0.bytes() {
+                state.write_u8(b.to_ascii_lowercase());
+            }
+            state.write_u8(0xff);
+        } else {
+            self.0.hash(state);
+        }
+    }
 }
 
 impl fmt::Display for Address {
@@ -116,4 +282,123 @@ mod tests {
         let addr = Address::new("0x742d35Cc".to_string());
         assert!(addr.is_err());
     }
+
+    #[test]
+    fn test_invalid_address_names_chain_format() {
+        let reason = |addr: &str| match Address::new(addr.to_string()) {
+            Err(DomainError::InvalidAddress { chain, reason }) => (chain, reason),
+            other => panic!("Expected InvalidAddress for {}, got {:?}", addr, other),
+        };
+
+        let (chain, message) = reason("0x742d35Cc");
+        assert_eq!(chain, ChainType::Ethereum);
+        assert!(message.contains("got 10"), "{}", message);
+        assert_eq!(reason("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEbZ").0, ChainType::Ethereum);
+        assert_eq!(reason("bc1qw508d6qejxtdg").0, ChainType::Bitcoin);
+        let (chain, message) = reason("DRpbCBMxVnDK7maPM5tGv6Mv");
+        assert_eq!(chain, ChainType::Solana);
+        assert!(message.contains("32-44"), "{}", message);
+
+        // Checked against the intended chain rather than the detected one
+        let evm = "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEbC".to_string();
+        let (chain, message) = match Address::for_chain(evm, ChainType::Solana) {
+            Err(DomainError::InvalidAddress { chain, reason }) => (chain, reason),
+            other => panic!("Expected InvalidAddress, got {:?}", other),
+        };
+        assert_eq!(chain, ChainType::Solana);
+        assert!(message.contains("Base58"), "{}", message);
+        assert!(Address::for_chain("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa".to_string(), ChainType::Bitcoin).is_ok());
+    }
+
+    #[test]
+    fn test_evm_address_case_insensitive_equality() {
+        use std::collections::HashSet;
+
+        let checksummed =
+            Address::new("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEbC".to_string()).unwrap();
+        let lowercase =
+            Address::new("0x742d35cc6634c0532925a3b844bc9e7595f0bebc".to_string()).unwrap();
+        assert_eq!(checksummed, lowercase);
+
+        assert_eq!(checksummed.to_lowercase(), lowercase.as_str());
+
+        let mut set = HashSet::new();
+        set.insert(checksummed);
+        assert!(set.contains(&lowercase));
+    }
+
+    #[test]
+    fn test_bitcoin_address_type_detection() {
+        let cases = [
+            ("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa", BitcoinAddressType::P2pkh),
+            ("mipcBbFg9gMiCh81Kj8tqqdgoZub1ZJRfn", BitcoinAddressType::P2pkh),
+            ("342ftSRCvFHfCeFFBuz4xwbeqnDw6BGUey", BitcoinAddressType::P2sh),
+            ("2MzQwSSnBHWHqSAqtTVQ6v47XtaisrJa1Vc", BitcoinAddressType::P2sh),
+            ("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4", BitcoinAddressType::SegwitV0),
+            ("tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx", BitcoinAddressType::SegwitV0),
+            (
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+                BitcoinAddressType::Taproot,
+            ),
+        ];
+        for (addr, expected) in cases {
+            let address = Address::new_unchecked(addr.to_string());
+            assert_eq!(address.bitcoin_address_type().unwrap(), expected, "{}", addr);
+        }
+    }
+
+    #[test]
+    fn test_bitcoin_address_type_malformed() {
+        // Ethereum address
+        let eth = Address::new_unchecked("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEbC".to_string());
+        assert!(eth.bitcoin_address_type().is_err());
+
+        // Invalid Bech32 character ('b' is not in the charset)
+        let bad_bech32 = Address::new_unchecked("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3tb".to_string());
+        assert!(bad_bech32.bitcoin_address_type().is_err());
+
+        // Well-formed Base58 but not Base58Check: the System Program's Solana pubkey
+        let system_program = Address::new_unchecked("11111111111111111111111111111111".to_string());
+        assert!(system_program.bitcoin_address_type().is_err());
+        assert_eq!(system_program.chain_type(), ChainType::Solana);
+
+        // Bad checksum (last character changed)
+        let bad_checksum = Address::new_unchecked("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNb".to_string());
+        assert!(bad_checksum.bitcoin_address_type().is_err());
+
+        // Invalid Base58 character ('0')
+        let bad_base58 = Address::new_unchecked("1A1zP1eP5QGefi2DMPTfTL5SLmv7Divf0a".to_string());
+        assert!(bad_base58.bitcoin_address_type().is_err());
+    }
+
+    #[test]
+    fn test_bech32_address_case_insensitive_equality() {
+        use std::collections::HashSet;
+
+        let lower = Address::new("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4".to_string()).unwrap();
+        let upper = Address::new("BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4".to_string()).unwrap();
+        assert_eq!(lower, upper);
+
+        let mut set = HashSet::new();
+        set.insert(upper);
+        assert!(set.contains(&lower));
+    }
+
+    #[test]
+    fn test_base58_address_case_sensitive_equality() {
+        let upper = Address::new("DRpbCBMxVnDK7maPM5tGv6MvB3v1sRMC86PZ8okm21hy".to_string()).unwrap();
+        let lower = Address::new("drpbcbmxvndk7mapm5tgv6mvb3v1srmc86pz8okm21hy".to_string()).unwrap();
+        assert_ne!(upper, lower);
+    }
+
+    #[test]
+    fn test_address_chain_type_detection() {
+        let eth = Address::new("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0".to_string()).unwrap();
+        let btc = Address::new("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq".to_string()).unwrap();
+        let sol = Address::new("DRpbCBMxVnDK7maPM5tGv6MvB3v1sRMC86PZ8okm21hy".to_string()).unwrap();
+
+        assert_eq!(eth.chain_type(), ChainType::Ethereum);
+        assert_eq!(btc.chain_type(), ChainType::Bitcoin);
+        assert_eq!(sol.chain_type(), ChainType::Solana);
+    }
 }
@@ -1,9 +1,14 @@
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
+/// Wei per Gwei
+const WEI_PER_GWEI: u128 = 1_000_000_000;
+
 /// Transfer amount (in Wei, smallest unit)
+///
+/// Serialized as a decimal string, like [`Balance`](super::Balance).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
-pub struct Amount(u128);
+pub struct Amount(#[serde(with = "super::decimal_string")] u128);
 
 impl Amount {
     /// Create zero amount
@@ -22,6 +27,11 @@ impl Amount {
         Self((ether * WEI_PER_ETHER as f64) as u128)
     }
 
+    /// Create amount from Gwei (10^9 Wei), the usual unit for gas prices
+    pub fn from_gwei(gwei: u64) -> Self {
+        Self(gwei as u128 * WEI_PER_GWEI)
+    }
+
     /// Get amount in Wei
     pub fn to_wei(&self) -> u128 {
         self.0
@@ -33,6 +43,11 @@ impl Amount {
         self.0 as f64 / WEI_PER_ETHER as f64
     }
 
+    /// Get amount in Gwei (floating point)
+    pub fn to_gwei(&self) -> f64 {
+        self.0 as f64 / WEI_PER_GWEI as f64
+    }
+
     /// Check if amount is zero
     pub fn is_zero(&self) -> bool {
         self.0 == 0
@@ -60,6 +75,14 @@ impl From<u128> for Amount {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_amount_serde_accepts_string_and_number() {
+        let amount = Amount::from_wei(1_000_000_000_000_000_000);
+        assert_eq!(serde_json::to_string(&amount).unwrap(), "\"1000000000000000000\"");
+        assert_eq!(serde_json::from_str::<Amount>("\"1000000000000000000\"").unwrap(), amount);
+        assert_eq!(serde_json::from_str::<Amount>("1000000000000000000").unwrap(), amount);
+    }
+
     #[test]
     fn test_amount_conversion() {
         let amount = Amount::from_ether(1.0);
@@ -67,6 +90,13 @@ mod tests {
         assert_eq!(amount.to_ether(), 1.0);
     }
 
+    #[test]
+    fn test_gwei_conversion() {
+        assert_eq!(Amount::from_gwei(30).to_wei(), 30_000_000_000);
+        assert_eq!(Amount::from_wei(1_500_000_000).to_gwei(), 1.5);
+        assert_eq!(Amount::from_gwei(u64::MAX).to_wei(), u64::MAX as u128 * 1_000_000_000);
+    }
+
     #[test]
     fn test_zero_amount() {
         let amount = Amount::zero();
@@ -1,10 +1,16 @@
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::str::FromStr;
+use super::Network;
+use crate::DomainError;
 
 /// Balance (in Wei, smallest unit)
 /// 1 ETH = 1,000,000,000,000,000,000 Wei
+///
+/// Serialized as a decimal string (`"1000000000000000000"`) so JavaScript
+/// consumers keep full precision; plain JSON numbers are still accepted.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
-pub struct Balance(u128);
+pub struct Balance(#[serde(with = "super::decimal_string")] u128);
 
 impl Balance {
     /// Create zero balance
@@ -23,6 +29,25 @@ impl Balance {
         Self((ether * WEI_PER_ETHER as f64) as u128)
     }
 
+    /// Parse a decimal amount in whole units of a currency with `decimals`
+    /// decimals (e.g. `"1.5"` with 18 gives 1.5 ETH in Wei)
+    ///
+    /// Integer arithmetic only, so no float rounding; `InvalidBalance` if the
+    /// string is not a plain non-negative decimal, has more fractional digits
+    /// than `decimals`, or overflows.
+    pub fn from_decimal_str(s: &str, decimals: u8) -> Result<Self, DomainError> {
+        let s = s.trim();
+        let (whole, fraction) = s.split_once('.').unwrap_or((s, ""));
+        if (whole.is_empty() && fraction.is_empty())
+            || !whole.bytes().chain(fraction.bytes()).all(|b| b.is_ascii_digit())
+            || fraction.len() > decimals as usize
+        {
+            return Err(DomainError::InvalidBalance);
+        }
+        let digits = format!("{}{:0<width$}", whole, fraction, width = decimals as usize);
+        digits.parse().map(Self).map_err(|_| DomainError::InvalidBalance)
+    }
+
     /// Get balance in Wei
     pub fn to_wei(&self) -> u128 {
         self.0
@@ -34,14 +59,179 @@ impl Balance {
         self.0 as f64 / WEI_PER_ETHER as f64
     }
 
+    /// Get balance in whole units of a currency with `decimals` decimals (floating point)
+    pub fn to_units(&self, decimals: u8) -> f64 {
+        self.0 as f64 / 10f64.powi(decimals as i32)
+    }
+
     /// Check if balance is zero
     pub fn is_zero(&self) -> bool {
         self.0 == 0
     }
 
+    /// Check if this balance is greater than or equal to another
+    pub fn gte(&self, other: &Balance) -> bool {
+        self >= other
+    }
+
+    /// Check if balance is strictly above `wei`
+    pub fn is_above(&self, wei: u128) -> bool {
+        self.0 > wei
+    }
+
+    /// Check if balance is strictly below `wei` (e.g. a hot wallet's minimum gas reserve)
+    pub fn is_below(&self, wei: u128) -> bool {
+        self.0 < wei
+    }
+
     /// Format balance as ETH string with specified decimal places
     pub fn format_ether(&self, decimals: usize) -> String {
-        format!("{:.prec$} ETH", self.to_ether(), prec = decimals)
+        self.format_with_symbol(decimals, "ETH")
+    }
+
+    /// Format balance with specified decimal places and currency symbol (e.g. "BNB")
+    pub fn format_with_symbol(&self, decimals: usize, symbol: &str) -> String {
+        format!("{:.prec$} {}", self.to_ether(), symbol, prec = decimals)
+    }
+
+    /// Exact amount in whole units of a currency with `decimals` decimals,
+    /// without trailing zeros (e.g. "1.5", "0.00000001")
+    pub fn format_exact(&self, decimals: u8) -> String {
+        self.format(decimals, &BalanceFormat::default())
+    }
+
+    /// Amount in whole units of a currency with `decimals` decimals, laid out
+    /// by `format` (separators, fixed precision)
+    ///
+    /// Integer arithmetic only: a fixed precision rounds half up on the exact
+    /// value, never through a float. E.g. 1234.5 ETH with
+    /// `BalanceFormat::for_locale("de")` and precision 2 is "1.234,50".
+    pub fn format(&self, decimals: u8, format: &BalanceFormat) -> String {
+        // 10^38 is the largest power of ten that fits in a u128
+        let decimals = decimals.min(38) as u32;
+        let scale = 10u128.pow(decimals);
+        let (mut whole, fraction) = (self.0 / scale, self.0 % scale);
+
+        let digits = match format.precision.map(u32::from) {
+            Some(precision) if precision < decimals => {
+                let dropped = 10u128.pow(decimals - precision);
+                let (mut kept, rest) = (fraction / dropped, fraction % dropped);
+                // rest >= dropped / 2, without overflowing
+                if rest >= dropped - rest {
+                    kept += 1;
+                    if kept == 10u128.pow(precision) {
+                        kept = 0;
+                        whole += 1;
+                    }
+                }
+                match precision {
+                    0 => String::new(),
+                    _ => format!("{:0width$}", kept, width = precision as usize),
+                }
+            }
+            Some(precision) => {
+                let exact = match decimals {
+                    0 => String::new(),
+                    _ => format!("{:0width$}", fraction, width = decimals as usize),
+                };
+                format!("{:0<width$}", exact, width = precision as usize)
+            }
+            None if fraction == 0 => String::new(),
+            None => format!("{:0width$}", fraction, width = decimals as usize)
+                .trim_end_matches('0')
+                .to_string(),
+        };
+
+        let mut formatted = whole.to_string();
+        if let Some(separator) = format.thousands_separator {
+            let len = formatted.len();
+            formatted = formatted
+                .chars()
+                .enumerate()
+                .flat_map(|(i, digit)| {
+                    let group = i > 0 && (len - i) % 3 == 0;
+                    group.then_some(separator).into_iter().chain([digit])
+                })
+                .collect();
+        }
+        if !digits.is_empty() {
+            formatted.push(format.decimal_separator);
+            formatted.push_str(&digits);
+        }
+        formatted
+    }
+
+    /// Format a native balance of `network` using its decimals and symbol (e.g. "0.5 BTC")
+    pub fn format_native(&self, precision: usize, network: &Network) -> String {
+        format!(
+            "{:.prec$} {}",
+            self.to_units(network.native_decimals()),
+            network.native_symbol(),
+            prec = precision
+        )
+    }
+}
+
+/// How [`Balance::format`] lays out an amount
+///
+/// The default is what `format_exact` prints: `.` as decimal separator, no
+/// thousands grouping, and no trailing zeros.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BalanceFormat {
+    /// Inserted between groups of three digits of the whole part (`None`: no grouping)
+    pub thousands_separator: Option<char>,
+    pub decimal_separator: char,
+    /// Fractional digits to show, rounding half up or padding with zeros
+    /// (`None`: all significant digits)
+    pub precision: Option<u8>,
+}
+
+impl Default for BalanceFormat {
+    fn default() -> Self {
+        Self {
+            thousands_separator: None,
+            decimal_separator: '.',
+            precision: None,
+        }
+    }
+}
+
+impl BalanceFormat {
+    /// Separators conventional for a locale such as `en`, `de`, `fr` or `de-CH`
+    ///
+    /// Only the language (and region, for Swiss German) is looked at;
+    /// `None` for a language not listed here.
+    pub fn for_locale(locale: &str) -> Option<Self> {
+        let locale = locale.trim().replace('_', "-").to_lowercase();
+        let language = locale.split('-').next().unwrap_or_default();
+        let (thousands, decimal) = match (language, locale.as_str()) {
+            (_, "de-ch") => ('\'', '.'),
+            ("en" | "ja" | "ko" | "zh" | "th" | "he", _) => (',', '.'),
+            ("de" | "es" | "it" | "nl" | "pt" | "id" | "tr" | "da" | "el", _) => ('.', ','),
+            ("fr" | "ru" | "pl" | "sv" | "cs" | "uk" | "fi" | "nb" | "no", _) => (' ', ','),
+            _ => return None,
+        };
+        Some(Self {
+            thousands_separator: Some(thousands),
+            decimal_separator: decimal,
+            precision: None,
+        })
+    }
+
+    pub fn with_thousands_separator(mut self, separator: char) -> Self {
+        self.thousands_separator = Some(separator);
+        self
+    }
+
+    pub fn with_decimal_separator(mut self, separator: char) -> Self {
+        self.decimal_separator = separator;
+        self
+    }
+
+    /// Show exactly `precision` fractional digits
+    pub fn with_precision(mut self, precision: u8) -> Self {
+        self.precision = Some(precision);
+        self
     }
 }
 
@@ -51,6 +241,15 @@ impl fmt::Display for Balance {
     }
 }
 
+/// Parses a plain integer string as Wei (e.g. a balance read back from JSON or a config file)
+impl FromStr for Balance {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_decimal_str(s, 0)
+    }
+}
+
 impl From<u128> for Balance {
     fn from(wei: u128) -> Self {
         Self::from_wei(wei)
@@ -61,6 +260,79 @@ impl From<u128> for Balance {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_balance_format_exact() {
+        assert_eq!(Balance::from_wei(1_500_000_000_000_000_000).format_exact(18), "1.5");
+        assert_eq!(Balance::from_wei(1).format_exact(8), "0.00000001");
+        assert_eq!(Balance::from_wei(2_000_000_000).format_exact(9), "2");
+        assert_eq!(Balance::zero().format_exact(18), "0");
+        assert_eq!(Balance::from_wei(u128::MAX).format_exact(0), u128::MAX.to_string());
+    }
+
+    #[test]
+    fn test_balance_format_with_separators_and_precision() {
+        let balance = Balance::from_wei(1_234_567_891_000_000_000_000);
+        let en = BalanceFormat::for_locale("en-US").unwrap();
+        assert_eq!(balance.format(18, &en), "1,234.567891");
+        assert_eq!(balance.format(18, &en.with_precision(2)), "1,234.57");
+        assert_eq!(balance.format(18, &en.with_precision(8)), "1,234.56789100");
+        assert_eq!(balance.format(18, &BalanceFormat::for_locale("de").unwrap().with_precision(2)), "1.234,57");
+        assert_eq!(balance.format(18, &BalanceFormat::for_locale("fr_FR").unwrap()), "1 234,567891");
+        assert_eq!(balance.format(18, &BalanceFormat::for_locale("de-CH").unwrap()), "1'234.567891");
+        assert!(BalanceFormat::for_locale("xx").is_none());
+
+        // Rounding carries into the whole part; half rounds up
+        let format = BalanceFormat::default().with_precision(2);
+        assert_eq!(Balance::from_wei(999_995).format(6, &format), "1.00");
+        assert_eq!(Balance::from_wei(1_994_999).format(6, &format), "1.99");
+        assert_eq!(Balance::from_wei(1_500_000).format(6, &format.with_precision(0)), "2");
+        assert_eq!(Balance::from_wei(7).format(0, &format), "7.00");
+        assert_eq!(Balance::from_wei(999_999).format(0, &BalanceFormat::default().with_thousands_separator(',')), "999,999");
+
+        // No float on the way: exact even beyond f64 precision
+        let max = Balance::from_wei(u128::MAX).format(18, &en.with_precision(18));
+        assert_eq!(max, "340,282,366,920,938,463,463.374607431768211455");
+    }
+
+    #[test]
+    fn test_balance_parses_integer_and_decimal_strings() {
+        assert_eq!("1500000000000000000".parse::<Balance>().unwrap().to_wei(), 1_500_000_000_000_000_000);
+        assert_eq!(u128::MAX.to_string().parse::<Balance>().unwrap().to_wei(), u128::MAX);
+        assert!("1.5".parse::<Balance>().is_err());
+        assert!("-1".parse::<Balance>().is_err());
+        assert!("+1".parse::<Balance>().is_err());
+        assert!("".parse::<Balance>().is_err());
+
+        assert_eq!(Balance::from_decimal_str("1.5", 18).unwrap().to_wei(), 1_500_000_000_000_000_000);
+        assert_eq!(Balance::from_decimal_str(".5", 8).unwrap().to_wei(), 50_000_000);
+        assert_eq!(Balance::from_decimal_str("2", 6).unwrap().to_wei(), 2_000_000);
+        assert_eq!(Balance::from_decimal_str("0.000000000000000001", 18).unwrap().to_wei(), 1);
+        assert!(Balance::from_decimal_str("0.1234567", 6).is_err());
+        assert!(Balance::from_decimal_str("1e18", 18).is_err());
+        assert!(Balance::from_decimal_str("1000000000000000000000", 18).is_err());
+        assert!(matches!(Balance::from_decimal_str("abc", 18), Err(DomainError::InvalidBalance)));
+    }
+
+    #[test]
+    fn test_balance_serializes_as_decimal_string() {
+        let balance = Balance::from_wei(u128::MAX);
+        let json = serde_json::to_string(&balance).unwrap();
+        assert_eq!(json, format!("\"{}\"", u128::MAX));
+        assert_eq!(serde_json::from_str::<Balance>(&json).unwrap(), balance);
+
+        // Numbers written before the switch still load,
+        assert_eq!(serde_json::from_str::<Balance>("1500000000000000000").unwrap().to_wei(), 1_500_000_000_000_000_000);
+        // including v1 numbers beyond u64, which must not round through f64
+        assert_eq!(serde_json::from_str::<Balance>(&u128::MAX.to_string()).unwrap().to_wei(), u128::MAX);
+        assert_eq!(
+            serde_json::from_str::<Balance>("18446744073709551617").unwrap().to_wei(),
+            u64::MAX as u128 + 2
+        );
+        assert!(serde_json::from_str::<Balance>("1.5").is_err());
+        assert!(serde_json::from_str::<Balance>("-1").is_err());
+        assert!(serde_json::from_str::<Balance>("\"-1\"").is_err());
+    }
+
     #[test]
     fn test_balance_conversion() {
         let balance = Balance::from_ether(1.0);
@@ -75,6 +347,29 @@ mod tests {
         assert_eq!(balance.to_wei(), 0);
     }
 
+    #[test]
+    fn test_balance_comparisons() {
+        let low = Balance::from_wei(100);
+        let high = Balance::from_wei(200);
+
+        assert!(high.gte(&low));
+        assert!(high.gte(&high));
+        assert!(!low.gte(&high));
+
+        assert!(high.is_above(100));
+        assert!(!low.is_above(100));
+        assert!(low.is_below(101));
+        assert!(!low.is_below(100));
+    }
+
+    #[test]
+    fn test_format_native_uses_network_decimals() {
+        assert_eq!(Balance::from_wei(1_500_000_000_000_000_000).format_native(2, &Network::Mainnet), "1.50 ETH");
+        assert_eq!(Balance::from_wei(150_000_000).format_native(2, &Network::BitcoinMainnet), "1.50 BTC");
+        assert_eq!(Balance::from_wei(1_500_000_000).format_native(2, &Network::SolanaDevnet), "1.50 SOL");
+        assert_eq!(Balance::from_wei(1_500_000_000_000_000_000).format_native(1, &Network::Polygon), "1.5 MATIC");
+    }
+
     #[test]
     fn test_balance_display() {
         let balance = Balance::from_ether(2.5);
@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+use super::{Address, Balance, Network};
+
+/// Balance of one address at a point in time, as saved by `rustwallet snapshot`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BalanceSnapshotEntry {
+    pub address: Address,
+    pub network: Network,
+    pub balance: Balance,
+    /// Unix time (seconds) the balance was read
+    pub timestamp: u64,
+}
+
+impl BalanceSnapshotEntry {
+    pub fn new(address: Address, network: Network, balance: Balance, timestamp: u64) -> Self {
+        Self {
+            address,
+            network,
+            balance,
+            timestamp,
+        }
+    }
+
+    /// How the balance moved between this snapshot and `current`
+    pub fn change_to(&self, current: Balance) -> BalanceChange {
+        let (before, after) = (self.balance.to_wei(), current.to_wei());
+        match after.cmp(&before) {
+            std::cmp::Ordering::Greater => BalanceChange::Increased(Balance::from_wei(after - before)),
+            std::cmp::Ordering::Less => BalanceChange::Decreased(Balance::from_wei(before - after)),
+            std::cmp::Ordering::Equal => BalanceChange::Unchanged,
+        }
+    }
+}
+
+/// Difference between a snapshotted balance and the current one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BalanceChange {
+    /// Grew by the given amount
+    Increased(Balance),
+    /// Shrank by the given amount
+    Decreased(Balance),
+    Unchanged,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_change_to() {
+        let address = Address::new("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEbC".to_string()).unwrap();
+        let entry = BalanceSnapshotEntry::new(address, Network::Mainnet, Balance::from_wei(1_000), 1_700_000_000);
+
+        assert_eq!(entry.change_to(Balance::from_wei(1_500)), BalanceChange::Increased(Balance::from_wei(500)));
+        assert_eq!(entry.change_to(Balance::from_wei(400)), BalanceChange::Decreased(Balance::from_wei(600)));
+        assert_eq!(entry.change_to(Balance::from_wei(1_000)), BalanceChange::Unchanged);
+
+        // Balances are stored as decimal strings, like everywhere else
+        let json = serde_json::to_value(&entry).unwrap();
+        assert_eq!(json["balance"], "1000");
+        assert_eq!(serde_json::from_value::<BalanceSnapshotEntry>(json).unwrap(), entry);
+    }
+}
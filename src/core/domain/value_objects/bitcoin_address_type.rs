@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Bitcoin address (script) type classification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BitcoinAddressType {
+    /// Pay-to-Public-Key-Hash (mainnet `1…`, testnet `m…`/`n…`)
+    P2pkh,
+    /// Pay-to-Script-Hash (mainnet `3…`, testnet `2…`)
+    P2sh,
+    /// Native SegWit v0, Bech32 (mainnet `bc1q…`, testnet `tb1q…`)
+    SegwitV0,
+    /// Taproot / SegWit v1, Bech32m (mainnet `bc1p…`, testnet `tb1p…`)
+    Taproot,
+}
+
+impl BitcoinAddressType {
+    /// Get the name of the address type
+    pub fn name(&self) -> &'static str {
+        match self {
+            BitcoinAddressType::P2pkh => "P2PKH",
+            BitcoinAddressType::P2sh => "P2SH",
+            BitcoinAddressType::SegwitV0 => "SegWit v0 (Bech32)",
+            BitcoinAddressType::Taproot => "Taproot (Bech32m)",
+        }
+    }
+
+    /// Check if this is a native SegWit (Bech32/Bech32m) address type
+    pub fn is_segwit(&self) -> bool {
+        matches!(self, BitcoinAddressType::SegwitV0 | BitcoinAddressType::Taproot)
+    }
+}
+
+impl fmt::Display for BitcoinAddressType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
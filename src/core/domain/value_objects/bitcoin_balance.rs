@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use super::Balance;
+
+/// Bitcoin balance split by confirmation status, computed from unspent outputs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BitcoinBalance {
+    /// Sum of outputs with at least the required confirmations (spendable now)
+    pub confirmed_sats: u64,
+    /// Sum of outputs below the confirmation threshold (including mempool)
+    pub unconfirmed_sats: u64,
+    /// Number of unspent outputs
+    pub utxo_count: usize,
+}
+
+impl BitcoinBalance {
+    /// Sum `(value_sats, confirmations)` pairs, counting an output as confirmed
+    /// once it has `min_confirmations`
+    pub fn from_utxos(utxos: impl IntoIterator<Item = (u64, u64)>, min_confirmations: u64) -> Self {
+        utxos.into_iter().fold(Self::default(), |mut balance, (value, confirmations)| {
+            if confirmations >= min_confirmations {
+                balance.confirmed_sats = balance.confirmed_sats.saturating_add(value);
+            } else {
+                balance.unconfirmed_sats = balance.unconfirmed_sats.saturating_add(value);
+            }
+            balance.utxo_count += 1;
+            balance
+        })
+    }
+
+    /// Confirmed plus unconfirmed satoshis
+    pub fn total_sats(&self) -> u64 {
+        self.confirmed_sats.saturating_add(self.unconfirmed_sats)
+    }
+
+    /// Confirmed satoshis as a `Balance` (smallest unit)
+    pub fn spendable(&self) -> Balance {
+        Balance::from_wei(self.confirmed_sats as u128)
+    }
+}
+
+impl fmt::Display for BitcoinBalance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} sats confirmed, {} sats unconfirmed ({} UTXOs)",
+            self.confirmed_sats, self.unconfirmed_sats, self.utxo_count
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bitcoin_balance_splits_by_confirmations() {
+        let balance = BitcoinBalance::from_utxos([(50_000, 10), (20_000, 1), (5_000, 0)], 2);
+        assert_eq!(balance.confirmed_sats, 50_000);
+        assert_eq!(balance.unconfirmed_sats, 25_000);
+        assert_eq!(balance.utxo_count, 3);
+        assert_eq!(balance.total_sats(), 75_000);
+        assert_eq!(balance.spendable().to_wei(), 50_000);
+
+        assert_eq!(BitcoinBalance::from_utxos([], 1), BitcoinBalance::default());
+    }
+}
@@ -1,5 +1,6 @@
 use std::fmt;
 use serde::{Deserialize, Serialize};
+use super::Address;
 
 /// Blockchain type classification
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -13,6 +14,11 @@ pub enum ChainType {
 }
 
 impl ChainType {
+    /// Detect which chain an address belongs to from its format
+    pub fn detect_from_address(address: &Address) -> ChainType {
+        address.chain_type()
+    }
+
     /// Get the name of the chain type
     pub fn name(&self) -> &'static str {
         match self {
@@ -40,6 +46,18 @@ impl ChainType {
         }
     }
 
+    /// What the chain's height counts: EVM blocks, Bitcoin's block height, Solana slots
+    ///
+    /// Solana slots advance even when no block is produced, so a slot number
+    /// is not comparable to a block number.
+    pub fn height_unit(&self) -> &'static str {
+        match self {
+            ChainType::Ethereum => "block",
+            ChainType::Bitcoin => "height",
+            ChainType::Solana => "slot",
+        }
+    }
+
     /// Get the decimals for the native currency
     pub fn decimals(&self) -> u8 {
         match self {
@@ -76,6 +94,7 @@ mod tests {
         assert_eq!(ChainType::Solana.native_currency(), "SOL");
         assert_eq!(ChainType::Solana.smallest_unit(), "Lamport");
         assert_eq!(ChainType::Solana.decimals(), 9);
+        assert_eq!(ChainType::Solana.height_unit(), "slot");
     }
 
     #[test]
@@ -0,0 +1,130 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::future::Future;
+use std::time::Instant;
+use crate::core::domain::errors::DomainError;
+
+/// Result of probing a blockchain endpoint
+///
+/// Unlike `is_connected`, this keeps the reason a probe failed (DNS, TLS,
+/// HTTP status such as 429, malformed response, ...) so it can be shown to users.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConnectionDiagnostics {
+    /// Whether the endpoint answered the height probe
+    pub reachable: bool,
+    /// The probe was refused by a rate limit: the endpoint is up but busy
+    pub throttled: bool,
+    /// Round-trip time of the height probe (also set when it failed)
+    pub latency_ms: u64,
+    /// Current block height / slot reported by the endpoint
+    pub block_height: Option<u64>,
+    /// Node software version, if the chain exposes one
+    pub rpc_version: Option<String>,
+    /// Why the probe failed
+    pub error: Option<String>,
+}
+
+impl ConnectionDiagnostics {
+    /// Time a height probe (usually `get_block_number`) and record its outcome
+    pub async fn measure<F>(probe: F) -> Self
+    where
+        F: Future<Output = Result<u64, DomainError>>,
+    {
+        let started = Instant::now();
+        let result = probe.await;
+        let latency_ms = started.elapsed().as_millis() as u64;
+
+        match result {
+            Ok(height) => Self {
+                reachable: true,
+                throttled: false,
+                latency_ms,
+                block_height: Some(height),
+                rpc_version: None,
+                error: None,
+            },
+            Err(e) => Self {
+                reachable: false,
+                throttled: matches!(e, DomainError::RateLimited(_)),
+                latency_ms,
+                block_height: None,
+                rpc_version: None,
+                error: Some(e.to_string()),
+            },
+        }
+    }
+
+    /// Whether requests can be sent: reachable, or up but throttled
+    pub fn is_up(&self) -> bool {
+        self.reachable || self.throttled
+    }
+
+    /// Attach the node version reported by the endpoint
+    pub fn with_rpc_version(mut self, rpc_version: Option<String>) -> Self {
+        self.rpc_version = rpc_version;
+        self
+    }
+}
+
+impl fmt::Display for ConnectionDiagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.throttled {
+            return write!(
+                f,
+                "up but rate limited after {} ms: {}",
+                self.latency_ms,
+                self.error.as_deref().unwrap_or("unknown error")
+            );
+        }
+        if !self.reachable {
+            return write!(
+                f,
+                "unreachable after {} ms: {}",
+                self.latency_ms,
+                self.error.as_deref().unwrap_or("unknown error")
+            );
+        }
+
+        write!(f, "reachable in {} ms", self.latency_ms)?;
+        if let Some(height) = self.block_height {
+            write!(f, ", height #{}", height)?;
+        }
+        if let Some(version) = &self.rpc_version {
+            write!(f, ", node {}", version)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_measure_success_and_failure() {
+        let ok = ConnectionDiagnostics::measure(async { Ok(42) })
+            .await
+            .with_rpc_version(Some("Geth/v1.14.0".to_string()));
+        assert!(ok.reachable);
+        assert_eq!(ok.block_height, Some(42));
+        assert!(ok.to_string().contains("height #42, node Geth/v1.14.0"));
+
+        let failed = ConnectionDiagnostics::measure(async {
+            Err(DomainError::NetworkError("Rate limited (HTTP 429)".to_string()))
+        })
+        .await;
+        assert!(!failed.reachable);
+        assert!(!failed.is_up());
+        assert_eq!(failed.block_height, None);
+        assert!(failed.to_string().starts_with("unreachable after"));
+        assert!(failed.to_string().contains("HTTP 429"));
+
+        let throttled = ConnectionDiagnostics::measure(async {
+            Err(DomainError::RateLimited("endpoint returned HTTP 429".to_string()))
+        })
+        .await;
+        assert!(!throttled.reachable);
+        assert!(throttled.is_up());
+        assert!(throttled.to_string().starts_with("up but rate limited"));
+    }
+}
@@ -0,0 +1,36 @@
+//! Serde representation of `u128` values as decimal strings
+//!
+//! JavaScript parses JSON numbers as doubles, which silently lose precision
+//! above 2^53 (about 0.009 ETH in Wei). Writing `"1000000000000000000"`
+//! instead keeps the exact value. Reading accepts both strings and plain
+//! numbers, so JSON written before the switch still loads.
+
+use serde::de::{self, Deserialize};
+use serde::{Deserializer, Serializer};
+use serde_json::value::RawValue;
+
+const EXPECTED: &str = "a non-negative integer or a decimal string";
+
+pub(crate) fn serialize<S: Serializer>(value: &u128, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+/// Reads the raw JSON token rather than a parsed number: serde_json turns
+/// integers beyond u64 into floats, which would round v1 balances above
+/// about 18.4 ETH
+pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u128, D::Error> {
+    let raw = Box::<RawValue>::deserialize(deserializer)?;
+    let token = raw.get().trim();
+    let digits = if token.starts_with('"') {
+        serde_json::from_str::<String>(token).map_err(de::Error::custom)?
+    } else {
+        token.to_string()
+    };
+    // `u128::from_str` also takes a leading '+', which JSON numbers never have
+    if digits.starts_with('+') {
+        return Err(de::Error::invalid_value(de::Unexpected::Str(&digits), &EXPECTED));
+    }
+    digits
+        .parse()
+        .map_err(|_| de::Error::invalid_value(de::Unexpected::Str(&digits), &EXPECTED))
+}
@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use super::{Amount, ChainType};
+
+/// Estimated cost of sending a transaction, in the chain's native currency
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeEstimate {
+    /// Total fee in the smallest native unit (Wei / Satoshi / Lamport)
+    pub total_native: Amount,
+    /// Name of the smallest native unit
+    pub unit: String,
+    /// Chain-specific breakdown of how the fee was computed
+    pub details: FeeDetails,
+    /// Rollups only: part of `total_native` paid for posting the transaction
+    /// data to L1 (Optimism/Base charge it on top of L2 gas; Arbitrum folds
+    /// it into the gas limit)
+    #[serde(default)]
+    pub l1_data_fee: Option<Amount>,
+}
+
+/// Chain-specific fee breakdown
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FeeDetails {
+    /// EVM: gas limit × gas price (Wei per gas)
+    Evm { gas_limit: u64, gas_price: u128 },
+    /// Bitcoin: virtual size × fee rate (sat/vB)
+    Bitcoin { vsize: u64, sat_per_vbyte: u64 },
+    /// Solana: signatures × lamports per signature
+    Solana { signatures: u64, lamports_per_signature: u64 },
+}
+
+impl FeeEstimate {
+    /// Build an estimate from its breakdown, computing the total with checked arithmetic
+    ///
+    /// Returns `None` if the total overflows.
+    pub fn from_details(details: FeeDetails) -> Option<Self> {
+        let (chain_type, total) = match &details {
+            FeeDetails::Evm { gas_limit, gas_price } => {
+                (ChainType::Ethereum, (*gas_limit as u128).checked_mul(*gas_price)?)
+            }
+            FeeDetails::Bitcoin { vsize, sat_per_vbyte } => {
+                (ChainType::Bitcoin, (*vsize as u128).checked_mul(*sat_per_vbyte as u128)?)
+            }
+            FeeDetails::Solana { signatures, lamports_per_signature } => (
+                ChainType::Solana,
+                (*signatures as u128).checked_mul(*lamports_per_signature as u128)?,
+            ),
+        };
+
+        Some(Self {
+            total_native: Amount::from_wei(total),
+            unit: chain_type.smallest_unit().to_string(),
+            details,
+            l1_data_fee: None,
+        })
+    }
+
+    /// Add an L1 data fee charged on top of the execution fee
+    ///
+    /// Returns `None` if the total overflows.
+    pub fn with_l1_data_fee(mut self, l1_data_fee: u128) -> Option<Self> {
+        let total = self.total_native.to_wei().checked_add(l1_data_fee)?;
+        self.total_native = Amount::from_wei(total);
+        self.l1_data_fee = Some(Amount::from_wei(l1_data_fee));
+        Some(self)
+    }
+}
+
+impl fmt::Display for FeeEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.total_native.to_wei(), self.unit)?;
+        if let Some(l1_data_fee) = &self.l1_data_fee {
+            write!(f, " (incl. {} {} L1 data fee)", l1_data_fee.to_wei(), self.unit)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_estimate_totals() {
+        let evm = FeeEstimate::from_details(FeeDetails::Evm {
+            gas_limit: 21_000,
+            gas_price: 30_000_000_000,
+        })
+        .unwrap();
+        assert_eq!(evm.total_native.to_wei(), 630_000_000_000_000);
+        assert_eq!(evm.unit, "Wei");
+
+        let btc = FeeEstimate::from_details(FeeDetails::Bitcoin { vsize: 141, sat_per_vbyte: 10 }).unwrap();
+        assert_eq!(btc.total_native.to_wei(), 1_410);
+        assert_eq!(btc.unit, "Satoshi");
+
+        let sol = FeeEstimate::from_details(FeeDetails::Solana {
+            signatures: 1,
+            lamports_per_signature: 5_000,
+        })
+        .unwrap();
+        assert_eq!(format!("{}", sol), "5000 Lamport");
+    }
+
+    #[test]
+    fn test_fee_estimate_with_l1_data_fee() {
+        let fee = FeeEstimate::from_details(FeeDetails::Evm { gas_limit: 21_000, gas_price: 1_000 })
+            .unwrap()
+            .with_l1_data_fee(500_000)
+            .unwrap();
+        assert_eq!(fee.total_native.to_wei(), 21_500_000);
+        assert_eq!(fee.l1_data_fee.unwrap().to_wei(), 500_000);
+        assert_eq!(format!("{}", fee), "21500000 Wei (incl. 500000 Wei L1 data fee)");
+
+        let full = FeeEstimate::from_details(FeeDetails::Evm { gas_limit: 1, gas_price: u128::MAX }).unwrap();
+        assert!(full.with_l1_data_fee(1).is_none());
+    }
+
+    #[test]
+    fn test_fee_estimate_overflow() {
+        let overflow = FeeEstimate::from_details(FeeDetails::Evm {
+            gas_limit: u64::MAX,
+            gas_price: u128::MAX,
+        });
+        assert!(overflow.is_none());
+    }
+}
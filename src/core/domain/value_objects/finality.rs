@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// How settled a block must be before it is reported
+///
+/// | Finality    | EVM tag     | Solana commitment | Bitcoin                      |
+/// |-------------|-------------|-------------------|------------------------------|
+/// | `Latest`    | `latest`    | `processed`       | tip                          |
+/// | `Safe`      | `safe`      | `confirmed`       | tip with 3 confirmations     |
+/// | `Finalized` | `finalized` | `finalized`       | tip with 6 confirmations     |
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Finality {
+    /// Chain head; may still be reorganized
+    #[default]
+    Latest,
+    /// Unlikely to be reorganized (EVM: justified by the beacon chain)
+    Safe,
+    /// Irreversible under the chain's consensus rules
+    Finalized,
+}
+
+impl Finality {
+    /// Get the name of the finality level
+    pub fn name(&self) -> &'static str {
+        match self {
+            Finality::Latest => "latest",
+            Finality::Safe => "safe",
+            Finality::Finalized => "finalized",
+        }
+    }
+}
+
+impl fmt::Display for Finality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
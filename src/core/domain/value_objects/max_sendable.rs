@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+use super::{Amount, Balance, FeeEstimate};
+use crate::DomainError;
+
+/// Largest amount an address can send in one transfer once the fee is paid
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaxSendable {
+    /// Spendable balance the amount is taken from
+    pub balance: Balance,
+    /// Fee for sending everything
+    pub fee: FeeEstimate,
+    /// `balance - fee`, in the smallest native unit
+    pub amount: Amount,
+}
+
+impl MaxSendable {
+    /// `balance` minus `fee`; `InsufficientBalance` if nothing would be left to send
+    pub fn new(balance: Balance, fee: FeeEstimate) -> Result<Self, DomainError> {
+        let cost = fee.total_native.to_wei();
+        let amount = balance
+            .to_wei()
+            .checked_sub(cost)
+            .filter(|amount| *amount > 0)
+            .ok_or(DomainError::InsufficientBalance {
+                required: cost.saturating_add(1),
+                available: balance.to_wei(),
+            })?;
+        Ok(Self {
+            balance,
+            fee,
+            amount: Amount::from_wei(amount),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::domain::value_objects::FeeDetails;
+
+    #[test]
+    fn test_max_sendable_subtracts_fee() {
+        let fee = FeeEstimate::from_details(FeeDetails::Evm { gas_limit: 21_000, gas_price: 10 }).unwrap();
+
+        let max = MaxSendable::new(Balance::from_wei(1_000_000), fee.clone()).unwrap();
+        assert_eq!(max.amount.to_wei(), 790_000);
+
+        assert!(matches!(
+            MaxSendable::new(Balance::from_wei(210_000), fee),
+            Err(DomainError::InsufficientBalance { required: 210_001, available: 210_000 })
+        ));
+    }
+}
@@ -1,13 +1,44 @@
+pub mod account_info;
 pub mod address;
 pub mod amount;
 pub mod balance;
+pub mod balance_snapshot;
+pub mod bitcoin_address_type;
+pub mod bitcoin_balance;
 pub mod chain_type;
+pub mod connection_diagnostics;
+mod decimal_string;
+pub mod fee_estimate;
+pub mod finality;
+pub mod max_sendable;
 pub mod network;
+pub mod payment_uri;
+pub mod secret_key;
+pub mod token_holding;
 pub mod transaction_hash;
+pub mod transaction_receipt;
+pub mod transfer_audit;
+pub mod tx_status;
+pub mod utxo;
 
+pub use account_info::AccountInfo;
 pub use address::Address;
 pub use amount::Amount;
-pub use balance::Balance;
+pub use balance::{Balance, BalanceFormat};
+pub use balance_snapshot::{BalanceChange, BalanceSnapshotEntry};
+pub use bitcoin_address_type::BitcoinAddressType;
+pub use bitcoin_balance::BitcoinBalance;
 pub use chain_type::ChainType;
-pub use network::Network;
+pub use connection_diagnostics::ConnectionDiagnostics;
+pub use fee_estimate::{FeeDetails, FeeEstimate};
+pub use finality::Finality;
+pub use max_sendable::MaxSendable;
+pub use network::{Network, NetworkKind};
+pub use payment_uri::PaymentUri;
+pub use secret_key::SecretKey;
+pub use token_holding::TokenHolding;
 pub use transaction_hash::TransactionHash;
+pub use transaction_receipt::{Erc20Transfer, Log, TransactionReceipt, ERC20_TRANSFER_TOPIC};
+pub use transfer_audit::{TransferAuditRecord, TransferOutcome};
+pub use tx_status::TxStatus;
+pub use utxo::Utxo;
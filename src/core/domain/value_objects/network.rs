@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::fmt;
 use super::ChainType;
+use crate::core::domain::errors::DomainError;
 
 /// Blockchain network types
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -18,6 +20,8 @@ pub enum Network {
     BscMainnet,
     /// BSC Testnet
     BscTestnet,
+    /// Polygon PoS Mainnet
+    Polygon,
 
     // Bitcoin Networks
     /// Bitcoin Mainnet
@@ -34,7 +38,75 @@ pub enum Network {
     SolanaTestnet,
 
     /// Custom network
-    Custom { name: String, chain_id: u64, rpc_url: String },
+    Custom {
+        name: String,
+        chain_id: u64,
+        rpc_url: String,
+        /// Missing in custom networks saved before kinds existed, which load as `Mainnet`
+        #[serde(default)]
+        kind: NetworkKind,
+        /// Native currency symbol; `ETH` when unset
+        #[serde(default)]
+        symbol: Option<String>,
+        /// Block explorer base URL, if known
+        #[serde(default)]
+        explorer_url: Option<String>,
+    },
+}
+
+/// `wallet_addEthereumChain` parameters (EIP-3085), as wallets export them
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ChainConfig {
+    chain_id: ChainConfigId,
+    chain_name: String,
+    rpc_urls: Vec<String>,
+    native_currency: Option<NativeCurrency>,
+    #[serde(default)]
+    block_explorer_urls: Vec<String>,
+}
+
+/// EIP-3085 requires a hex string; plain numbers are accepted too
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ChainConfigId {
+    Hex(String),
+    Number(u64),
+}
+
+#[derive(Deserialize)]
+struct NativeCurrency {
+    symbol: String,
+    decimals: u8,
+}
+
+/// Whether a network's coins carry real value
+///
+/// Every built-in network declares its kind explicitly (see [`Network::kind`]),
+/// so adding a network forces a decision instead of falling into a default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum NetworkKind {
+    /// Production network; the default, as the cautious assumption for unknown networks
+    #[default]
+    Mainnet,
+    /// Public test network with valueless coins
+    Testnet,
+    /// Public development network, typically reset from time to time
+    Devnet,
+    /// Node on the local machine (Anvil, Hardhat, a regtest node)
+    Local,
+}
+
+impl fmt::Display for NetworkKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            NetworkKind::Mainnet => "mainnet",
+            NetworkKind::Testnet => "testnet",
+            NetworkKind::Devnet => "devnet",
+            NetworkKind::Local => "local",
+        };
+        f.write_str(name)
+    }
 }
 
 impl Network {
@@ -47,6 +119,7 @@ impl Network {
             Network::Holesky => 17000,
             Network::BscMainnet => 56,
             Network::BscTestnet => 97,
+            Network::Polygon => 137,
             Network::BitcoinMainnet => 0, // Bitcoin doesn't use chain IDs
             Network::BitcoinTestnet => 0,
             Network::SolanaMainnet => 0, // Solana doesn't use chain IDs
@@ -66,6 +139,7 @@ impl Network {
             Network::Holesky => "https://holesky.infura.io/v3/",
             Network::BscMainnet => "https://bsc-dataseed.binance.org",
             Network::BscTestnet => "https://data-seed-prebsc-1-s1.binance.org:8545",
+            Network::Polygon => "https://polygon-rpc.com",
 
             // Bitcoin Networks (use blockchain.info API)
             Network::BitcoinMainnet => "https://blockchain.info",
@@ -80,6 +154,44 @@ impl Network {
         }
     }
 
+    /// Default RPC URL using the given Infura project ID or Alchemy API key
+    ///
+    /// The Infura key wins when both are given and the network is served by
+    /// Infura; without a usable key this is [`default_rpc_url`](Self::default_rpc_url).
+    pub fn rpc_url_with_keys(&self, infura_key: Option<&str>, alchemy_key: Option<&str>) -> Cow<'_, str> {
+        if let (Some(key), Some(host)) = (infura_key, self.infura_host()) {
+            return Cow::Owned(format!("https://{}.infura.io/v3/{}", host, key.trim()));
+        }
+        if let (Some(key), Some(host)) = (alchemy_key, self.alchemy_host()) {
+            return Cow::Owned(format!("https://{}.g.alchemy.com/v2/{}", host, key.trim()));
+        }
+        Cow::Borrowed(self.default_rpc_url())
+    }
+
+    /// Infura subdomain serving this network
+    fn infura_host(&self) -> Option<&'static str> {
+        match self {
+            Network::Mainnet => Some("mainnet"),
+            Network::Goerli => Some("goerli"),
+            Network::Sepolia => Some("sepolia"),
+            Network::Holesky => Some("holesky"),
+            Network::Polygon => Some("polygon-mainnet"),
+            _ => None,
+        }
+    }
+
+    /// Alchemy subdomain serving this network
+    fn alchemy_host(&self) -> Option<&'static str> {
+        match self {
+            Network::Mainnet => Some("eth-mainnet"),
+            Network::Goerli => Some("eth-goerli"),
+            Network::Sepolia => Some("eth-sepolia"),
+            Network::Holesky => Some("eth-holesky"),
+            Network::Polygon => Some("polygon-mainnet"),
+            _ => None,
+        }
+    }
+
     /// Get network name
     pub fn name(&self) -> &str {
         match self {
@@ -90,6 +202,7 @@ impl Network {
             Network::Holesky => "Holesky Testnet",
             Network::BscMainnet => "BSC Mainnet",
             Network::BscTestnet => "BSC Testnet",
+            Network::Polygon => "Polygon Mainnet",
 
             // Bitcoin Networks
             Network::BitcoinMainnet => "Bitcoin Mainnet",
@@ -104,12 +217,153 @@ impl Network {
         }
     }
 
-    /// Check if this is a testnet
+    /// Every built-in network (all variants except `Custom`)
+    pub fn built_in() -> [Network; 12] {
+        [
+            Network::Mainnet,
+            Network::Goerli,
+            Network::Sepolia,
+            Network::Holesky,
+            Network::BscMainnet,
+            Network::BscTestnet,
+            Network::Polygon,
+            Network::BitcoinMainnet,
+            Network::BitcoinTestnet,
+            Network::SolanaMainnet,
+            Network::SolanaDevnet,
+            Network::SolanaTestnet,
+        ]
+    }
+
+    /// Canonical short name accepted by `from_name` (`None` for custom networks)
+    pub fn short_name(&self) -> Option<&'static str> {
+        let name = match self {
+            Network::Mainnet => "mainnet",
+            Network::Goerli => "goerli",
+            Network::Sepolia => "sepolia",
+            Network::Holesky => "holesky",
+            Network::BscMainnet => "bsc",
+            Network::BscTestnet => "bsc-testnet",
+            Network::Polygon => "polygon",
+            Network::BitcoinMainnet => "btc",
+            Network::BitcoinTestnet => "btc-testnet",
+            Network::SolanaMainnet => "sol",
+            Network::SolanaDevnet => "sol-devnet",
+            Network::SolanaTestnet => "sol-testnet",
+            Network::Custom { .. } => return None,
+        };
+        Some(name)
+    }
+
+    /// Look up a built-in network by its short name (case-insensitive), e.g. "bsc", "sol-devnet"
+    pub fn from_name(name: &str) -> Option<Network> {
+        let network = match name.to_lowercase().as_str() {
+            "mainnet" | "eth" | "ethereum" => Network::Mainnet,
+            "sepolia" => Network::Sepolia,
+            "goerli" => Network::Goerli,
+            "holesky" => Network::Holesky,
+            "bsc" | "bsc-mainnet" => Network::BscMainnet,
+            "bsc-testnet" => Network::BscTestnet,
+            "polygon" | "matic" => Network::Polygon,
+            "bitcoin" | "btc" => Network::BitcoinMainnet,
+            "bitcoin-testnet" | "btc-testnet" => Network::BitcoinTestnet,
+            "solana" | "sol" => Network::SolanaMainnet,
+            "solana-devnet" | "sol-devnet" => Network::SolanaDevnet,
+            "solana-testnet" | "sol-testnet" => Network::SolanaTestnet,
+            _ => return None,
+        };
+        Some(network)
+    }
+
+    /// Custom EVM network from a `wallet_addEthereumChain` (EIP-3085) chain definition
+    ///
+    /// Reads `chainId` (hex, e.g. `"0xa4b1"`), `chainName`, the first of
+    /// `rpcUrls` and, when present, `nativeCurrency.symbol` and the first of
+    /// `blockExplorerUrls`. The definition does not say whether coins carry
+    /// value, so the network is treated as a mainnet, unless the RPC URL is
+    /// on this machine (an Anvil or Hardhat node, possibly forking a mainnet).
+    pub fn from_chain_config_json(json: &str) -> Result<Network, DomainError> {
+        let invalid = |reason: String| DomainError::ConfigurationError(format!("Invalid chain config: {}", reason));
+        let config: ChainConfig = serde_json::from_str(json).map_err(|e| invalid(e.to_string()))?;
+
+        let chain_id = match config.chain_id {
+            ChainConfigId::Hex(hex) => hex
+                .strip_prefix("0x")
+                .and_then(|digits| u64::from_str_radix(digits, 16).ok())
+                .ok_or_else(|| invalid(format!("chainId {} is not a 0x-prefixed hex number", hex)))?,
+            ChainConfigId::Number(chain_id) => chain_id,
+        };
+        if chain_id == 0 {
+            return Err(invalid("chainId must not be zero".to_string()));
+        }
+        if config.chain_name.trim().is_empty() {
+            return Err(invalid("chainName is empty".to_string()));
+        }
+        let rpc_url = config
+            .rpc_urls
+            .into_iter()
+            .find(|url| url.starts_with("https://") || url.starts_with("http://"))
+            .ok_or_else(|| invalid("rpcUrls has no http(s) URL".to_string()))?;
+        let kind = if is_local_rpc_url(&rpc_url) { NetworkKind::Local } else { NetworkKind::Mainnet };
+        if let Some(currency) = &config.native_currency {
+            // EIP-3085 only allows 18, which is what every EVM amount here assumes
+            if currency.decimals != 18 {
+                return Err(invalid(format!(
+                    "native currency has {} decimals, only 18 is supported",
+                    currency.decimals
+                )));
+            }
+        }
+
+        Ok(Network::Custom {
+            name: config.chain_name,
+            chain_id,
+            rpc_url,
+            kind,
+            symbol: config.native_currency.map(|currency| currency.symbol),
+            explorer_url: config
+                .block_explorer_urls
+                .into_iter()
+                .next()
+                .map(|url| url.trim_end_matches('/').to_string()),
+        })
+    }
+
+    /// Block explorer base URL (custom networks that declare one)
+    pub fn explorer_url(&self) -> Option<&str> {
+        match self {
+            Network::Custom { explorer_url, .. } => explorer_url.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Whether this is a production network, a testnet, a devnet or a local node
+    pub fn kind(&self) -> NetworkKind {
+        match self {
+            Network::Mainnet
+            | Network::BscMainnet
+            | Network::Polygon
+            | Network::BitcoinMainnet
+            | Network::SolanaMainnet => NetworkKind::Mainnet,
+            Network::Goerli
+            | Network::Sepolia
+            | Network::Holesky
+            | Network::BscTestnet
+            | Network::BitcoinTestnet
+            | Network::SolanaTestnet => NetworkKind::Testnet,
+            Network::SolanaDevnet => NetworkKind::Devnet,
+            Network::Custom { kind, .. } => *kind,
+        }
+    }
+
+    /// Check if this is a production network whose coins carry real value
+    pub fn is_mainnet(&self) -> bool {
+        self.kind() == NetworkKind::Mainnet
+    }
+
+    /// Check if this is a testnet (any non-mainnet kind: testnet, devnet or local)
     pub fn is_testnet(&self) -> bool {
-        !matches!(
-            self,
-            Network::Mainnet | Network::BscMainnet | Network::BitcoinMainnet | Network::SolanaMainnet
-        )
+        !self.is_mainnet()
     }
 
     /// Check if this is an EVM network
@@ -122,6 +376,7 @@ impl Network {
                 | Network::Holesky
                 | Network::BscMainnet
                 | Network::BscTestnet
+                | Network::Polygon
         )
     }
 
@@ -143,6 +398,61 @@ impl Network {
         )
     }
 
+    /// Get the native currency symbol for this network
+    ///
+    /// Unlike `ChainType::native_currency()`, this distinguishes EVM chains
+    /// whose gas token is not ETH (e.g. BNB on BSC, MATIC on Polygon).
+    pub fn native_symbol(&self) -> &str {
+        match self {
+            Network::BscMainnet => "BNB",
+            Network::BscTestnet => "tBNB",
+            Network::Polygon => "MATIC",
+            Network::Custom { symbol: Some(symbol), .. } => symbol,
+            _ => self.chain_type().native_currency(),
+        }
+    }
+
+    /// Decimals of the native currency: 18 for EVM gas tokens, 8 for BTC, 9 for SOL
+    ///
+    /// Use this rather than `ChainType::decimals()` when formatting native
+    /// balances, so the value stays per network if a gas token ever deviates
+    /// from its chain family.
+    pub fn native_decimals(&self) -> u8 {
+        if self.is_bitcoin() {
+            8
+        } else if self.is_solana() {
+            9
+        } else {
+            18
+        }
+    }
+
+    /// Confirmations to wait for before treating a transaction as settled
+    ///
+    /// Used when the caller does not ask for a specific depth:
+    /// - Ethereum mainnet: 12 blocks (~2.5 min). Post-merge reorgs deeper than
+    ///   a block or two are very rare; full finality takes ~64 blocks, which
+    ///   `Finality::Finalized` reports directly.
+    /// - Ethereum testnets and custom networks: 12 as well, since their
+    ///   consensus (or lack of knowledge about it) gives no reason to trust less.
+    /// - BSC: 15 blocks. Validators rotate in small sets and short reorgs are
+    ///   more common than on Ethereum; 15 is the depth exchanges settle at.
+    /// - Polygon PoS: 128 blocks. The chain has had reorgs dozens of blocks
+    ///   deep, and blocks arrive every ~2 s, so this is still ~4 minutes.
+    /// - Bitcoin: 6 blocks, the long-standing convention for irreversibility.
+    /// - Solana: 1. Reorg safety comes from the `finalized` commitment (about
+    ///   32 slots), not from counting slots on top of a transaction.
+    pub fn recommended_confirmations(&self) -> u64 {
+        match self {
+            Network::Mainnet | Network::Goerli | Network::Sepolia | Network::Holesky => 12,
+            Network::BscMainnet | Network::BscTestnet => 15,
+            Network::Polygon => 128,
+            Network::BitcoinMainnet | Network::BitcoinTestnet => 6,
+            Network::SolanaMainnet | Network::SolanaDevnet | Network::SolanaTestnet => 1,
+            Network::Custom { .. } => 12,
+        }
+    }
+
     /// Get the chain type for this network
     pub fn chain_type(&self) -> ChainType {
         if self.is_bitcoin() {
@@ -166,12 +476,26 @@ impl fmt::Display for Network {
     }
 }
 
+#[allow(clippy::derivable_impls)] // Keep the safety default explicit
 impl Default for Network {
     fn default() -> Self {
         Network::Sepolia // Default to Sepolia testnet for safety
     }
 }
 
+/// Whether an http(s) URL points at localhost or a loopback address
+/// (including bracketed IPv6, `http://[::1]:8545`)
+pub fn is_local_rpc_url(url: &str) -> bool {
+    let authority = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = authority.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = authority.rsplit_once('@').map_or(authority, |(_, host)| host);
+    let host = match host.strip_prefix('[') {
+        Some(bracketed) => bracketed.split(']').next().unwrap_or_default(),
+        None => host.split(':').next().unwrap_or_default(),
+    };
+    host.eq_ignore_ascii_case("localhost") || host.parse::<std::net::IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -182,9 +506,154 @@ mod tests {
         assert_eq!(Network::Sepolia.chain_id(), 11155111);
     }
 
+    #[test]
+    fn test_rpc_url_with_keys() {
+        assert_eq!(
+            Network::Sepolia.rpc_url_with_keys(Some("abc"), Some("xyz")),
+            "https://sepolia.infura.io/v3/abc"
+        );
+        assert_eq!(
+            Network::Holesky.rpc_url_with_keys(None, Some("xyz")),
+            "https://eth-holesky.g.alchemy.com/v2/xyz"
+        );
+        assert_eq!(
+            Network::Polygon.rpc_url_with_keys(None, Some("xyz")),
+            "https://polygon-mainnet.g.alchemy.com/v2/xyz"
+        );
+        // Networks neither provider serves keep their public endpoint
+        assert_eq!(
+            Network::BscMainnet.rpc_url_with_keys(Some("abc"), Some("xyz")),
+            Network::BscMainnet.default_rpc_url()
+        );
+        assert_eq!(Network::Sepolia.rpc_url_with_keys(None, None), "https://sepolia.infura.io/v3/");
+    }
+
+    #[test]
+    fn test_network_from_name() {
+        assert_eq!(Network::from_name("ETH"), Some(Network::Mainnet));
+        assert_eq!(Network::from_name("bsc"), Some(Network::BscMainnet));
+        assert_eq!(Network::from_name("btc-testnet"), Some(Network::BitcoinTestnet));
+        assert_eq!(Network::from_name("sol-devnet"), Some(Network::SolanaDevnet));
+        assert_eq!(Network::from_name("dogecoin"), None);
+    }
+
+    #[test]
+    fn test_network_short_names_round_trip() {
+        for network in Network::built_in() {
+            let short_name = network.short_name().unwrap();
+            assert_eq!(Network::from_name(short_name), Some(network));
+        }
+        let custom = Network::Custom {
+            name: "Local".to_string(),
+            chain_id: 31337,
+            rpc_url: "http://localhost:8545".to_string(),
+            kind: NetworkKind::Local,
+            symbol: None,
+            explorer_url: None,
+        };
+        assert_eq!(custom.short_name(), None);
+    }
+
+    #[test]
+    fn test_network_native_symbol() {
+        assert_eq!(Network::Mainnet.native_symbol(), "ETH");
+        assert_eq!(Network::Sepolia.native_symbol(), "ETH");
+        assert_eq!(Network::BscMainnet.native_symbol(), "BNB");
+        assert_eq!(Network::Polygon.native_symbol(), "MATIC");
+        assert_eq!(Network::BitcoinMainnet.native_symbol(), "BTC");
+        assert_eq!(Network::SolanaDevnet.native_symbol(), "SOL");
+    }
+
+    #[test]
+    fn test_network_recommended_confirmations() {
+        assert_eq!(Network::Mainnet.recommended_confirmations(), 12);
+        assert_eq!(Network::BscMainnet.recommended_confirmations(), 15);
+        assert_eq!(Network::Polygon.recommended_confirmations(), 128);
+        assert_eq!(Network::BitcoinMainnet.recommended_confirmations(), 6);
+        assert_eq!(Network::SolanaMainnet.recommended_confirmations(), 1);
+    }
+
+    #[test]
+    fn test_network_native_decimals() {
+        assert_eq!(Network::Mainnet.native_decimals(), 18);
+        assert_eq!(Network::BscMainnet.native_decimals(), 18);
+        assert_eq!(Network::BitcoinTestnet.native_decimals(), 8);
+        assert_eq!(Network::SolanaMainnet.native_decimals(), 9);
+    }
+
     #[test]
     fn test_network_is_testnet() {
         assert!(!Network::Mainnet.is_testnet());
         assert!(Network::Sepolia.is_testnet());
     }
+
+    #[test]
+    fn test_network_kind() {
+        assert!(Network::Polygon.is_mainnet());
+        assert_eq!(Network::SolanaDevnet.kind(), NetworkKind::Devnet);
+        assert!(Network::SolanaDevnet.is_testnet());
+        for network in Network::built_in() {
+            assert_ne!(network.is_mainnet(), network.is_testnet());
+        }
+
+        let custom = |kind| Network::Custom {
+            name: "Arbitrum One".to_string(),
+            chain_id: 42161,
+            rpc_url: "https://arb1.arbitrum.io/rpc".to_string(),
+            kind,
+            symbol: None,
+            explorer_url: None,
+        };
+        assert!(custom(NetworkKind::Mainnet).is_mainnet());
+        assert!(custom(NetworkKind::Local).is_testnet());
+
+        // Custom networks saved without a kind are treated as mainnets
+        let saved = r#"{"Custom":{"name":"Arbitrum One","chain_id":42161,"rpc_url":"https://arb1.arbitrum.io/rpc"}}"#;
+        assert!(serde_json::from_str::<Network>(saved).unwrap().is_mainnet());
+    }
+
+    #[test]
+    fn test_network_from_chain_config_json() {
+        let json = r#"{
+            "chainId": "0xa4b1",
+            "chainName": "Arbitrum One",
+            "rpcUrls": ["wss://arb1.arbitrum.io/ws", "https://arb1.arbitrum.io/rpc"],
+            "nativeCurrency": {"name": "Ether", "symbol": "ETH", "decimals": 18},
+            "blockExplorerUrls": ["https://arbiscan.io/"]
+        }"#;
+        let network = Network::from_chain_config_json(json).unwrap();
+        assert_eq!(network.chain_id(), 42161);
+        assert_eq!(network.name(), "Arbitrum One");
+        assert_eq!(network.default_rpc_url(), "https://arb1.arbitrum.io/rpc");
+        assert_eq!(network.native_symbol(), "ETH");
+        assert_eq!(network.explorer_url(), Some("https://arbiscan.io"));
+        assert!(network.is_mainnet());
+
+        let gnosis = r#"{"chainId": 100, "chainName": "Gnosis", "rpcUrls": ["https://rpc.gnosischain.com"],
+            "nativeCurrency": {"name": "xDAI", "symbol": "XDAI", "decimals": 18}}"#;
+        let gnosis = Network::from_chain_config_json(gnosis).unwrap();
+        assert_eq!(gnosis.native_symbol(), "XDAI");
+        assert_eq!(gnosis.explorer_url(), None);
+
+        // A local fork keeps its chain ID but is not treated as a mainnet
+        for url in ["http://localhost:8545", "http://127.0.0.1:8545/", "http://[::1]:8545"] {
+            let fork = format!(r#"{{"chainId": "0x1", "chainName": "Anvil fork", "rpcUrls": ["{}"]}}"#, url);
+            let fork = Network::from_chain_config_json(&fork).unwrap();
+            assert_eq!(fork.default_rpc_url(), url);
+            assert_eq!(fork.kind(), NetworkKind::Local);
+        }
+
+        for invalid in [
+            r#"{"chainId": "42161", "chainName": "Arbitrum One", "rpcUrls": ["https://arb1.arbitrum.io/rpc"]}"#,
+            r#"{"chainId": "0xa4b1", "chainName": "Arbitrum One", "rpcUrls": []}"#,
+            r#"{"chainId": "0x1", "chainName": "Odd", "rpcUrls": ["https://rpc.example"],
+                "nativeCurrency": {"name": "Odd", "symbol": "ODD", "decimals": 6}}"#,
+            "not json",
+        ] {
+            assert!(matches!(
+                Network::from_chain_config_json(invalid),
+                Err(DomainError::ConfigurationError(_))
+            ));
+        }
+    }
 }
@@ -0,0 +1,189 @@
+use std::str::FromStr;
+use super::{Address, Amount, ChainType};
+use crate::DomainError;
+
+/// Payment request encoded as a URI (as found in QR codes)
+///
+/// Supported schemes:
+/// - `ethereum:[pay-]<address>[@<chain id>]?value=<wei>` (EIP-681; `value`
+///   may use scientific notation, e.g. `1.5e18`)
+/// - `bitcoin:<address>?amount=<BTC>` (BIP-21)
+/// - `solana:<address>?amount=<SOL>` (Solana Pay transfer requests)
+///
+/// Labels, messages and other informational parameters are ignored. Requests
+/// this wallet cannot honour faithfully (EIP-681 contract calls, Solana Pay
+/// `spl-token`, BIP-21 `req-*` parameters) are rejected rather than paid as a
+/// plain native transfer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaymentUri {
+    /// Chain named by the scheme
+    pub chain: ChainType,
+    /// Recipient, validated for `chain`
+    pub address: Address,
+    /// Requested amount in the chain's smallest unit, if the URI names one
+    pub amount: Option<Amount>,
+    /// EVM chain id from `@<chain id>`, if given
+    pub chain_id: Option<u64>,
+}
+
+impl PaymentUri {
+    /// Parse and validate a payment URI
+    pub fn parse(s: &str) -> Result<Self, DomainError> {
+        let invalid = |reason: &str| DomainError::InvalidQuery(format!("Invalid payment URI '{}': {}", s, reason));
+
+        let (scheme, rest) = s.trim().split_once(':').ok_or_else(|| invalid("missing scheme"))?;
+        let chain = match scheme.to_lowercase().as_str() {
+            "ethereum" => ChainType::Ethereum,
+            "bitcoin" => ChainType::Bitcoin,
+            "solana" => ChainType::Solana,
+            _ => return Err(invalid("unknown scheme (use ethereum:, bitcoin: or solana:)")),
+        };
+        let (target, query) = rest.split_once('?').unwrap_or((rest, ""));
+
+        let mut chain_id = None;
+        let address = if chain == ChainType::Ethereum {
+            let target = target.strip_prefix("pay-").unwrap_or(target);
+            if target.contains('/') {
+                return Err(invalid("contract calls are not supported"));
+            }
+            let (address, id) = target.split_once('@').unwrap_or((target, ""));
+            if !id.is_empty() {
+                chain_id = Some(id.parse::<u64>().map_err(|_| invalid("invalid chain id"))?);
+            }
+            address
+        } else {
+            target
+        };
+        let address = Address::new(address.to_string())?;
+        if address.chain_type() != chain {
+            return Err(DomainError::ChainMismatch {
+                expected: chain,
+                detected: address.chain_type(),
+            });
+        }
+
+        let mut amount = None;
+        for param in query.split('&').filter(|param| !param.is_empty()) {
+            let (key, value) = param.split_once('=').unwrap_or((param, ""));
+            let parsed = match (chain, key) {
+                (ChainType::Ethereum, "value") => parse_scaled(value, 0),
+                (ChainType::Bitcoin, "amount") => parse_scaled(value, 8),
+                (ChainType::Solana, "amount") => parse_scaled(value, 9),
+                (ChainType::Solana, "spl-token") => return Err(invalid("token transfer requests are not supported")),
+                (ChainType::Bitcoin, key) if key.starts_with("req-") => {
+                    return Err(invalid(&format!("unsupported required parameter '{}'", key)))
+                }
+                _ => continue,
+            };
+            if amount.is_some() {
+                return Err(invalid("amount given more than once"));
+            }
+            amount = Some(Amount::from_wei(parsed.ok_or(DomainError::InvalidAmount)?));
+        }
+
+        Ok(Self {
+            chain,
+            address,
+            amount,
+            chain_id,
+        })
+    }
+}
+
+impl FromStr for PaymentUri {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// `number * 10^scale` as an integer, where `number` is a plain decimal
+/// (`0.01`) or in scientific notation (`1.5e18`); `None` if it is malformed,
+/// has a fractional remainder or overflows
+fn parse_scaled(number: &str, scale: u32) -> Option<u128> {
+    let (mantissa, exponent) = match number.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, exponent.parse::<u32>().ok()?),
+        None => (number, 0),
+    };
+    let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    if (whole.is_empty() && fraction.is_empty())
+        || !whole.bytes().chain(fraction.bytes()).all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let shift = exponent.checked_add(scale)?;
+    let fraction = fraction.trim_end_matches('0');
+    let zeros = shift.checked_sub(fraction.len() as u32)?;
+    let digits: u128 = format!("{}{}", whole, fraction).parse().ok()?;
+    digits.checked_mul(10u128.checked_pow(zeros)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ETH_ADDRESS: &str = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+    const BTC_ADDRESS: &str = "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa";
+    const SOL_ADDRESS: &str = "DRpbCBMxVnDK7maPM5tGv6MvB3v1sRMC86PZ8okm21hy";
+
+    #[test]
+    fn test_parse_payment_uris() {
+        let uri = PaymentUri::parse(&format!("ethereum:{}?value=1.5e18", ETH_ADDRESS)).unwrap();
+        assert_eq!(uri.chain, ChainType::Ethereum);
+        assert_eq!(uri.address.as_str(), ETH_ADDRESS);
+        assert_eq!(uri.amount, Some(Amount::from_wei(1_500_000_000_000_000_000)));
+        assert_eq!(uri.chain_id, None);
+
+        let uri = PaymentUri::parse(&format!("ethereum:pay-{}@11155111", ETH_ADDRESS)).unwrap();
+        assert_eq!(uri.chain_id, Some(11155111));
+        assert_eq!(uri.amount, None);
+
+        let uri = PaymentUri::parse(&format!("bitcoin:{}?amount=0.01&label=Coffee", BTC_ADDRESS)).unwrap();
+        assert_eq!(uri.chain, ChainType::Bitcoin);
+        assert_eq!(uri.amount, Some(Amount::from_wei(1_000_000)));
+
+        let uri: PaymentUri = format!("solana:{}?amount=0.5", SOL_ADDRESS).parse().unwrap();
+        assert_eq!(uri.chain, ChainType::Solana);
+        assert_eq!(uri.amount, Some(Amount::from_wei(500_000_000)));
+    }
+
+    #[test]
+    fn test_parse_payment_uri_rejects_bad_input() {
+        let err = |uri: String| PaymentUri::parse(&uri).unwrap_err();
+
+        assert!(matches!(err(format!("litecoin:{}", BTC_ADDRESS)), DomainError::InvalidQuery(_)));
+        assert!(matches!(
+            err(format!("bitcoin:{}", ETH_ADDRESS)),
+            DomainError::ChainMismatch { expected: ChainType::Bitcoin, detected: ChainType::Ethereum }
+        ));
+        assert!(matches!(
+            err("ethereum:0x1234".to_string()),
+            DomainError::InvalidAddress { chain: ChainType::Ethereum, .. }
+        ));
+        assert!(matches!(
+            err(format!("ethereum:{}/transfer?address={}", ETH_ADDRESS, ETH_ADDRESS)),
+            DomainError::InvalidQuery(_)
+        ));
+        assert!(matches!(err(format!("ethereum:{}?value=1.5", ETH_ADDRESS)), DomainError::InvalidAmount));
+        assert!(matches!(err(format!("bitcoin:{}?amount=0.000000001", BTC_ADDRESS)), DomainError::InvalidAmount));
+        assert!(matches!(
+            err(format!("bitcoin:{}?amount=1&amount=2", BTC_ADDRESS)),
+            DomainError::InvalidQuery(_)
+        ));
+        assert!(matches!(err(format!("bitcoin:{}?req-foo=1", BTC_ADDRESS)), DomainError::InvalidQuery(_)));
+    }
+
+    #[test]
+    fn test_parse_scaled() {
+        assert_eq!(parse_scaled("1e18", 0), Some(1_000_000_000_000_000_000));
+        assert_eq!(parse_scaled("2.014e18", 0), Some(2_014_000_000_000_000_000));
+        assert_eq!(parse_scaled("1000", 0), Some(1000));
+        assert_eq!(parse_scaled("0.10", 1), Some(1));
+        assert_eq!(parse_scaled(".5", 9), Some(500_000_000));
+        assert_eq!(parse_scaled("1e40", 0), None);
+        assert_eq!(parse_scaled("-1", 0), None);
+        assert_eq!(parse_scaled("", 0), None);
+    }
+}
@@ -0,0 +1,82 @@
+use serde::{Deserialize, Deserializer};
+use std::fmt;
+use zeroize::Zeroize;
+
+/// Private key material that is wiped from memory on drop
+///
+/// `Debug` and `Display` print `[REDACTED]`, so logging a command or error that
+/// carries the key never leaks it. Use `expose_secret()` only at the point of
+/// signing. Deserializable (e.g. from a config file) but deliberately not
+/// serializable.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SecretKey(String);
+
+impl SecretKey {
+    /// Wrap a private key string (hex, WIF, Base58 or JSON array, depending on the chain)
+    pub fn new(key: String) -> Self {
+        Self(key)
+    }
+
+    /// Access the raw key for signing or address derivation
+    pub fn expose_secret(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SecretKey([REDACTED])")
+    }
+}
+
+impl fmt::Display for SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[REDACTED]")
+    }
+}
+
+impl From<String> for SecretKey {
+    fn from(key: String) -> Self {
+        Self::new(key)
+    }
+}
+
+impl From<&str> for SecretKey {
+    fn from(key: &str) -> Self {
+        Self::new(key.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "0x0000000000000000000000000000000000000000000000000000000000000001";
+
+    #[test]
+    fn test_debug_and_display_are_redacted() {
+        let key = SecretKey::from(KEY);
+        assert_eq!(format!("{:?}", key), "SecretKey([REDACTED])");
+        assert_eq!(key.to_string(), "[REDACTED]");
+        assert_eq!(key.expose_secret(), KEY);
+    }
+
+    #[test]
+    fn test_zeroize_clears_key() {
+        let mut key = SecretKey::from(KEY);
+        key.0.zeroize();
+        assert!(key.expose_secret().is_empty());
+    }
+}
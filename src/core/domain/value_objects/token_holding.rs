@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+use super::Address;
+
+/// Balance of one token account (e.g. an SPL token account on Solana)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenHolding {
+    /// Token the account holds (SPL mint address)
+    pub mint: Address,
+    /// Amount in the token's smallest unit
+    pub amount: u128,
+    /// Decimals of the mint, to display `amount`
+    pub decimals: u8,
+}
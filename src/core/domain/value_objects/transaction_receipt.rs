@@ -0,0 +1,174 @@
+use serde::{Deserialize, Serialize};
+use super::{Address, Amount, TransactionHash};
+
+/// `keccak256("Transfer(address,address,uint256)")` - topic0 of ERC-20 `Transfer` events
+pub const ERC20_TRANSFER_TOPIC: &str =
+    "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+
+/// Receipt of a mined transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionReceipt {
+    /// Hash of the transaction this receipt belongs to
+    pub tx_hash: TransactionHash,
+    /// Block the transaction was included in
+    pub block_number: Option<u64>,
+    /// Whether execution succeeded (false = reverted)
+    pub success: bool,
+    /// Gas used by this transaction alone
+    pub gas_used: u128,
+    /// Price actually paid per gas (base fee + priority fee), in Wei
+    pub effective_gas_price: u128,
+    /// Event logs emitted during execution
+    pub logs: Vec<Log>,
+}
+
+/// Event log emitted by a contract
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Log {
+    /// Contract that emitted the event
+    pub address: Address,
+    /// Indexed topics as 0x-prefixed 32-byte hex strings (topic0 = event signature)
+    pub topics: Vec<String>,
+    /// Non-indexed event data
+    pub data: Vec<u8>,
+}
+
+/// Decoded ERC-20 `Transfer(from, to, value)` event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Erc20Transfer {
+    /// Token contract that emitted the event
+    pub token: Address,
+    pub from: Address,
+    pub to: Address,
+    /// Transferred amount in the token's smallest unit
+    pub amount: u128,
+}
+
+impl TransactionReceipt {
+    /// Total fee paid: gas used × effective gas price (`None` on overflow)
+    pub fn fee_paid(&self) -> Option<Amount> {
+        self.gas_used
+            .checked_mul(self.effective_gas_price)
+            .map(Amount::from_wei)
+    }
+
+    /// All ERC-20 `Transfer` events in this receipt, in log order
+    pub fn erc20_transfers(&self) -> Vec<Erc20Transfer> {
+        self.logs.iter().filter_map(Log::as_erc20_transfer).collect()
+    }
+
+    /// ERC-20 `Transfer` events emitted by a specific token contract
+    pub fn erc20_transfers_for_token(&self, token: &Address) -> Vec<Erc20Transfer> {
+        self.erc20_transfers()
+            .into_iter()
+            .filter(|t| &t.token == token)
+            .collect()
+    }
+}
+
+impl Log {
+    /// Decode this log as an ERC-20 `Transfer` event
+    ///
+    /// Returns `None` for other events, ERC-721 transfers (tokenId is indexed,
+    /// so there are 4 topics and no data) and amounts that do not fit in `u128`.
+    pub fn as_erc20_transfer(&self) -> Option<Erc20Transfer> {
+        if self.topics.len() != 3 || !self.topics[0].eq_ignore_ascii_case(ERC20_TRANSFER_TOPIC) {
+            return None;
+        }
+        if self.data.len() != 32 || self.data[..16].iter().any(|b| *b != 0) {
+            return None;
+        }
+
+        let mut amount = [0u8; 16];
+        amount.copy_from_slice(&self.data[16..]);
+
+        Some(Erc20Transfer {
+            token: self.address.clone(),
+            from: topic_to_address(&self.topics[1])?,
+            to: topic_to_address(&self.topics[2])?,
+            amount: u128::from_be_bytes(amount),
+        })
+    }
+}
+
+/// Extract the address from a left-padded 32-byte topic
+fn topic_to_address(topic: &str) -> Option<Address> {
+    let hex = topic.strip_prefix("0x")?;
+    if hex.len() != 64 || !hex[..24].bytes().all(|b| b == b'0') {
+        return None;
+    }
+    Address::new(format!("0x{}", &hex[24..])).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+    const FROM: &str = "0x742d35cc6634c0532925a3b844bc9e7595f0beb0";
+    const TO: &str = "0x7e5f4552091a69125d5dfcb7b8c2659029395bdf";
+
+    fn topic(addr: &str) -> String {
+        format!("0x{:0>64}", &addr[2..])
+    }
+
+    fn transfer_log(amount: u128) -> Log {
+        let mut data = vec![0u8; 16];
+        data.extend_from_slice(&amount.to_be_bytes());
+        Log {
+            address: Address::new(TOKEN.to_string()).unwrap(),
+            topics: vec![ERC20_TRANSFER_TOPIC.to_string(), topic(FROM), topic(TO)],
+            data,
+        }
+    }
+
+    fn receipt(logs: Vec<Log>) -> TransactionReceipt {
+        TransactionReceipt {
+            tx_hash: TransactionHash::new_unchecked(format!("0x{}", "ab".repeat(32))),
+            block_number: Some(1),
+            success: true,
+            gas_used: 51_000,
+            effective_gas_price: 2_000_000_000,
+            logs,
+        }
+    }
+
+    #[test]
+    fn test_decode_erc20_transfer() {
+        let transfer = transfer_log(1_500_000).as_erc20_transfer().unwrap();
+        assert_eq!(transfer.token.as_str(), TOKEN);
+        assert_eq!(transfer.from, Address::new(FROM.to_string()).unwrap());
+        assert_eq!(transfer.to, Address::new(TO.to_string()).unwrap());
+        assert_eq!(transfer.amount, 1_500_000);
+    }
+
+    #[test]
+    fn test_filter_skips_other_events() {
+        let mut approval = transfer_log(1);
+        approval.topics[0] = format!("0x{}", "8c".repeat(32));
+        let mut erc721 = transfer_log(1);
+        erc721.topics.push(topic(TO));
+        erc721.data.clear();
+
+        let receipt = receipt(vec![approval, transfer_log(42), erc721]);
+        let transfers = receipt.erc20_transfers();
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].amount, 42);
+
+        let other_token = Address::new(FROM.to_string()).unwrap();
+        assert!(receipt.erc20_transfers_for_token(&other_token).is_empty());
+    }
+
+    #[test]
+    fn test_fee_paid() {
+        let receipt = receipt(vec![]);
+        assert_eq!(receipt.fee_paid(), Some(Amount::from_wei(102_000_000_000_000)));
+    }
+
+    #[test]
+    fn test_amount_exceeding_u128_is_skipped() {
+        let mut log = transfer_log(0);
+        log.data[0] = 1;
+        assert!(log.as_erc20_transfer().is_none());
+    }
+}
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+use super::{Address, Amount, Network, TransactionHash};
+use crate::core::domain::errors::{redact_payloads, DomainError};
+
+/// Durable record of one transfer attempt, written to an `AuditSink`
+///
+/// Holds only what is needed to reconstruct who sent what where; the
+/// signing key is never part of it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferAuditRecord {
+    /// Unix time (seconds) the attempt finished
+    pub timestamp: u64,
+    pub from: Address,
+    pub to: Address,
+    /// In the smallest unit of the native currency, or of `token` when set;
+    /// `None` for a sweep that failed before its amount was known
+    pub amount: Option<Amount>,
+    /// Token contract or mint, for token transfers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<Address>,
+    pub network: Network,
+    pub outcome: TransferOutcome,
+}
+
+/// Whether an audited transfer was broadcast
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum TransferOutcome {
+    /// Accepted by the node under this hash
+    Sent { tx_hash: TransactionHash },
+    /// Rejected before or during submission
+    Failed { error: String },
+}
+
+impl TransferOutcome {
+    /// Outcome of a send that returned `result`, with payloads redacted from errors
+    pub fn from_result(result: Result<&TransactionHash, &DomainError>) -> Self {
+        match result {
+            Ok(tx_hash) => TransferOutcome::Sent { tx_hash: tx_hash.clone() },
+            Err(e) => TransferOutcome::Failed { error: redact_payloads(&e.to_string()) },
+        }
+    }
+}
+
+impl TransferAuditRecord {
+    pub fn new(
+        timestamp: u64,
+        from: Address,
+        to: Address,
+        amount: Option<Amount>,
+        network: Network,
+        outcome: TransferOutcome,
+    ) -> Self {
+        Self {
+            timestamp,
+            from,
+            to,
+            amount,
+            token: None,
+            network,
+            outcome,
+        }
+    }
+
+    /// Mark the record as a transfer of `token` rather than the native currency
+    pub fn with_token(mut self, token: Address) -> Self {
+        self.token = Some(token);
+        self
+    }
+}
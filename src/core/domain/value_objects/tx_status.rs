@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Where a sent transaction stands on its chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TxStatus {
+    /// The node does not know the transaction (not propagated yet, or dropped)
+    NotFound,
+    /// Known to the node but not yet included in a block
+    Pending,
+    /// Included in `block` (a slot on Solana) and executed successfully
+    Confirmed { block: Option<u64> },
+    /// Included in `block`, but execution failed
+    Reverted { block: Option<u64> },
+}
+
+impl TxStatus {
+    /// Whether the status is final: the transaction was included, successfully or not
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, TxStatus::Confirmed { .. } | TxStatus::Reverted { .. })
+    }
+}
+
+impl fmt::Display for TxStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (label, block) = match self {
+            TxStatus::NotFound => return write!(f, "not found"),
+            TxStatus::Pending => return write!(f, "pending"),
+            TxStatus::Confirmed { block } => ("confirmed", block),
+            TxStatus::Reverted { block } => ("reverted", block),
+        };
+        match block {
+            Some(block) => write!(f, "{} in block #{}", label, block),
+            None => write!(f, "{}", label),
+        }
+    }
+}
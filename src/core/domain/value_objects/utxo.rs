@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+/// Unspent Bitcoin transaction output
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Utxo {
+    /// Id of the transaction that created the output, as shown by block explorers
+    pub txid: String,
+    /// Index of the output within that transaction
+    pub vout: u32,
+    /// Value in satoshis
+    pub value_sats: u64,
+    /// Confirmations (0 while in the mempool)
+    pub confirmations: u64,
+}
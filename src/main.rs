@@ -1,5 +1,5 @@
 use clap::Parser;
-use rustwallet::adapter::interfaces::Cli;
+use rustwallet::adapter::interfaces::{error_report, exit_code, Cli};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[tokio::main]
@@ -15,9 +15,14 @@ async fn main() -> anyhow::Result<()> {
 
     // Parse CLI arguments
     let cli = Cli::parse();
+    let verbose = cli.verbose;
 
-    // Run CLI
-    cli.run().await?;
+    // Run CLI; errors are printed sanitized unless --verbose, and the exit
+    // code tells scripts what kind of failure it was
+    if let Err(error) = cli.run().await {
+        eprintln!("Error: {}", error_report(&error, verbose));
+        std::process::exit(exit_code(&error));
+    }
 
     Ok(())
 }
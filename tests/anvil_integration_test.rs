@@ -0,0 +1,106 @@
+//! Integration tests against a local Anvil node
+//!
+//! Balances are seeded with `anvil_setBalance`, so the results do not depend
+//! on faucets or on the state of a public network. The tests need a node at
+//! `http://localhost:8545`, so they are marked `#[ignore]` (CI starts one in
+//! its `anvil` job); start it (optionally forking a chain) and run:
+//!
+//! ```bash
+//! anvil &
+//! cargo test --features dev --test anvil_integration_test -- --ignored
+//! ```
+#![cfg(feature = "dev")]
+
+use rustwallet::adapter::infrastructure::blockchain::AlloyBlockchainService;
+use rustwallet::core::domain::{
+    errors::DomainError,
+    services::BlockchainService,
+    value_objects::{Address, Network, NetworkKind, SecretKey},
+};
+use std::time::Duration;
+
+/// Anvil's first default account and its well-known private key
+const DEV_ADDRESS: &str = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+const DEV_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+/// Anvil's second default account
+const RECIPIENT: &str = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8";
+
+const ONE_ETH: u128 = 1_000_000_000_000_000_000;
+
+/// The local node as a custom network, loaded the way `--network-file` loads it
+fn anvil_network() -> Network {
+    Network::from_chain_config_json(
+        r#"{"chainId": "0x7a69", "chainName": "Anvil", "rpcUrls": ["http://localhost:8545"]}"#,
+    )
+    .expect("Valid chain definition")
+}
+
+async fn anvil_service() -> AlloyBlockchainService {
+    AlloyBlockchainService::new_with_default_rpc(anvil_network())
+        .await
+        .expect("Failed to create service")
+}
+
+#[tokio::test]
+#[ignore] // Requires a local Anvil node
+async fn test_anvil_seeded_balance_is_read_back() {
+    let network = anvil_network();
+    assert_eq!(network.kind(), NetworkKind::Local);
+
+    let service = anvil_service().await;
+    let address = Address::new(RECIPIENT.to_string()).unwrap();
+    service.set_balance(&address, 42 * ONE_ETH).await.expect("anvil_setBalance failed");
+
+    assert_eq!(service.get_balance(&address).await.unwrap().to_wei(), 42 * ONE_ETH);
+}
+
+#[tokio::test]
+#[ignore] // Requires a local Anvil node
+async fn test_anvil_transfer_between_seeded_accounts() {
+    let service = anvil_service().await;
+    let from = Address::new(DEV_ADDRESS.to_string()).unwrap();
+    let to = Address::new(RECIPIENT.to_string()).unwrap();
+    service.set_balance(&from, 10 * ONE_ETH).await.unwrap();
+    service.set_balance(&to, 0).await.unwrap();
+
+    let signer = service.signer_for_key(&SecretKey::new(DEV_KEY.to_string())).unwrap();
+    let tx_hash = service
+        .transfer(&from, &to, ONE_ETH, signer.as_ref())
+        .await
+        .expect("Transfer failed");
+    let receipt = service
+        .wait_for_receipt(&tx_hash, Duration::from_secs(30))
+        .await
+        .expect("No receipt");
+
+    assert!(receipt.success);
+    assert_eq!(service.get_balance(&to).await.unwrap().to_wei(), ONE_ETH);
+    assert!(service.get_balance(&from).await.unwrap().to_wei() < 9 * ONE_ETH);
+}
+
+#[tokio::test]
+#[ignore] // Requires a local Anvil node
+async fn test_anvil_transfer_beyond_balance_is_rejected() {
+    let service = anvil_service().await;
+    let from = Address::new(DEV_ADDRESS.to_string()).unwrap();
+    let to = Address::new(RECIPIENT.to_string()).unwrap();
+    service.set_balance(&from, ONE_ETH).await.unwrap();
+
+    let signer = service.signer_for_key(&SecretKey::new(DEV_KEY.to_string())).unwrap();
+    let result = service.transfer(&from, &to, 1_000 * ONE_ETH, signer.as_ref()).await;
+
+    assert!(result.is_err(), "Transfer beyond the balance was accepted");
+    assert_eq!(service.get_balance(&from).await.unwrap().to_wei(), ONE_ETH);
+}
+
+#[tokio::test]
+#[ignore] // Requires a local Anvil node
+async fn test_anvil_transfer_rejects_key_of_another_account() {
+    let service = anvil_service().await;
+    let to = Address::new(RECIPIENT.to_string()).unwrap();
+
+    let signer = service.signer_for_key(&SecretKey::new(DEV_KEY.to_string())).unwrap();
+    let result = service.transfer(&to, &to, ONE_ETH, signer.as_ref()).await;
+
+    assert!(matches!(result, Err(DomainError::SignerMismatch { .. })));
+}
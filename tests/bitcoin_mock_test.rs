@@ -0,0 +1,362 @@
+//! Deterministic Bitcoin tests against a mocked blockchain.info API
+//!
+//! A local `wiremock` server returns canned blockchain.info responses,
+//! exercising balance lookups, UTXO paging, PSBT building and error paths
+//! without network access.
+//!
+//! Run with: cargo test --test bitcoin_mock_test
+
+mod common;
+
+use rustwallet::adapter::infrastructure::blockchain::BitcoinBlockchainService;
+use rustwallet::core::application::GetBalanceHandler;
+use rustwallet::core::domain::{
+    errors::DomainError,
+    queries::GetBalanceQuery,
+    services::{BlockchainService, QueryHandler},
+    value_objects::{Address, Balance, FeeDetails, FeeEstimate, Network, SecretKey},
+};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+use common::{query_balance, query_balances, BTC_ADDRESS, TEST_KEY};
+
+#[tokio::test]
+async fn test_bitcoin_balance_via_handler() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/balance"))
+        .and(query_param("active", BTC_ADDRESS))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            BTC_ADDRESS: { "final_balance": 5_000_000_000u64, "n_tx": 3, "total_received": 5_000_000_000u64 }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let service = BitcoinBlockchainService::new_with_api_urls(
+        Network::BitcoinMainnet,
+        &server.uri(),
+        &format!("{}/fees", server.uri()),
+    )
+    .await
+    .expect("Failed to create service");
+    let handler = GetBalanceHandler::new(Arc::new(service));
+
+    let query = GetBalanceQuery::new(Address::new(BTC_ADDRESS.to_string()).unwrap(), Network::BitcoinMainnet);
+    let result = handler.handle(query).await.expect("Balance query failed");
+
+    assert_eq!(result.balance.to_wei(), 5_000_000_000);
+}
+
+#[tokio::test]
+async fn test_bitcoin_http_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/balance"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+
+    let service = BitcoinBlockchainService::new_with_api_urls(
+        Network::BitcoinMainnet,
+        &server.uri(),
+        &format!("{}/fees", server.uri()),
+    )
+    .await
+    .expect("Failed to create service");
+    let handler = GetBalanceHandler::new(Arc::new(service));
+
+    let query = GetBalanceQuery::new(Address::new(BTC_ADDRESS.to_string()).unwrap(), Network::BitcoinMainnet);
+    assert!(matches!(handler.handle(query).await, Err(DomainError::NetworkError(_))));
+}
+
+#[tokio::test]
+async fn test_bitcoin_rejects_oversized_response() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/balance"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            BTC_ADDRESS: { "final_balance": 1u64, "n_tx": 1, "note": "x".repeat(4096) }
+        })))
+        .mount(&server)
+        .await;
+
+    let service = Arc::new(bitcoin_service(&server).await.with_max_response_size(1024));
+    let balance = query_balance(service, BTC_ADDRESS, Network::BitcoinMainnet).await;
+    assert!(matches!(balance, Err(DomainError::InvalidResponse(_))));
+}
+
+async fn bitcoin_service(server: &MockServer) -> BitcoinBlockchainService {
+    BitcoinBlockchainService::new_with_api_urls(
+        Network::BitcoinMainnet,
+        &server.uri(),
+        &format!("{}/fees", server.uri()),
+    )
+    .await
+    .expect("Failed to create service")
+}
+
+#[tokio::test]
+async fn test_bitcoin_unused_address_has_zero_balance() {
+    const UNUSED: &str = "bc1qnkyhslv83yyp0q0suxw0uj3lg9drgqq9c0auzc";
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/balance"))
+        .and(query_param("active", UNUSED))
+        .respond_with(ResponseTemplate::new(200).set_body_string(include_str!(
+            "fixtures/blockchain_info/balance_unused_address.json"
+        )))
+        .mount(&server)
+        .await;
+    // A recognised empty-address response must not need the fallback
+    Mock::given(method("GET"))
+        .and(path(format!("/rawaddr/{}", UNUSED)))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let service = Arc::new(bitcoin_service(&server).await);
+    let balance = query_balance(service, UNUSED, Network::BitcoinMainnet).await.unwrap();
+    assert_eq!(balance, Balance::zero());
+}
+
+#[tokio::test]
+async fn test_bitcoin_balance_falls_back_to_rawaddr() {
+    const SEGWIT: &str = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+    let server = MockServer::start().await;
+    // Keyed by the upper-cased address, so the lookup by our key misses
+    Mock::given(method("GET"))
+        .and(path("/balance"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(include_str!(
+            "fixtures/blockchain_info/balance_unexpected_shape.json"
+        )))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/rawaddr/{}", SEGWIT)))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(include_str!("fixtures/blockchain_info/rawaddr.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let service = Arc::new(bitcoin_service(&server).await);
+    let balance = query_balance(service, SEGWIT, Network::BitcoinMainnet).await.unwrap();
+    assert_eq!(balance.to_wei(), 12345);
+}
+
+#[tokio::test]
+async fn test_bitcoin_get_balances_uses_multi_address_lookup() {
+    const SEGWIT: &str = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/balance"))
+        .and(query_param("active", format!("{}|{}", BTC_ADDRESS, SEGWIT)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            BTC_ADDRESS: { "final_balance": 5_000_000_000u64, "n_tx": 3 },
+            SEGWIT: { "final_balance": 1_000u64, "n_tx": 1 },
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let service = Arc::new(bitcoin_service(&server).await);
+    let balances = query_balances(service, &[BTC_ADDRESS, SEGWIT], Network::BitcoinMainnet).await;
+
+    assert_eq!(balances[0].as_ref().unwrap().to_wei(), 5_000_000_000);
+    assert_eq!(balances[1].as_ref().unwrap().to_wei(), 1_000);
+}
+
+#[tokio::test]
+async fn test_bitcoin_detailed_balance_pages_through_utxos() {
+    let server = MockServer::start().await;
+    // A full first page (1000 confirmed outputs of 100 sats) forces a second request
+    let full_page: Vec<Value> = (0..1000)
+        .map(|n| json!({ "tx_hash_big_endian": format!("{:064x}", n), "tx_output_n": 0, "value": 100, "confirmations": 6 }))
+        .collect();
+    Mock::given(method("GET"))
+        .and(path("/unspent"))
+        .and(query_param("active", BTC_ADDRESS))
+        .and(query_param("offset", "0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "unspent_outputs": full_page })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/unspent"))
+        .and(query_param("offset", "1000"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "unspent_outputs": [
+            { "tx_hash_big_endian": "aa", "tx_output_n": 1, "value": 7000, "confirmations": 1 },
+            { "tx_hash_big_endian": "bb", "tx_output_n": 0, "value": 500, "confirmations": 0 },
+        ]})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let service = bitcoin_service(&server).await.with_min_confirmations(2);
+    let balance = service
+        .get_balance_detailed(&Address::new(BTC_ADDRESS.to_string()).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(balance.confirmed_sats, 100_000);
+    assert_eq!(balance.unconfirmed_sats, 7_500);
+    assert_eq!(balance.utxo_count, 1002);
+}
+
+#[tokio::test]
+async fn test_bitcoin_detailed_balance_without_utxos() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/unspent"))
+        .respond_with(ResponseTemplate::new(500).set_body_string("No free outputs to spend"))
+        .mount(&server)
+        .await;
+
+    let service = bitcoin_service(&server).await;
+    let balance = service
+        .get_balance_detailed(&Address::new(BTC_ADDRESS.to_string()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(balance.total_sats(), 0);
+    assert_eq!(balance.utxo_count, 0);
+}
+
+#[tokio::test]
+async fn test_bitcoin_max_sendable_spends_confirmed_utxos() {
+    const FROM: &str = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/unspent"))
+        .and(query_param("active", FROM))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "unspent_outputs": [
+            { "tx_hash_big_endian": "11".repeat(32), "tx_output_n": 0, "value": 40_000, "confirmations": 3 },
+            { "tx_hash_big_endian": "22".repeat(32), "tx_output_n": 1, "value": 60_000, "confirmations": 1 },
+            { "tx_hash_big_endian": "33".repeat(32), "tx_output_n": 0, "value": 5_000, "confirmations": 0 },
+        ]})))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/fees"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "halfHourFee": 10 })))
+        .mount(&server)
+        .await;
+
+    let service = bitcoin_service(&server).await;
+    let from = Address::new(FROM.to_string()).unwrap();
+    let to = Address::new(BTC_ADDRESS.to_string()).unwrap();
+    let max = service.max_sendable(&from, &to).await.unwrap();
+
+    // Two P2WPKH inputs (68 vB each) into one P2PKH output (34 vB) plus 11 vB overhead
+    assert_eq!(max.balance.to_wei(), 100_000);
+    assert_eq!(max.fee.total_native.to_wei(), 181 * 10);
+    assert_eq!(max.amount.to_wei(), 100_000 - 1_810);
+
+    // Only the three-confirmation output counts once the threshold rises
+    let strict = bitcoin_service(&server).await.with_min_confirmations(3);
+    let max = strict.max_sendable(&from, &to).await.unwrap();
+    assert_eq!(max.amount.to_wei(), 40_000 - 113 * 10);
+
+    // Nothing confirmed enough to cover the fee plus the dust limit
+    let stricter = bitcoin_service(&server).await.with_min_confirmations(4);
+    assert!(matches!(
+        stricter.max_sendable(&from, &to).await,
+        Err(DomainError::InsufficientBalance { available: 0, .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_bitcoin_psbt_create_sign_and_broadcast() {
+    // Native SegWit address of private key 1
+    const FROM: &str = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/unspent"))
+        .and(query_param("active", FROM))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "unspent_outputs": [
+            { "tx_hash_big_endian": "11".repeat(32), "tx_output_n": 0, "value": 40_000, "confirmations": 3 },
+            { "tx_hash_big_endian": "22".repeat(32), "tx_output_n": 1, "value": 90_000, "confirmations": 0 },
+        ]})))
+        .mount(&server)
+        .await;
+    let pushed = Arc::new(Mutex::new(None));
+    let sent = pushed.clone();
+    Mock::given(method("POST"))
+        .and(path("/pushtx"))
+        .respond_with(move |request: &Request| {
+            *sent.lock().unwrap() = Some(String::from_utf8(request.body.clone()).unwrap());
+            ResponseTemplate::new(200).set_body_string("Transaction Submitted")
+        })
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let service = bitcoin_service(&server).await;
+    let from = Address::new(FROM.to_string()).unwrap();
+    let to = Address::new(BTC_ADDRESS.to_string()).unwrap();
+
+    // Only the confirmed output is spendable
+    assert!(matches!(
+        service.create_psbt(&from, &to, 50_000, 5).await,
+        Err(DomainError::InsufficientBalance { available: 40_000, .. })
+    ));
+    let psbt = service.create_psbt(&from, &to, 30_000, 5).await.unwrap();
+
+    // Unsigned, or signed by a key owning none of the inputs, it cannot be sent
+    assert!(matches!(service.finalize_and_broadcast(&psbt).await, Err(DomainError::TransferFailed(_))));
+    let other_key = SecretKey::from("0000000000000000000000000000000000000000000000000000000000000002");
+    assert!(matches!(service.sign_psbt(&psbt, &other_key), Err(DomainError::TransferFailed(_))));
+
+    let key = SecretKey::from(TEST_KEY);
+    let signed = service.sign_psbt(&psbt, &key).unwrap();
+    let txid = service.finalize_and_broadcast(&signed).await.unwrap();
+    assert_eq!(txid.as_str().len(), 64);
+
+    let body = pushed.lock().unwrap().clone().unwrap();
+    let raw = body.strip_prefix("tx=").unwrap();
+    // Version 2, SegWit marker, one input spending the confirmed UTXO
+    assert!(raw.starts_with(&format!("020000000001{:02x}{}", 1, "11".repeat(32))));
+}
+
+#[tokio::test]
+async fn test_bitcoin_block_height_and_fee_estimate() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/latestblock"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "height": 870000, "hash": "00" })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/fees"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "fastestFee": 20, "halfHourFee": 12, "hourFee": 8, "economyFee": 4, "minimumFee": 1
+        })))
+        .mount(&server)
+        .await;
+
+    let service = BitcoinBlockchainService::new_with_api_urls(
+        Network::BitcoinMainnet,
+        &server.uri(),
+        &format!("{}/fees", server.uri()),
+    )
+    .await
+    .expect("Failed to create service");
+
+    assert_eq!(service.get_block_number().await.unwrap(), 870000);
+
+    let from = Address::new("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4".to_string()).unwrap();
+    let to = Address::new(BTC_ADDRESS.to_string()).unwrap();
+    let fee = service.estimate_fee(&from, &to, 10_000).await.unwrap();
+    assert_eq!(fee.total_native.to_wei(), 141 * 12);
+
+    // The half-hour rate confirms in about half an hour; 20 sat/vB makes the next block
+    assert_eq!(service.estimate_confirmation_time(&fee).await.unwrap(), Duration::from_secs(1_800));
+    let faster = FeeEstimate::from_details(FeeDetails::Bitcoin { vsize: 141, sat_per_vbyte: 20 }).unwrap();
+    assert_eq!(service.estimate_confirmation_time(&faster).await.unwrap(), Duration::from_secs(600));
+}
@@ -126,6 +126,7 @@ async fn test_bitcoin_connectivity() {
 async fn test_bitcoin_multiple_addresses() {
     println!("\n🟠 Bitcoin Multiple Addresses Test\n");
 
+    #[allow(clippy::useless_vec)]
     let addresses = vec![
         "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa", // Satoshi's address
         "3J98t1WpEZ73CNmYviecrnyiWrnqRhWNLy", // P2SH address
@@ -262,6 +263,7 @@ async fn test_solana_connectivity() {
 async fn test_solana_multiple_addresses() {
     println!("\n🟣 Solana Multiple Addresses Test\n");
 
+    #[allow(clippy::useless_vec)]
     let addresses = vec![
         "11111111111111111111111111111111",                             // System program
         "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",                  // Token program
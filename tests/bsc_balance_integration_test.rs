@@ -230,6 +230,7 @@ async fn test_bsc_custom_rpc() {
     let address = Address::new("0x28C6c06298d514Db089934071355E5743bf21d60".to_string())
         .expect("Valid address");
 
+    #[allow(clippy::expect_fun_call)]
     for rpc in custom_rpcs {
         let service = AlloyBlockchainService::new(Network::BscMainnet, rpc)
             .await
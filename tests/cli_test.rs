@@ -0,0 +1,62 @@
+//! End-to-end CLI tests against a mocked JSON-RPC server
+//!
+//! Commands are parsed with `Cli::try_parse_from` and run against a local
+//! `wiremock` node, checking what the CLI actually broadcasts.
+//!
+//! Run with: cargo test --test cli_test
+#![cfg(feature = "cli")]
+
+mod common;
+
+use serde_json::{json, Value};
+use wiremock::MockServer;
+
+use common::{mount_json_rpc_results, SentTransactions, ETH_ADDRESS, TEST_KEY, TEST_KEY_ADDRESS};
+
+#[tokio::test]
+async fn test_cli_transfer_gas_price_gwei_sets_transaction_gas_price() {
+    use alloy::consensus::Transaction;
+    use clap::Parser;
+    use rustwallet::adapter::interfaces::cli::Cli;
+
+    let server = MockServer::start().await;
+    mount_json_rpc_results(&server, [
+        ("eth_chainId", json!("0x1")),
+        ("eth_estimateGas", json!("0x5208")),
+        ("eth_gasPrice", json!("0x3b9aca00")),
+        ("eth_getBalance", json!("0xde0b6b3a7640000")),
+        ("eth_getTransactionCount", json!("0x0")),
+        ("eth_getTransactionByHash", Value::Null),
+    ])
+    .await;
+    let sent = SentTransactions::mount(&server).await;
+
+    let key_env = "RUSTWALLET_TEST_GAS_PRICE_KEY";
+    std::env::set_var(key_env, TEST_KEY);
+    let rpc_url = server.uri();
+    let cli = Cli::try_parse_from([
+        "rustwallet",
+        "--quiet",
+        "transfer",
+        "--from",
+        TEST_KEY_ADDRESS,
+        "--to",
+        ETH_ADDRESS,
+        "--amount",
+        "0.01",
+        "--network",
+        "mainnet",
+        "--rpc-url",
+        &rpc_url,
+        "--private-key-env",
+        key_env,
+        "--gas-price-gwei",
+        "30",
+    ])
+    .unwrap();
+    cli.run().await.expect("Transfer failed");
+
+    let envelope = sent.single();
+    assert_eq!(envelope.gas_price(), Some(30_000_000_000));
+    assert_eq!(envelope.value(), alloy::primitives::U256::from(10_000_000_000_000_000u128));
+}
@@ -0,0 +1,128 @@
+//! Shared fixtures for the `wiremock`-based integration tests
+//!
+//! Each mock test file includes this module with `mod common;`, mounts these
+//! canned JSON-RPC responses on its own local `MockServer` and queries through
+//! the application handlers, as the CLI and APIs do.
+#![allow(dead_code)] // Every test crate uses a different subset
+
+use alloy::consensus::TxEnvelope;
+use alloy::eips::eip2718::Decodable2718;
+use rustwallet::core::application::handlers::{GetBalanceHandler, GetBalancesHandler};
+use rustwallet::core::domain::{
+    errors::DomainError,
+    queries::{GetBalanceQuery, GetBalancesQuery},
+    services::{BlockchainService, QueryHandler},
+    value_objects::{Address, Balance, Network},
+};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use wiremock::matchers::{body_partial_json, method};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+pub const ETH_ADDRESS: &str = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+pub const BTC_ADDRESS: &str = "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa";
+pub const SOL_ADDRESS: &str = "DRpbCBMxVnDK7maPM5tGv6MvB3v1sRMC86PZ8okm21hy";
+
+/// Private key `0x…01`, a well-known test key with no real funds
+pub const TEST_KEY: &str = "0000000000000000000000000000000000000000000000000000000000000001";
+/// EVM address of [`TEST_KEY`]
+pub const TEST_KEY_ADDRESS: &str = "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf";
+
+/// Respond to a JSON-RPC request with `result`, echoing the request id
+pub fn json_rpc_result(result: Value) -> impl Fn(&Request) -> ResponseTemplate {
+    move |request: &Request| {
+        let body: Value = serde_json::from_slice(&request.body).unwrap_or_default();
+        ResponseTemplate::new(200).set_body_json(json!({
+            "jsonrpc": "2.0",
+            "id": body["id"],
+            "result": result,
+        }))
+    }
+}
+
+/// Respond to a JSON-RPC request with an error object, echoing the request id
+pub fn json_rpc_error(code: i64, message: &str) -> impl Fn(&Request) -> ResponseTemplate {
+    let message = message.to_string();
+    move |request: &Request| {
+        let body: Value = serde_json::from_slice(&request.body).unwrap_or_default();
+        ResponseTemplate::new(200).set_body_json(json!({
+            "jsonrpc": "2.0",
+            "id": body["id"],
+            "error": { "code": code, "message": message },
+        }))
+    }
+}
+
+/// Answer each JSON-RPC method with a fixed result
+pub async fn mount_json_rpc_results<'a>(server: &MockServer, results: impl IntoIterator<Item = (&'a str, Value)>) {
+    for (rpc_method, result) in results {
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": rpc_method })))
+            .respond_with(json_rpc_result(result))
+            .mount(server)
+            .await;
+    }
+}
+
+/// Raw transactions received through `eth_sendRawTransaction`
+///
+/// The mock answers each broadcast with the transaction's keccak256 hash, as a
+/// node accepting it would.
+#[derive(Clone, Default)]
+pub struct SentTransactions(Arc<Mutex<Vec<String>>>);
+
+impl SentTransactions {
+    /// Mount the `eth_sendRawTransaction` mock on `server`
+    pub async fn mount(server: &MockServer) -> Self {
+        let sent = Self::default();
+        let received = sent.0.clone();
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "eth_sendRawTransaction" })))
+            .respond_with(move |request: &Request| {
+                let body: Value = serde_json::from_slice(&request.body).unwrap();
+                let raw = body["params"][0].as_str().unwrap().to_string();
+                let hash = format!("{:?}", alloy::primitives::keccak256(alloy::hex::decode(&raw).unwrap()));
+                received.lock().unwrap().push(raw);
+                json_rpc_result(json!(hash))(request)
+            })
+            .mount(server)
+            .await;
+        sent
+    }
+
+    /// Hex-encoded transactions in the order they were broadcast
+    pub fn raw(&self) -> Vec<String> {
+        self.0.lock().unwrap().clone()
+    }
+
+    /// Decode the only transaction broadcast, panicking unless exactly one was
+    pub fn single(&self) -> TxEnvelope {
+        let raw = self.raw();
+        assert_eq!(raw.len(), 1, "expected exactly one broadcast transaction");
+        TxEnvelope::decode_2718(&mut alloy::hex::decode(&raw[0]).unwrap().as_slice()).unwrap()
+    }
+}
+
+/// Query one balance through `GetBalanceHandler`
+pub async fn query_balance(
+    service: Arc<dyn BlockchainService>,
+    address: &str,
+    network: Network,
+) -> Result<Balance, DomainError> {
+    let query = GetBalanceQuery::new(Address::new(address.to_string())?, network);
+    Ok(GetBalanceHandler::new(service).handle(query).await?.balance)
+}
+
+/// Query several balances through `GetBalancesHandler`, one result per address
+pub async fn query_balances(
+    service: Arc<dyn BlockchainService>,
+    addresses: &[&str],
+    network: Network,
+) -> Vec<Result<Balance, DomainError>> {
+    let addresses = addresses.iter().map(|address| Address::new(address.to_string()).unwrap()).collect();
+    let results = GetBalancesHandler::new(service)
+        .handle(GetBalancesQuery::new(addresses, network))
+        .await
+        .expect("Batch balance query failed");
+    results.into_iter().map(|result| result.map(|result| result.balance)).collect()
+}
@@ -0,0 +1,1269 @@
+//! Deterministic EVM tests against a mocked JSON-RPC server
+//!
+//! A local `wiremock` server answers `eth_*` requests with canned responses,
+//! exercising the Alloy service's request building and response parsing
+//! (balances, fees, transfers, receipts, ENS) without network access.
+//!
+//! Run with: cargo test --test evm_mock_test
+
+mod common;
+
+use rustwallet::adapter::infrastructure::blockchain::{
+    interval_ticks, AlloyBlockchainService, CachedBlockchainService, OfflineTransaction,
+    RateLimitedBlockchainService,
+};
+use alloy::rpc::types::BlockId;
+use rustwallet::adapter::infrastructure::keys::PrivateKeySigner;
+use rustwallet::adapter::infrastructure::resilience::RetryPolicy;
+use rustwallet::core::application::{
+    handlers::{GetAccountInfoHandler, TransferHandler},
+    GetBalanceHandler,
+};
+use rustwallet::core::domain::{
+    commands::TransferCommand,
+    errors::DomainError,
+    queries::{GetAccountInfoQuery, GetBalanceQuery},
+    services::{BlockchainService, CommandHandler, QueryHandler},
+    value_objects::{Address, Amount, Finality, Network, NetworkKind, SecretKey, TransactionHash, ERC20_TRANSFER_TOPIC},
+};
+use futures::StreamExt;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use wiremock::matchers::{body_partial_json, header, method, path};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+use common::{query_balance, query_balances, json_rpc_result, json_rpc_error, mount_json_rpc_results, SentTransactions, ETH_ADDRESS, TEST_KEY, TEST_KEY_ADDRESS};
+
+#[tokio::test]
+async fn test_evm_balance_via_handler() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_getBalance" })))
+        .respond_with(json_rpc_result(json!("0xde0b6b3a7640000"))) // 1 ETH
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let service = AlloyBlockchainService::new(Network::Mainnet, &server.uri())
+        .await
+        .expect("Failed to create service");
+    let handler = GetBalanceHandler::new(Arc::new(service));
+
+    let query = GetBalanceQuery::new(Address::new(ETH_ADDRESS.to_string()).unwrap(), Network::Mainnet);
+    let result = handler.handle(query).await.expect("Balance query failed");
+
+    assert_eq!(result.balance.to_wei(), 1_000_000_000_000_000_000);
+    assert_eq!(result.network, Network::Mainnet);
+}
+
+alloy::sol! {
+    struct Result3 {
+        bool success;
+        bytes returnData;
+    }
+}
+
+#[tokio::test]
+async fn test_evm_get_balances_uses_one_multicall() {
+    use alloy::sol_types::SolValue;
+
+    let server = MockServer::start().await;
+    let output = vec![
+        Result3 { success: true, returnData: alloy::primitives::U256::from(10u64).abi_encode().into() },
+        Result3 { success: true, returnData: alloy::primitives::U256::from(20u64).abi_encode().into() },
+    ]
+    .abi_encode();
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({
+            "method": "eth_call",
+            "params": [{ "to": "0xca11bde05977b3631167028862be2a173976ca11" }],
+        })))
+        .respond_with(json_rpc_result(json!(format!("0x{}", alloy::hex::encode(output)))))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_getBalance" })))
+        .respond_with(json_rpc_result(json!("0x0")))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let service = AlloyBlockchainService::new(Network::Mainnet, &server.uri()).await.unwrap();
+    let addresses = [ETH_ADDRESS, "0x0000000000000000000000000000000000000001"];
+    let balances = query_balances(Arc::new(service), &addresses, Network::Mainnet).await;
+
+    assert_eq!(balances.len(), 2);
+    assert_eq!(balances[0].as_ref().unwrap().to_wei(), 10);
+    assert_eq!(balances[1].as_ref().unwrap().to_wei(), 20);
+}
+
+#[tokio::test]
+async fn test_evm_get_balances_falls_back_without_multicall() {
+    let server = MockServer::start().await;
+    // No contract at the Multicall3 address: eth_call returns empty bytes
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_call" })))
+        .respond_with(json_rpc_result(json!("0x")))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_getBalance" })))
+        .respond_with(json_rpc_result(json!("0x2a")))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let service = AlloyBlockchainService::new(Network::Mainnet, &server.uri()).await.unwrap();
+    let balances = query_balances(Arc::new(service), &[ETH_ADDRESS, ETH_ADDRESS], Network::Mainnet).await;
+
+    assert!(balances.iter().all(|balance| balance.as_ref().unwrap().to_wei() == 42));
+}
+
+#[tokio::test]
+async fn test_evm_block_number_and_rpc_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_blockNumber" })))
+        .respond_with(json_rpc_result(json!("0x12d687")))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_getBalance" })))
+        .respond_with(json_rpc_error(-32000, "header not found"))
+        .mount(&server)
+        .await;
+
+    let service = AlloyBlockchainService::new(Network::Mainnet, &server.uri())
+        .await
+        .expect("Failed to create service");
+
+    assert_eq!(service.get_block_number().await.unwrap(), 1_234_567);
+    assert!(service.is_connected().await);
+
+    let handler = GetBalanceHandler::new(Arc::new(service));
+    let query = GetBalanceQuery::new(Address::new(ETH_ADDRESS.to_string()).unwrap(), Network::Mainnet);
+    let result = handler.handle(query).await;
+    assert!(matches!(result, Err(DomainError::NetworkError(_))));
+}
+
+#[tokio::test]
+async fn test_evm_rate_limited_endpoint_is_connected_but_throttled() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_blockNumber" })))
+        .respond_with(ResponseTemplate::new(429).set_body_string("Too Many Requests"))
+        .expect(4)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_getBalance" })))
+        .respond_with(json_rpc_error(-32005, "project rate limit exceeded"))
+        .mount(&server)
+        .await;
+
+    let service = Arc::new(
+        AlloyBlockchainService::new(Network::Mainnet, &server.uri())
+            .await
+            .expect("Failed to create service"),
+    );
+
+    // First probe + 2 retries, all throttled: up, not down
+    assert!(service.is_connected().await);
+    let diagnostics = service.diagnose().await;
+    assert!(!diagnostics.reachable);
+    assert!(diagnostics.throttled);
+    assert!(diagnostics.is_up());
+
+    let balance = query_balance(service, ETH_ADDRESS, Network::Mainnet).await;
+    assert!(matches!(balance, Err(DomainError::RateLimited(_))));
+
+    // A dead endpoint is not mistaken for a throttled one
+    let dead = AlloyBlockchainService::new(Network::Mainnet, "http://127.0.0.1:1").await.unwrap();
+    assert!(!dead.is_connected().await);
+    assert!(!dead.diagnose().await.is_up());
+}
+
+#[tokio::test]
+async fn test_evm_call_returns_raw_bytes_and_surfaces_reverts() {
+    const USDC: &str = "0xA0b86991c6218b36c1D19D4a2e9Eb0cE3606eB48";
+    let server = MockServer::start().await;
+    // decimals() => 6
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({
+            "method": "eth_call",
+            "params": [{ "to": USDC.to_lowercase(), "input": "0x313ce567" }, "0x10"],
+        })))
+        .respond_with(json_rpc_result(json!(format!("0x{:064x}", 6))))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_call", "params": [{ "input": "0xdeadbeef" }] })))
+        .respond_with(json_rpc_error(3, "execution reverted"))
+        .mount(&server)
+        .await;
+
+    let service = AlloyBlockchainService::new(Network::Mainnet, &server.uri())
+        .await
+        .expect("Failed to create service");
+    let usdc = Address::new(USDC.to_string()).unwrap();
+
+    let output = service.call(&usdc, vec![0x31, 0x3c, 0xe5, 0x67], BlockId::number(16)).await.unwrap();
+    assert_eq!(output.len(), 32);
+    assert_eq!(output[31], 6);
+
+    match service.call(&usdc, vec![0xde, 0xad, 0xbe, 0xef], BlockId::latest()).await {
+        Err(DomainError::BlockchainError(message)) => assert!(message.contains("execution reverted")),
+        other => panic!("Expected BlockchainError, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_evm_transfer_reports_decoded_revert_reason() {
+    use alloy::sol_types::{Revert, SolError};
+
+    let server = MockServer::start().await;
+    let data = format!("0x{}", alloy::hex::encode(Revert::from("ERC20: transfer amount exceeds balance").abi_encode()));
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_estimateGas" })))
+        .respond_with(move |request: &Request| {
+            let body: Value = serde_json::from_slice(&request.body).unwrap();
+            ResponseTemplate::new(200).set_body_json(json!({
+                "jsonrpc": "2.0",
+                "id": body["id"],
+                "error": { "code": 3, "message": "execution reverted", "data": data },
+            }))
+        })
+        .mount(&server)
+        .await;
+
+    let service = AlloyBlockchainService::new(Network::Mainnet, &server.uri()).await.unwrap();
+    let handler = TransferHandler::new(Arc::new(service));
+    let command = TransferCommand::new(
+        Address::new(TEST_KEY_ADDRESS.to_string()).unwrap(),
+        Address::new(ETH_ADDRESS.to_string()).unwrap(),
+        Amount::from_wei(1),
+        Network::Mainnet,
+        SecretKey::from(TEST_KEY),
+    );
+
+    match handler.handle(command).await {
+        Err(DomainError::WouldRevert(reason)) => assert_eq!(reason, "ERC20: transfer amount exceeds balance"),
+        other => panic!("Expected WouldRevert, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_evm_get_storage_at_reads_proxy_slot() {
+    use alloy::primitives::{b256, U256};
+
+    const PROXY: &str = "0xA0b86991c6218b36c1D19D4a2e9Eb0cE3606eB48";
+    // EIP-1967 implementation slot
+    const SLOT: &str = "0x360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc";
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({
+            "method": "eth_getStorageAt",
+            "params": [PROXY.to_lowercase(), SLOT, "0x10"],
+        })))
+        .respond_with(json_rpc_result(json!(format!("0x{:0>64}", &ETH_ADDRESS.to_lowercase()[2..]))))
+        .mount(&server)
+        .await;
+
+    let service = AlloyBlockchainService::new(Network::Mainnet, &server.uri())
+        .await
+        .expect("Failed to create service");
+    let proxy = Address::new(PROXY.to_string()).unwrap();
+    let value = service
+        .get_storage_at(&proxy, SLOT.parse::<U256>().unwrap(), BlockId::number(16))
+        .await
+        .unwrap();
+
+    assert_eq!(value, b256!("000000000000000000000000d8da6bf26964af9d7eed9e03e53415d37aa96045"));
+    assert_eq!(&value[12..], ETH_ADDRESS.parse::<alloy::primitives::Address>().unwrap().as_slice());
+}
+
+#[tokio::test]
+async fn test_evm_balance_series_samples_blocks_and_flags_pruned_state() {
+    // Balance at block n is n wei; blocks before 100 have been pruned
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_getBalance" })))
+        .respond_with(|request: &Request| {
+            let body: Value = serde_json::from_slice(&request.body).unwrap();
+            let tag = body["params"][1].as_str().unwrap().to_string();
+            let block = u64::from_str_radix(tag.trim_start_matches("0x"), 16).unwrap();
+            if block < 100 {
+                json_rpc_error(-32000, "missing trie node 4f1a (path ) <nil>")(request)
+            } else {
+                json_rpc_result(json!(tag))(request)
+            }
+        })
+        .mount(&server)
+        .await;
+
+    let service = AlloyBlockchainService::new(Network::Mainnet, &server.uri())
+        .await
+        .expect("Failed to create service");
+    let address = Address::new(ETH_ADDRESS.to_string()).unwrap();
+
+    let series = service.balance_series(&address, 100, 145, 10).await.unwrap();
+    let points: Vec<(u64, u128)> = series.into_iter().map(|(block, balance)| (block, balance.to_wei())).collect();
+    assert_eq!(points, vec![(100, 100), (110, 110), (120, 120), (130, 130), (140, 140), (145, 145)]);
+
+    match service.balance_series(&address, 90, 120, 10).await {
+        Err(DomainError::ConfigurationError(message)) => assert!(message.contains("archive node")),
+        other => panic!("Expected ConfigurationError, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_evm_account_info_combines_balance_nonce_and_code() {
+    let server = MockServer::start().await;
+    for (rpc_method, result) in [
+        ("eth_getBalance", json!("0xde0b6b3a7640000")), // 1 ETH
+        ("eth_getTransactionCount", json!("0x2a")),
+        ("eth_getCode", json!("0x6080604052")),
+    ] {
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": rpc_method })))
+            .respond_with(json_rpc_result(result))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let service = AlloyBlockchainService::new(Network::Mainnet, &server.uri())
+        .await
+        .expect("Failed to create service");
+    let handler = GetAccountInfoHandler::new(Arc::new(service));
+    let address = Address::new(ETH_ADDRESS.to_string()).unwrap();
+    let info = handler
+        .handle(GetAccountInfoQuery::new(address.clone(), Network::Mainnet))
+        .await
+        .unwrap();
+
+    assert_eq!(info.address, address);
+    assert_eq!(info.balance.to_wei(), 1_000_000_000_000_000_000);
+    assert_eq!(info.transaction_count, Some(42));
+    assert_eq!(info.code_size, Some(5));
+    assert_eq!(info.is_contract, Some(true));
+}
+
+#[tokio::test]
+async fn test_evm_receipt_logs_decode_erc20_transfer() {
+    let server = MockServer::start().await;
+    let tx_hash = format!("0x{}", "ab".repeat(32));
+    let token = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
+    let recipient = "0x7e5f4552091a69125d5dfcb7b8c2659029395bdf";
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_getTransactionReceipt" })))
+        .respond_with(json_rpc_result(json!({
+            "transactionHash": tx_hash,
+            "transactionIndex": "0x0",
+            "blockHash": format!("0x{}", "cd".repeat(32)),
+            "blockNumber": "0x10",
+            "from": ETH_ADDRESS.to_lowercase(),
+            "to": token,
+            "cumulativeGasUsed": "0xc738",
+            "gasUsed": "0xc738",
+            "effectiveGasPrice": "0x3b9aca00",
+            "contractAddress": null,
+            "logsBloom": format!("0x{}", "00".repeat(256)),
+            "type": "0x2",
+            "status": "0x1",
+            "logs": [{
+                "address": token,
+                "topics": [
+                    ERC20_TRANSFER_TOPIC,
+                    format!("0x{:0>64}", &ETH_ADDRESS.to_lowercase()[2..]),
+                    format!("0x{:0>64}", &recipient[2..]),
+                ],
+                "data": format!("0x{:064x}", 2_500_000u128),
+                "blockNumber": "0x10",
+                "transactionHash": tx_hash,
+                "transactionIndex": "0x0",
+                "blockHash": format!("0x{}", "cd".repeat(32)),
+                "logIndex": "0x0",
+                "removed": false
+            }]
+        })))
+        .mount(&server)
+        .await;
+
+    let service = AlloyBlockchainService::new(Network::Mainnet, &server.uri())
+        .await
+        .expect("Failed to create service");
+    let receipt = service
+        .wait_for_receipt(&TransactionHash::new(tx_hash).unwrap(), Duration::from_secs(5))
+        .await
+        .expect("Receipt query failed");
+
+    assert!(receipt.success);
+    assert_eq!(receipt.block_number, Some(16));
+    assert_eq!(receipt.gas_used, 51_000);
+    assert_eq!(receipt.logs.len(), 1);
+
+    let transfers = receipt.erc20_transfers();
+    assert_eq!(transfers.len(), 1);
+    assert_eq!(transfers[0].token, Address::new(token.to_string()).unwrap());
+    assert_eq!(transfers[0].from, Address::new(ETH_ADDRESS.to_string()).unwrap());
+    assert_eq!(transfers[0].to, Address::new(recipient.to_string()).unwrap());
+    assert_eq!(transfers[0].amount, 2_500_000);
+}
+
+#[tokio::test]
+async fn test_wait_for_confirmations_polls_until_enough_blocks() {
+    let server = MockServer::start().await;
+    let tx_hash = format!("0x{}", "ab".repeat(32));
+    let receipt = json!({
+        "transactionHash": tx_hash,
+        "transactionIndex": "0x0",
+        "blockHash": format!("0x{}", "cd".repeat(32)),
+        "blockNumber": "0x10",
+        "from": ETH_ADDRESS.to_lowercase(),
+        "to": ETH_ADDRESS.to_lowercase(),
+        "cumulativeGasUsed": "0x5208",
+        "gasUsed": "0x5208",
+        "effectiveGasPrice": "0x3b9aca00",
+        "contractAddress": null,
+        "logsBloom": format!("0x{}", "00".repeat(256)),
+        "type": "0x2",
+        "status": "0x1",
+        "logs": []
+    });
+    // Not mined on the first lookup, mined in block 16 after that
+    let lookups = Arc::new(AtomicUsize::new(0));
+    let counter = lookups.clone();
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_getTransactionReceipt" })))
+        .respond_with(move |request: &Request| {
+            let result = if counter.fetch_add(1, Ordering::SeqCst) == 0 { Value::Null } else { receipt.clone() };
+            json_rpc_result(result)(request)
+        })
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_getTransactionByHash" })))
+        .respond_with(json_rpc_result(json!({ "hash": tx_hash })))
+        .mount(&server)
+        .await;
+    // The tip advances one block per lookup: 16, 17, 18, ...
+    let heights = Arc::new(AtomicUsize::new(16));
+    let tip = heights.clone();
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_blockNumber" })))
+        .respond_with(move |request: &Request| {
+            json_rpc_result(json!(format!("0x{:x}", tip.fetch_add(1, Ordering::SeqCst))))(request)
+        })
+        .mount(&server)
+        .await;
+
+    let service = AlloyBlockchainService::new(Network::Mainnet, &server.uri()).await.unwrap();
+    let hash = TransactionHash::new(tx_hash).unwrap();
+    let receipt = service
+        .wait_for_confirmations(&hash, 3, interval_ticks(Duration::from_millis(10)))
+        .await
+        .expect("Confirmation wait failed");
+    assert!(receipt.success);
+    assert_eq!(receipt.block_number, Some(16));
+    // Blocks 16 and 17 were too few confirmations; 18 was the third
+    assert_eq!(heights.load(Ordering::SeqCst), 19);
+
+    // Running out of ticks ends the wait with an error
+    let result = service
+        .wait_for_confirmations(&hash, 100, futures::stream::iter([()]).boxed())
+        .await;
+    assert!(matches!(result, Err(DomainError::BlockchainError(_))));
+}
+
+#[tokio::test]
+async fn test_transaction_tracker_reports_reverted_evm_transaction() {
+    use rustwallet::core::application::TransactionTracker;
+    use rustwallet::core::domain::value_objects::TxStatus;
+
+    let server = MockServer::start().await;
+    let tx_hash = format!("0x{}", "ab".repeat(32));
+    let receipt = json!({
+        "transactionHash": tx_hash,
+        "transactionIndex": "0x0",
+        "blockHash": format!("0x{}", "cd".repeat(32)),
+        "blockNumber": "0x10",
+        "from": ETH_ADDRESS.to_lowercase(),
+        "to": ETH_ADDRESS.to_lowercase(),
+        "cumulativeGasUsed": "0x5208",
+        "gasUsed": "0x5208",
+        "effectiveGasPrice": "0x3b9aca00",
+        "contractAddress": null,
+        "logsBloom": format!("0x{}", "00".repeat(256)),
+        "type": "0x2",
+        "status": "0x0",
+        "logs": []
+    });
+    // Not mined on the first lookup, reverted after that
+    let lookups = Arc::new(AtomicUsize::new(0));
+    let counter = lookups.clone();
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_getTransactionReceipt" })))
+        .respond_with(move |request: &Request| {
+            let result = if counter.fetch_add(1, Ordering::SeqCst) == 0 { Value::Null } else { receipt.clone() };
+            json_rpc_result(result)(request)
+        })
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_getTransactionByHash" })))
+        .respond_with(json_rpc_result(Value::Null))
+        .mount(&server)
+        .await;
+
+    let service = Arc::new(AlloyBlockchainService::new(Network::Mainnet, &server.uri()).await.unwrap());
+    let hash = TransactionHash::new(tx_hash).unwrap();
+    assert_eq!(service.get_transaction_status(&hash).await.unwrap(), TxStatus::NotFound);
+
+    let tracker = TransactionTracker::new().with_service(Network::Mainnet, service);
+    tracker.track(hash.clone(), Network::Mainnet).unwrap();
+    let completions: Vec<_> = tracker
+        .completions(interval_ticks(Duration::from_millis(10)))
+        .map(|(tx_hash, status)| (tx_hash, status.unwrap()))
+        .collect()
+        .await;
+    assert_eq!(completions, vec![(hash, TxStatus::Reverted { block: Some(16) })]);
+}
+
+/// A Custom network with a rollup's chain id
+fn rollup_network(chain_id: u64, rpc_url: &str) -> Network {
+    Network::Custom {
+        name: "rollup".to_string(),
+        chain_id,
+        rpc_url: rpc_url.to_string(),
+        kind: NetworkKind::Local,
+        symbol: None,
+        explorer_url: None,
+    }
+}
+
+#[tokio::test]
+async fn test_op_stack_fee_estimate_adds_l1_data_fee() {
+    let server = MockServer::start().await;
+    mount_json_rpc_results(&server, [
+        ("eth_estimateGas", json!("0x5208")), // 21000
+        ("eth_gasPrice", json!("0x3e8")),     // 1000 wei
+    ])
+    .await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({
+            "method": "eth_call",
+            "params": [{ "to": "0x420000000000000000000000000000000000000f" }],
+        })))
+        .respond_with(json_rpc_result(json!(format!("0x{:064x}", 50_000_000u64))))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let service = AlloyBlockchainService::new(rollup_network(10, &server.uri()), &server.uri()).await.unwrap();
+    let address = Address::new(ETH_ADDRESS.to_string()).unwrap();
+    let fee = service.estimate_fee(&address, &address, 1).await.unwrap();
+
+    assert_eq!(fee.l1_data_fee.unwrap().to_wei(), 50_000_000);
+    assert_eq!(fee.total_native.to_wei(), 21_000 * 1_000 + 50_000_000);
+}
+
+#[tokio::test]
+async fn test_arbitrum_fee_estimate_breaks_out_l1_gas() {
+    let server = MockServer::start().await;
+    mount_json_rpc_results(&server, [
+        ("eth_estimateGas", json!("0x7530")), // 30000, including 9000 L1 gas
+        ("eth_gasPrice", json!("0x3e8")),
+    ])
+    .await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({
+            "method": "eth_call",
+            "params": [{ "to": "0x00000000000000000000000000000000000000c8" }],
+        })))
+        .respond_with(json_rpc_result(json!(format!("0x{:064x}{:064x}{:064x}", 9_000, 1_000, 20))))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let service = AlloyBlockchainService::new(rollup_network(42161, &server.uri()), &server.uri()).await.unwrap();
+    let address = Address::new(ETH_ADDRESS.to_string()).unwrap();
+    let fee = service.estimate_fee(&address, &address, 1).await.unwrap();
+
+    assert_eq!(fee.l1_data_fee.unwrap().to_wei(), 9_000 * 1_000);
+    assert_eq!(fee.total_native.to_wei(), 30_000 * 1_000);
+}
+
+#[tokio::test]
+async fn test_evm_transfer_with_same_nonce_returns_existing_hash() {
+    let server = MockServer::start().await;
+    // Fixed fees so both attempts sign the identical transaction
+    mount_json_rpc_results(&server, [
+        ("eth_chainId", json!("0x1")),
+        ("eth_estimateGas", json!("0x5208")),
+        ("eth_gasPrice", json!("0x3b9aca00")),
+        ("eth_feeHistory", json!({
+            "oldestBlock": "0x1",
+            "baseFeePerGas": ["0x3b9aca00", "0x3b9aca00"],
+            "gasUsedRatio": [0.5],
+            "reward": [["0x5f5e100"]],
+        })),
+    ])
+    .await;
+
+    // The node knows the transaction once it has been broadcast
+    let broadcast: Arc<Mutex<Option<String>>> = Arc::default();
+    let sent = broadcast.clone();
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_sendRawTransaction" })))
+        .respond_with(move |request: &Request| {
+            let body: Value = serde_json::from_slice(&request.body).unwrap();
+            let raw = body["params"][0].as_str().unwrap();
+            let raw = alloy::hex::decode(raw).unwrap();
+            let hash = format!("{:?}", alloy::primitives::keccak256(&raw));
+            *sent.lock().unwrap() = Some(hash.clone());
+            json_rpc_result(json!(hash))(request)
+        })
+        .expect(1)
+        .mount(&server)
+        .await;
+    // The accepted transfer drained the account, which must not fail the retry
+    let spent = broadcast.clone();
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_getBalance" })))
+        .respond_with(move |request: &Request| {
+            let balance = if spent.lock().unwrap().is_some() { "0x0" } else { "0xde0b6b3a7640000" };
+            json_rpc_result(json!(balance))(request)
+        })
+        .mount(&server)
+        .await;
+    let known = broadcast.clone();
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_getTransactionByHash" })))
+        .respond_with(move |request: &Request| {
+            let result = match known.lock().unwrap().clone() {
+                Some(hash) => json!({ "hash": hash }),
+                None => Value::Null,
+            };
+            json_rpc_result(result)(request)
+        })
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let service = AlloyBlockchainService::new(Network::Mainnet, &server.uri())
+        .await
+        .expect("Failed to create service");
+    let from = Address::new(TEST_KEY_ADDRESS.to_string()).unwrap();
+    let to = Address::new(ETH_ADDRESS.to_string()).unwrap();
+    let key = SecretKey::from(TEST_KEY);
+    let signer = PrivateKeySigner::new(&key, &Network::Mainnet).unwrap();
+
+    let first = service
+        .transfer_with_nonce(&from, &to, 1_000, &signer, Some(7))
+        .await
+        .expect("First transfer failed");
+    // Retry after e.g. a timed-out response: same nonce, no second broadcast
+    let retry = service
+        .transfer_with_nonce(&from, &to, 1_000, &signer, Some(7))
+        .await
+        .expect("Retry failed");
+
+    assert_eq!(first, retry);
+    assert_eq!(Some(first.to_string()), broadcast.lock().unwrap().clone());
+}
+
+#[tokio::test]
+async fn test_evm_sweep_sends_balance_minus_exact_fee() {
+    use alloy::consensus::Transaction;
+
+    let server = MockServer::start().await;
+    mount_json_rpc_results(&server, [
+        ("eth_chainId", json!("0x1")),
+        ("eth_estimateGas", json!("0x5208")),
+        ("eth_gasPrice", json!("0x3b9aca00")),
+        ("eth_getBalance", json!("0xde0b6b3a7640000")),
+        ("eth_getTransactionCount", json!("0x4")),
+        ("eth_getTransactionByHash", Value::Null),
+    ])
+    .await;
+    let sent = SentTransactions::mount(&server).await;
+
+    let service = AlloyBlockchainService::new(Network::Mainnet, &server.uri()).await.unwrap();
+    let from = Address::new(TEST_KEY_ADDRESS.to_string()).unwrap();
+    let to = Address::new(ETH_ADDRESS.to_string()).unwrap();
+    let key = SecretKey::from(TEST_KEY);
+    let signer = PrivateKeySigner::new(&key, &Network::Mainnet).unwrap();
+
+    let (_, swept) = service.sweep(&from, &to, &signer).await.expect("Sweep failed");
+
+    let envelope = sent.single();
+    // 1 ETH - 21000 gas × 1 gwei, at exactly that price so nothing is left behind
+    let expected = 1_000_000_000_000_000_000u128 - 21_000 * 1_000_000_000;
+    assert_eq!(envelope.value(), alloy::primitives::U256::from(expected));
+    assert_eq!(swept.to_wei(), expected);
+    assert_eq!(envelope.gas_limit(), 21_000);
+    assert_eq!(envelope.gas_price(), Some(1_000_000_000));
+    assert_eq!(envelope.nonce(), 4);
+}
+
+#[tokio::test]
+async fn test_evm_transfer_handler_sends_command_gas_price() {
+    use alloy::consensus::Transaction;
+
+    let server = MockServer::start().await;
+    mount_json_rpc_results(&server, [
+        ("eth_chainId", json!("0x1")),
+        ("eth_estimateGas", json!("0x5208")),
+        ("eth_gasPrice", json!("0x3b9aca00")),
+        ("eth_getBalance", json!("0xde0b6b3a7640000")),
+        ("eth_getTransactionCount", json!("0x0")),
+        ("eth_getTransactionByHash", Value::Null),
+    ])
+    .await;
+    let sent = SentTransactions::mount(&server).await;
+
+    let service = AlloyBlockchainService::new(Network::Mainnet, &server.uri()).await.unwrap();
+    let handler = TransferHandler::new(Arc::new(service));
+    let command = TransferCommand::new(
+        Address::new(TEST_KEY_ADDRESS.to_string()).unwrap(),
+        Address::new(ETH_ADDRESS.to_string()).unwrap(),
+        Amount::from_ether(0.01),
+        Network::Mainnet,
+        SecretKey::from(TEST_KEY),
+    )
+    .with_gas_price_gwei(30);
+
+    handler.handle(command).await.expect("Transfer failed");
+
+    // The node suggests 1 gwei; the command's 30 gwei must win
+    let envelope = sent.single();
+    assert!(envelope.is_legacy());
+    assert_eq!(envelope.gas_price(), Some(30_000_000_000));
+}
+
+#[tokio::test]
+async fn test_evm_sweep_rejects_balance_below_fee() {
+    let server = MockServer::start().await;
+    mount_json_rpc_results(&server, [
+        ("eth_chainId", json!("0x1")),
+        ("eth_estimateGas", json!("0x5208")),
+        ("eth_gasPrice", json!("0x3b9aca00")),
+        ("eth_getBalance", json!("0x1000")),
+    ])
+    .await;
+
+    let service = AlloyBlockchainService::new(Network::Mainnet, &server.uri()).await.unwrap();
+    let from = Address::new(TEST_KEY_ADDRESS.to_string()).unwrap();
+    let to = Address::new(ETH_ADDRESS.to_string()).unwrap();
+    let key = SecretKey::from(TEST_KEY);
+    let signer = PrivateKeySigner::new(&key, &Network::Mainnet).unwrap();
+
+    assert!(matches!(
+        service.sweep(&from, &to, &signer).await,
+        Err(DomainError::InsufficientBalance { available: 0x1000, .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_evm_speed_up_bumps_the_stuck_transactions_own_fees() {
+    use alloy::consensus::Transaction;
+
+    const STUCK: &str = "0x1111111111111111111111111111111111111111111111111111111111111111";
+    let server = MockServer::start().await;
+    mount_json_rpc_results(&server, [
+        ("eth_chainId", json!("0x1")),
+        ("eth_estimateGas", json!("0x5208")),
+        ("eth_getBalance", json!("0xde0b6b3a7640000")),
+        // 1 gwei base fee, 0.1 gwei tip: the estimate is ~2.1 gwei
+        ("eth_feeHistory", json!({
+            "oldestBlock": "0x1",
+            "baseFeePerGas": ["0x3b9aca00", "0x3b9aca00"],
+            "gasUsedRatio": [0.5],
+            "reward": [["0x5f5e100"]],
+        })),
+    ])
+    .await;
+    // The stuck transaction pays more than today's estimate: 4 gwei max fee, 2 gwei tip
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_getTransactionByHash" })))
+        .respond_with(|request: &Request| {
+            let body: Value = serde_json::from_slice(&request.body).unwrap();
+            let result = if body["params"][0] == STUCK {
+                json!({
+                    "hash": STUCK,
+                    "from": TEST_KEY_ADDRESS.to_lowercase(),
+                    "nonce": "0x7",
+                    "maxFeePerGas": "0xee6b2800",
+                    "maxPriorityFeePerGas": "0x77359400",
+                })
+            } else {
+                Value::Null
+            };
+            json_rpc_result(result)(request)
+        })
+        .mount(&server)
+        .await;
+    let sent = SentTransactions::mount(&server).await;
+
+    let service = AlloyBlockchainService::new(Network::Mainnet, &server.uri()).await.unwrap();
+    let from = Address::new(TEST_KEY_ADDRESS.to_string()).unwrap();
+    let to = Address::new(ETH_ADDRESS.to_string()).unwrap();
+    let key = SecretKey::from(TEST_KEY);
+    let signer = PrivateKeySigner::new(&key, &Network::Mainnet).unwrap();
+    let stuck = TransactionHash::new(STUCK.to_string()).unwrap();
+
+    // Well above the current estimate, but not 10% above the stuck transaction's max fee
+    assert!(matches!(
+        service.speed_up(&stuck, &from, &to, 1_000, 4_000_000_000, &signer).await,
+        Err(DomainError::InvalidQuery(_))
+    ));
+    let unknown = TransactionHash::new(format!("0x{}", "2".repeat(64))).unwrap();
+    assert!(matches!(
+        service.speed_up(&unknown, &from, &to, 1_000, 5_000_000_000, &signer).await,
+        Err(DomainError::InvalidQuery(_))
+    ));
+
+    service.speed_up(&stuck, &from, &to, 1_000, 5_000_000_000, &signer).await.expect("Speed-up failed");
+
+    let envelope = sent.single();
+    assert_eq!(envelope.nonce(), 7);
+    assert_eq!(envelope.max_fee_per_gas(), 5_000_000_000);
+    // 10% above the stuck tip, not the lower current estimate
+    assert_eq!(envelope.max_priority_fee_per_gas(), Some(2_200_000_000));
+    assert_eq!(envelope.value(), alloy::primitives::U256::from(1_000));
+}
+
+#[tokio::test]
+async fn test_evm_offline_signing_then_broadcast_raw() {
+    use alloy::consensus::{Transaction, TxEnvelope};
+    use alloy::eips::eip2718::Decodable2718;
+
+    let server = MockServer::start().await;
+    let sent = SentTransactions::mount(&server).await;
+
+    let service = AlloyBlockchainService::new(Network::Sepolia, &server.uri()).await.unwrap();
+    let key = SecretKey::from(TEST_KEY);
+    let tx = OfflineTransaction::new(Address::new(ETH_ADDRESS.to_string()).unwrap(), 1_000, 3, 30_000_000_000, 1_000_000_000);
+
+    // Signing needs no network access
+    let (raw, hash) = service.sign_transaction_offline(&tx, &key).unwrap();
+    let envelope = TxEnvelope::decode_2718(&mut alloy::hex::decode(&raw).unwrap().as_slice()).unwrap();
+    assert_eq!(format!("{:?}", envelope.tx_hash()), hash.to_string());
+    assert_eq!(envelope.chain_id(), Some(11155111));
+    assert_eq!(envelope.nonce(), 3);
+    assert_eq!(
+        envelope.recover_signer().unwrap().to_checksum(None),
+        TEST_KEY_ADDRESS
+    );
+
+    let broadcast = service.broadcast_raw(&raw).await.unwrap();
+    assert_eq!(broadcast, hash);
+    assert_eq!(sent.raw(), [raw]);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_evm_ipc_socket() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let path = std::env::temp_dir().join(format!("rustwallet-test-{}.ipc", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let listener = tokio::net::UnixListener::bind(&path).unwrap();
+    // Minimal node: answer every request with block number 0x10
+    tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut buf = vec![0u8; 4096];
+        loop {
+            let n = socket.read(&mut buf).await.unwrap();
+            if n == 0 {
+                break;
+            }
+            let request: Value = serde_json::from_slice(&buf[..n]).unwrap();
+            let response = json!({ "jsonrpc": "2.0", "id": request["id"], "result": "0x10" });
+            socket.write_all(response.to_string().as_bytes()).await.unwrap();
+        }
+    });
+
+    let service = AlloyBlockchainService::new_ipc(Network::Mainnet, &path).await.unwrap();
+    assert_eq!(service.get_block_number().await.unwrap(), 16);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn test_evm_custom_headers_are_sent() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(header("x-api-key", "test-key"))
+        .and(body_partial_json(json!({ "method": "eth_blockNumber" })))
+        .respond_with(json_rpc_result(json!("0x1")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let headers = HashMap::from([("x-api-key".to_string(), "test-key".to_string())]);
+    let service = AlloyBlockchainService::new_with_headers(Network::Mainnet, &server.uri(), headers)
+        .await
+        .expect("Failed to create service");
+
+    assert_eq!(service.get_block_number().await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn test_evm_with_endpoint_targets_sibling_provider() {
+    let primary = MockServer::start().await;
+    let backup = MockServer::start().await;
+    for (server, block) in [(&primary, "0x1"), (&backup, "0x2")] {
+        Mock::given(method("POST"))
+            .and(header("x-api-key", "test-key"))
+            .and(body_partial_json(json!({ "method": "eth_blockNumber" })))
+            .respond_with(json_rpc_result(json!(block)))
+            .mount(server)
+            .await;
+    }
+
+    let headers = HashMap::from([("x-api-key".to_string(), "test-key".to_string())]);
+    let service = AlloyBlockchainService::new_with_headers(Network::Mainnet, &primary.uri(), headers)
+        .await
+        .expect("Failed to create service");
+    let service: Arc<dyn BlockchainService> =
+        Arc::new(RateLimitedBlockchainService::new(Arc::new(service), 100.0).unwrap());
+
+    // Same network, decorator and headers; different endpoint
+    let sibling = service.with_endpoint(&backup.uri()).await.expect("Failed to switch endpoint");
+    assert_eq!(service.get_block_number().await.unwrap(), 1);
+    assert_eq!(sibling.get_block_number().await.unwrap(), 2);
+
+    assert!(matches!(
+        service.with_endpoint("not a url").await,
+        Err(DomainError::ConfigurationError(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_decorated_block_ticks_end_on_shutdown() {
+    let service = AlloyBlockchainService::builder()
+        .network(Network::Mainnet)
+        .rpc_urls(["http://127.0.0.1:1"])
+        .build()
+        .await
+        .expect("Failed to create service");
+    let cached = Arc::new(CachedBlockchainService::new(Arc::new(service), Network::Mainnet));
+    let service = RateLimitedBlockchainService::new(cached, 100.0).unwrap();
+
+    // The ticks come from the Alloy service through both decorators, not from the fallback
+    let mut ticks = service.new_block_ticks(futures::stream::pending().boxed());
+    service.shutdown().await;
+    let next = tokio::time::timeout(Duration::from_secs(5), ticks.next()).await;
+    assert_eq!(next, Ok(None));
+}
+
+#[tokio::test]
+async fn test_watch_balance_yields_only_changes() {
+    let server = MockServer::start().await;
+    let calls = Arc::new(Mutex::new(0usize));
+    let counter = calls.clone();
+    // Balance over successive polls: 1, 1, (error), 1, 2 wei
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_getBalance" })))
+        .respond_with(move |request: &Request| {
+            let mut calls = counter.lock().unwrap();
+            *calls += 1;
+            match *calls {
+                3 => ResponseTemplate::new(503),
+                n if n < 5 => json_rpc_result(json!("0x1"))(request),
+                _ => json_rpc_result(json!("0x2"))(request),
+            }
+        })
+        .mount(&server)
+        .await;
+
+    let service = AlloyBlockchainService::new(Network::Mainnet, &server.uri())
+        .await
+        .expect("Failed to create service");
+    let address = Address::new(ETH_ADDRESS.to_string()).unwrap();
+    let reads: Vec<Result<u128, DomainError>> = service
+        .watch_balance(&address, interval_ticks(Duration::from_millis(10)))
+        .take(3)
+        .map(|read| read.map(|balance| balance.to_wei()))
+        .collect()
+        .await;
+
+    assert!(matches!(reads[..], [Ok(1), Err(_), Ok(2)]), "{:?}", reads);
+    assert_eq!(*calls.lock().unwrap(), 5);
+}
+
+#[tokio::test]
+async fn test_evm_builder_fails_over_to_next_rpc_url() {
+    let down = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&down)
+        .await;
+    let slow = MockServer::start().await;
+    let stale_block = json_rpc_result(json!("0x1"));
+    Mock::given(method("POST"))
+        .respond_with(move |request: &Request| stale_block(request).set_delay(Duration::from_secs(5)))
+        .mount(&slow)
+        .await;
+    let healthy = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_blockNumber" })))
+        .respond_with(json_rpc_result(json!("0x7")))
+        .expect(1)
+        .mount(&healthy)
+        .await;
+
+    let service = AlloyBlockchainService::builder()
+        .network(Network::Mainnet)
+        .rpc_urls([down.uri(), slow.uri(), healthy.uri()])
+        .timeout(Duration::from_millis(200))
+        .build()
+        .await
+        .expect("Failed to build service");
+    assert_eq!(service.get_block_number().await.unwrap(), 7);
+
+    assert!(matches!(
+        AlloyBlockchainService::builder().build().await,
+        Err(DomainError::ConfigurationError(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_evm_chain_id_is_verified_once_before_first_query() {
+    // A BSC node (chain ID 56)
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_chainId" })))
+        .respond_with(json_rpc_result(json!("0x38")))
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_getBalance" })))
+        .respond_with(json_rpc_result(json!("0x2a")))
+        .mount(&server)
+        .await;
+    let address = Address::new(ETH_ADDRESS.to_string()).unwrap();
+
+    let mainnet = AlloyBlockchainService::builder()
+        .network(Network::Mainnet)
+        .rpc_urls([server.uri()])
+        .verify_chain_id(true)
+        .build()
+        .await
+        .unwrap();
+    let error = mainnet.get_balance(&address).await.unwrap_err();
+    assert!(matches!(&error, DomainError::ConfigurationError(m) if m.contains("chain ID 56")));
+
+    let bsc = AlloyBlockchainService::builder()
+        .network(Network::BscMainnet)
+        .rpc_urls([server.uri()])
+        .verify_chain_id(true)
+        .build()
+        .await
+        .unwrap();
+    assert_eq!(bsc.get_balance(&address).await.unwrap().to_wei(), 42);
+    assert_eq!(bsc.get_balance(&address).await.unwrap().to_wei(), 42);
+
+    // Local endpoints are not checked unless asked
+    let unchecked = AlloyBlockchainService::new(Network::Mainnet, &server.uri()).await.unwrap();
+    assert_eq!(unchecked.get_balance(&address).await.unwrap().to_wei(), 42);
+}
+
+#[cfg(feature = "dev")]
+#[tokio::test]
+async fn test_evm_set_balance_calls_anvil() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "anvil_setBalance" })))
+        .respond_with(json_rpc_result(Value::Null))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let service = AlloyBlockchainService::new(Network::Mainnet, &server.uri()).await.unwrap();
+    let address = Address::new(ETH_ADDRESS.to_string()).unwrap();
+    service.set_balance(&address, 1_000_000_000_000_000_000).await.unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    let body: Value = serde_json::from_slice(&requests[0].body).unwrap();
+    assert!(body["params"][0].as_str().unwrap().eq_ignore_ascii_case(ETH_ADDRESS));
+    assert_eq!(body["params"][1], "0xde0b6b3a7640000");
+}
+
+#[tokio::test]
+async fn test_evm_builder_retries_rate_limited_requests() {
+    let server = MockServer::start().await;
+    let calls = Arc::new(Mutex::new(0u32));
+    let counter = calls.clone();
+    let block_number = json_rpc_result(json!("0x2a"));
+    Mock::given(method("POST"))
+        .respond_with(move |request: &Request| {
+            let mut calls = counter.lock().unwrap();
+            *calls += 1;
+            if *calls <= 2 {
+                ResponseTemplate::new(429)
+            } else {
+                block_number(request)
+            }
+        })
+        .mount(&server)
+        .await;
+
+    let service = AlloyBlockchainService::builder()
+        .network(Network::Mainnet)
+        .rpc_urls([server.uri()])
+        .retry_policy(RetryPolicy::new(3, Duration::from_millis(10)))
+        .build()
+        .await
+        .expect("Failed to build service");
+    assert_eq!(service.get_block_number().await.unwrap(), 42);
+    assert_eq!(*calls.lock().unwrap(), 3);
+}
+
+#[tokio::test]
+async fn test_evm_diagnose_reports_height_and_client_version() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_blockNumber" })))
+        .respond_with(json_rpc_result(json!("0x10")))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "web3_clientVersion" })))
+        .respond_with(json_rpc_result(json!("Geth/v1.14.0-stable")))
+        .mount(&server)
+        .await;
+
+    let service = AlloyBlockchainService::new(Network::Mainnet, &server.uri())
+        .await
+        .expect("Failed to create service");
+    let diagnostics = service.diagnose().await;
+
+    assert!(diagnostics.reachable);
+    assert_eq!(diagnostics.block_height, Some(16));
+    assert_eq!(diagnostics.rpc_version.as_deref(), Some("Geth/v1.14.0-stable"));
+    assert_eq!(diagnostics.error, None);
+}
+
+#[tokio::test]
+async fn test_evm_finalized_height_uses_block_tag() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_getBlockByNumber", "params": ["finalized", false] })))
+        .respond_with(json_rpc_result(json!({
+            "hash": format!("0x{}", "11".repeat(32)),
+            "parentHash": format!("0x{}", "22".repeat(32)),
+            "sha3Uncles": format!("0x{}", "33".repeat(32)),
+            "miner": ETH_ADDRESS.to_lowercase(),
+            "stateRoot": format!("0x{}", "44".repeat(32)),
+            "transactionsRoot": format!("0x{}", "55".repeat(32)),
+            "receiptsRoot": format!("0x{}", "66".repeat(32)),
+            "logsBloom": format!("0x{}", "00".repeat(256)),
+            "difficulty": "0x0",
+            "number": "0x1234",
+            "gasLimit": "0x1c9c380",
+            "gasUsed": "0x0",
+            "timestamp": "0x65920080",
+            "extraData": "0x",
+            "mixHash": format!("0x{}", "77".repeat(32)),
+            "nonce": "0x0000000000000000",
+            "baseFeePerGas": "0x3b9aca00",
+            "uncles": [],
+            "transactions": [],
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_blockNumber" })))
+        .respond_with(json_rpc_result(json!("0x1240")))
+        .mount(&server)
+        .await;
+
+    let service = AlloyBlockchainService::new(Network::Mainnet, &server.uri())
+        .await
+        .expect("Failed to create service");
+
+    assert_eq!(service.get_height(Finality::Finalized).await.unwrap(), 0x1234);
+    assert_eq!(service.get_height(Finality::Latest).await.unwrap(), 0x1240);
+}
+
+alloy::sol! {
+    error OffchainLookup(address sender, string[] urls, bytes callData, bytes4 callbackFunction, bytes extraData);
+}
+
+#[tokio::test]
+async fn test_ens_resolves_offchain_name_via_ccip_read() {
+    use alloy::primitives::{Address as AlloyAddress, Bytes, FixedBytes};
+    use alloy::sol_types::{SolError, SolValue};
+    use rustwallet::adapter::infrastructure::blockchain::EnsResolver;
+    use rustwallet::core::domain::services::NameResolver;
+
+    let server = MockServer::start().await;
+    let resolver = AlloyAddress::repeat_byte(0xaa);
+    let owner: AlloyAddress = ETH_ADDRESS.parse().unwrap();
+    let callback = [0xde, 0xad, 0xbe, 0xef];
+    let lookup = OffchainLookup {
+        sender: resolver,
+        urls: vec![format!("{}/gateway/{{sender}}/{{data}}.json", server.uri())],
+        callData: Bytes::from_static(&[0x12, 0x34]),
+        callbackFunction: FixedBytes(callback),
+        extraData: Bytes::from_static(&[0x56]),
+    }
+    .abi_encode();
+
+    // Registry -> resolver, which supports ENSIP-10 and defers `resolve` to the gateway
+    let gateway_answer = Bytes::from(owner.abi_encode()).abi_encode();
+    let rpc_calls = Arc::new(Mutex::new(Vec::new()));
+    let calls = rpc_calls.clone();
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_call" })))
+        .respond_with(move |request: &Request| {
+            let body: Value = serde_json::from_slice(&request.body).unwrap();
+            let input = body["params"][0]["input"].as_str().unwrap().to_string();
+            calls.lock().unwrap().push(input.clone());
+            let reply = |result: Vec<u8>| json!({ "jsonrpc": "2.0", "id": body["id"], "result": format!("0x{}", alloy::hex::encode(result)) });
+            let response = match &input[..10] {
+                "0x0178b8bf" => reply(resolver.abi_encode()),
+                "0x01ffc9a7" => reply(true.abi_encode()),
+                "0x9061b923" => json!({
+                    "jsonrpc": "2.0",
+                    "id": body["id"],
+                    "error": { "code": 3, "message": "execution reverted", "data": format!("0x{}", alloy::hex::encode(&lookup)) },
+                }),
+                "0xdeadbeef" => reply(gateway_answer.clone()),
+                other => panic!("Unexpected eth_call {}", other),
+            };
+            ResponseTemplate::new(200).set_body_json(response)
+        })
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/gateway/{:#x}/0x1234.json", resolver)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "data": "0xabcd" })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let service = AlloyBlockchainService::new(Network::Mainnet, &server.uri()).await.unwrap();
+    let ens = EnsResolver::new(service).unwrap();
+    let address = ens.resolve_name("Offchain.Example.eth").await.unwrap();
+    assert_eq!(address.as_str(), ETH_ADDRESS);
+
+    // The gateway's answer and the lookup's extra data go back to the resolver's callback
+    let callback_input = rpc_calls.lock().unwrap().last().cloned().unwrap();
+    let expected = (Bytes::from_static(&[0xab, 0xcd]), Bytes::from_static(&[0x56])).abi_encode_params();
+    assert_eq!(callback_input, format!("0xdeadbeef{}", alloy::hex::encode(expected)));
+
+    // Redirects are bounded: with no hops allowed the lookup gives up
+    let service = AlloyBlockchainService::new(Network::Mainnet, &server.uri()).await.unwrap();
+    let ens = EnsResolver::new(service).unwrap().with_max_hops(0);
+    let err = ens.resolve_name("offchain.example.eth").await.unwrap_err();
+    assert!(err.to_string().contains("CCIP-read"), "{}", err);
+}
@@ -0,0 +1,141 @@
+//! gRPC API tests against a mocked JSON-RPC server
+//!
+//! The tonic service is served on a local port and backed by services pointed
+//! at a `wiremock` node, checking request handling and status code mapping.
+//!
+//! Run with: cargo test --test grpc_api_test --features grpc
+#![cfg(feature = "grpc")]
+
+mod common;
+
+use rustwallet::adapter::infrastructure::blockchain::AlloyBlockchainService;
+use rustwallet::core::domain::{services::BlockchainService, value_objects::Network};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use wiremock::matchers::{body_partial_json, method};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+use common::{json_rpc_result, mount_json_rpc_results, SentTransactions, ETH_ADDRESS, SOL_ADDRESS, TEST_KEY, TEST_KEY_ADDRESS};
+
+#[tokio::test]
+async fn test_grpc_balance_and_transfer() {
+    use rustwallet::adapter::infrastructure::blockchain::NoopBlockchainService;
+    use rustwallet::adapter::interfaces::grpc::{self, proto, WalletGrpcService};
+
+    let service = WalletGrpcService::with_resolver(Arc::new(|network: Network| {
+        Box::pin(async move {
+            let service: Arc<dyn BlockchainService> =
+                Arc::new(NoopBlockchainService::new().with_chain_type(network.chain_type()));
+            Ok(service)
+        })
+    }));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(grpc::serve(listener, service));
+    let mut client = proto::wallet_client::WalletClient::connect(url).await.unwrap();
+
+    let balance = client
+        .get_balance(proto::GetBalanceRequest {
+            address: SOL_ADDRESS.to_string(),
+            network: "sol".to_string(),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(balance.balance, "1500000000000000000");
+    assert_eq!(balance.network, "sol");
+    assert_eq!(balance.chain_type, "Solana");
+
+    let status = client
+        .get_balance(proto::GetBalanceRequest {
+            address: ETH_ADDRESS.to_string(),
+            network: "nowhere".to_string(),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+    // The stub refuses to send, which surfaces as an upstream failure
+    let status = client
+        .transfer(proto::TransferRequest {
+            from_address: ETH_ADDRESS.to_string(),
+            to_address: "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEbC".to_string(),
+            amount: "1000".to_string(),
+            network: "mainnet".to_string(),
+            private_key: "11".repeat(32),
+            gas_price: None,
+            wait_for_receipt: false,
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::Unavailable);
+}
+
+#[tokio::test]
+async fn test_grpc_transfer_sends_gas_price_and_maps_rejections() {
+    use alloy::consensus::Transaction;
+    use alloy::sol_types::{Revert, SolError};
+    use rustwallet::adapter::interfaces::grpc::{self, proto, WalletGrpcService};
+
+    let server = MockServer::start().await;
+    mount_json_rpc_results(&server, [
+        ("eth_chainId", json!("0x1")),
+        ("eth_gasPrice", json!("0x3b9aca00")),
+        ("eth_getBalance", json!("0xde0b6b3a7640000")),
+        ("eth_getTransactionCount", json!("0x0")),
+        ("eth_getTransactionByHash", Value::Null),
+    ])
+    .await;
+    // Sending 1 wei reverts; anything else estimates at 21000 gas
+    let data = format!("0x{}", alloy::hex::encode(Revert::from("ERC20: transfer amount exceeds balance").abi_encode()));
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_estimateGas" })))
+        .respond_with(move |request: &Request| {
+            let body: Value = serde_json::from_slice(&request.body).unwrap();
+            if body["params"][0]["value"] == "0x1" {
+                return ResponseTemplate::new(200).set_body_json(json!({
+                    "jsonrpc": "2.0",
+                    "id": body["id"],
+                    "error": { "code": 3, "message": "execution reverted", "data": data },
+                }));
+            }
+            json_rpc_result(json!("0x5208"))(request)
+        })
+        .mount(&server)
+        .await;
+    let sent = SentTransactions::mount(&server).await;
+
+    let rpc_url = server.uri();
+    let service = WalletGrpcService::with_resolver(Arc::new(move |network: Network| {
+        let rpc_url = rpc_url.clone();
+        Box::pin(async move {
+            let service: Arc<dyn BlockchainService> = Arc::new(AlloyBlockchainService::new(network, &rpc_url).await?);
+            Ok(service)
+        })
+    }));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(grpc::serve(listener, service));
+    let mut client = proto::wallet_client::WalletClient::connect(url).await.unwrap();
+    let request = |from: &str, amount: &str| proto::TransferRequest {
+        from_address: from.to_string(),
+        to_address: ETH_ADDRESS.to_string(),
+        amount: amount.to_string(),
+        network: "mainnet".to_string(),
+        private_key: TEST_KEY.to_string(),
+        gas_price: Some("30000000000".to_string()),
+        wait_for_receipt: false,
+    };
+
+    client.transfer(request(TEST_KEY_ADDRESS, "1000")).await.unwrap();
+    let envelope = sent.single();
+    assert_eq!(envelope.gas_price(), Some(30_000_000_000));
+
+    let status = client.transfer(request("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEbC", "1000")).await.unwrap_err();
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    assert!(status.message().contains("does not match from address"));
+
+    let status = client.transfer(request(TEST_KEY_ADDRESS, "1")).await.unwrap_err();
+    assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+    assert_eq!(status.message(), "Transaction would revert: ERC20: transfer amount exceeds balance");
+}
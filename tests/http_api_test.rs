@@ -0,0 +1,128 @@
+//! REST API tests against a mocked JSON-RPC server
+//!
+//! The axum router is served on a local port and backed by services pointed
+//! at a `wiremock` node, checking request handling and HTTP status mapping.
+//!
+//! Run with: cargo test --test http_api_test --features http
+#![cfg(feature = "http")]
+
+mod common;
+
+use rustwallet::adapter::infrastructure::blockchain::AlloyBlockchainService;
+use rustwallet::core::domain::{services::BlockchainService, value_objects::Network};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use wiremock::matchers::{body_partial_json, method};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+use common::{ETH_ADDRESS, TEST_KEY, TEST_KEY_ADDRESS};
+
+#[tokio::test]
+async fn test_rest_api_balance_and_transfer() {
+    use rustwallet::adapter::infrastructure::{audit::JsonlAuditSink, blockchain::NoopBlockchainService};
+    use rustwallet::adapter::interfaces::http::{self, HttpState};
+
+    let audit_log = std::env::temp_dir().join(format!("rustwallet-rest-audit-{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&audit_log);
+    let state = HttpState::with_resolver(Arc::new(|network: Network| {
+        Box::pin(async move {
+            let service: Arc<dyn BlockchainService> =
+                Arc::new(NoopBlockchainService::new().with_chain_type(network.chain_type()));
+            Ok(service)
+        })
+    }))
+    .with_audit_sink(Arc::new(JsonlAuditSink::new(&audit_log)));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(http::serve(listener, state));
+    let client = reqwest::Client::new();
+
+    let response = client.get(format!("{}/balance/mainnet/{}", base, ETH_ADDRESS)).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["balance"], "1500000000000000000");
+    assert_eq!(body["address"], ETH_ADDRESS);
+
+    let response = client.get(format!("{}/balance/mainnet/0x1234", base)).send().await.unwrap();
+    assert_eq!(response.status(), 400);
+    let body: Value = response.json().await.unwrap();
+    assert!(body["error"].as_str().unwrap().contains("40 hex characters"));
+
+    let response = client.get(format!("{}/balance/nowhere/{}", base, ETH_ADDRESS)).send().await.unwrap();
+    assert_eq!(response.status(), 400);
+
+    // The stub refuses to send, which surfaces as an upstream failure
+    let response = client
+        .post(format!("{}/transfer", base))
+        .json(&json!({
+            "from_address": ETH_ADDRESS,
+            "to_address": "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEbC",
+            "amount": "1000",
+            "network": "Mainnet",
+            "private_key": "0x".to_string() + &"11".repeat(32),
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 502);
+    let body: Value = response.json().await.unwrap();
+    assert!(body["error"].as_str().unwrap().contains("offline"));
+
+    // The failed attempt is audited like a CLI transfer
+    let record: Value = serde_json::from_str(std::fs::read_to_string(&audit_log).unwrap().trim()).unwrap();
+    assert_eq!(record["outcome"]["status"], "failed");
+    assert_eq!(record["amount"], "1000");
+}
+
+#[tokio::test]
+async fn test_rest_api_reports_rejected_transfers_as_client_errors() {
+    use alloy::sol_types::{Revert, SolError};
+    use rustwallet::adapter::interfaces::http::{self, HttpState};
+
+    let server = MockServer::start().await;
+    let data = format!("0x{}", alloy::hex::encode(Revert::from("ERC20: transfer amount exceeds balance").abi_encode()));
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_estimateGas" })))
+        .respond_with(move |request: &Request| {
+            let body: Value = serde_json::from_slice(&request.body).unwrap();
+            ResponseTemplate::new(200).set_body_json(json!({
+                "jsonrpc": "2.0",
+                "id": body["id"],
+                "error": { "code": 3, "message": "execution reverted", "data": data },
+            }))
+        })
+        .mount(&server)
+        .await;
+    let rpc_url = server.uri();
+    let state = HttpState::with_resolver(Arc::new(move |network: Network| {
+        let rpc_url = rpc_url.clone();
+        Box::pin(async move {
+            let service: Arc<dyn BlockchainService> = Arc::new(AlloyBlockchainService::new(network, &rpc_url).await?);
+            Ok(service)
+        })
+    }));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(http::serve(listener, state));
+    let client = reqwest::Client::new();
+    let transfer = |from: &str| {
+        client.post(format!("{}/transfer", base)).json(&json!({
+            "from_address": from,
+            "to_address": ETH_ADDRESS,
+            "amount": "1000",
+            "network": "Mainnet",
+            "private_key": TEST_KEY,
+        }))
+    };
+
+    // Key 1 does not control this address: the request is wrong, not the node
+    let response = transfer("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEbC").send().await.unwrap();
+    assert_eq!(response.status(), 400);
+    let body: Value = response.json().await.unwrap();
+    assert!(body["error"].as_str().unwrap().contains("does not match from address"));
+
+    let response = transfer(TEST_KEY_ADDRESS).send().await.unwrap();
+    assert_eq!(response.status(), 422);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["error"], "Transaction would revert: ERC20: transfer amount exceeds balance");
+}
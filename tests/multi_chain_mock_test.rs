@@ -0,0 +1,100 @@
+//! Deterministic MultiChainBlockchainService tests against mocked endpoints
+//!
+//! Each network gets its own local `wiremock` server, checking that the
+//! multi-chain service routes requests to the configured endpoint per chain.
+//!
+//! Run with: cargo test --test multi_chain_mock_test
+
+mod common;
+
+use rustwallet::adapter::infrastructure::resilience::RetryPolicy;
+use rustwallet::core::domain::value_objects::{Address, Network};
+use serde_json::json;
+use std::collections::HashMap;
+use std::time::Duration;
+use wiremock::matchers::{body_partial_json, header, method};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::{json_rpc_result, ETH_ADDRESS, SOL_ADDRESS};
+
+#[tokio::test]
+async fn test_multi_chain_labels_heights_per_chain() {
+    use rustwallet::adapter::infrastructure::blockchain::{
+        MultiChainBlockchainService, MultiChainConfig, NetworkServiceConfig,
+    };
+
+    let evm = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_blockNumber" })))
+        .respond_with(json_rpc_result(json!("0x10")))
+        .mount(&evm)
+        .await;
+    let solana = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "getSlot" })))
+        .respond_with(json_rpc_result(json!(250)))
+        .mount(&solana)
+        .await;
+
+    let config = MultiChainConfig::new()
+        .with_network(Network::Sepolia, NetworkServiceConfig::new().with_rpc_urls([evm.uri()]))
+        .with_network(Network::SolanaDevnet, NetworkServiceConfig::new().with_rpc_urls([solana.uri()]));
+    let mut service = MultiChainBlockchainService::from_config(config);
+    service.initialize_for_network(&Network::Sepolia).await.unwrap();
+    service.initialize_for_network(&Network::SolanaDevnet).await.unwrap();
+
+    assert_eq!(service.get_height_labeled(&Network::Sepolia).await.unwrap(), (16, "block"));
+    assert_eq!(service.get_height_labeled(&Network::SolanaDevnet).await.unwrap(), (250, "slot"));
+}
+
+#[tokio::test]
+async fn test_multi_chain_from_config_uses_configured_endpoints() {
+    use rustwallet::adapter::infrastructure::blockchain::{
+        MultiChainBlockchainService, MultiChainConfig, NetworkServiceConfig,
+    };
+
+    let down = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&down)
+        .await;
+    let evm = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_getBalance" })))
+        .respond_with(json_rpc_result(json!("0x2a")))
+        .mount(&evm)
+        .await;
+    let solana = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "getBalance" })))
+        .and(header("x-api-key", "sol-key"))
+        .respond_with(json_rpc_result(json!({ "context": { "slot": 1 }, "value": 7 })))
+        .mount(&solana)
+        .await;
+
+    let config = MultiChainConfig::new()
+        .with_timeout(Duration::from_secs(5))
+        .with_network(
+            Network::Sepolia,
+            NetworkServiceConfig::new()
+                .with_rpc_urls([down.uri(), evm.uri()])
+                .with_retry_policy(RetryPolicy::none()),
+        )
+        .with_network(
+            Network::SolanaDevnet,
+            NetworkServiceConfig::new()
+                .with_rpc_urls([solana.uri()])
+                .with_headers(HashMap::from([("x-api-key".to_string(), "sol-key".to_string())])),
+        );
+    let mut service = MultiChainBlockchainService::from_config(config);
+
+    // Initialized services and on-demand ones both come from the config
+    service.initialize_for_network(&Network::Sepolia).await.unwrap();
+    let eth = Address::new(ETH_ADDRESS.to_string()).unwrap();
+    let balance = service.get_balance_for_network(&eth, &Network::Sepolia).await.unwrap();
+    assert_eq!(balance.to_wei(), 42);
+
+    let sol = Address::new(SOL_ADDRESS.to_string()).unwrap();
+    let results = service.get_balances_across_networks(&sol, &[Network::SolanaDevnet]).await;
+    assert_eq!(results[0].1.as_ref().unwrap().to_wei(), 7);
+}
@@ -1,3 +1,4 @@
+#![allow(clippy::empty_line_after_doc_comments)]
 /// Integration tests for MultiChainBlockchainService
 ///
 /// This test file demonstrates how to use the MultiChainBlockchainService
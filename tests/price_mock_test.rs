@@ -0,0 +1,68 @@
+//! Deterministic CoinGecko price tests against a mocked REST API
+//!
+//! A local `wiremock` server stands in for the CoinGecko API, checking price
+//! parsing, caching and the errors for unreachable or unknown assets.
+//!
+//! Run with: cargo test --test price_mock_test
+
+use rustwallet::adapter::infrastructure::prices::{CoinGeckoPriceProvider, PriceProvider};
+use rustwallet::core::domain::{
+    errors::DomainError,
+    value_objects::{Address, ChainType},
+};
+use serde_json::json;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_coingecko_price_is_cached() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/simple/price"))
+        .and(query_param("ids", "ethereum"))
+        .and(query_param("vs_currencies", "usd"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "ethereum": { "usd": 3000.5 } })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/simple/token_price/ethereum"))
+        .and(query_param("contract_addresses", "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48": { "usd": 0.9998 }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = CoinGeckoPriceProvider::new_with_base_url(&server.uri()).unwrap();
+    // Second lookup is answered from the cache (the mock expects one request)
+    assert_eq!(provider.get_price(ChainType::Ethereum, None).await.unwrap(), 3000.5);
+    assert_eq!(provider.get_price(ChainType::Ethereum, None).await.unwrap(), 3000.5);
+
+    let usdc = Address::new("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".to_string()).unwrap();
+    assert_eq!(provider.get_price(ChainType::Ethereum, Some(&usdc)).await.unwrap(), 0.9998);
+}
+
+#[tokio::test]
+async fn test_coingecko_unreachable_or_unknown_asset() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/simple/price"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+        .mount(&server)
+        .await;
+
+    let provider = CoinGeckoPriceProvider::new_with_base_url(&server.uri()).unwrap();
+    assert!(matches!(
+        provider.get_price(ChainType::Solana, None).await,
+        Err(DomainError::InvalidQuery(_))
+    ));
+
+    // Nothing listening: reported as a network error, not a panic or a hang
+    let unreachable = CoinGeckoPriceProvider::new_with_base_url("http://127.0.0.1:9").unwrap();
+    assert!(matches!(
+        unreachable.get_price(ChainType::Bitcoin, None).await,
+        Err(DomainError::NetworkError(_))
+    ));
+}
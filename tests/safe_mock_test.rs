@@ -0,0 +1,93 @@
+//! Gnosis Safe proposal tests against mocked node and Transaction Service APIs
+//!
+//! A local `wiremock` server answers the Safe contract calls and accepts the
+//! proposal, checking the owner signature submitted to the Transaction Service.
+//!
+//! Run with: cargo test --test safe_mock_test --features safe
+#![cfg(feature = "safe")]
+
+mod common;
+
+use rustwallet::adapter::infrastructure::blockchain::AlloyBlockchainService;
+use rustwallet::core::domain::{
+    errors::DomainError,
+    value_objects::{Address, Network, SecretKey},
+};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+use common::{ETH_ADDRESS, TEST_KEY, TEST_KEY_ADDRESS};
+
+#[tokio::test]
+async fn test_propose_safe_transaction_signs_and_submits() {
+    use alloy::primitives::{Address as AlloyAddress, PrimitiveSignature, B256, U256};
+    use alloy::sol_types::SolValue;
+
+    let server = MockServer::start().await;
+    let safe = "0x1111111111111111111111111111111111111111";
+    let owner: AlloyAddress = TEST_KEY_ADDRESS.parse().unwrap();
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_call" })))
+        .respond_with(move |request: &Request| {
+            let body: Value = serde_json::from_slice(&request.body).unwrap();
+            let input = body["params"][0]["input"].as_str().unwrap();
+            let result = match &input[..10] {
+                // isOwner(address): only the key-1 address is an owner
+                "0x2f54bf6e" => input.ends_with(&alloy::hex::encode(owner)).abi_encode(),
+                // nonce()
+                "0xaffed0e0" => U256::from(5u64).abi_encode(),
+                // domainSeparator()
+                "0xf698da25" => B256::repeat_byte(0x11).abi_encode(),
+                other => panic!("Unexpected eth_call {}", other),
+            };
+            ResponseTemplate::new(200).set_body_json(
+                json!({ "jsonrpc": "2.0", "id": body["id"], "result": format!("0x{}", alloy::hex::encode(result)) }),
+            )
+        })
+        .mount(&server)
+        .await;
+    let proposals: Arc<Mutex<Vec<Value>>> = Arc::default();
+    let received = proposals.clone();
+    Mock::given(method("POST"))
+        .and(path("/api/v1/safes/0x1111111111111111111111111111111111111111/multisig-transactions/"))
+        .respond_with(move |request: &Request| {
+            received.lock().unwrap().push(serde_json::from_slice(&request.body).unwrap());
+            ResponseTemplate::new(201)
+        })
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let service = AlloyBlockchainService::builder()
+        .network(Network::Sepolia)
+        .rpc_urls([server.uri()])
+        .safe_service_url(server.uri())
+        .build()
+        .await
+        .unwrap();
+    let safe = Address::new(safe.to_string()).unwrap();
+    let to = Address::new(ETH_ADDRESS.to_string()).unwrap();
+    let owner_key = SecretKey::from(TEST_KEY);
+    let safe_tx_hash = service.propose_safe_transaction(&safe, &to, 1_000, &owner_key).await.unwrap();
+
+    let proposal = proposals.lock().unwrap()[0].clone();
+    assert_eq!(proposal["to"], ETH_ADDRESS);
+    assert_eq!(proposal["value"], "1000");
+    assert_eq!(proposal["nonce"], "5");
+    assert_eq!(proposal["contractTransactionHash"], safe_tx_hash.to_string());
+    assert_eq!(proposal["sender"], owner.to_checksum(None));
+    // The owner's signature is over the Safe tx hash itself (no EIP-191 prefix)
+    let signature = alloy::hex::decode(proposal["signature"].as_str().unwrap()).unwrap();
+    let signature = PrimitiveSignature::try_from(signature.as_slice()).unwrap();
+    let signer = signature
+        .recover_address_from_prehash(&B256::from(*safe_tx_hash.as_bytes()))
+        .unwrap();
+    assert_eq!(signer, owner);
+
+    // A key that is not an owner is refused before anything is submitted
+    let stranger = SecretKey::from("0000000000000000000000000000000000000000000000000000000000000002");
+    let err = service.propose_safe_transaction(&safe, &to, 1_000, &stranger).await.unwrap_err();
+    assert!(matches!(err, DomainError::InvalidQuery(_)), "{}", err);
+}
@@ -0,0 +1,671 @@
+//! Deterministic Solana tests against a mocked JSON-RPC server
+//!
+//! A local `wiremock` server returns canned `getBalance`, token account and
+//! transaction responses, exercising the Solana service's parsing, batching
+//! and SPL transfer paths without network access.
+//!
+//! Run with: cargo test --test solana_mock_test
+
+mod common;
+
+use rustwallet::adapter::infrastructure::blockchain::{BitcoinBlockchainService, SolanaBlockchainService, SolanaTxVersion};
+use rustwallet::adapter::infrastructure::resilience::RetryPolicy;
+use rustwallet::core::application::{handlers::GetAccountInfoHandler, GetBalanceHandler};
+use rustwallet::core::domain::{
+    errors::DomainError,
+    queries::{GetAccountInfoQuery, GetBalanceQuery},
+    services::{BlockchainService, QueryHandler},
+    value_objects::{Address, Finality, Network, SecretKey},
+};
+use base64::Engine;
+use ed25519_dalek::Verifier;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+use common::{json_rpc_result, json_rpc_error, query_balance, query_balances, SOL_ADDRESS};
+
+#[tokio::test]
+async fn test_solana_balance_via_handler() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "getBalance" })))
+        .respond_with(json_rpc_result(json!({
+            "context": { "apiVersion": "2.0.0", "slot": 300000000 },
+            "value": 2_500_000_000u64, // 2.5 SOL
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let service = SolanaBlockchainService::new_with_rpc_url(Network::SolanaMainnet, &server.uri(), HashMap::new())
+        .await
+        .expect("Failed to create service");
+    let handler = GetBalanceHandler::new(Arc::new(service));
+
+    let query = GetBalanceQuery::new(Address::new(SOL_ADDRESS.to_string()).unwrap(), Network::SolanaMainnet);
+    let result = handler.handle(query).await.expect("Balance query failed");
+
+    assert_eq!(result.balance.to_wei(), 2_500_000_000);
+}
+
+#[tokio::test]
+async fn test_solana_system_program_balance_via_handler() {
+    // Base58 and 32 characters starting with 1, but not a Bitcoin address
+    const SYSTEM_PROGRAM: &str = "11111111111111111111111111111111";
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "getBalance", "params": [SYSTEM_PROGRAM] })))
+        .respond_with(json_rpc_result(json!({ "context": { "slot": 1 }, "value": 1u64 })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let service = SolanaBlockchainService::new_with_rpc(Network::SolanaMainnet, &server.uri())
+        .await
+        .expect("Failed to create service");
+    let balance = query_balance(Arc::new(service), SYSTEM_PROGRAM, Network::SolanaMainnet).await;
+
+    assert_eq!(balance.unwrap().to_wei(), 1);
+}
+
+#[tokio::test]
+async fn test_solana_balance_uses_configured_commitment() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({
+            "method": "getBalance",
+            "params": [SOL_ADDRESS, { "commitment": "confirmed" }],
+        })))
+        .respond_with(json_rpc_result(json!({
+            "context": { "apiVersion": "2.0.0", "slot": 300000000 },
+            "value": 1_000_000_000u64,
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let service = SolanaBlockchainService::new_with_rpc(Network::SolanaMainnet, &server.uri())
+        .await
+        .expect("Failed to create service")
+        .with_commitment(Finality::Safe);
+
+    let balance = query_balance(Arc::new(service), SOL_ADDRESS, Network::SolanaMainnet).await.unwrap();
+    assert_eq!(balance.to_wei(), 1_000_000_000);
+}
+
+#[tokio::test]
+async fn test_solana_get_balances_sends_one_batch() {
+    const OTHER: &str = "11111111111111111111111111111111";
+    let server = MockServer::start().await;
+    // Answer out of order, with an error for the second address
+    Mock::given(method("POST"))
+        .respond_with(|request: &Request| {
+            let batch: Vec<Value> = serde_json::from_slice(&request.body).unwrap();
+            assert_eq!(batch.len(), 2);
+            assert!(batch.iter().all(|call| call["method"] == "getBalance"));
+            ResponseTemplate::new(200).set_body_json(json!([
+                { "jsonrpc": "2.0", "id": batch[1]["id"], "error": { "code": -32602, "message": "Invalid param" } },
+                { "jsonrpc": "2.0", "id": batch[0]["id"], "result": { "context": { "slot": 1 }, "value": 3_000_000_000u64 } },
+            ]))
+        })
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let service = SolanaBlockchainService::new_with_rpc(Network::SolanaMainnet, &server.uri())
+        .await
+        .unwrap();
+    let balances = query_balances(Arc::new(service), &[SOL_ADDRESS, OTHER], Network::SolanaMainnet).await;
+
+    assert_eq!(balances[0].as_ref().unwrap().to_wei(), 3_000_000_000);
+    assert!(matches!(&balances[1], Err(DomainError::BlockchainError(msg)) if msg.contains("Invalid param")));
+}
+
+/// Keep-alive HTTP/1.1 server answering every JSON-RPC call (single or
+/// batch) with a 5 SOL `getBalance` result; counts accepted TCP connections
+///
+/// wiremock hides connection handling, so connection reuse is checked
+/// against this instead.
+async fn counting_solana_server() -> (String, Arc<AtomicUsize>) {
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let connections = Arc::new(AtomicUsize::new(0));
+    let accepted = connections.clone();
+    tokio::spawn(async move {
+        while let Ok((socket, _)) = listener.accept().await {
+            accepted.fetch_add(1, Ordering::SeqCst);
+            tokio::spawn(async move {
+                let mut socket = BufReader::new(socket);
+                loop {
+                    let mut content_length = 0;
+                    loop {
+                        let mut line = String::new();
+                        if socket.read_line(&mut line).await.unwrap_or(0) == 0 {
+                            return;
+                        }
+                        if line == "\r\n" {
+                            break;
+                        }
+                        if let Some((name, value)) = line.split_once(':') {
+                            if name.eq_ignore_ascii_case("content-length") {
+                                content_length = value.trim().parse().unwrap();
+                            }
+                        }
+                    }
+                    let mut body = vec![0; content_length];
+                    socket.read_exact(&mut body).await.unwrap();
+
+                    let answer = |call: &Value| {
+                        json!({ "jsonrpc": "2.0", "id": call["id"], "result": { "context": { "slot": 1 }, "value": 5_000_000_000u64 } })
+                    };
+                    let response = match serde_json::from_slice::<Value>(&body).unwrap() {
+                        Value::Array(calls) => Value::Array(calls.iter().map(answer).collect()),
+                        call => answer(&call),
+                    }
+                    .to_string();
+                    // One write: separate head/body writes stall on Nagle + delayed ACK
+                    let reply = format!(
+                        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{}",
+                        response.len(),
+                        response
+                    );
+                    socket.get_mut().write_all(reply.as_bytes()).await.unwrap();
+                }
+            });
+        }
+    });
+    (url, connections)
+}
+
+#[tokio::test]
+async fn test_solana_requests_reuse_pooled_connections() {
+    let (url, connections) = counting_solana_server().await;
+    let service = SolanaBlockchainService::new_with_rpc(Network::SolanaMainnet, &url).await.unwrap();
+    let addresses = vec![Address::new(SOL_ADDRESS.to_string()).unwrap(); 5];
+
+    for _ in 0..10 {
+        service.get_balance(&addresses[0]).await.unwrap();
+        let balances = service.get_balances(&addresses).await;
+        assert!(balances.iter().all(|balance| balance.as_ref().unwrap().to_wei() == 5_000_000_000));
+    }
+    assert_eq!(connections.load(Ordering::SeqCst), 1);
+
+    // Without pooling every request opens a new connection
+    let (url, connections) = counting_solana_server().await;
+    let service = SolanaBlockchainService::new_with_rpc(Network::SolanaMainnet, &url)
+        .await
+        .unwrap()
+        .with_max_connections(0)
+        .unwrap();
+    for _ in 0..3 {
+        service.get_balance(&addresses[0]).await.unwrap();
+    }
+    assert_eq!(connections.load(Ordering::SeqCst), 3);
+}
+
+/// Requests per second with and without connection reuse
+///
+/// Run with: cargo test --release --features bench --test solana_mock_test -- --nocapture bench
+#[cfg(feature = "bench")]
+#[tokio::test(flavor = "multi_thread")]
+async fn bench_solana_pooled_vs_unpooled_requests() {
+    const REQUESTS: usize = 2_000;
+    let address = Address::new(SOL_ADDRESS.to_string()).unwrap();
+
+    let mut rates = Vec::new();
+    for max_connections in [8, 0] {
+        let (url, connections) = counting_solana_server().await;
+        let service = SolanaBlockchainService::new_with_rpc(Network::SolanaMainnet, &url)
+            .await
+            .unwrap()
+            .with_max_connections(max_connections)
+            .unwrap();
+        let started = std::time::Instant::now();
+        for _ in 0..REQUESTS {
+            service.get_balance(&address).await.unwrap();
+        }
+        let rate = REQUESTS as f64 / started.elapsed().as_secs_f64();
+        println!(
+            "max_connections={}: {:.0} req/s over {} connection(s)",
+            max_connections,
+            rate,
+            connections.load(Ordering::SeqCst)
+        );
+        rates.push(rate);
+    }
+    assert!(rates[0] > rates[1], "pooling should beat reconnecting: {:?}", rates);
+}
+
+#[tokio::test]
+async fn test_solana_rpc_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(json_rpc_error(-32602, "Invalid param: WrongSize"))
+        .mount(&server)
+        .await;
+
+    let service = SolanaBlockchainService::new_with_rpc_url(Network::SolanaMainnet, &server.uri(), HashMap::new())
+        .await
+        .expect("Failed to create service");
+    let handler = GetBalanceHandler::new(Arc::new(service));
+
+    let query = GetBalanceQuery::new(Address::new(SOL_ADDRESS.to_string()).unwrap(), Network::SolanaMainnet);
+    match handler.handle(query).await {
+        Err(DomainError::BlockchainError(msg)) => assert!(msg.contains("WrongSize")),
+        other => panic!("Expected BlockchainError, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_solana_and_bitcoin_retry_rate_limited_requests() {
+    let server = MockServer::start().await;
+    // The first two requests of each service are throttled
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(429))
+        .up_to_n_times(2)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .respond_with(json_rpc_result(json!(250)))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/latestblock"))
+        .respond_with(ResponseTemplate::new(429))
+        .up_to_n_times(2)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/latestblock"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "height": 870000, "hash": "00" })))
+        .mount(&server)
+        .await;
+    let policy = RetryPolicy::new(2, Duration::from_millis(10));
+
+    let solana = SolanaBlockchainService::new_with_rpc_url(Network::SolanaMainnet, &server.uri(), HashMap::new())
+        .await
+        .unwrap()
+        .with_retry_policy(policy);
+    assert_eq!(solana.get_block_number().await.unwrap(), 250);
+
+    let bitcoin = BitcoinBlockchainService::new_with_api_urls(Network::BitcoinMainnet, &server.uri(), &server.uri())
+        .await
+        .unwrap();
+    assert!(matches!(bitcoin.get_block_number().await, Err(DomainError::RateLimited(_))));
+    let bitcoin = bitcoin.with_retry_policy(policy);
+    assert_eq!(bitcoin.get_block_number().await.unwrap(), 870000);
+}
+
+#[tokio::test]
+async fn test_solana_rejects_oversized_response() {
+    let server = MockServer::start().await;
+    let padding = "x".repeat(4096);
+    Mock::given(method("POST"))
+        .respond_with(json_rpc_result(json!({ "context": { "slot": 1, "padding": padding }, "value": 1 })))
+        .mount(&server)
+        .await;
+
+    let service = || async {
+        SolanaBlockchainService::new_with_rpc(Network::SolanaMainnet, &server.uri())
+            .await
+            .expect("Failed to create service")
+    };
+    // Fits the default limit
+    let balance = query_balance(Arc::new(service().await), SOL_ADDRESS, Network::SolanaMainnet).await;
+    assert_eq!(balance.unwrap().to_wei(), 1);
+
+    let limited = Arc::new(service().await.with_max_response_size(1024));
+    let balance = query_balance(limited, SOL_ADDRESS, Network::SolanaMainnet).await;
+    assert!(matches!(balance, Err(DomainError::InvalidResponse(_))));
+}
+
+#[tokio::test]
+async fn test_solana_height_maps_finality_to_commitment() {
+    let server = MockServer::start().await;
+    for (commitment, slot) in [("processed", 300u64), ("confirmed", 299), ("finalized", 268)] {
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "getSlot", "params": [{ "commitment": commitment }] })))
+            .respond_with(json_rpc_result(json!(slot)))
+            .mount(&server)
+            .await;
+    }
+
+    let service = SolanaBlockchainService::new_with_rpc_url(Network::SolanaMainnet, &server.uri(), HashMap::new())
+        .await
+        .expect("Failed to create service");
+
+    assert_eq!(service.get_block_number().await.unwrap(), 300);
+    assert_eq!(service.get_height(Finality::Safe).await.unwrap(), 299);
+    assert_eq!(service.get_height(Finality::Finalized).await.unwrap(), 268);
+}
+
+#[tokio::test]
+async fn test_solana_diagnose_explains_rate_limit() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(429).set_body_string("Too many requests"))
+        .mount(&server)
+        .await;
+
+    let service = SolanaBlockchainService::new_with_rpc_url(Network::SolanaMainnet, &server.uri(), HashMap::new())
+        .await
+        .expect("Failed to create service");
+    let diagnostics = service.diagnose().await;
+
+    assert!(!diagnostics.reachable);
+    assert!(diagnostics.error.unwrap().contains("rate limited"));
+}
+
+#[tokio::test]
+async fn test_solana_account_info_reports_program_accounts() {
+    const PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "getAccountInfo" })))
+        .respond_with(|request: &Request| {
+            let body: Value = serde_json::from_slice(&request.body).unwrap();
+            let value = if body["params"][0] == PROGRAM {
+                json!({
+                    "owner": "BPFLoader2111111111111111111111111111111111",
+                    "data": ["", "base64"],
+                    "lamports": 4_000_000_000u64,
+                    "executable": true,
+                    "space": 134_080,
+                })
+            } else {
+                Value::Null
+            };
+            json_rpc_result(json!({ "context": { "slot": 1 }, "value": value }))(request)
+        })
+        .mount(&server)
+        .await;
+
+    let service = SolanaBlockchainService::new_with_rpc(Network::SolanaMainnet, &server.uri())
+        .await
+        .expect("Failed to create service");
+    let handler = GetAccountInfoHandler::new(Arc::new(service));
+    let query = |address: &str| GetAccountInfoQuery::new(Address::new(address.to_string()).unwrap(), Network::SolanaMainnet);
+
+    let program = handler.handle(query(PROGRAM)).await.unwrap();
+    assert_eq!(program.balance.to_wei(), 4_000_000_000);
+    assert_eq!(program.is_contract, Some(true));
+    assert_eq!(program.code_size, Some(134_080));
+    assert_eq!(program.transaction_count, None);
+
+    let unused = handler.handle(query(SOL_ADDRESS)).await.unwrap();
+    assert_eq!(unused.balance.to_wei(), 0);
+    assert_eq!(unused.is_contract, Some(false));
+}
+
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+#[tokio::test]
+async fn test_solana_lists_nonzero_token_accounts_of_both_programs() {
+    const PYUSD_MINT: &str = "2b1kV6DkPAnxd5ixfnxCpjxmKwqjjaYmCZfHsFu24GXo";
+    fn token_account(mint: &str, amount: &str, decimals: u8) -> Value {
+        json!({
+            "pubkey": "11111111111111111111111111111111",
+            "account": {
+                "owner": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+                "lamports": 2_039_280,
+                "data": {
+                    "parsed": {
+                        "info": { "mint": mint, "owner": SOL_ADDRESS, "tokenAmount": { "amount": amount, "decimals": decimals } },
+                        "type": "account",
+                    },
+                    "program": "spl-token",
+                },
+            },
+        })
+    }
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(|request: &Request| {
+            let batch: Vec<Value> = serde_json::from_slice(&request.body).unwrap();
+            let responses: Vec<Value> = batch
+                .iter()
+                .map(|call| {
+                    assert_eq!(call["method"], "getTokenAccountsByOwner");
+                    assert_eq!(call["params"][0], SOL_ADDRESS);
+                    let accounts = if call["params"][1]["programId"] == "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA" {
+                        json!([token_account(USDC_MINT, "5000000", 6), token_account(USDC_MINT, "0", 6)])
+                    } else {
+                        json!([token_account(PYUSD_MINT, "250", 6)])
+                    };
+                    json!({ "jsonrpc": "2.0", "id": call["id"], "result": { "context": { "slot": 1 }, "value": accounts } })
+                })
+                .collect();
+            ResponseTemplate::new(200).set_body_json(responses)
+        })
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let service = SolanaBlockchainService::new_with_rpc(Network::SolanaMainnet, &server.uri())
+        .await
+        .unwrap();
+    let holdings = service
+        .list_token_accounts(&Address::new(SOL_ADDRESS.to_string()).unwrap())
+        .await
+        .unwrap();
+
+    let summary: Vec<(&str, u128, u8)> = holdings
+        .iter()
+        .map(|holding| (holding.mint.as_str(), holding.amount, holding.decimals))
+        .collect();
+    assert_eq!(summary, vec![(USDC_MINT, 5_000_000, 6), (PYUSD_MINT, 250, 6)]);
+}
+
+/// Mock `getAccountInfo` for an SPL transfer: the mint (6 decimals), the
+/// sender's token account holding `sender_tokens`, and a recipient with no
+/// token account yet
+async fn mount_spl_accounts(server: &MockServer, sender_tokens: u64) {
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "getAccountInfo" })))
+        .respond_with(move |request: &Request| {
+            let body: Value = serde_json::from_slice(&request.body).unwrap();
+            let value = if body["params"][0] == USDC_MINT {
+                json!({
+                    "owner": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+                    "data": { "parsed": { "info": { "decimals": 6 }, "type": "mint" }, "program": "spl-token" },
+                })
+            } else if body["params"][1]["encoding"] == "jsonParsed" {
+                json!({
+                    "owner": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+                    "data": { "parsed": { "info": { "tokenAmount": { "amount": sender_tokens.to_string() } } } },
+                })
+            } else {
+                Value::Null
+            };
+            json_rpc_result(json!({ "context": { "slot": 1 }, "value": value }))(request)
+        })
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_solana_spl_transfer_creates_recipient_token_account() {
+    let server = MockServer::start().await;
+    mount_spl_accounts(&server, 5_000_000).await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "getLatestBlockhash" })))
+        .respond_with(json_rpc_result(json!({
+            "context": { "slot": 1 },
+            "value": { "blockhash": "EkSnNWid2cvwEVnVx9aBqawnmiCNiDgp3gUdkDPTKN1N", "lastValidBlockHeight": 100 },
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "sendTransaction" })))
+        .respond_with(|request: &Request| {
+            // Echo the transaction's first signature, as a real node does
+            let body: Value = serde_json::from_slice(&request.body).unwrap();
+            let tx = base64::engine::general_purpose::STANDARD
+                .decode(body["params"][0].as_str().unwrap())
+                .unwrap();
+            json_rpc_result(json!(bs58::encode(&tx[1..65]).into_string()))(request)
+        })
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let signer = ed25519_dalek::SigningKey::from_bytes(&[1u8; 32]);
+    let from = Address::new(bs58::encode(signer.verifying_key().to_bytes()).into_string()).unwrap();
+    let private_key = SecretKey::new(bs58::encode(signer.to_bytes()).into_string());
+
+    let service = SolanaBlockchainService::new_with_rpc_url(Network::SolanaMainnet, &server.uri(), HashMap::new())
+        .await
+        .expect("Failed to create service");
+    let mint = Address::new(USDC_MINT.to_string()).unwrap();
+    let to = Address::new(SOL_ADDRESS.to_string()).unwrap();
+    let signature = service
+        .transfer_token(&mint, &from, &to, 1_250_000, &private_key)
+        .await
+        .expect("Token transfer failed");
+
+    // Inspect what was broadcast
+    let requests = server.received_requests().await.unwrap();
+    let send = requests
+        .iter()
+        .map(|r| serde_json::from_slice::<Value>(&r.body).unwrap())
+        .find(|body| body["method"] == "sendTransaction")
+        .unwrap();
+    let tx = base64::engine::general_purpose::STANDARD
+        .decode(send["params"][0].as_str().unwrap())
+        .unwrap();
+    let raw_signature: [u8; 64] = tx[1..65].try_into().unwrap();
+    let message = &tx[65..];
+    assert_eq!(signature.as_str(), bs58::encode(raw_signature).into_string());
+    signer
+        .verifying_key()
+        .verify(message, &ed25519_dalek::Signature::from_bytes(&raw_signature))
+        .expect("Transaction signature must verify");
+
+    // Fee payer is the sender; create-ATA + TransferChecked instructions
+    let num_keys = message[3] as usize;
+    assert_eq!(&message[4..36], signer.verifying_key().as_bytes());
+    assert_eq!(message[4 + num_keys * 32 + 32], 2);
+}
+
+#[tokio::test]
+async fn test_solana_spl_transfer_insufficient_tokens() {
+    let server = MockServer::start().await;
+    mount_spl_accounts(&server, 1_000).await;
+
+    let signer = ed25519_dalek::SigningKey::from_bytes(&[1u8; 32]);
+    let from = Address::new(bs58::encode(signer.verifying_key().to_bytes()).into_string()).unwrap();
+    let private_key = SecretKey::new(bs58::encode(signer.to_bytes()).into_string());
+
+    let service = SolanaBlockchainService::new_with_rpc_url(Network::SolanaMainnet, &server.uri(), HashMap::new())
+        .await
+        .expect("Failed to create service");
+    let mint = Address::new(USDC_MINT.to_string()).unwrap();
+    let to = Address::new(SOL_ADDRESS.to_string()).unwrap();
+
+    match service.transfer_token(&mint, &from, &to, 2_000, &private_key).await {
+        Err(DomainError::InsufficientBalance { required, available }) => {
+            assert_eq!((required, available), (2_000, 1_000));
+        }
+        other => panic!("Expected InsufficientBalance, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_solana_v0_transfer_references_lookup_table() {
+    const LOOKUP_TABLE: &str = "4Nd1mBQtrMJVYVfKf2PJy9NZUZdTAsp7D4xWLs4gDB4T";
+    let token_program = bs58::decode("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA").into_vec().unwrap();
+    let mint = bs58::decode(USDC_MINT).into_vec().unwrap();
+
+    let server = MockServer::start().await;
+    // Active table: u32 type = 1, deactivation slot = u64::MAX, rest of the
+    // 56-byte header zeroed, then [token program, mint]
+    let mut table_data = vec![1, 0, 0, 0];
+    table_data.extend_from_slice(&u64::MAX.to_le_bytes());
+    table_data.resize(56, 0);
+    table_data.extend_from_slice(&token_program);
+    table_data.extend_from_slice(&mint);
+    let table_account = json!({
+        "owner": "AddressLookupTab1e1111111111111111111111111",
+        "data": [base64::engine::general_purpose::STANDARD.encode(&table_data), "base64"],
+    });
+    Mock::given(method("POST"))
+        .and(|request: &Request| {
+            let body: Value = serde_json::from_slice(&request.body).unwrap_or_default();
+            body["method"] == "getAccountInfo" && body["params"][0] == LOOKUP_TABLE
+        })
+        .respond_with(json_rpc_result(json!({ "context": { "slot": 1 }, "value": table_account })))
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    mount_spl_accounts(&server, 5_000_000).await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "getLatestBlockhash" })))
+        .respond_with(json_rpc_result(json!({
+            "context": { "slot": 1 },
+            "value": { "blockhash": "EkSnNWid2cvwEVnVx9aBqawnmiCNiDgp3gUdkDPTKN1N", "lastValidBlockHeight": 100 },
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "sendTransaction" })))
+        .respond_with(|request: &Request| {
+            let body: Value = serde_json::from_slice(&request.body).unwrap();
+            let tx = base64::engine::general_purpose::STANDARD
+                .decode(body["params"][0].as_str().unwrap())
+                .unwrap();
+            json_rpc_result(json!(bs58::encode(&tx[1..65]).into_string()))(request)
+        })
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let signer = ed25519_dalek::SigningKey::from_bytes(&[1u8; 32]);
+    let from = Address::new(bs58::encode(signer.verifying_key().to_bytes()).into_string()).unwrap();
+    let private_key = SecretKey::new(bs58::encode(signer.to_bytes()).into_string());
+
+    let service = SolanaBlockchainService::new_with_rpc_url(Network::SolanaMainnet, &server.uri(), HashMap::new())
+        .await
+        .expect("Failed to create service")
+        .with_tx_version(SolanaTxVersion::V0 {
+            lookup_tables: vec![Address::new(LOOKUP_TABLE.to_string()).unwrap()],
+        });
+    service
+        .transfer_token(
+            &Address::new(USDC_MINT.to_string()).unwrap(),
+            &from,
+            &Address::new(SOL_ADDRESS.to_string()).unwrap(),
+            1_250_000,
+            &private_key,
+        )
+        .await
+        .expect("Token transfer failed");
+
+    let requests = server.received_requests().await.unwrap();
+    let send = requests
+        .iter()
+        .map(|r| serde_json::from_slice::<Value>(&r.body).unwrap())
+        .find(|body| body["method"] == "sendTransaction")
+        .unwrap();
+    let tx = base64::engine::general_purpose::STANDARD
+        .decode(send["params"][0].as_str().unwrap())
+        .unwrap();
+    let message = &tx[65..];
+    assert_eq!(message[0], 0x80, "expected a v0 message");
+    // One lookup: the table, no writable indexes, the mint (index 1) read-only;
+    // the invoked token program stays a static key
+    let lookup_table = bs58::decode(LOOKUP_TABLE).into_vec().unwrap();
+    let lookups = &message[message.len() - 36..];
+    assert_eq!(lookups[0], 1);
+    assert_eq!(&lookups[1..33], &lookup_table[..]);
+    assert_eq!(&lookups[33..], &[0, 1, 1]);
+}
@@ -21,7 +21,7 @@
 /// ## 2. Set Environment Variables
 ///
 /// ```bash
-/// # Test wallet private key (64 hex characters, no 0x prefix)
+/// # Test wallet private key (64 hex characters, 0x prefix optional)
 /// export TEST_PRIVATE_KEY="your_test_private_key_here"
 ///
 /// # Optional: Specific test addresses
@@ -51,15 +51,15 @@
 ///
 use rustwallet::core::domain::{
     services::BlockchainService,
-    value_objects::{Address, Amount, Network},
+    value_objects::{Address, Amount, Network, SecretKey},
 };
 use rustwallet::adapter::infrastructure::blockchain::AlloyBlockchainService;
 use std::env;
 use std::time::Instant;
 
 /// Helper function to get test private key from environment
-fn get_test_private_key() -> Option<String> {
-    env::var("TEST_PRIVATE_KEY").ok()
+fn get_test_private_key() -> Option<SecretKey> {
+    env::var("TEST_PRIVATE_KEY").ok().map(SecretKey::new)
 }
 
 /// Helper function to get test from address (derived from private key)
@@ -148,7 +148,7 @@ async fn test_eth_transfer_sepolia() {
             &from_address,
             &to_address,
             transfer_amount.to_wei(),
-            &private_key,
+            service.signer_for_key(&private_key).expect("Valid private key").as_ref(),
         )
         .await
         .expect("Transfer failed");
@@ -248,7 +248,7 @@ async fn test_bsc_transfer_testnet() {
             &from_address,
             &to_address,
             transfer_amount.to_wei(),
-            &private_key,
+            service.signer_for_key(&private_key).expect("Valid private key").as_ref(),
         )
         .await
         .expect("Transfer failed");
@@ -308,8 +308,9 @@ async fn test_transfer_insufficient_balance() {
     // Try to transfer more than balance
     let huge_amount = Amount::from_ether(1000000.0);
 
+    let signer = service.signer_for_key(&private_key).expect("Valid private key");
     let result = service
-        .transfer(&from_address, &to_address, huge_amount.to_wei(), &private_key)
+        .transfer(&from_address, &to_address, huge_amount.to_wei(), signer.as_ref())
         .await;
 
     assert!(result.is_err(), "Should fail with insufficient balance");
@@ -323,26 +324,17 @@ async fn test_transfer_insufficient_balance() {
 async fn test_transfer_invalid_private_key() {
     println!("\n🧪 Testing Error: Invalid Private Key\n");
 
-    let from_address = Address::new("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEbC".to_string())
-        .expect("Valid address");
-    let to_address = Address::new("0x8894E0a0c962CB723c1976a4421c95949bE2D4E3".to_string())
-        .expect("Valid address");
-
     let service = AlloyBlockchainService::new_with_default_rpc(Network::Sepolia)
         .await
         .expect("Failed to create service");
 
-    // Try with invalid private key
-    let invalid_key = "invalid_private_key";
-    let amount = Amount::from_ether(0.001);
-
-    let result = service
-        .transfer(&from_address, &to_address, amount.to_wei(), invalid_key)
-        .await;
+    // Try with invalid private key: rejected before anything is signed
+    let invalid_key = SecretKey::from("invalid_private_key");
+    let result = service.signer_for_key(&invalid_key);
 
     assert!(result.is_err(), "Should fail with invalid private key");
     println!("  ✓ Correctly rejected invalid private key");
-    println!("  Error: {:?}", result.unwrap_err());
+    println!("  Error: {:?}", result.err().unwrap());
     println!("\n✅ Error handling test PASSED");
 }
 
@@ -372,8 +364,9 @@ async fn test_transfer_key_address_mismatch() {
 
     let amount = Amount::from_ether(0.001);
 
+    let signer = service.signer_for_key(&private_key).expect("Valid private key");
     let result = service
-        .transfer(&wrong_from_address, &to_address, amount.to_wei(), &private_key)
+        .transfer(&wrong_from_address, &to_address, amount.to_wei(), signer.as_ref())
         .await;
 
     assert!(result.is_err(), "Should fail with mismatched address");
@@ -426,7 +419,7 @@ async fn test_transfer_performance() {
                 &from_address,
                 &to_address,
                 Amount::from_ether(0.0001).to_wei(),
-                &private_key,
+                service.signer_for_key(&private_key).expect("Valid private key").as_ref(),
             )
             .await;
 
@@ -1,5 +1,5 @@
 use rustwallet::core::domain::{
-    value_objects::{Address, Amount, Network},
+    value_objects::{Address, Amount, Network, SecretKey},
 };
 
 /// Integration test for ETH to BSC transfer scenario
@@ -66,13 +66,16 @@ fn test_transfer_command_structure() {
         to.clone(),
         amount,
         Network::Sepolia,
-        "test_private_key".to_string(),
+        SecretKey::from("test_private_key"),
     );
 
     assert_eq!(command.from_address, from);
     assert_eq!(command.to_address, to);
     assert_eq!(command.amount, amount);
 
+    // The private key must never show up in Debug output
+    assert!(!format!("{:?}", command).contains("test_private_key"));
+
     println!("✅ Transfer command structure validated");
 }
 